@@ -0,0 +1,29 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Integration Tests
+//!
+//! Scenarios spanning several rounds, run against a mock runtime composing
+//! `pallet_parachain_staking` with `pallet_session`, `pallet_im_online`, `pallet_offences`,
+//! `pallet_dkg_metadata` and `pallet_balances` wired as in the parachain runtime. Staking is
+//! the session manager, so collators are elected, handed to the DKG and liveness tracking, and
+//! reported for offences through the same calls as on chain, rather than through the direct
+//! calls the pallet mocks make.
+
+#![cfg(test)]
+
+mod mock;
+mod tests;
@@ -0,0 +1,486 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+//! Mock runtime wiring staking, sessions, liveness, offences and the DKG as the parachain does
+#![allow(clippy::all, dead_code)]
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{
+		ConstU128, ConstU32, ConstU64, Everything, GenesisBuild, OnFinalize, OnIdle, OnInitialize,
+	},
+	weights::Weight,
+};
+use frame_system::EnsureRoot;
+use pallet_parachain_staking::{InflationInfo, Range};
+use pallet_session::historical as pallet_session_historical;
+use sp_core::{ecdsa, H256};
+use sp_runtime::{
+	testing::{TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
+	MultiSignature, Perbill, Percent, Permill,
+};
+
+pub type Signature = MultiSignature;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+pub type Balance = u128;
+pub type BlockNumber = u64;
+pub type DKGId = dkg_runtime_primitives::crypto::AuthorityId;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		DKG: pallet_dkg_metadata::{Pallet, Storage, Call, Event<T>, Config<T>},
+		ParachainStaking: pallet_parachain_staking::{Pallet, Call, Storage, Config<T>, Event<T>},
+		BlockAuthor: block_author::{Pallet, Storage},
+		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
+		Historical: pallet_session_historical::{Pallet},
+		Offences: pallet_offences::{Pallet, Storage, Event},
+		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, Config<T>},
+	}
+);
+
+/// Collators have no stash and controller, so they are identified by their account
+pub struct IdentityCollator;
+impl<T> sp_runtime::traits::Convert<T, Option<T>> for IdentityCollator {
+	fn convert(t: T) -> Option<T> {
+		Some(t)
+	}
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = sp_runtime::generic::Header<BlockNumber, BlakeTwo256>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<C> frame_system::offchain::CreateSignedTransaction<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	fn create_transaction<A: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: <Signature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<6000>;
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 4];
+	type MaxLocks = ();
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+sp_runtime::impl_opaque_keys! {
+	pub struct MockSessionKeys {
+		pub dkg: DKG,
+		pub im_online: ImOnline,
+	}
+}
+
+parameter_types! {
+	pub const Offset: BlockNumber = 0;
+	// sessions, and so rounds, change every `BlocksPerRound` blocks
+	pub const Period: BlockNumber = 5;
+}
+
+impl pallet_session::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = IdentityCollator;
+	type ShouldEndSession = pallet_parachain_staking::RelayAlignedRounds<
+		Test,
+		pallet_session::PeriodicSessions<Period, Offset>,
+	>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	type SessionManager = ParachainStaking;
+	type SessionHandler = <MockSessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = MockSessionKeys;
+	type WeightInfo = ();
+}
+
+impl pallet_session::historical::Config for Test {
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = IdentityCollator;
+}
+
+parameter_types! {
+	pub const DecayPercentage: Percent = Percent::from_percent(50);
+	pub const RefreshDelay: Permill = Permill::from_percent(90);
+}
+
+impl pallet_dkg_metadata::Config for Test {
+	type DKGId = DKGId;
+	type RuntimeEvent = RuntimeEvent;
+	type OnAuthoritySetChangeHandler = ();
+	type OnDKGPublicKeyChangeHandler = ();
+	type OffChainAuthId = dkg_runtime_primitives::offchain::crypto::OffchainAuthId;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	type RefreshDelay = RefreshDelay;
+	type KeygenJailSentence = Period;
+	type SigningJailSentence = Period;
+	type DecayPercentage = DecayPercentage;
+	type Reputation = u128;
+	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+	type UnsignedInterval = ConstU64<3>;
+	type AuthorityIdOf = pallet_dkg_metadata::AuthorityIdOf<Self>;
+	type ProposalHandler = ();
+	type WeightInfo = pallet_dkg_metadata::weights::WebbWeight<Test>;
+}
+
+impl pallet_im_online::Config for Test {
+	type AuthorityId = UintAuthorityId;
+	type RuntimeEvent = RuntimeEvent;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	type ValidatorSet = Historical;
+	/// Unlike on chain, collators missing their heartbeats are reported, so offences reach
+	/// staking through `pallet_offences`
+	type ReportUnresponsiveness = Offences;
+	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+	type WeightInfo = ();
+	type MaxKeys = ConstU32<100>;
+	type MaxPeerInHeartbeats = ConstU32<100>;
+	type MaxPeerDataEncodingSize = ConstU32<1_000>;
+}
+
+impl pallet_offences::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type IdentificationTuple = pallet_session_historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ParachainStaking;
+}
+
+impl block_author::Config for Test {}
+
+parameter_types! {
+	pub const BlocksPerRound: u32 = 5;
+	pub const RewardPaymentDelay: u32 = 2;
+	pub const CollatorCommission: Perbill = Perbill::from_percent(20);
+	pub const ParachainBondReservePercent: Percent = Percent::from_percent(30);
+	pub const MinCollatorStk: Balance = 100;
+	pub const TreasuryAccount: AccountId = AccountId::new([99; 32]);
+}
+
+impl pallet_parachain_staking::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type MonetaryGovernanceOrigin = EnsureRoot<AccountId>;
+	type MaxTotalIssuance = ConstU128<{ u128::MAX }>;
+	type MinBlocksPerRound = ConstU32<3>;
+	type LeaveCandidatesDelay = ConstU32<2>;
+	type LeaveCancellationCutoff = ConstU32<0>;
+	type CandidateBondLessDelay = ConstU32<2>;
+	type LeaveDelegatorsDelay = ConstU32<2>;
+	type RevokeDelegationDelay = ConstU32<2>;
+	type DelegationBondLessDelay = ConstU32<2>;
+	type RewardPaymentDelay = RewardPaymentDelay;
+	type MinSelectedCandidates = ConstU32<5>;
+	type MaxTopDelegationsPerCandidate = ConstU32<4>;
+	type MaxBottomDelegationsPerCandidate = ConstU32<4>;
+	type MaxDelegationsPerDelegator = ConstU32<4>;
+	type MinCollatorStk = MinCollatorStk;
+	type MinCandidateStk = MinCollatorStk;
+	type MinDelegatorStk = ConstU128<5>;
+	type MinDelegation = ConstU128<3>;
+	type BlockAuthor = BlockAuthor;
+	type ValidatorIdOf = IdentityCollator;
+	type AccountIdOf = IdentityCollator;
+	type ValidatorId = AccountId;
+	type MaxInvulnerables = ConstU32<10>;
+	type ValidatorRegistration = Session;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type OnCollatorPayout = ();
+	type OnNewRound = ();
+	type OnDelegationKicked = ();
+	type MaxOfflineRounds = ConstU32<2>;
+	type PendingRequestRewards = PendingRequestRewards;
+	type RequestExpiryRounds = ConstU32<0>;
+	type CollatorLiveness = pallet_parachain_staking::AuthoredBlocks<Test>;
+	type DeferredDelegationThreshold = ConstU128<0>;
+	type MaxRewardHistoryRounds = ConstU32<4>;
+	type MaxExposurePageSize = ConstU32<100>;
+	type BasePointsPerBlock = ConstU32<20>;
+	type MaxBonusPointsPerBlock = ConstU32<0>;
+	type RemoteDelegatorOrigin = frame_system::EnsureSigned<AccountId>;
+	type MaxPayoutSplitShares = ConstU32<2>;
+	type RewardCurve = ();
+	type Slash = ();
+	type EmergencyUnbondPenalty = EmergencyUnbondPenalty;
+	type SlashDeferDuration = ConstU32<0>;
+	type SlashCancelOrigin = EnsureRoot<AccountId>;
+	type MaxDeferredSlashesPerRound = ConstU32<100>;
+	type MaxTotalDropPerBlock = MaxTotalDropPerBlock;
+	type CircuitBreaker = ();
+	type RemitReward = ();
+	type RemitRewardsEvery = ConstU32<2>;
+	type ElectionLookahead = ConstU64<0>;
+	type ElectionPageSize = ConstU32<16>;
+	type VestingBalance = ();
+	type SessionKeys = pallet_parachain_staking::PalletSessionKeys<Test>;
+	type RelayBlockNumber = RelayBlockNumber;
+	type TreasuryAccount = TreasuryAccount;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const PendingRequestRewards: pallet_parachain_staking::PendingRequestRewards =
+		pallet_parachain_staking::PendingRequestRewards::UntilExecution;
+	pub const EmergencyUnbondPenalty: Perbill = Perbill::from_percent(10);
+	pub const MaxTotalDropPerBlock: Percent = Percent::from_percent(0);
+}
+
+/// The relay chain advances along with the parachain
+pub struct RelayBlockNumber;
+impl sp_runtime::traits::BlockNumberProvider for RelayBlockNumber {
+	type BlockNumber = u32;
+
+	fn current_block_number() -> u32 {
+		System::block_number() as u32
+	}
+}
+
+/// Block author set by the tests, standing in for the nimbus author inherent
+#[frame_support::pallet]
+pub mod block_author {
+	use super::AccountId;
+	use frame_support::{pallet_prelude::*, traits::Get};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	pub(super) type BlockAuthor<T> = StorageValue<_, AccountId, OptionQuery>;
+
+	impl<T: Config> Pallet<T> {
+		pub fn set(author: AccountId) {
+			<BlockAuthor<T>>::put(author)
+		}
+	}
+
+	impl<T: Config> Get<AccountId> for Pallet<T> {
+		fn get() -> AccountId {
+			<BlockAuthor<T>>::get().unwrap_or_else(|| AccountId::new([0; 32]))
+		}
+	}
+}
+
+/// Account of test user `seed`
+pub fn account(seed: u8) -> AccountId {
+	AccountId::new([seed; 32])
+}
+
+/// Session keys of test user `seed`
+pub fn session_keys(seed: u8) -> MockSessionKeys {
+	MockSessionKeys {
+		dkg: DKGId::from(ecdsa::Public::from_raw([seed; 33])),
+		im_online: UintAuthorityId(seed as u64),
+	}
+}
+
+pub struct ExtBuilder {
+	// endowed accounts with balances
+	balances: Vec<(u8, Balance)>,
+	// [collator, amount]
+	collators: Vec<(u8, Balance)>,
+	// [delegator, collator, amount]
+	delegations: Vec<(u8, u8, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> ExtBuilder {
+		ExtBuilder { balances: vec![], collators: vec![], delegations: vec![] }
+	}
+}
+
+impl ExtBuilder {
+	pub fn with_balances(mut self, balances: Vec<(u8, Balance)>) -> Self {
+		self.balances = balances;
+		self
+	}
+
+	pub fn with_candidates(mut self, collators: Vec<(u8, Balance)>) -> Self {
+		self.collators = collators;
+		self
+	}
+
+	pub fn with_delegations(mut self, delegations: Vec<(u8, u8, Balance)>) -> Self {
+		self.delegations = delegations;
+		self
+	}
+
+	/// Builds the genesis the way the chain spec does: the genesis candidates are given session
+	/// keys, with which the first session and the DKG authorities are set up
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Test>()
+			.expect("Frame system builds valid default genesis config");
+
+		pallet_balances::GenesisConfig::<Test> {
+			balances: self
+				.balances
+				.into_iter()
+				.map(|(who, amount)| (account(who), amount))
+				.collect(),
+		}
+		.assimilate_storage(&mut t)
+		.expect("Pallet balances storage can be assimilated");
+		pallet_dkg_metadata::GenesisConfig::<Test> {
+			authorities: self.collators.iter().map(|(c, _)| session_keys(*c).dkg).collect(),
+			keygen_threshold: 2,
+			signature_threshold: 1,
+			authority_ids: self.collators.iter().map(|(c, _)| account(*c)).collect(),
+		}
+		.assimilate_storage(&mut t)
+		.expect("DKG storage can be assimilated");
+		pallet_parachain_staking::GenesisConfig::<Test> {
+			candidates: self.collators.iter().map(|(c, bond)| (account(*c), *bond)).collect(),
+			delegations: self
+				.delegations
+				.into_iter()
+				.map(|(d, c, amount)| (account(d), account(c), amount, Percent::zero()))
+				.collect(),
+			inflation_config: InflationInfo {
+				expect: Range { min: 700, ideal: 700, max: 700 },
+				annual: Range {
+					min: Perbill::from_percent(50),
+					ideal: Perbill::from_percent(50),
+					max: Perbill::from_percent(50),
+				},
+				// unrealistically high parameterization, only for testing
+				round: Range {
+					min: Perbill::from_percent(5),
+					ideal: Perbill::from_percent(5),
+					max: Perbill::from_percent(5),
+				},
+			},
+			collator_commission: CollatorCommission::get(),
+			parachain_bond_reserve_percent: ParachainBondReservePercent::get(),
+			blocks_per_round: BlocksPerRound::get(),
+		}
+		.assimilate_storage(&mut t)
+		.expect("Parachain Staking's storage can be assimilated");
+		pallet_session::GenesisConfig::<Test> {
+			keys: self
+				.collators
+				.iter()
+				.map(|(c, _)| (account(*c), account(*c), session_keys(*c)))
+				.collect(),
+		}
+		.assimilate_storage(&mut t)
+		.expect("Session storage can be assimilated");
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+/// Finishes the current block, authored by `author` if given, and starts the next one the way
+/// the executive does, leaving the whole block to `on_idle`. Returns the new block number.
+pub fn roll_one_block(author: Option<AccountId>) -> BlockNumber {
+	if let Some(author) = author {
+		BlockAuthor::set(author);
+		ParachainStaking::on_finalize(System::block_number());
+	}
+	System::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
+	System::reset_events();
+	System::on_initialize(System::block_number());
+	Session::on_initialize(System::block_number());
+	ParachainStaking::on_initialize(System::block_number());
+	ParachainStaking::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+	System::block_number()
+}
+
+/// Rolls to the first block of `round`, each block authored in turn by the collators selected
+/// for its round. The session genesis opens the first two sessions, so the chain starts past
+/// round 1. Returns the events of the blocks played.
+pub fn roll_to_round_begin(round: u32) -> Vec<RuntimeEvent> {
+	let mut events = Vec::new();
+	while ParachainStaking::round().current < round {
+		let selected = ParachainStaking::selected_candidates();
+		let author = (!selected.is_empty())
+			.then(|| selected[System::block_number() as usize % selected.len()].clone());
+		roll_one_block(author);
+		events.extend(System::events().into_iter().map(|r| r.event));
+	}
+	events
+}
+
+/// Round the chain is in
+pub fn current_round() -> u32 {
+	ParachainStaking::round().current
+}
@@ -0,0 +1,130 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+use crate::mock::*;
+use frame_support::assert_ok;
+use pallet_parachain_staking::Event as StakingEvent;
+
+fn staking_events(events: &[RuntimeEvent]) -> Vec<StakingEvent<Test>> {
+	events
+		.iter()
+		.filter_map(|e| match e {
+			RuntimeEvent::ParachainStaking(inner) => Some(inner.clone()),
+			_ => None,
+		})
+		.collect()
+}
+
+fn genesis() -> ExtBuilder {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 2_000), (2, 2_000), (3, 2_000), (4, 2_000), (5, 500)])
+		.with_candidates(vec![(1, 1_000), (2, 1_000), (3, 1_000)])
+		.with_delegations(vec![(5, 1, 400)])
+}
+
+#[test]
+fn elected_candidates_validate_from_the_next_session() {
+	genesis().build().execute_with(|| {
+		let start = current_round();
+		assert_eq!(Session::validators(), vec![account(1), account(2), account(3)]);
+
+		assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(account(4)), 1_000, 3));
+		assert_ok!(ParachainStaking::rotate_and_set_keys(
+			RuntimeOrigin::signed(account(4)),
+			session_keys(4),
+			vec![],
+		));
+
+		// selected when the round changes, queued by the session it opens
+		roll_to_round_begin(start + 1);
+		assert!(ParachainStaking::selected_candidates().contains(&account(4)));
+		assert!(!Session::validators().contains(&account(4)));
+		assert!(Session::queued_keys().iter().any(|(who, _)| *who == account(4)));
+
+		roll_to_round_begin(start + 2);
+		assert!(Session::validators().contains(&account(4)));
+	});
+}
+
+#[test]
+fn collators_are_paid_for_the_blocks_they_author() {
+	genesis().build().execute_with(|| {
+		let start = current_round();
+		let events = roll_to_round_begin(start + RewardPaymentDelay::get() + 1);
+
+		let rewarded = |who: AccountId| {
+			staking_events(&events).into_iter().any(
+				|e| matches!(e, StakingEvent::Rewarded { account, rewards } if account == who && rewards > 0),
+			)
+		};
+		// every collator authored blocks, and the delegator backs one of them
+		assert!(rewarded(account(1)));
+		assert!(rewarded(account(2)));
+		assert!(rewarded(account(3)));
+		assert!(rewarded(account(5)));
+	});
+}
+
+#[test]
+fn dkg_authorities_follow_the_session_validators() {
+	genesis().build().execute_with(|| {
+		let start = current_round();
+		let authorities = DKG::current_authorities_accounts();
+		assert!([1, 2, 3].iter().all(|c| authorities.contains(&account(*c))));
+		assert!(!authorities.contains(&account(4)));
+
+		assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(account(4)), 1_000, 3));
+		assert_ok!(ParachainStaking::rotate_and_set_keys(
+			RuntimeOrigin::signed(account(4)),
+			session_keys(4),
+			vec![],
+		));
+		roll_to_round_begin(start + 1);
+		assert!(DKG::next_authorities_accounts().contains(&account(4)));
+
+		roll_to_round_begin(start + 2);
+		assert!(DKG::current_authorities_accounts().contains(&account(4)));
+	});
+}
+
+#[test]
+fn collators_missing_heartbeats_are_slashed() {
+	genesis().build().execute_with(|| {
+		let start = current_round();
+		// no collator sends a heartbeat, so all of them are reported when the session ends
+		let events = roll_to_round_begin(start + 1);
+
+		let offline = events
+			.iter()
+			.filter(|e| {
+				matches!(e, RuntimeEvent::ImOnline(pallet_im_online::Event::SomeOffline { .. }))
+			})
+			.count();
+		assert_eq!(offline, 1);
+		// every validator offline is the largest unresponsiveness slash, 7%
+		for collator in [1, 2, 3] {
+			assert!(staking_events(&events).contains(&StakingEvent::CandidateSlashed {
+				candidate: account(collator),
+				amount: 70,
+				new_bond: 930,
+			}));
+			assert_eq!(
+				ParachainStaking::candidate_info(account(collator)).map(|info| info.bond),
+				Some(930)
+			);
+		}
+		assert_eq!(Balances::reserved_balance(account(1)), 930);
+	});
+}
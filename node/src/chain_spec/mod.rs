@@ -28,20 +28,23 @@ use sp_runtime::{
 };
 use tangle_rococo_runtime::{
 	nimbus_session_adapter::{NimbusId, VrfId},
-	AccountId, AssetRegistryConfig, AuraId, ClaimsConfig, DKGId, HasherBn254Config, ImOnlineConfig,
-	ImOnlineId, MerkleTreeBn254Config, MixerBn254Config, MixerVerifierBn254Config,
-	ParachainStakingConfig, Signature, VAnchorBn254Config, VAnchorVerifierConfig, HOURS, MILLIUNIT,
-	UNIT,
+	precompiles::TanglePrecompiles,
+	AccountId, AssetRegistryConfig, AuraId, ClaimsConfig, DKGId, EVMConfig, HasherBls381Config,
+	HasherBn254Config, ImOnlineConfig, ImOnlineId, MerkleTreeBls381Config, MerkleTreeBn254Config,
+	MixerBn254Config, MixerVerifierBls381Config, MixerVerifierBn254Config, ParachainStakingConfig,
+	Runtime, Signature, VAnchorBls381Config, VAnchorBn254Config, VAnchorVerifierBls381Config,
+	VAnchorVerifierConfig, MILLIUNIT, SESSION_PERIOD_BLOCKS, UNIT,
 };
 
 pub mod minerva_testnet_fixtures;
 pub mod rococo;
+pub mod staking_genesis;
 
 /// Specialized `ChainSpec` for the normal parachain runtime.
 pub type ChainSpec = sc_service::GenericChainSpec<tangle_rococo_runtime::GenesisConfig, Extensions>;
 const COLLATOR_COMMISSION: Perbill = Perbill::from_percent(20);
 const PARACHAIN_BOND_RESERVE_PERCENT: Percent = Percent::from_percent(30);
-const BLOCKS_PER_ROUND: u32 = HOURS;
+const BLOCKS_PER_ROUND: u32 = SESSION_PERIOD_BLOCKS;
 
 /// Helper function to generate a crypto pair from seed
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
@@ -138,6 +141,29 @@ where
 }
 
 /// Convert public keys to Acco, Aura and DKG keys
+/// EVM genesis giving every precompile address code that reverts, so Solidity's
+/// `extcodesize` check passes when contracts call a precompile
+pub fn evm_genesis() -> EVMConfig {
+	// PUSH1 0x00 PUSH1 0x00 REVERT
+	let revert_bytecode = vec![0x60, 0x00, 0x60, 0x00, 0xFD];
+	EVMConfig {
+		accounts: TanglePrecompiles::<Runtime>::used_addresses()
+			.into_iter()
+			.map(|address| {
+				(
+					address,
+					pallet_evm::GenesisAccount {
+						nonce: Default::default(),
+						balance: Default::default(),
+						storage: Default::default(),
+						code: revert_bytecode.clone(),
+					},
+				)
+			})
+			.collect(),
+	}
+}
+
 fn generate_invulnerables<PK: Clone + Into<AccountId>>(
 	public_keys: &[(PK, DKGId)],
 ) -> Vec<(AccountId, AuraId, DKGId, NimbusId, VrfId, ImOnlineId)> {
@@ -230,6 +256,60 @@ pub fn development_config(id: ParaId) -> ChainSpec {
 	)
 }
 
+/// Chain spec of the standalone `--dev` node, with Alice as its only collator
+pub fn standalone_dev_config(id: ParaId) -> ChainSpec {
+	let mut properties = sc_chain_spec::Properties::new();
+	properties.insert("tokenSymbol".into(), "tTNT".into());
+	properties.insert("tokenDecimals".into(), 18u32.into());
+	properties.insert("ss58Format".into(), 42.into());
+
+	ChainSpec::from_genesis(
+		// Name
+		"Standalone Development",
+		// ID
+		"tangle-standalone-dev",
+		ChainType::Development,
+		move || {
+			testnet_genesis(
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				vec![(
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_collator_keys_from_seed("Alice"),
+					get_dkg_keys_from_seed("Alice"),
+					get_nimbus_keys_from_seed("Alice"),
+					get_vrf_keys_from_seed("Alice"),
+					get_im_online_keys_from_seed("Alice"),
+				)],
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+					get_account_id_from_seed::<sr25519::Public>("Dave"),
+					get_account_id_from_seed::<sr25519::Public>("Eve"),
+					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+				],
+				id,
+			)
+		},
+		// Bootnodes
+		Vec::new(),
+		// Telemetry
+		None,
+		// Protocol ID
+		Some("tangle-standalone-dev"),
+		// Fork ID
+		None,
+		// Properties
+		Some(properties),
+		// Extensions
+		Extensions {
+			// unused, the standalone node does not follow a relay chain
+			relay_chain: "dev".into(),
+			para_id: id.into(),
+		},
+	)
+}
+
 pub fn local_testnet_config(id: ParaId) -> ChainSpec {
 	// Give your base currency a unit name and decimal places
 	let mut properties = sc_chain_spec::Properties::new();
@@ -367,6 +447,9 @@ fn testnet_genesis(
 	log::info!("Bn254 x5 w3 params");
 	let bn254_x5_3_params = setup_params::<ark_bn254::Fr>(curve_bn254, 5, 3);
 
+	log::info!("Bls381 x5 w3 params");
+	let bls381_x5_3_params = setup_params::<ark_bls12_381::Fr>(Curve::Bls381, 5, 3);
+
 	log::info!("Verifier params for mixer");
 	let mixer_verifier_bn254_params = {
 		let vk_bytes = include_bytes!("../../../verifying_keys/mixer/bn254/verifying_key.bin");
@@ -461,8 +544,34 @@ fn testnet_genesis(
 			]),
 			phantom: Default::default(),
 		},
+		// no BLS12-381 circuits are published yet, their verifying keys and vanchors are set up
+		// by governance through `force_set_parameters` and `create`
+		hasher_bls_381: HasherBls381Config {
+			parameters: Some(bls381_x5_3_params.to_bytes()),
+			phantom: Default::default(),
+		},
+		mixer_verifier_bls_381: MixerVerifierBls381Config {
+			parameters: None,
+			phantom: Default::default(),
+		},
+		merkle_tree_bls_381: MerkleTreeBls381Config {
+			phantom: Default::default(),
+			default_hashes: None,
+		},
+		v_anchor_bls_381: VAnchorBls381Config {
+			max_deposit_amount: 1_000_000 * UNIT,
+			min_withdraw_amount: 0,
+			vanchors: vec![],
+			phantom: Default::default(),
+		},
+		v_anchor_verifier_bls_381: VAnchorVerifierBls381Config {
+			parameters: None,
+			phantom: Default::default(),
+		},
 		treasury: Default::default(),
 		vesting: Default::default(),
+		evm: evm_genesis(),
+		ethereum: Default::default(),
 		parachain_staking: ParachainStakingConfig {
 			candidates: invulnerables
 				.iter()
@@ -12,19 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::chain_spec::*;
+use crate::chain_spec::{staking_genesis::StakingGenesis, *};
 use arkworks_setups::{common::setup_params, Curve};
 use cumulus_primitives_core::ParaId;
 use hex_literal::hex;
 use sc_service::ChainType;
 use sp_core::{crypto::UncheckedInto, sr25519};
 use tangle_rococo_runtime::{
-	AccountId, AssetRegistryConfig, AuraId, ClaimsConfig, DKGId, HasherBn254Config, ImOnlineConfig,
-	ImOnlineId, MerkleTreeBn254Config, MixerBn254Config, MixerVerifierBn254Config,
-	ParachainStakingConfig, VAnchorBn254Config, VAnchorVerifierConfig, MILLIUNIT, UNIT,
+	AccountId, AssetRegistryConfig, AuraId, ClaimsConfig, DKGId, HasherBls381Config,
+	HasherBn254Config, ImOnlineConfig, ImOnlineId, MerkleTreeBls381Config, MerkleTreeBn254Config,
+	MixerBn254Config, MixerVerifierBls381Config, MixerVerifierBn254Config, ParachainStakingConfig,
+	VAnchorBls381Config, VAnchorBn254Config, VAnchorVerifierBls381Config, VAnchorVerifierConfig,
+	MILLIUNIT, UNIT,
 };
 
-pub fn tangle_alpha_config(id: ParaId) -> ChainSpec {
+pub fn tangle_alpha_config(id: ParaId, staking: StakingGenesis) -> ChainSpec {
 	// Give your base currency a unit name and decimal places
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "TNT".into());
@@ -74,6 +76,7 @@ pub fn tangle_alpha_config(id: ParaId) -> ChainSpec {
 					hex!["28714d0740d6b321ad67b8e1a4edd0b53376f735bd10e4904a2c49167bcb7841"].into(),
 				],
 				id,
+				&staking,
 			)
 		},
 		// Bootnodes
@@ -94,7 +97,7 @@ pub fn tangle_alpha_config(id: ParaId) -> ChainSpec {
 	)
 }
 
-pub fn tangle_rococo_config(id: ParaId) -> ChainSpec {
+pub fn tangle_rococo_config(id: ParaId, staking: StakingGenesis) -> ChainSpec {
 	// Give your base currency a unit name and decimal places
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "TNT".into());
@@ -144,6 +147,7 @@ pub fn tangle_rococo_config(id: ParaId) -> ChainSpec {
 					hex!["28714d0740d6b321ad67b8e1a4edd0b53376f735bd10e4904a2c49167bcb7841"].into(),
 				],
 				id,
+				&staking,
 			)
 		},
 		// Bootnodes
@@ -169,12 +173,36 @@ fn rococo_genesis(
 	invulnerables: Vec<(AccountId, AuraId, DKGId, NimbusId, VrfId, ImOnlineId)>,
 	endowed_accounts: Vec<AccountId>,
 	id: ParaId,
+	staking: &StakingGenesis,
 ) -> tangle_rococo_runtime::GenesisConfig {
+	let invulnerables = if staking.invulnerables.is_empty() {
+		invulnerables
+	} else {
+		generate_invulnerables(
+			&staking
+				.invulnerables
+				.iter()
+				.map(|i| (i.account.clone(), i.dkg_key.clone()))
+				.collect::<Vec<_>>(),
+		)
+	};
+	// stakers from the staking genesis need funds to bond
+	let mut endowed_accounts = endowed_accounts;
+	for staker in staking.stakers() {
+		if !endowed_accounts.contains(&staker) {
+			endowed_accounts.push(staker);
+		}
+	}
+	let invulnerable_accounts: Vec<AccountId> = invulnerables.iter().map(|x| x.0.clone()).collect();
+
 	let curve_bn254 = Curve::Bn254;
 
 	log::info!("Bn254 x5 w3 params");
 	let bn254_x5_3_params = setup_params::<ark_bn254::Fr>(curve_bn254, 5, 3);
 
+	log::info!("Bls381 x5 w3 params");
+	let bls381_x5_3_params = setup_params::<ark_bls12_381::Fr>(Curve::Bls381, 5, 3);
+
 	log::info!("Verifier params for mixer");
 	let mixer_verifier_bn254_params = {
 		let vk_bytes = include_bytes!("../../../verifying_keys/mixer/bn254/verifying_key.bin");
@@ -269,17 +297,40 @@ fn rococo_genesis(
 			]),
 			phantom: Default::default(),
 		},
+		// no BLS12-381 circuits are published yet, their verifying keys and vanchors are set up
+		// by governance through `force_set_parameters` and `create`
+		hasher_bls_381: HasherBls381Config {
+			parameters: Some(bls381_x5_3_params.to_bytes()),
+			phantom: Default::default(),
+		},
+		mixer_verifier_bls_381: MixerVerifierBls381Config {
+			parameters: None,
+			phantom: Default::default(),
+		},
+		merkle_tree_bls_381: MerkleTreeBls381Config {
+			phantom: Default::default(),
+			default_hashes: None,
+		},
+		v_anchor_bls_381: VAnchorBls381Config {
+			max_deposit_amount: 1_000_000 * UNIT,
+			min_withdraw_amount: 0,
+			vanchors: vec![],
+			phantom: Default::default(),
+		},
+		v_anchor_verifier_bls_381: VAnchorVerifierBls381Config {
+			parameters: None,
+			phantom: Default::default(),
+		},
 		treasury: Default::default(),
 		vesting: Default::default(),
+		evm: evm_genesis(),
+		ethereum: Default::default(),
 		parachain_staking: ParachainStakingConfig {
-			candidates: invulnerables
-				.iter()
-				.cloned()
-				.map(|(account, _, _, _, _, _)| {
-					(account, tangle_rococo_runtime::staking::NORMAL_COLLATOR_MINIMUM_STAKE)
-				})
-				.collect(),
-			delegations: vec![], //delegations
+			candidates: staking.candidates(
+				&invulnerable_accounts,
+				tangle_rococo_runtime::staking::NORMAL_COLLATOR_MINIMUM_STAKE,
+			),
+			delegations: staking.delegations(),
 			inflation_config: tangle_rococo_runtime::staking::inflation_config::<
 				tangle_rococo_runtime::Runtime,
 			>(),
@@ -0,0 +1,128 @@
+// Copyright 2022 Webb Technologies Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Staking genesis read from a JSON file passed with `--staking-genesis`, so testnets can set
+//! their collators and delegations without recompiling the node.
+//!
+//! Accounts and DKG keys are SS58 encoded, amounts are in the smallest unit:
+//!
+//! ```json
+//! {
+//!   "invulnerables": [{ "account": "5Grw...", "dkgKey": "KW39..." }],
+//!   "candidates": [{ "account": "5Grw...", "bond": 1000000000000000000000 }],
+//!   "delegations": [
+//!     {
+//!       "delegator": "5FHn...",
+//!       "candidate": "5Grw...",
+//!       "amount": 5000000000000000000,
+//!       "autoCompound": 50
+//!     }
+//!   ]
+//! }
+//! ```
+
+use serde::Deserialize;
+use sp_runtime::Percent;
+use std::path::Path;
+use tangle_rococo_runtime::{AccountId, Balance, DKGId};
+
+/// An invulnerable collator; its other session keys are derived from the account
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisInvulnerable {
+	pub account: AccountId,
+	pub dkg_key: DKGId,
+}
+
+/// A collator candidate and its self bond
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisCandidate {
+	pub account: AccountId,
+	pub bond: Balance,
+}
+
+/// A delegation and the percent of its rewards to auto-compound
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisDelegation {
+	pub delegator: AccountId,
+	pub candidate: AccountId,
+	pub amount: Balance,
+	#[serde(default)]
+	pub auto_compound: u8,
+}
+
+/// Staking genesis overriding the built in one of a chain spec. Sections left out of the file
+/// keep the chain spec's defaults.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct StakingGenesis {
+	#[serde(default)]
+	pub invulnerables: Vec<GenesisInvulnerable>,
+	#[serde(default)]
+	pub candidates: Vec<GenesisCandidate>,
+	#[serde(default)]
+	pub delegations: Vec<GenesisDelegation>,
+}
+
+impl StakingGenesis {
+	/// Read the staking genesis from the JSON file at `path`
+	pub fn from_json_file(path: &Path) -> Result<Self, String> {
+		let file = std::fs::File::open(path)
+			.map_err(|e| format!("Error opening staking genesis file {:?}: {}", path, e))?;
+		let genesis: Self = serde_json::from_reader(std::io::BufReader::new(file))
+			.map_err(|e| format!("Error parsing staking genesis file {:?}: {}", path, e))?;
+		if genesis.delegations.iter().any(|d| d.auto_compound > 100) {
+			return Err(format!("Auto-compound percent above 100 in {:?}", path))
+		}
+		Ok(genesis)
+	}
+
+	/// Candidates for the staking genesis config, bonding `default_bond` for each invulnerable
+	/// when the file lists no candidates
+	pub fn candidates(
+		&self,
+		invulnerables: &[AccountId],
+		default_bond: Balance,
+	) -> Vec<(AccountId, Balance)> {
+		if self.candidates.is_empty() {
+			return invulnerables.iter().cloned().map(|account| (account, default_bond)).collect()
+		}
+		self.candidates.iter().map(|c| (c.account.clone(), c.bond)).collect()
+	}
+
+	/// Delegations for the staking genesis config
+	pub fn delegations(&self) -> Vec<(AccountId, AccountId, Balance, Percent)> {
+		self.delegations
+			.iter()
+			.map(|d| {
+				(
+					d.delegator.clone(),
+					d.candidate.clone(),
+					d.amount,
+					Percent::from_percent(d.auto_compound),
+				)
+			})
+			.collect()
+	}
+
+	/// Accounts that need an endowment to stake: candidates and delegators
+	pub fn stakers(&self) -> impl Iterator<Item = AccountId> + '_ {
+		self.candidates
+			.iter()
+			.map(|c| c.account.clone())
+			.chain(self.delegations.iter().map(|d| d.delegator.clone()))
+	}
+}
@@ -12,15 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sc_cli::KeySubcommand;
+use crate::{key::KeyCmd, service_dev::Sealing};
 use std::path::PathBuf;
+use tangle_rococo_runtime::AccountId;
 
 /// Sub-commands supported by the collator.
 #[derive(Debug, clap::Subcommand)]
 pub enum Subcommand {
 	/// Key management cli utilities
 	#[clap(subcommand)]
-	Key(KeySubcommand),
+	Key(KeyCmd),
 
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
@@ -81,6 +82,26 @@ pub struct Cli {
 	#[clap(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// JSON file with the invulnerables, candidates and delegations to use as the staking
+	/// genesis of the `tangle-alpha` and `tangle-rococo` chain specs.
+	#[clap(long, value_name = "PATH")]
+	pub staking_genesis: Option<PathBuf>,
+
+	/// Seal blocks locally instead of collating them for a relay chain, e.g. for integration
+	/// tests producing blocks through `engine_createBlock`. `--dev` seals instantly by default.
+	#[clap(long, arg_enum)]
+	pub sealing: Option<Sealing>,
+
+	/// Secret URI of the nimbus key named as the author of blocks sealed with `--sealing`.
+	/// Defaults to the development key, e.g. `//Alice`.
+	#[clap(long, value_name = "SURI")]
+	pub sealing_author: Option<String>,
+
+	/// Account of the collator whose candidate rank and delegation count are reported in the
+	/// staking Prometheus metrics.
+	#[clap(long, value_name = "SS58")]
+	pub staking_metrics_account: Option<AccountId>,
+
 	/// Relay chain arguments
 	#[clap(raw = true)]
 	pub relay_chain_args: Vec<String>,
@@ -14,7 +14,7 @@
 // limitations under the License.
 
 use crate::{
-	chain_spec,
+	chain_spec::{self, staking_genesis::StakingGenesis},
 	cli::{Cli, RelayChainCli, Subcommand},
 	service::{new_partial, rococo::Executor as RococoExecutor},
 };
@@ -23,6 +23,7 @@ use cumulus_client_cli::generate_genesis_block;
 use cumulus_primitives_core::ParaId;
 use frame_benchmarking_cli::{BenchmarkCmd, SUBSTRATE_REFERENCE_HARDWARE};
 use log::info;
+use nimbus_primitives::NimbusId;
 use sc_cli::{
 	ChainSpec, CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams,
 	NetworkParams, Result, RuntimeVersion, SharedParams, SubstrateCli,
@@ -31,9 +32,9 @@ use sc_service::{
 	config::{BasePath, PrometheusConfig},
 	TaskManager,
 };
-use sp_core::hexdisplay::HexDisplay;
+use sp_core::{hexdisplay::HexDisplay, sr25519, Pair};
 use sp_runtime::traits::{AccountIdConversion, Block as BlockT};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::Path};
 use tangle_rococo_runtime::{Block, RuntimeApi};
 
 pub enum Runtime {
@@ -54,13 +55,23 @@ fn runtime(_id: &str) -> Runtime {
 	Runtime::Rococo
 }
 
-fn load_spec(id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
+fn load_spec(
+	id: &str,
+	staking_genesis: Option<&Path>,
+) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
+	let staking = || -> std::result::Result<StakingGenesis, String> {
+		staking_genesis.map_or(Ok(Default::default()), StakingGenesis::from_json_file)
+	};
 	Ok(match id {
 		"tangle-dev" => Box::new(chain_spec::development_config(2000.into())),
+		// Standalone node sealing its own blocks, started with `--dev`
+		"dev" => Box::new(chain_spec::standalone_dev_config(2000.into())),
 		// Independency relay chain config
-		"tangle-alpha" => Box::new(chain_spec::rococo::tangle_alpha_config(2000.into())),
+		"tangle-alpha" =>
+			Box::new(chain_spec::rococo::tangle_alpha_config(2000.into(), staking()?)),
 		/* Rococo para-id 4006 */
-		"tangle-rococo" => Box::new(chain_spec::rococo::tangle_rococo_config(4006.into())),
+		"tangle-rococo" =>
+			Box::new(chain_spec::rococo::tangle_rococo_config(4006.into(), staking()?)),
 		// Currently tangle-minerva testnet
 		// TODO : Switch to kusama runtime once we have it
 		"tangle" => Box::new(chain_spec::tangle_minerva_config(2000.into())),
@@ -99,7 +110,7 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
-		load_spec(id)
+		load_spec(id, self.staking_genesis.as_deref())
 	}
 
 	fn native_runtime_version(chain_spec: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
@@ -300,6 +311,28 @@ pub fn run() -> Result<()> {
 			let collator_options = cli.run.collator_options();
 
 			runner.run_node_until_exit(|config| async move {
+				let sealing = cli.sealing.or_else(|| {
+					cli.run
+						.base
+						.shared_params
+						.is_dev()
+						.then(|| crate::service_dev::Sealing::Instant)
+				});
+				if let Some(sealing) = sealing {
+					let suri = cli
+						.sealing_author
+						.clone()
+						.or_else(|| config.dev_key_seed.clone())
+						.unwrap_or_else(|| "//Alice".into());
+					let author: NimbusId = sr25519::Pair::from_string(&suri, None)
+						.map_err(|e| format!("Invalid sealing author {}: {:?}", suri, e))?
+						.public()
+						.into();
+					info!("Sealing blocks with {:?} sealing, authored by {:?}", sealing, author);
+					return crate::service_dev::start_dev_node(config, sealing, author)
+						.map_err(Into::into)
+				}
+
 				let hwbench = if !cli.no_hardware_benchmarks {
 					config.database.path().map(|database_path| {
 						let _ = std::fs::create_dir_all(&database_path);
@@ -344,6 +377,7 @@ pub fn run() -> Result<()> {
 					collator_options,
 					id,
 					hwbench,
+					cli.staking_metrics_account.clone(),
 				)
 				.await
 				.map(|r| r.0)
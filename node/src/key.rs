@@ -0,0 +1,106 @@
+// Copyright 2022 Webb Technologies Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key management sub-commands of the collator
+
+use codec::Encode;
+use sc_cli::{Error, KeySubcommand, SubstrateCli};
+use sp_core::{crypto::KeyTypeId, ecdsa, hexdisplay::HexDisplay, sr25519, Pair};
+use sp_runtime::app_crypto::AppKey;
+use tangle_rococo_runtime::{
+	nimbus_session_adapter::{NimbusId, VrfId},
+	AuraId, DKGId, ImOnlineId, RuntimeCall, SessionKeys,
+};
+
+/// Key management cli utilities
+#[derive(Debug, clap::Subcommand)]
+pub enum KeyCmd {
+	#[clap(flatten)]
+	BaseCli(KeySubcommand),
+
+	/// Generate every session key of a collator, with the `session.set_keys` call data
+	/// registering them.
+	GenerateCollatorBundle(GenerateCollatorBundleCmd),
+}
+
+impl KeyCmd {
+	/// Run the key sub-command
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		match self {
+			KeyCmd::BaseCli(cmd) => cmd.run(cli),
+			KeyCmd::GenerateCollatorBundle(cmd) => cmd.run(),
+		}
+	}
+}
+
+/// Generate the aura, dkg, nimbus, vrf and im_online keys of a collator from one secret
+#[derive(Debug, clap::Parser)]
+pub struct GenerateCollatorBundleCmd {
+	/// Secret URI to derive the keys from. A new secret phrase is generated when left out.
+	#[clap(long)]
+	pub suri: Option<String>,
+
+	/// Password of the secret URI
+	#[clap(long)]
+	pub password: Option<String>,
+}
+
+impl GenerateCollatorBundleCmd {
+	/// Print the keys, the encoded `SessionKeys` and the `session.set_keys` call data
+	pub fn run(&self) -> Result<(), Error> {
+		let suri = match &self.suri {
+			Some(suri) => suri.clone(),
+			None => {
+				let (_, phrase, _) = sr25519::Pair::generate_with_phrase(None);
+				println!("Secret phrase: {}", phrase);
+				phrase
+			},
+		};
+		let password = self.password.as_deref();
+		// aura, nimbus, vrf and im_online share the sr25519 key, as in the chain specs
+		let sr25519 = sr25519::Pair::from_string(&suri, password)
+			.map_err(|e| Error::Input(format!("Invalid secret URI: {:?}", e)))?
+			.public();
+		let ecdsa = ecdsa::Pair::from_string(&suri, password)
+			.map_err(|e| Error::Input(format!("Invalid secret URI: {:?}", e)))?
+			.public();
+		let keys = SessionKeys {
+			aura: AuraId::from(sr25519),
+			dkg: DKGId::from(ecdsa),
+			nimbus: NimbusId::from(sr25519),
+			vrf: VrfId::from(sr25519),
+			im_online: ImOnlineId::from(sr25519),
+		};
+
+		let print_key = |name: &str, key_type: KeyTypeId, scheme: &str, public: &[u8]| {
+			println!(
+				"{:<10} key type {} ({}): 0x{}",
+				name,
+				String::from_utf8_lossy(&key_type.0),
+				scheme,
+				HexDisplay::from(&public)
+			);
+		};
+		print_key("aura", AuraId::ID, "sr25519", sr25519.as_ref());
+		print_key("dkg", DKGId::ID, "ecdsa", ecdsa.as_ref());
+		print_key("nimbus", NimbusId::ID, "sr25519", sr25519.as_ref());
+		print_key("vrf", VrfId::ID, "sr25519", sr25519.as_ref());
+		print_key("im_online", ImOnlineId::ID, "sr25519", sr25519.as_ref());
+
+		println!("Session keys: 0x{}", HexDisplay::from(&keys.encode()));
+		let call = RuntimeCall::Session(pallet_session::Call::set_keys { keys, proof: Vec::new() });
+		println!("set_keys call data: 0x{}", HexDisplay::from(&call.encode()));
+		Ok(())
+	}
+}
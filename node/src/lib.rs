@@ -17,3 +17,5 @@ pub mod chain_spec;
 pub mod rpc;
 pub mod service;
 pub mod service_aura;
+pub mod service_dev;
+pub mod staking_metrics;
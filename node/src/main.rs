@@ -22,8 +22,11 @@ mod service;
 mod aura_or_nimbus_consensus;
 mod cli;
 mod command;
+mod key;
 mod rpc;
 mod service_aura;
+mod service_dev;
+mod staking_metrics;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
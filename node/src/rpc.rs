@@ -5,16 +5,23 @@
 
 #![warn(missing_docs)]
 
+pub mod eth;
+mod leaves;
+mod staking;
+
 use std::sync::Arc;
 
-use tangle_rococo_runtime::{opaque::Block, AccountId, Balance, Index as Nonce};
+use tangle_rococo_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index as Nonce};
 
+use futures::channel::mpsc::Sender;
 use sc_client_api::AuxStore;
+use sc_consensus_manual_seal::EngineCommand;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use webb_primitives::runtime::Element;
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
@@ -27,6 +34,8 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Channel to ask a manual sealing standalone node for blocks
+	pub command_sink: Option<Sender<EngineCommand<Hash>>>,
 }
 
 /// Instantiate all RPC extensions.
@@ -44,15 +53,31 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: tangle_relayer_runtime_api::MerkleTreeLeavesApi<Block, Element>,
+	C::Api: sp_api::Metadata<Block>,
+	C::Api: pallet_parachain_staking_rpc_runtime_api::ParachainStakingApi<
+		Block,
+		AccountId,
+		Balance,
+		BlockNumber,
+	>,
 	P: TransactionPool + Sync + Send + 'static,
 {
 	use frame_rpc_system::{System, SystemApiServer};
+	use leaves::{Leaves, LeavesApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
+	use staking::{StakingCalls, StakingCallsApiServer};
 
 	let mut module = RpcExtension::new(());
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, pool, deny_unsafe, command_sink } = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(StakingCalls::new(client.clone()).into_rpc())?;
+	module.merge(Leaves::<_, _, Element>::new(client).into_rpc())?;
+	if let Some(command_sink) = command_sink {
+		module.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
 	Ok(module)
 }
@@ -0,0 +1,176 @@
+//! Ethereum JSON-RPC, so Ethereum wallets and Solidity tooling can talk to the EVM of the
+//! runtime.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use fc_rpc::{
+	EthBlockDataCacheTask, OverrideHandle, RuntimeApiStorageOverride, SchemaV1Override,
+	SchemaV2Override, SchemaV3Override, StorageOverride,
+};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use fp_storage::EthereumStorageSchema;
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	client::BlockchainEvents,
+	AuxStore,
+};
+use sc_network::NetworkService;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_transaction_pool::{ChainApi, Pool};
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::ProvideRuntimeApi;
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_runtime::traits::BlakeTwo256;
+use tangle_rococo_runtime::{opaque::Block, Hash, TransactionConverter};
+
+use super::RpcExtension;
+
+/// Dependencies of the Ethereum RPC
+pub struct EthDeps<C, P, A: ChainApi> {
+	/// The client instance to use.
+	pub client: Arc<C>,
+	/// Transaction pool instance.
+	pub pool: Arc<P>,
+	/// Graph of the transaction pool.
+	pub graph: Arc<Pool<A>>,
+	/// The node's network service.
+	pub network: Arc<NetworkService<Block, Hash>>,
+	/// Whether the node authors blocks.
+	pub is_authority: bool,
+	/// Ethereum block and transaction mappings kept by frontier.
+	pub frontier_backend: Arc<fc_db::Backend<Block>>,
+	/// Readers of the Ethereum storage of each runtime storage schema.
+	pub overrides: Arc<OverrideHandle<Block>>,
+	/// Cache of Ethereum blocks and transaction statuses.
+	pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+	/// Log filters installed through `eth_newFilter`, if filters are enabled.
+	pub filter_pool: Option<FilterPool>,
+	/// Most logs returned by one `eth_getLogs` query.
+	pub max_past_logs: u32,
+	/// Fee history of recent blocks.
+	pub fee_history_cache: FeeHistoryCache,
+	/// Blocks kept in the fee history.
+	pub fee_history_cache_limit: FeeHistoryCacheLimit,
+	/// Multiplier of the block gas limit allowed to `eth_call` and `eth_estimateGas`.
+	pub execute_gas_limit_multiplier: u64,
+}
+
+/// Readers of the Ethereum storage of every runtime storage schema, falling back to the
+/// runtime API
+pub fn overrides_handle<C, BE>(client: Arc<C>) -> Arc<OverrideHandle<Block>>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: Send + Sync + 'static,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	let mut overrides_map = BTreeMap::new();
+	overrides_map.insert(
+		EthereumStorageSchema::V1,
+		Box::new(SchemaV1Override::new(client.clone()))
+			as Box<dyn StorageOverride<_> + Send + Sync>,
+	);
+	overrides_map.insert(
+		EthereumStorageSchema::V2,
+		Box::new(SchemaV2Override::new(client.clone()))
+			as Box<dyn StorageOverride<_> + Send + Sync>,
+	);
+	overrides_map.insert(
+		EthereumStorageSchema::V3,
+		Box::new(SchemaV3Override::new(client.clone()))
+			as Box<dyn StorageOverride<_> + Send + Sync>,
+	);
+
+	Arc::new(OverrideHandle {
+		schemas: overrides_map,
+		fallback: Box::new(RuntimeApiStorageOverride::new(client)),
+	})
+}
+
+/// Add the `eth`, `net` and `web3` RPC methods to `io`
+pub fn create_eth<C, BE, P, A>(
+	mut io: RpcExtension,
+	deps: EthDeps<C, P, A>,
+	subscription_task_executor: SubscriptionTaskExecutor,
+) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore + BlockchainEvents<Block>,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: Send + Sync + 'static,
+	C::Api: BlockBuilder<Block>
+		+ fp_rpc::EthereumRuntimeRPCApi<Block>
+		+ fp_rpc::ConvertTransactionRuntimeApi<Block>,
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+	P: TransactionPool<Block = Block> + 'static,
+	A: ChainApi<Block = Block> + 'static,
+{
+	use fc_rpc::{
+		Eth, EthApiServer, EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, Net,
+		NetApiServer, Web3, Web3ApiServer,
+	};
+
+	/// Most filters installed at once through `eth_newFilter`
+	const MAX_STORED_FILTERS: usize = 500;
+
+	let EthDeps {
+		client,
+		pool,
+		graph,
+		network,
+		is_authority,
+		frontier_backend,
+		overrides,
+		block_data_cache,
+		filter_pool,
+		max_past_logs,
+		fee_history_cache,
+		fee_history_cache_limit,
+		execute_gas_limit_multiplier,
+	} = deps;
+
+	io.merge(
+		Eth::new(
+			client.clone(),
+			pool.clone(),
+			graph,
+			Some(TransactionConverter),
+			network.clone(),
+			// accounts are held by the wallets, the node never signs for them
+			Vec::new(),
+			overrides.clone(),
+			frontier_backend.clone(),
+			is_authority,
+			block_data_cache.clone(),
+			fee_history_cache,
+			fee_history_cache_limit,
+			execute_gas_limit_multiplier,
+		)
+		.into_rpc(),
+	)?;
+
+	if let Some(filter_pool) = filter_pool {
+		io.merge(
+			EthFilter::new(
+				client.clone(),
+				frontier_backend,
+				filter_pool,
+				MAX_STORED_FILTERS,
+				max_past_logs,
+				block_data_cache,
+			)
+			.into_rpc(),
+		)?;
+	}
+
+	io.merge(Net::new(client.clone(), network.clone(), true).into_rpc())?;
+	io.merge(Web3::new(client.clone()).into_rpc())?;
+	io.merge(
+		EthPubSub::new(pool, client, network, subscription_task_executor, overrides).into_rpc(),
+	)?;
+
+	Ok(io)
+}
@@ -0,0 +1,72 @@
+//! RPC syncing the leaves of a merkle tree in ranges rather than one `mt_getLeaf` call per index.
+
+use std::sync::Arc;
+
+use codec::{Codec, Encode};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use tangle_relayer_runtime_api::MerkleTreeLeavesApi;
+
+/// Error code returned when the runtime API call fails
+const RUNTIME_ERROR: i32 = 1;
+
+#[rpc(client, server)]
+pub trait LeavesApi<BlockHash> {
+	/// SCALE encoded leaves of `tree_id` from index `from` up to, but excluding, `to`. Fewer
+	/// leaves than requested are returned at the end of the tree or past the runtime's page size,
+	/// so callers continue from `from` plus the number of leaves returned.
+	#[method(name = "mt_getLeaves")]
+	fn get_leaves(
+		&self,
+		tree_id: u32,
+		from: u32,
+		to: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Bytes>>;
+}
+
+/// Implements [`LeavesApiServer`]
+pub struct Leaves<C, Block, Element> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(Block, Element)>,
+}
+
+impl<C, Block, Element> Leaves<C, Block, Element> {
+	/// Create a new instance of the leaves RPC
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, Element> LeavesApiServer<<Block as BlockT>::Hash> for Leaves<C, Block, Element>
+where
+	Block: BlockT,
+	Element: Codec + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: MerkleTreeLeavesApi<Block, Element>,
+{
+	fn get_leaves(
+		&self,
+		tree_id: u32,
+		from: u32,
+		to: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Bytes>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let leaves = self.client.runtime_api().get_leaves(&at, tree_id, from, to).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				RUNTIME_ERROR,
+				"Unable to query the tree leaves.",
+				Some(e.to_string()),
+			))
+		})?;
+		Ok(leaves.iter().map(|leaf| leaf.encode().into()).collect())
+	}
+}
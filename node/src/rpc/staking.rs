@@ -0,0 +1,390 @@
+//! RPC for reviewing staking calls before they are approved, e.g. by council members or proxy
+//! delegates who are only shown a call hash on-chain.
+
+use std::sync::Arc;
+
+use codec::{Compact, Decode};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_parachain_staking_rpc_runtime_api::{ParachainStakingApi, PendingCall};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde::{Deserialize, Serialize};
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{blake2_256, hexdisplay::HexDisplay, Bytes, H256, U256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use tangle_rococo_runtime::{AccountId, Balance, BlockNumber};
+
+/// Nesting limit when decoding calls, matching the one applied to extrinsics
+const MAX_CALL_DEPTH: u32 = 256;
+
+/// Name of the pallet whose calls are listed as staking calls
+const STAKING_PALLET: &str = "ParachainStaking";
+
+/// A pending call decoded with the metadata of the on-chain runtime
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCall {
+	/// Blake2-256 hash of the call, as shown by council proposals and proxy announcements
+	pub hash: H256,
+	/// The whole call, e.g. `Proxy.proxy { .., call: ParachainStaking.set_total_selected { new:
+	/// 8 } }`
+	pub description: String,
+	/// Every staking call dispatched by the call, including those nested in proxies, batches
+	/// and council proposals
+	pub staking_calls: Vec<String>,
+}
+
+#[rpc(client, server)]
+pub trait StakingCallsApi<BlockHash> {
+	/// Decode the call waiting for approval with hash `call_hash` at `at`. Council proposals are
+	/// decoded from their stored call data. Proxy announcements only store the hash, so their
+	/// `call` data must be given and is checked against the hash.
+	#[method(name = "staking_decodeCall")]
+	fn decode_call(
+		&self,
+		call_hash: H256,
+		call: Option<Bytes>,
+		at: Option<BlockHash>,
+	) -> RpcResult<DecodedCall>;
+}
+
+/// Implements [`StakingCallsApiServer`]
+pub struct StakingCalls<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> StakingCalls<C, Block> {
+	/// Create a new instance of the staking calls RPC
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error codes returned by the staking calls RPC
+pub enum Error {
+	/// The runtime could not be queried
+	RuntimeError,
+	/// The runtime metadata is not supported
+	MetadataError,
+	/// The bytes are not a call of the on-chain runtime
+	DecodeError,
+	/// No call with the hash is waiting for approval
+	NotPending,
+	/// The call is pending but its call data is not stored on-chain
+	CallDataMissing,
+	/// The call data given does not have the hash of the pending call
+	HashMismatch,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::MetadataError => 2,
+			Error::DecodeError => 3,
+			Error::NotPending => 4,
+			Error::CallDataMissing => 5,
+			Error::HashMismatch => 6,
+		}
+	}
+}
+
+fn error(code: Error, message: &str, data: Option<String>) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(code.into(), message, data)).into()
+}
+
+/// Decodes values of the types of a runtime's metadata into readable descriptions
+struct CallDecoder<'a> {
+	registry: &'a PortableRegistry,
+	staking_calls: Vec<String>,
+}
+
+impl<'a> CallDecoder<'a> {
+	/// Describe the value of type `id` at the start of `input`, collecting the staking calls
+	/// found in it
+	fn decode(&mut self, id: u32, input: &mut &[u8], depth: u32) -> Result<String, String> {
+		if depth > MAX_CALL_DEPTH {
+			return Err("call nested too deeply".into())
+		}
+		let ty = self.registry.resolve(id).ok_or_else(|| format!("unknown type {}", id))?;
+		match ty.type_def() {
+			TypeDef::Composite(composite) => match composite.fields() {
+				[field] if field.name().is_none() => self.decode(field.ty().id(), input, depth + 1),
+				fields => self.decode_fields(fields, input, depth),
+			},
+			TypeDef::Variant(variant) => {
+				let index = u8::decode(input).map_err(|e| e.to_string())?;
+				let variant = variant
+					.variants()
+					.iter()
+					.find(|variant| variant.index() == index)
+					.ok_or_else(|| format!("unknown variant {} of {:?}", index, ty.path()))?;
+				let is_runtime_call =
+					ty.path().segments().last().map_or(false, |name| name == "RuntimeCall");
+				match variant.fields() {
+					// `RuntimeCall` wraps the call enum of each pallet, shown as `Pallet.call`
+					[call] if is_runtime_call => {
+						let description = format!(
+							"{}.{}",
+							variant.name(),
+							self.decode(call.ty().id(), input, depth + 1)?
+						);
+						if variant.name() == STAKING_PALLET {
+							self.staking_calls.push(description.clone());
+						}
+						Ok(description)
+					},
+					[] => Ok(variant.name().clone()),
+					fields => Ok(format!(
+						"{}{}",
+						variant.name(),
+						self.decode_fields(fields, input, depth)?
+					)),
+				}
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0;
+				// Every element takes at least a byte, bounding the work for malformed lengths
+				if len as usize > input.len() {
+					return Err("sequence longer than the call".into())
+				}
+				self.decode_elements(sequence.type_param().id(), len, input, depth)
+			},
+			TypeDef::Array(array) =>
+				self.decode_elements(array.type_param().id(), array.len(), input, depth),
+			TypeDef::Tuple(tuple) => {
+				let mut elements = Vec::new();
+				for element in tuple.fields() {
+					elements.push(self.decode(element.id(), input, depth + 1)?);
+				}
+				Ok(format!("({})", elements.join(", ")))
+			},
+			TypeDef::Primitive(primitive) => decode_primitive(primitive, input),
+			// Compact integers and the single-field wrappers compacted by the runtime share
+			// their encoding
+			TypeDef::Compact(_) =>
+				Ok(Compact::<u128>::decode(input).map_err(|e| e.to_string())?.0.to_string()),
+			TypeDef::BitSequence(_) => Err("bit sequences are not supported".into()),
+		}
+	}
+
+	fn decode_fields(
+		&mut self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+		depth: u32,
+	) -> Result<String, String> {
+		let mut described = Vec::new();
+		for field in fields {
+			let value = self.decode(field.ty().id(), input, depth + 1)?;
+			described.push(match field.name() {
+				Some(name) => format!("{}: {}", name, value),
+				None => value,
+			});
+		}
+		Ok(match fields.first().and_then(|field| field.name()) {
+			Some(_) => format!(" {{ {} }}", described.join(", ")),
+			None => format!("({})", described.join(", ")),
+		})
+	}
+
+	fn decode_elements(
+		&mut self,
+		id: u32,
+		len: u32,
+		input: &mut &[u8],
+		depth: u32,
+	) -> Result<String, String> {
+		let is_byte = self
+			.registry
+			.resolve(id)
+			.map_or(false, |ty| matches!(ty.type_def(), TypeDef::Primitive(TypeDefPrimitive::U8)));
+		if is_byte {
+			let bytes = input.get(..len as usize).ok_or("bytes longer than the call")?;
+			*input = &input[len as usize..];
+			return Ok(format!("0x{}", HexDisplay::from(&bytes)))
+		}
+		let mut elements = Vec::new();
+		for _ in 0..len {
+			elements.push(self.decode(id, input, depth + 1)?);
+		}
+		Ok(format!("[{}]", elements.join(", ")))
+	}
+}
+
+fn decode_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<String, String> {
+	fn value<T: Decode + ToString>(input: &mut &[u8]) -> Result<String, String> {
+		T::decode(input).map(|value| value.to_string()).map_err(|e| e.to_string())
+	}
+	match primitive {
+		TypeDefPrimitive::Bool => value::<bool>(input),
+		TypeDefPrimitive::Char => u32::decode(input)
+			.ok()
+			.and_then(char::from_u32)
+			.map(|c| format!("{:?}", c))
+			.ok_or_else(|| "invalid char".into()),
+		TypeDefPrimitive::Str =>
+			String::decode(input).map(|s| format!("{:?}", s)).map_err(|e| e.to_string()),
+		TypeDefPrimitive::U8 => value::<u8>(input),
+		TypeDefPrimitive::U16 => value::<u16>(input),
+		TypeDefPrimitive::U32 => value::<u32>(input),
+		TypeDefPrimitive::U64 => value::<u64>(input),
+		TypeDefPrimitive::U128 => value::<u128>(input),
+		TypeDefPrimitive::U256 => <[u8; 32]>::decode(input)
+			.map(|bytes| U256::from_little_endian(&bytes).to_string())
+			.map_err(|e| e.to_string()),
+		TypeDefPrimitive::I8 => value::<i8>(input),
+		TypeDefPrimitive::I16 => value::<i16>(input),
+		TypeDefPrimitive::I32 => value::<i32>(input),
+		TypeDefPrimitive::I64 => value::<i64>(input),
+		TypeDefPrimitive::I128 => value::<i128>(input),
+		TypeDefPrimitive::I256 => Err("256 bit signed integers are not supported".into()),
+	}
+}
+
+/// Decode `call` with the SCALE encoded runtime `metadata`, returning its description and the
+/// staking calls it dispatches
+fn decode_with_metadata(
+	metadata: &[u8],
+	call: &[u8],
+) -> Result<(String, Vec<String>), jsonrpsee::core::Error> {
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..]).map_err(|e| {
+		error(Error::MetadataError, "Unable to decode the runtime metadata.", Some(e.to_string()))
+	})?;
+	let registry = match metadata.1 {
+		RuntimeMetadata::V14(metadata) => metadata.types,
+		_ => return Err(error(Error::MetadataError, "Unsupported runtime metadata version.", None)),
+	};
+	let call_type = registry
+		.types()
+		.iter()
+		.find(|ty| ty.ty().path().segments().last().map_or(false, |name| name == "RuntimeCall"))
+		.ok_or_else(|| error(Error::MetadataError, "The metadata has no call type.", None))?
+		.id();
+
+	let mut decoder = CallDecoder { registry: &registry, staking_calls: Vec::new() };
+	let mut input = call;
+	let description = decoder
+		.decode(call_type, &mut input, 0)
+		.and_then(|description| match input.is_empty() {
+			true => Ok(description),
+			false => Err("trailing bytes after the call".into()),
+		})
+		.map_err(|e| error(Error::DecodeError, "Unable to decode the call.", Some(e)))?;
+	Ok((description, decoder.staking_calls))
+}
+
+impl<C, Block> StakingCallsApiServer<<Block as BlockT>::Hash> for StakingCalls<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: Metadata<Block> + ParachainStakingApi<Block, AccountId, Balance, BlockNumber>,
+{
+	fn decode_call(
+		&self,
+		call_hash: H256,
+		call: Option<Bytes>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<DecodedCall> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let runtime_error = |e: sp_api::ApiError| {
+			error(Error::RuntimeError, "Unable to query the runtime.", Some(e.to_string()))
+		};
+
+		let call = match api.pending_call(&at, call_hash.into()).map_err(runtime_error)? {
+			Some(PendingCall::Proposal(proposal)) => proposal,
+			Some(PendingCall::Announcement) => call
+				.ok_or_else(|| {
+					error(
+						Error::CallDataMissing,
+						"The call data of announcements is not stored, it must be given.",
+						None,
+					)
+				})?
+				.to_vec(),
+			None => return Err(error(Error::NotPending, "No such call is pending.", None)),
+		};
+		if H256::from(blake2_256(&call)) != call_hash {
+			return Err(error(Error::HashMismatch, "The call data does not match the hash.", None))
+		}
+
+		let metadata = api.metadata(&at).map_err(runtime_error)?;
+		let (description, staking_calls) = decode_with_metadata(&metadata, &call)?;
+		Ok(DecodedCall { hash: call_hash, description, staking_calls })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use sp_runtime::MultiAddress;
+	use tangle_rococo_runtime::{Runtime, RuntimeCall};
+
+	fn decode(call: RuntimeCall) -> (String, Vec<String>) {
+		decode_with_metadata(&Runtime::metadata().encode(), &call.encode()).unwrap()
+	}
+
+	fn set_total_selected(new: u32) -> RuntimeCall {
+		RuntimeCall::ParachainStaking(pallet_parachain_staking::Call::set_total_selected { new })
+	}
+
+	#[test]
+	fn decodes_staking_calls_through_proxies() {
+		let (description, staking_calls) = decode(RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+			real: MultiAddress::Id(AccountId::new([1; 32])),
+			force_proxy_type: None,
+			call: Box::new(set_total_selected(8)),
+		}));
+		assert_eq!(
+			description,
+			format!(
+				"Proxy.proxy {{ real: Id(0x{}), force_proxy_type: None, call: \
+				 ParachainStaking.set_total_selected {{ new: 8 }} }}",
+				"01".repeat(32)
+			)
+		);
+		assert_eq!(staking_calls, vec!["ParachainStaking.set_total_selected { new: 8 }"]);
+	}
+
+	#[test]
+	fn decodes_staking_calls_batched_in_council_proposals() {
+		let (_, staking_calls) = decode(RuntimeCall::Council(pallet_collective::Call::propose {
+			threshold: 2,
+			proposal: Box::new(RuntimeCall::Utility(pallet_utility::Call::batch_all {
+				calls: vec![
+					set_total_selected(8),
+					RuntimeCall::System(frame_system::Call::remark { remark: vec![1, 2] }),
+					RuntimeCall::ParachainStaking(
+						pallet_parachain_staking::Call::set_blocks_per_round { new: 600 },
+					),
+				],
+			})),
+			length_bound: 100,
+		}));
+		assert_eq!(
+			staking_calls,
+			vec![
+				"ParachainStaking.set_total_selected { new: 8 }",
+				"ParachainStaking.set_blocks_per_round { new: 600 }",
+			]
+		);
+	}
+
+	#[test]
+	fn rejects_malformed_calls() {
+		let mut call = set_total_selected(8).encode();
+		call.push(0);
+		assert!(decode_with_metadata(&Runtime::metadata().encode(), &call).is_err());
+		assert!(
+			decode_with_metadata(&Runtime::metadata().encode(), &call[..call.len() - 3]).is_err()
+		);
+	}
+}
@@ -33,14 +33,22 @@ use cumulus_primitives_core::ParaId;
 use cumulus_relay_chain_inprocess_interface::build_inprocess_relay_chain;
 use cumulus_relay_chain_interface::{RelayChainError, RelayChainInterface, RelayChainResult};
 use cumulus_relay_chain_rpc_interface::{create_client_and_start_worker, RelayChainRpcInterface};
+use fc_mapping_sync::{MappingSyncWorker, SyncStrategy};
+use fc_rpc::{EthBlockDataCacheTask, EthTask, OverrideHandle};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use futures::StreamExt;
 use jsonrpsee::RpcModule;
 use nimbus_consensus::{BuildNimbusConsensusParams, NimbusConsensus};
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf, sync::Mutex};
 
 // Substrate Imports
+use sc_client_api::BlockchainEvents;
 use sc_executor::NativeElseWasmExecutor;
 use sc_network::{NetworkBlock, NetworkService};
-use sc_service::{Configuration, PartialComponents, TFullBackend, TFullClient, TaskManager};
+use sc_service::{
+	BasePath, Configuration, DatabaseSource, PartialComponents, TFullBackend, TFullClient,
+	TaskManager,
+};
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker, TelemetryWorkerHandle};
 use sp_api::ConstructRuntimeApi;
 use sp_keystore::SyncCryptoStorePtr;
@@ -67,6 +75,170 @@ pub mod rococo {
 	}
 }
 
+/// Most logs returned by one `eth_getLogs` query
+const MAX_PAST_LOGS: u32 = 10_000;
+/// Blocks kept in the `eth_feeHistory` cache
+const FEE_HISTORY_LIMIT: FeeHistoryCacheLimit = 2048;
+/// Multiplier of the block gas limit allowed to `eth_call` and `eth_estimateGas`
+const EXECUTE_GAS_LIMIT_MULTIPLIER: u64 = 10;
+/// Blocks an unused `eth_newFilter` filter is kept for
+const FILTER_RETAIN_THRESHOLD: u64 = 100;
+
+/// Directory of the frontier database `path` of the chain being run
+fn frontier_database_dir(config: &Configuration, path: &str) -> PathBuf {
+	let config_dir = config
+		.base_path
+		.as_ref()
+		.map(|base_path| base_path.config_dir(config.chain_spec.id()))
+		.unwrap_or_else(|| {
+			BasePath::from_project("", "", "tangle").config_dir(config.chain_spec.id())
+		});
+	config_dir.join("frontier").join(path)
+}
+
+/// Open the database mapping Ethereum blocks and transactions to substrate ones, next to the
+/// substrate database and in the same format
+pub fn open_frontier_backend<C>(
+	client: Arc<C>,
+	config: &Configuration,
+) -> Result<Arc<fc_db::Backend<Block>>, String>
+where
+	C: sp_blockchain::HeaderBackend<Block>,
+{
+	let source = match config.database {
+		DatabaseSource::RocksDb { .. } =>
+			DatabaseSource::RocksDb { path: frontier_database_dir(config, "db"), cache_size: 0 },
+		DatabaseSource::ParityDb { .. } =>
+			DatabaseSource::ParityDb { path: frontier_database_dir(config, "paritydb") },
+		DatabaseSource::Auto { .. } => DatabaseSource::Auto {
+			rocksdb_path: frontier_database_dir(config, "db"),
+			paritydb_path: frontier_database_dir(config, "paritydb"),
+			cache_size: 0,
+		},
+		_ => return Err("Supported db sources: `rocksdb` | `paritydb` | `auto`".to_string()),
+	};
+
+	Ok(Arc::new(fc_db::Backend::<Block>::new(client, &fc_db::DatabaseSettings { source })?))
+}
+
+/// Caches shared by the Ethereum RPC and the tasks keeping them up to date
+pub struct FrontierPartial {
+	/// Ethereum block and transaction mappings kept by frontier.
+	pub frontier_backend: Arc<fc_db::Backend<Block>>,
+	/// Readers of the Ethereum storage of each runtime storage schema.
+	pub overrides: Arc<OverrideHandle<Block>>,
+	/// Cache of Ethereum blocks and transaction statuses.
+	pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+	/// Log filters installed through `eth_newFilter`.
+	pub filter_pool: FilterPool,
+	/// Fee history of recent blocks.
+	pub fee_history_cache: FeeHistoryCache,
+}
+
+impl FrontierPartial {
+	/// Ethereum RPC dependencies of a node whose network is `network`
+	pub fn eth_deps<C, P, A: sc_transaction_pool::ChainApi>(
+		&self,
+		client: Arc<C>,
+		pool: Arc<P>,
+		graph: Arc<sc_transaction_pool::Pool<A>>,
+		network: Arc<NetworkService<Block, Hash>>,
+		is_authority: bool,
+	) -> crate::rpc::eth::EthDeps<C, P, A> {
+		crate::rpc::eth::EthDeps {
+			client,
+			pool,
+			graph,
+			network,
+			is_authority,
+			frontier_backend: self.frontier_backend.clone(),
+			overrides: self.overrides.clone(),
+			block_data_cache: self.block_data_cache.clone(),
+			filter_pool: Some(self.filter_pool.clone()),
+			max_past_logs: MAX_PAST_LOGS,
+			fee_history_cache: self.fee_history_cache.clone(),
+			fee_history_cache_limit: FEE_HISTORY_LIMIT,
+			execute_gas_limit_multiplier: EXECUTE_GAS_LIMIT_MULTIPLIER,
+		}
+	}
+}
+
+/// Open the frontier database and spawn the tasks mapping imported blocks into it and keeping
+/// the Ethereum RPC caches up to date
+pub fn spawn_frontier_tasks<C>(
+	config: &Configuration,
+	task_manager: &TaskManager,
+	client: Arc<C>,
+	backend: Arc<TFullBackend<Block>>,
+	sync_strategy: SyncStrategy,
+) -> Result<FrontierPartial, sc_service::Error>
+where
+	C: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::StorageProvider<Block, TFullBackend<Block>>
+		+ sc_client_api::AuxStore
+		+ BlockchainEvents<Block>
+		+ sp_blockchain::HeaderBackend<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+{
+	let frontier_backend = open_frontier_backend(client.clone(), config)?;
+	let overrides = crate::rpc::eth::overrides_handle(client.clone());
+	let filter_pool: FilterPool = Arc::new(Mutex::new(BTreeMap::new()));
+	let fee_history_cache: FeeHistoryCache = Arc::new(Mutex::new(BTreeMap::new()));
+
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-mapping-sync-worker",
+		Some("frontier"),
+		MappingSyncWorker::new(
+			client.import_notification_stream(),
+			Duration::new(6, 0),
+			client.clone(),
+			backend,
+			frontier_backend.clone(),
+			3,
+			0,
+			sync_strategy,
+		)
+		.for_each(|()| futures::future::ready(())),
+	);
+
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-filter-pool",
+		Some("frontier"),
+		EthTask::filter_pool_task(client.clone(), filter_pool.clone(), FILTER_RETAIN_THRESHOLD),
+	);
+
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-fee-history",
+		Some("frontier"),
+		EthTask::fee_history_task(
+			client,
+			overrides.clone(),
+			fee_history_cache.clone(),
+			FEE_HISTORY_LIMIT,
+		),
+	);
+
+	let block_data_cache = Arc::new(EthBlockDataCacheTask::new(
+		task_manager.spawn_handle(),
+		overrides.clone(),
+		50,
+		50,
+		config.prometheus_registry().cloned(),
+	));
+
+	Ok(FrontierPartial {
+		frontier_backend,
+		overrides,
+		block_data_cache,
+		filter_pool,
+		fee_history_cache,
+	})
+}
+
 /// Starts a `ServiceBuilder` for a full service.
 ///
 /// Use this macro if you don't actually need the full service, but just the builder in order to
@@ -222,6 +394,7 @@ async fn start_node_impl<RuntimeApi, Executor, RB, BIQ, BIC>(
 	build_import_queue: BIQ,
 	build_consensus: BIC,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	staking_metrics_account: Option<AccountId>,
 ) -> sc_service::error::Result<(
 	TaskManager,
 	Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>>,
@@ -242,11 +415,19 @@ where
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ fp_rpc::EthereumRuntimeRPCApi<Block>
+		+ fp_rpc::ConvertTransactionRuntimeApi<Block>
 		+ dkg_runtime_primitives::DKGApi<
 			Block,
 			dkg_runtime_primitives::crypto::AuthorityId,
 			NumberFor<Block>,
-		> + sp_consensus_aura::AuraApi<Block, AuraId>,
+		> + sp_consensus_aura::AuraApi<Block, AuraId>
+		+ pallet_parachain_staking_rpc_runtime_api::ParachainStakingApi<
+			Block,
+			AccountId,
+			Balance,
+			NumberFor<Block>,
+		>,
 	sc_client_api::StateBackendFor<TFullBackend<Block>, Block>: sp_api::StateBackend<BlakeTwo256>,
 	Executor: sc_executor::NativeExecutionDispatch + 'static,
 	RB: Fn(
@@ -350,18 +531,37 @@ where
 		);
 	}
 
+	let frontier = spawn_frontier_tasks(
+		&parachain_config,
+		&task_manager,
+		client.clone(),
+		backend.clone(),
+		SyncStrategy::Parachain,
+	)?;
+
 	let rpc_builder = {
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
+		let network = network.clone();
 
-		Box::new(move |deny_unsafe, _| {
+		Box::new(move |deny_unsafe, subscription_task_executor| {
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
+				command_sink: None,
 			};
+			let eth_deps = frontier.eth_deps(
+				client.clone(),
+				transaction_pool.clone(),
+				transaction_pool.pool().clone(),
+				network.clone(),
+				validator,
+			);
 
-			crate::rpc::create_full(deps).map_err(Into::into)
+			let module = crate::rpc::create_full(deps)?;
+			crate::rpc::eth::create_eth(module, eth_deps, subscription_task_executor)
+				.map_err(Into::into)
 		})
 	};
 
@@ -388,6 +588,15 @@ where
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	if let Some(registry) = prometheus_registry.as_ref() {
+		let metrics = crate::staking_metrics::StakingMetrics::register(registry)?;
+		task_manager.spawn_handle().spawn(
+			"staking-metrics",
+			None,
+			crate::staking_metrics::run(client.clone(), metrics, staking_metrics_account),
+		);
+	}
+
 	if let Some(hwbench) = hwbench {
 		sc_sysinfo::print_hwbench(&hwbench);
 
@@ -529,6 +738,7 @@ pub async fn start_parachain_node(
 	collator_options: CollatorOptions,
 	id: ParaId,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	staking_metrics_account: Option<AccountId>,
 ) -> sc_service::error::Result<(
 	TaskManager,
 	Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<rococo::Executor>>>,
@@ -598,6 +808,7 @@ pub async fn start_parachain_node(
 			}))
 		},
 		hwbench,
+		staking_metrics_account,
 	)
 	.await
 }
@@ -317,6 +317,7 @@ where
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
+				command_sink: None,
 			};
 
 			rpc_ext_builder(deps)
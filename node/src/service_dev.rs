@@ -0,0 +1,238 @@
+// Copyright 2022 Webb Technologies Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Standalone development service. Blocks are sealed by the node itself instead of being
+//! validated by a relay chain, so pallets and dApps can be developed against a single `--dev`
+//! node and integration tests can drive block production through `engine_createBlock`.
+
+use crate::service::{rococo::Executor, spawn_frontier_tasks};
+use codec::Encode;
+use cumulus_primitives_parachain_inherent::MockValidationDataInherentDataProvider;
+use fc_mapping_sync::SyncStrategy;
+use nimbus_primitives::{NimbusId, NIMBUS_ENGINE_ID};
+use sc_consensus::BlockImportParams;
+use sc_consensus_manual_seal::{
+	consensus::ConsensusDataProvider, run_instant_seal, run_manual_seal, EngineCommand,
+	InstantSealParams, ManualSealParams,
+};
+use sc_executor::NativeElseWasmExecutor;
+use sc_service::{Configuration, TFullClient, TaskManager};
+use sp_blockchain::HeaderBackend;
+use sp_inherents::InherentData;
+use sp_runtime::{generic::Digest, traits::Block as BlockT, DigestItem};
+use std::sync::Arc;
+use tangle_rococo_runtime::{opaque::Block, Hash, RuntimeApi};
+
+type FullClient = TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>;
+
+/// How a standalone development node seals blocks
+#[derive(Debug, Copy, Clone, clap::ArgEnum)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool
+	Instant,
+	/// Seal blocks only when asked to through the `engine_createBlock` RPC
+	Manual,
+}
+
+/// Names `author` in the nimbus pre-runtime digest of every sealed block. Blocks are not
+/// signed, so no authoring keys are needed, but `author` must still be eligible to author.
+pub struct AuthorDigestProvider {
+	author: NimbusId,
+}
+
+impl ConsensusDataProvider<Block> for AuthorDigestProvider {
+	type Transaction = sp_api::TransactionFor<FullClient, Block>;
+
+	fn create_digest(
+		&self,
+		_parent: &<Block as BlockT>::Header,
+		_inherents: &InherentData,
+	) -> Result<Digest, sc_consensus_manual_seal::Error> {
+		Ok(Digest { logs: vec![DigestItem::PreRuntime(NIMBUS_ENGINE_ID, self.author.encode())] })
+	}
+
+	fn append_block_import(
+		&self,
+		_parent: &<Block as BlockT>::Header,
+		_params: &mut BlockImportParams<Block, Self::Transaction>,
+		_inherents: &InherentData,
+	) -> Result<(), sc_consensus_manual_seal::Error> {
+		Ok(())
+	}
+}
+
+/// Start a node sealing its own blocks with `sealing`, authored by `author`, instead of
+/// collating them for a relay chain
+pub fn start_dev_node(
+	config: Configuration,
+	sealing: Sealing,
+	author: NimbusId,
+) -> Result<TaskManager, sc_service::Error> {
+	let executor = NativeElseWasmExecutor::<Executor>::new(
+		config.wasm_method,
+		config.default_heap_pages,
+		config.max_runtime_instances,
+		config.runtime_cache_size,
+	);
+
+	let (client, backend, keystore_container, mut task_manager) =
+		sc_service::new_full_parts::<Block, RuntimeApi, _>(&config, None, executor)?;
+	let client = Arc::new(client);
+	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+
+	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+		config.transaction_pool.clone(),
+		config.role.is_authority().into(),
+		config.prometheus_registry(),
+		task_manager.spawn_essential_handle(),
+		client.clone(),
+	);
+
+	let import_queue = sc_consensus_manual_seal::import_queue(
+		Box::new(client.clone()),
+		&task_manager.spawn_essential_handle(),
+		config.prometheus_registry(),
+	);
+
+	let (network, system_rpc_tx, tx_handler_controller, start_network) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue,
+			block_announce_validator_builder: None,
+			warp_sync: None,
+		})?;
+
+	let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+		task_manager.spawn_handle(),
+		client.clone(),
+		transaction_pool.clone(),
+		config.prometheus_registry(),
+		None,
+	);
+
+	// the runtime requires the parachain validation data in every block, so it is mocked
+	let create_inherent_data_providers = {
+		let client = client.clone();
+		move |block: Hash, ()| {
+			let current_para_block = client.number(block).ok().flatten().unwrap_or_default();
+			async move {
+				let time = sp_timestamp::InherentDataProvider::from_system_time();
+				let mocked_parachain = MockValidationDataInherentDataProvider {
+					current_para_block,
+					relay_offset: 1000,
+					relay_blocks_per_para_block: 2,
+					xcm_config: Default::default(),
+					raw_downward_messages: Vec::new(),
+					raw_horizontal_messages: Vec::new(),
+				};
+				let nimbus_inherent = nimbus_primitives::InherentDataProvider;
+				Ok((time, mocked_parachain, nimbus_inherent))
+			}
+		}
+	};
+
+	// bypasses nimbus authorship: the runtime only needs to find an eligible author
+	let consensus_data_provider = AuthorDigestProvider { author };
+
+	let mut command_sink = None;
+	match sealing {
+		Sealing::Instant => task_manager.spawn_essential_handle().spawn_blocking(
+			"instant-seal",
+			Some("block-authoring"),
+			run_instant_seal(InstantSealParams {
+				block_import: client.clone(),
+				env: proposer_factory,
+				client: client.clone(),
+				pool: transaction_pool.clone(),
+				select_chain,
+				consensus_data_provider: Some(Box::new(consensus_data_provider)),
+				create_inherent_data_providers,
+			}),
+		),
+		Sealing::Manual => {
+			let (sink, commands_stream) =
+				futures::channel::mpsc::channel::<EngineCommand<Hash>>(1024);
+			command_sink = Some(sink);
+			task_manager.spawn_essential_handle().spawn_blocking(
+				"manual-seal",
+				Some("block-authoring"),
+				run_manual_seal(ManualSealParams {
+					block_import: client.clone(),
+					env: proposer_factory,
+					client: client.clone(),
+					pool: transaction_pool.clone(),
+					commands_stream,
+					select_chain,
+					consensus_data_provider: Some(Box::new(consensus_data_provider)),
+					create_inherent_data_providers,
+				}),
+			);
+		},
+	}
+
+	let frontier = spawn_frontier_tasks(
+		&config,
+		&task_manager,
+		client.clone(),
+		backend.clone(),
+		SyncStrategy::Normal,
+	)?;
+
+	let rpc_builder = {
+		let client = client.clone();
+		let transaction_pool = transaction_pool.clone();
+		let network = network.clone();
+
+		Box::new(move |deny_unsafe, subscription_task_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: transaction_pool.clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+			};
+			let eth_deps = frontier.eth_deps(
+				client.clone(),
+				transaction_pool.clone(),
+				transaction_pool.pool().clone(),
+				network.clone(),
+				true,
+			);
+
+			let module = crate::rpc::create_full(deps)?;
+			crate::rpc::eth::create_eth(module, eth_deps, subscription_task_executor)
+				.map_err(Into::into)
+		})
+	};
+
+	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		rpc_builder,
+		client,
+		transaction_pool,
+		task_manager: &mut task_manager,
+		config,
+		keystore: keystore_container.sync_keystore(),
+		backend,
+		network,
+		system_rpc_tx,
+		tx_handler_controller,
+		telemetry: None,
+	})?;
+
+	start_network.start_network();
+
+	Ok(task_manager)
+}
@@ -0,0 +1,103 @@
+// Copyright 2022 Webb Technologies Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus metrics of the parachain staking state, read from the staking runtime API on
+//! every new best block, so operators can alert on their collator falling out of the active set.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use futures::StreamExt;
+use pallet_parachain_staking_rpc_runtime_api::ParachainStakingApi;
+use sc_client_api::BlockchainEvents;
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+};
+use substrate_prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+
+/// The staking gauges of the node
+#[derive(Clone)]
+pub struct StakingMetrics {
+	round: Gauge<U64>,
+	selected_candidates: Gauge<U64>,
+	candidate_rank: Gauge<U64>,
+	delegation_count: Gauge<U64>,
+	pending_payouts: Gauge<U64>,
+}
+
+impl StakingMetrics {
+	/// Register the staking gauges with `registry`
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let gauge = |name: &str, help: &str| register(Gauge::new(name, help)?, registry);
+		Ok(Self {
+			round: gauge("tangle_staking_round", "Index of the current staking round")?,
+			selected_candidates: gauge(
+				"tangle_staking_selected_candidates",
+				"Collators selected to author blocks in the current round",
+			)?,
+			candidate_rank: gauge(
+				"tangle_staking_candidate_rank",
+				"Rank of the collator among the active candidates by backing, 1 being the most \
+				 backed, or 0 when it is not an active candidate",
+			)?,
+			delegation_count: gauge(
+				"tangle_staking_delegation_count",
+				"Delegations to the collator, or 0 when it is not a candidate",
+			)?,
+			pending_payouts: gauge(
+				"tangle_staking_pending_payouts",
+				"Collators still to be paid out across the rounds awaiting payout",
+			)?,
+		})
+	}
+}
+
+/// Update `metrics` on every new best block until the client shuts down. The rank and
+/// delegation count are those of `collator`, and stay at 0 without one.
+pub async fn run<Block, C, AccountId, Balance>(
+	client: Arc<C>,
+	metrics: StakingMetrics,
+	collator: Option<AccountId>,
+) where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + BlockchainEvents<Block>,
+	C::Api: ParachainStakingApi<Block, AccountId, Balance, NumberFor<Block>>,
+	AccountId: Codec + Clone,
+	Balance: Codec,
+{
+	let mut imports = client.import_notification_stream();
+	while let Some(notification) = imports.next().await {
+		if !notification.is_new_best {
+			continue
+		}
+		let at = BlockId::hash(notification.hash);
+		match client.runtime_api().collator_metrics(&at, collator.clone()) {
+			Ok(state) => {
+				metrics.round.set(state.round.into());
+				metrics.selected_candidates.set(state.selected_candidates.into());
+				metrics.candidate_rank.set(state.rank.unwrap_or_default().into());
+				metrics.delegation_count.set(state.delegation_count.unwrap_or_default().into());
+				metrics.pending_payouts.set(state.pending_payouts.into());
+			},
+			Err(e) => log::debug!(
+				target: "staking-metrics",
+				"Unable to read the staking metrics at {}: {}",
+				notification.hash,
+				e
+			),
+		}
+	}
+}
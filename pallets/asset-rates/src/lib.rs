@@ -0,0 +1,129 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Asset Rates
+//!
+//! Conversion rates from the native currency to registered assets, set by governance. An asset
+//! is worth its rate in native currency per unit, so a native balance converts to `balance / rate`
+//! of the asset. Assets without a rate cannot be converted, e.g. to pay transaction fees in them.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, traits::tokens::BalanceConversion};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, One, Zero},
+	FixedPointNumber, FixedU128,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The id of a registered asset.
+		type AssetId: Parameter + Copy;
+
+		/// The balance of the native currency and of the assets.
+		type Balance: Parameter + AtLeast32BitUnsigned + Copy;
+
+		/// The origin which may set the rates.
+		type RateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The native value of a unit of an asset was set, or removed with `None`
+		RateSet { asset_id: T::AssetId, rate: Option<FixedU128> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A rate of zero would make every native balance worth an unbounded amount of the asset
+		ZeroRate,
+		/// The asset has no rate to convert with
+		UnknownRate,
+	}
+
+	/// The native value of a unit of each asset
+	///
+	/// map AssetId => FixedU128
+	#[pallet::storage]
+	#[pallet::getter(fn rate)]
+	pub type Rates<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, FixedU128, OptionQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the native value of a unit of `asset_id`, or remove its rate with `None`
+		#[pallet::weight(T::WeightInfo::set_rate())]
+		pub fn set_rate(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			rate: Option<FixedU128>,
+		) -> DispatchResult {
+			T::RateOrigin::ensure_origin(origin)?;
+			ensure!(rate.map_or(true, |rate| !rate.is_zero()), Error::<T>::ZeroRate);
+			Rates::<T>::set(asset_id, rate);
+			Self::deposit_event(Event::RateSet { asset_id, rate });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The native value of `balance` of `asset_id`, if the asset has a rate
+	pub fn to_native_balance(balance: T::Balance, asset_id: T::AssetId) -> Option<T::Balance> {
+		Rates::<T>::get(asset_id).map(|rate| rate.saturating_mul_int(balance))
+	}
+}
+
+impl<T: Config> BalanceConversion<T::Balance, T::AssetId, T::Balance> for Pallet<T> {
+	type Error = Error<T>;
+
+	/// The amount of `asset_id` worth `balance` of the native currency, rounded up so that fees
+	/// paid in the asset are never worth less than in the native currency
+	fn to_asset_balance(balance: T::Balance, asset_id: T::AssetId) -> Result<T::Balance, Error<T>> {
+		let rate = Rates::<T>::get(asset_id).ok_or(Error::<T>::UnknownRate)?;
+		let amount = rate.reciprocal().ok_or(Error::<T>::ZeroRate)?.saturating_mul_int(balance);
+		if rate.saturating_mul_int(amount) < balance {
+			Ok(amount.saturating_add(One::one()))
+		} else {
+			Ok(amount)
+		}
+	}
+}
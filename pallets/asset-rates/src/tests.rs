@@ -0,0 +1,90 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{RuntimeEvent, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn assets_without_a_rate_cannot_be_converted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			<AssetRates as BalanceConversion<_, _, _>>::to_asset_balance(100, ASSET),
+			Err(Error::<Runtime>::UnknownRate)
+		);
+		assert_eq!(AssetRates::to_native_balance(100, ASSET), None);
+	});
+}
+
+#[test]
+fn only_the_rate_origin_sets_non_zero_rates() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetRates::set_rate(RuntimeOrigin::signed(2), ASSET, Some(FixedU128::from(2))),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRates::set_rate(RuntimeOrigin::signed(ADMIN), ASSET, Some(FixedU128::zero())),
+			Error::<Runtime>::ZeroRate
+		);
+		assert_ok!(AssetRates::set_rate(
+			RuntimeOrigin::signed(ADMIN),
+			ASSET,
+			Some(FixedU128::from(2))
+		));
+		System::assert_last_event(RuntimeEvent::AssetRates(crate::Event::RateSet {
+			asset_id: ASSET,
+			rate: Some(FixedU128::from(2)),
+		}));
+		assert_ok!(AssetRates::set_rate(RuntimeOrigin::signed(ADMIN), ASSET, None));
+		assert_eq!(AssetRates::rate(ASSET), None);
+	});
+}
+
+#[test]
+fn native_balances_convert_at_the_rate_rounding_up() {
+	ExtBuilder::default().build().execute_with(|| {
+		// a unit of the asset is worth two native units
+		assert_ok!(AssetRates::set_rate(
+			RuntimeOrigin::signed(ADMIN),
+			ASSET,
+			Some(FixedU128::from(2))
+		));
+		assert_eq!(
+			<AssetRates as BalanceConversion<_, _, _>>::to_asset_balance(100, ASSET),
+			Ok(50)
+		);
+		assert_eq!(
+			<AssetRates as BalanceConversion<_, _, _>>::to_asset_balance(101, ASSET),
+			Ok(51)
+		);
+		assert_eq!(AssetRates::to_native_balance(50, ASSET), Some(100));
+
+		// a unit of the asset is worth a quarter of a native unit
+		assert_ok!(AssetRates::set_rate(
+			RuntimeOrigin::signed(ADMIN),
+			ASSET,
+			Some(FixedU128::saturating_from_rational(1, 4))
+		));
+		assert_eq!(
+			<AssetRates as BalanceConversion<_, _, _>>::to_asset_balance(100, ASSET),
+			Ok(400)
+		);
+		assert_eq!(AssetRates::to_native_balance(400, ASSET), Some(100));
+	});
+}
@@ -36,7 +36,7 @@ use sp_runtime::{
 	transaction_validity::{
 		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	RuntimeDebug,
+	DispatchError, DispatchResult, RuntimeDebug,
 };
 use sp_std::{convert::TryInto, fmt::Debug, prelude::*, vec};
 
@@ -91,6 +91,24 @@ impl WeightInfo for TestWeightInfo {
 	}
 }
 
+/// Delegates claimed funds to a collator candidate, for `claim_and_delegate`
+pub trait DelegateClaim<AccountId, Balance> {
+	/// Delegate `amount` of the funds `who` just claimed to `candidate`
+	fn delegate(who: &AccountId, candidate: AccountId, amount: Balance) -> DispatchResult;
+	/// Upper bound of the weight of `delegate`
+	fn weight() -> Weight;
+}
+
+/// Claims cannot be delegated
+impl<AccountId, Balance> DelegateClaim<AccountId, Balance> for () {
+	fn delegate(_who: &AccountId, _candidate: AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("delegating claims is not supported"))
+	}
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+}
+
 /// The kind of statement an account needs to make for a claim to be valid.
 #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -183,8 +201,12 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -199,6 +221,8 @@ pub mod pallet {
 		type MoveClaimOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		/// RuntimeOrigin permitted to call force_ extrinsics
 		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Delegates the funds claimed with `claim_and_delegate`
+		type DelegateClaim: DelegateClaim<Self::AccountId, BalanceOf<Self>>;
 		type WeightInfo: WeightInfo;
 	}
 
@@ -253,6 +277,8 @@ pub mod pallet {
 	pub(super) type Signing<T> = StorageMap<_, Identity, EthereumAddress, StatementKind>;
 
 	/// Pre-claimed Ethereum accounts, by the Account ID that they are claimed to.
+	/// Each of those accounts holds a sufficient reference until it attests, so it is not reaped
+	/// while its claim is pending.
 	#[pallet::storage]
 	pub(super) type Preclaims<T: Config> = StorageMap<_, Identity, T::AccountId, EthereumAddress>;
 
@@ -303,6 +329,7 @@ pub mod pallet {
 				.iter()
 				.filter_map(|(a, _, i, _)| Some((i.clone()?, a.clone())))
 				.for_each(|(i, a)| {
+					frame_system::Pallet::<T>::inc_sufficients(&i);
 					Preclaims::<T>::insert(i, a);
 				});
 			// build expiryConfig
@@ -370,6 +397,59 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Make a claim to collect your WEBBs and delegate all of them to a collator candidate,
+		/// e.g. while they are still vesting.
+		///
+		/// The dispatch origin for this call must be _None_.
+		///
+		/// Unsigned Validation:
+		/// A call to `claim_and_delegate` is deemed valid if the signature provided matches
+		/// the expected signed message of:
+		///
+		/// > Ethereum Signed Message:
+		/// > (configured prefix string)(address)(statement)(candidate)
+		///
+		/// and `address` matches the `dest` account and `candidate` the `candidate` account, so
+		/// the candidate cannot be swapped out before the claim is included. The `statement`
+		/// must be empty if the claim does not need one.
+		///
+		/// Parameters:
+		/// - `dest`: The destination account to payout the claim.
+		/// - `ethereum_signature`: The signature of an ethereum signed message matching the format
+		///   described above.
+		/// - `statement`: The identity of the statement which is being attested to in the
+		///   signature, if the claim needs one.
+		/// - `candidate`: The collator candidate the claimed WEBBs are delegated to.
+		///
+		/// <weight>
+		/// The weight of this call is that of `claim_attest`, which also checks a statement, plus
+		/// the weight of the delegation.
+		///
+		/// Total Complexity: O(1)
+		/// </weight>
+		#[pallet::weight(T::WeightInfo::claim_attest().saturating_add(T::DelegateClaim::weight()))]
+		pub fn claim_and_delegate(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			ethereum_signature: EcdsaSignature,
+			statement: Vec<u8>,
+			candidate: T::AccountId,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let data = dest.using_encoded(to_ascii_hex);
+			let extra = Self::delegation_extra(&statement, &candidate);
+			let signer = Self::eth_recover(&ethereum_signature, &data, &extra)
+				.ok_or(Error::<T>::InvalidEthereumSignature)?;
+			match Signing::<T>::get(signer) {
+				None => ensure!(statement.is_empty(), Error::<T>::InvalidStatement),
+				Some(s) => ensure!(s.to_text() == &statement[..], Error::<T>::InvalidStatement),
+			}
+
+			let amount = Self::process_claim(signer, dest.clone())?;
+			T::DelegateClaim::delegate(&dest, candidate, amount)
+		}
+
 		/// Mint a new claim to collect WEBBs.
 		///
 		/// The dispatch origin for this call must be _Root_.
@@ -484,6 +564,7 @@ pub mod pallet {
 			}
 			Self::process_claim(signer, who.clone())?;
 			Preclaims::<T>::remove(&who);
+			frame_system::Pallet::<T>::dec_sufficients(&who);
 			Ok(())
 		}
 
@@ -548,6 +629,22 @@ pub mod pallet {
 						Some(statement.as_slice()),
 					)
 				},
+				// <weight>
+				// The weight of this logic is included in the `claim_and_delegate` dispatchable.
+				// </weight>
+				Call::claim_and_delegate {
+					dest: account,
+					ethereum_signature,
+					statement,
+					candidate,
+				} => {
+					let data = account.using_encoded(to_ascii_hex);
+					let extra = Self::delegation_extra(&statement, &candidate);
+					(
+						Self::eth_recover(&ethereum_signature, &data, &extra),
+						(!statement.is_empty()).then(|| statement.as_slice()),
+					)
+				},
 				_ => return Err(InvalidTransaction::Call.into()),
 			};
 
@@ -614,7 +711,18 @@ impl<T: Config> Pallet<T> {
 		Some(res)
 	}
 
-	fn process_claim(signer: EthereumAddress, dest: T::AccountId) -> sp_runtime::DispatchResult {
+	// The extra data signed to claim and delegate to `candidate`, after the statement if any.
+	fn delegation_extra(statement: &[u8], candidate: &T::AccountId) -> Vec<u8> {
+		let mut extra = statement.to_vec();
+		extra.extend(candidate.using_encoded(to_ascii_hex));
+		extra
+	}
+
+	// Pays out the claim of `signer` to `dest`, returning the amount claimed.
+	fn process_claim(
+		signer: EthereumAddress,
+		dest: T::AccountId,
+	) -> Result<BalanceOf<T>, DispatchError> {
 		let balance_due = <Claims<T>>::get(&signer).ok_or(Error::<T>::SignerHasNoClaim)?;
 
 		let new_total = Self::total().checked_sub(&balance_due).ok_or(Error::<T>::PotUnderflow)?;
@@ -647,7 +755,34 @@ impl<T: Config> Pallet<T> {
 			amount: balance_due,
 		});
 
-		Ok(())
+		Ok(balance_due)
+	}
+}
+
+pub mod migrations {
+	use super::*;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	/// Adds the sufficient reference every preclaimed account holds since storage version 1, so
+	/// accounts with a pending claim cannot be reaped before they attest.
+	pub struct AddPreclaimRefs<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for AddPreclaimRefs<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut accounts = 0u64;
+			for account in Preclaims::<T>::iter_keys() {
+				frame_system::Pallet::<T>::inc_sufficients(&account);
+				accounts = accounts.saturating_add(1);
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+			frame_support::log::info!(
+				"added claims sufficient references for {} accounts",
+				accounts
+			);
+			T::DbWeight::get().reads_writes(accounts.saturating_mul(2) + 1, accounts + 1)
+		}
 	}
 }
 
@@ -866,6 +1001,22 @@ mod tests {
 	ord_parameter_types! {
 		pub const Six: u64 = 6;
 	}
+	parameter_types! {
+		pub static Delegated: Vec<(u64, u64, u64)> = vec![];
+	}
+
+	/// Records delegations, rejecting those to account 0 as if it were no candidate
+	pub struct RecordDelegations;
+	impl DelegateClaim<u64, u64> for RecordDelegations {
+		fn delegate(who: &u64, candidate: u64, amount: u64) -> DispatchResult {
+			ensure!(candidate != 0, DispatchError::Other("not a candidate"));
+			Delegated::mutate(|delegated| delegated.push((*who, candidate, amount)));
+			Ok(())
+		}
+		fn weight() -> Weight {
+			Weight::zero()
+		}
+	}
 
 	impl Config for Test {
 		type RuntimeEvent = RuntimeEvent;
@@ -873,6 +1024,7 @@ mod tests {
 		type ForceOrigin = frame_system::EnsureRoot<u64>;
 		type Prefix = Prefix;
 		type MoveClaimOrigin = frame_system::EnsureSignedBy<Six, u64>;
+		type DelegateClaim = RecordDelegations;
 		type WeightInfo = TestWeightInfo;
 	}
 
@@ -1068,6 +1220,45 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn preclaimed_accounts_are_kept_until_they_attest() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(System::sufficients(&42), 1);
+			assert_eq!(System::sufficients(&43), 1);
+			// nothing to reap an account with a pending claim for
+			assert!(frame_system::Account::<Test>::contains_key(&42));
+
+			assert_ok!(Claims::attest(
+				RuntimeOrigin::signed(42),
+				StatementKind::Saft.to_text().to_vec()
+			));
+			assert_eq!(System::sufficients(&42), 0);
+			assert_eq!(System::sufficients(&43), 1);
+			assert_eq!(Balances::free_balance(&42), 300);
+		});
+	}
+
+	#[test]
+	fn the_storage_version_1_migration_adds_a_sufficient_reference_per_preclaim() {
+		use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+		new_test_ext().execute_with(|| {
+			// as before version 1, when preclaimed accounts held no reference
+			StorageVersion::new(0).put::<Claims>();
+			System::dec_sufficients(&42);
+			System::dec_sufficients(&43);
+
+			migrations::AddPreclaimRefs::<Test>::on_runtime_upgrade();
+			assert_eq!(System::sufficients(&42), 1);
+			assert_eq!(System::sufficients(&43), 1);
+			assert_eq!(Claims::on_chain_storage_version(), 1);
+
+			// and only once
+			migrations::AddPreclaimRefs::<Test>::on_runtime_upgrade();
+			assert_eq!(System::sufficients(&42), 1);
+		});
+	}
+
 	#[test]
 	fn attest_claiming_works() {
 		new_test_ext().execute_with(|| {
@@ -1112,6 +1303,56 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn claiming_and_delegating_works() {
+		new_test_ext().execute_with(|| {
+			let extra = to_ascii_hex(&7u64.encode());
+			let s = sig::<Test>(&alice(), &42u64.encode(), &extra);
+			// the candidate is signed for, so it cannot be swapped out
+			let r = Claims::claim_and_delegate(RuntimeOrigin::none(), 42, s.clone(), vec![], 8);
+			assert_noop!(r, Error::<Test>::SignerHasNoClaim);
+
+			assert_ok!(Claims::claim_and_delegate(RuntimeOrigin::none(), 42, s, vec![], 7));
+			assert_eq!(Balances::free_balance(&42), 100);
+			assert_eq!(Vesting::vesting_balance(&42), Some(50));
+			assert_eq!(Claims::total(), total_claims() - 100);
+			assert_eq!(Delegated::get(), vec![(42, 7, 100)]);
+
+			let statement = StatementKind::Regular.to_text();
+			let extra = [statement, &to_ascii_hex(&7u64.encode())[..]].concat();
+			let s = sig::<Test>(&dave(), &43u64.encode(), &extra);
+			assert_ok!(Claims::claim_and_delegate(
+				RuntimeOrigin::none(),
+				43,
+				s,
+				statement.to_vec(),
+				7
+			));
+			assert_eq!(Delegated::get(), vec![(42, 7, 100), (43, 7, 200)]);
+		});
+	}
+
+	#[test]
+	fn claim_is_not_paid_out_if_it_cannot_be_delegated() {
+		use frame_support::traits::UnfilteredDispatchable;
+		new_test_ext().execute_with(|| {
+			let s = sig::<Test>(&alice(), &42u64.encode(), &to_ascii_hex(&0u64.encode()));
+			// dispatched as a call, which reverts the claim when the delegation fails
+			let call = ClaimsCall::<Test>::claim_and_delegate {
+				dest: 42,
+				ethereum_signature: s,
+				statement: vec![],
+				candidate: 0,
+			};
+			assert_noop!(
+				call.dispatch_bypass_filter(RuntimeOrigin::none()),
+				DispatchError::Other("not a candidate")
+			);
+			assert_eq!(Claims::claims(&eth(&alice())), Some(100));
+			assert_eq!(Vesting::vesting_balance(&42), None);
+		});
+	}
+
 	#[test]
 	fn attesting_works() {
 		new_test_ext().execute_with(|| {
@@ -1501,6 +1742,23 @@ mod tests {
 				),
 				InvalidTransaction::Custom(ValidityError::InvalidEthereumSignature.into()).into(),
 			);
+			let s = sig::<Test>(&alice(), &1u64.encode(), &to_ascii_hex(&7u64.encode()));
+			let call = ClaimsCall::claim_and_delegate {
+				dest: 1,
+				ethereum_signature: s,
+				statement: vec![],
+				candidate: 7,
+			};
+			assert_eq!(
+				<Pallet<Test>>::validate_unsigned(source, &call),
+				Ok(ValidTransaction {
+					priority: 100,
+					requires: vec![],
+					provides: vec![("claims", eth(&alice())).encode()],
+					longevity: TransactionLongevity::max_value(),
+					propagate: true,
+				})
+			);
 
 			let s = sig::<Test>(&bob(), &1u64.encode(), StatementKind::Regular.to_text());
 			let call = ClaimsCall::claim_attest {
@@ -1716,6 +1974,7 @@ mod benchmarking {
 			let statement = StatementKind::Regular;
 			let signature = sig::<T>(&secret_key, &account.encode(), statement.to_text());
 			super::Pallet::<T>::mint_claim(RawOrigin::Root.into(), eth_address, VALUE.into(), vesting, Some(statement))?;
+			frame_system::Pallet::<T>::inc_sufficients(&account);
 			Preclaims::<T>::insert(&account, eth_address);
 			assert_eq!(Claims::<T>::get(eth_address), Some(VALUE.into()));
 
@@ -1757,6 +2016,7 @@ mod benchmarking {
 			let new_eth_address = eth(&new_secret_key);
 
 			let account: T::AccountId = account("user", c, SEED);
+			frame_system::Pallet::<T>::inc_sufficients(&account);
 			Preclaims::<T>::insert(&account, eth_address);
 
 			assert!(Claims::<T>::contains_key(eth_address));
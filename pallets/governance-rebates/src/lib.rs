@@ -0,0 +1,298 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Governance Rebates
+//!
+//! Refunds the fees of successful governance participation calls (e.g. referendum and council
+//! votes) from a funding account such as the treasury. Rebates are paid by the
+//! [`RebateGovernanceFees`] signed extension after dispatch, so the fee is still charged up
+//! front and failed calls are never refunded. Spending is capped per budget period, both in
+//! total and in the number of rebates a single account may receive.
+//!
+//! Fees paid in an asset through `pallet_asset_tx_payment` are rebated in that asset, converted
+//! from the native fee as the fee was. The runtime's asset fee handler notes the asset with
+//! [`Pallet::note_fee_asset`] for the extension to pick up.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchInfo, PostDispatchInfo},
+	pallet_prelude::*,
+	traits::{fungibles, tokens::BalanceConversion, Contains, Currency, ExistenceRequirement},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension, Zero},
+	transaction_validity::TransactionValidityError,
+	DispatchResult, SaturatedConversion, Saturating,
+};
+use sp_std::marker::PhantomData;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_transaction_payment::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency rebates are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The id of an asset fees may be paid in.
+		type AssetId: Parameter + Copy;
+
+		/// The assets fees may be paid in, which the fees paid in them are rebated in.
+		type Assets: fungibles::Transfer<
+			Self::AccountId,
+			AssetId = Self::AssetId,
+			Balance = BalanceOf<Self>,
+		>;
+
+		/// Converts a native fee to the amount of an asset it was paid with.
+		type AssetConversion: BalanceConversion<BalanceOf<Self>, Self::AssetId, BalanceOf<Self>>;
+
+		/// The account rebates are paid from.
+		type RebateSource: Get<Self::AccountId>;
+
+		/// The calls whose fees are rebated.
+		type RebatableCalls: Contains<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// Length in blocks of a budget period.
+		#[pallet::constant]
+		type BudgetPeriod: Get<Self::BlockNumber>;
+
+		/// The most that is rebated in total during a budget period.
+		#[pallet::constant]
+		type BudgetPerPeriod: Get<BalanceOf<Self>>;
+
+		/// The most rebates a single account receives during a budget period.
+		#[pallet::constant]
+		type MaxRebatesPerAccount: Get<u32>;
+
+		/// Weight information for paying a rebate.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The fee of a governance call was rebated, in `asset_id` if it was paid in an asset.
+		/// `amount` is in that asset.
+		FeeRebated { who: T::AccountId, amount: BalanceOf<T>, asset_id: Option<T::AssetId> },
+	}
+
+	/// The amount rebated so far in the current budget period
+	///
+	/// (PeriodIndex, Spent)
+	#[pallet::storage]
+	#[pallet::getter(fn spent_in_period)]
+	pub type SpentInPeriod<T: Config> = StorageValue<_, (T::BlockNumber, BalanceOf<T>), ValueQuery>;
+
+	/// The number of rebates each account received in its last rebated budget period
+	///
+	/// map AccountId => (PeriodIndex, Count)
+	#[pallet::storage]
+	#[pallet::getter(fn account_rebates)]
+	pub type AccountRebates<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+	/// The account paying the fee of the extrinsic being applied in an asset, and the asset.
+	/// Noted by the asset fee handler and taken by the rebate extension in the same extrinsic.
+	///
+	/// (AccountId, AssetId)
+	#[pallet::storage]
+	#[pallet::getter(fn fee_asset)]
+	pub type FeeAsset<T: Config> = StorageValue<_, (T::AccountId, T::AssetId), OptionQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Index of the budget period the current block belongs to
+	fn current_period() -> T::BlockNumber {
+		let period = T::BudgetPeriod::get();
+		if period.is_zero() {
+			return Zero::zero()
+		}
+		<frame_system::Pallet<T>>::block_number() / period
+	}
+
+	/// Note that `who` pays the fee of the extrinsic being applied in `asset_id`, for it to be
+	/// rebated in the same asset. Called by the runtime's asset fee handler when withdrawing it.
+	pub fn note_fee_asset(who: &T::AccountId, asset_id: T::AssetId) {
+		FeeAsset::<T>::put((who.clone(), asset_id));
+	}
+
+	/// Pays the native `fee` back to `who`, in `asset_id` if it was paid in an asset, if both the
+	/// period budget and the account allowance permit it. Budgets are in the native currency.
+	/// Returns the amount rebated, in the asset it was paid in.
+	pub(crate) fn rebate(
+		who: &T::AccountId,
+		fee: BalanceOf<T>,
+		asset_id: Option<T::AssetId>,
+	) -> BalanceOf<T> {
+		if fee.is_zero() {
+			return Zero::zero()
+		}
+		let period = Self::current_period();
+
+		let (account_period, count) = AccountRebates::<T>::get(who);
+		let count = if account_period == period { count } else { 0 };
+		if count >= T::MaxRebatesPerAccount::get() {
+			return Zero::zero()
+		}
+
+		let (spent_period, spent) = SpentInPeriod::<T>::get();
+		let spent = if spent_period == period { spent } else { Zero::zero() };
+		let amount = fee.min(T::BudgetPerPeriod::get().saturating_sub(spent));
+		if amount.is_zero() {
+			return Zero::zero()
+		}
+
+		let source = T::RebateSource::get();
+		let paid = match asset_id {
+			None => T::Currency::transfer(&source, who, amount, ExistenceRequirement::KeepAlive)
+				.map(|()| amount)
+				.ok(),
+			Some(asset_id) =>
+				T::AssetConversion::to_asset_balance(amount, asset_id).ok().and_then(|amount| {
+					<T::Assets as fungibles::Transfer<_>>::transfer(
+						asset_id, &source, who, amount, true,
+					)
+					.ok()
+				}),
+		};
+		let paid = match paid {
+			Some(paid) => paid,
+			None => return Zero::zero(),
+		};
+
+		SpentInPeriod::<T>::put((period, spent.saturating_add(amount)));
+		AccountRebates::<T>::insert(who, (period, count.saturating_add(1)));
+		Self::deposit_event(Event::FeeRebated { who: who.clone(), amount: paid, asset_id });
+		paid
+	}
+}
+
+/// Refunds the fee of successful calls in `T::RebatableCalls` out of the rebate budget.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct RebateGovernanceFees<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> RebateGovernanceFees<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for RebateGovernanceFees<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for RebateGovernanceFees<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "RebateGovernanceFees")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for RebateGovernanceFees<T>
+where
+	<T as frame_system::Config>::RuntimeCall:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "RebateGovernanceFees";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::RuntimeCall;
+	type AdditionalSigned = ();
+	/// The signer and the asset it paid the fee in, if the call is eligible for a rebate
+	type Pre = Option<(T::AccountId, Option<T::AssetId>)>;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	/// Takes the asset noted for the fee, and charges the block for a rebate of eligible calls
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let asset_id = FeeAsset::<T>::take().filter(|(payer, _)| payer == who).map(|(_, id)| id);
+		if !T::RebatableCalls::contains(call) {
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+				T::DbWeight::get().reads_writes(1, 1),
+				info.class,
+			);
+			return Ok(None)
+		}
+		<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+			T::WeightInfo::rebate(),
+			info.class,
+		);
+		Ok(Some((who.clone(), asset_id)))
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		if let (Some(Some((who, asset_id))), Ok(())) = (pre, result) {
+			// the tip is voluntary and is never rebated
+			let fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+				len as u32,
+				info,
+				post_info,
+				Zero::zero(),
+			);
+			Pallet::<T>::rebate(&who, fee.saturated_into::<u128>().saturated_into(), asset_id);
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,142 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg(test)]
+use super::*;
+use frame_support::{dispatch::DispatchInfo, weights::Weight};
+use mock::{RuntimeEvent, *};
+use sp_runtime::DispatchError;
+
+fn remark() -> RuntimeCall {
+	mock::RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+const BALANCE_TRANSFER: &<Runtime as frame_system::Config>::RuntimeCall =
+	&mock::RuntimeCall::Balances(pallet_balances::Call::transfer { dest: ALICE, value: 10 });
+
+fn info() -> DispatchInfo {
+	DispatchInfo { weight: Weight::from_ref_time(10), ..Default::default() }
+}
+
+fn dispatch(who: AccountId, call: &RuntimeCall, result: DispatchResult) {
+	let pre = RebateGovernanceFees::<Runtime>::new()
+		.pre_dispatch(&who, call, &info(), 10)
+		.unwrap();
+	assert_eq!(
+		RebateGovernanceFees::<Runtime>::post_dispatch(
+			Some(pre),
+			&info(),
+			&PostDispatchInfo::default(),
+			10,
+			&result
+		),
+		Ok(())
+	);
+}
+
+#[test]
+fn rebates_fee_of_rebatable_calls() {
+	ExtBuilder::default().build().execute_with(|| {
+		let fee =
+			TransactionPayment::compute_actual_fee(10, &info(), &PostDispatchInfo::default(), 0);
+		dispatch(ALICE, &remark(), Ok(()));
+		assert_eq!(Balances::free_balance(ALICE), 100 + fee);
+		assert_eq!(Balances::free_balance(TREASURY), 1_000_000 - fee);
+		System::assert_last_event(RuntimeEvent::GovernanceRebates(crate::Event::FeeRebated {
+			who: ALICE,
+			amount: fee,
+			asset_id: None,
+		}));
+	});
+}
+
+#[test]
+fn rebates_fees_paid_in_an_asset_in_that_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		let fee =
+			TransactionPayment::compute_actual_fee(10, &info(), &PostDispatchInfo::default(), 0);
+		GovernanceRebates::note_fee_asset(&ALICE, ASSET);
+		dispatch(ALICE, &remark(), Ok(()));
+		assert_eq!(GovernanceRebates::fee_asset(), None);
+		assert_eq!(Balances::free_balance(ALICE), 100);
+		assert_eq!(Assets::balance(ASSET, ALICE), 2 * fee);
+		assert_eq!(Assets::balance(ASSET, TREASURY), 1_000_000 - 2 * fee);
+		System::assert_last_event(RuntimeEvent::GovernanceRebates(crate::Event::FeeRebated {
+			who: ALICE,
+			amount: 2 * fee,
+			asset_id: Some(ASSET),
+		}));
+		// the budget is spent in native value
+		assert_eq!(GovernanceRebates::spent_in_period(), (0, fee));
+
+		// an asset noted for another signer or a call that is not rebated is not carried over
+		GovernanceRebates::note_fee_asset(&BOB, ASSET);
+		dispatch(ALICE, &remark(), Ok(()));
+		assert_eq!(Balances::free_balance(ALICE), 100 + fee);
+		GovernanceRebates::note_fee_asset(&ALICE, ASSET);
+		dispatch(ALICE, BALANCE_TRANSFER, Ok(()));
+		assert_eq!(GovernanceRebates::fee_asset(), None);
+	});
+}
+
+#[test]
+fn rebates_are_charged_to_the_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		let before = System::block_weight().total();
+		dispatch(ALICE, &remark(), Ok(()));
+		assert_eq!(System::block_weight().total(), before + <() as crate::WeightInfo>::rebate());
+	});
+}
+
+#[test]
+fn does_not_rebate_other_or_failed_calls() {
+	ExtBuilder::default().build().execute_with(|| {
+		dispatch(ALICE, BALANCE_TRANSFER, Ok(()));
+		dispatch(ALICE, &remark(), Err(DispatchError::BadOrigin));
+		assert_eq!(Balances::free_balance(ALICE), 100);
+		assert_eq!(GovernanceRebates::account_rebates(ALICE), (0, 0));
+	});
+}
+
+#[test]
+fn rebates_are_capped_per_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(GovernanceRebates::rebate(&ALICE, 10, None), 10);
+		assert_eq!(GovernanceRebates::rebate(&ALICE, 10, None), 10);
+		assert_eq!(GovernanceRebates::rebate(&ALICE, 10, None), 0);
+		assert_eq!(GovernanceRebates::rebate(&BOB, 10, None), 10);
+
+		// allowance is restored in the next period
+		System::set_block_number(BUDGET_PERIOD);
+		assert_eq!(GovernanceRebates::rebate(&ALICE, 10, None), 10);
+	});
+}
+
+#[test]
+fn rebates_are_capped_per_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			GovernanceRebates::rebate(&ALICE, BUDGET_PER_PERIOD - 1, None),
+			BUDGET_PER_PERIOD - 1
+		);
+		// only what is left of the budget is paid
+		assert_eq!(GovernanceRebates::rebate(&BOB, 10, None), 1);
+		assert_eq!(GovernanceRebates::rebate(&BOB, 10, None), 0);
+		assert_eq!(GovernanceRebates::spent_in_period(), (0, BUDGET_PER_PERIOD));
+
+		System::set_block_number(BUDGET_PERIOD);
+		assert_eq!(GovernanceRebates::rebate(&BOB, 10, None), 10);
+		assert_eq!(GovernanceRebates::spent_in_period(), (1, 10));
+	});
+}
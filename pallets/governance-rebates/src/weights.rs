@@ -0,0 +1,55 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_governance_rebates, estimated from a `pallet_balances` transfer plus the
+//! storage the rebate touches until the rebate is benchmarked
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_governance_rebates.
+pub trait WeightInfo {
+	fn rebate() -> Weight;
+}
+
+/// Weights for pallet_governance_rebates using the Substrate node and recommended hardware.
+pub struct WebbWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for WebbWeight<T> {
+	// Storage: GovernanceRebates FeeAsset (r:1 w:1)
+	// Storage: GovernanceRebates AccountRebates (r:1 w:1)
+	// Storage: GovernanceRebates SpentInPeriod (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: AssetRates Rates (r:1 w:0)
+	fn rebate() -> Weight {
+		Weight::from_ref_time(45_000_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn rebate() -> Weight {
+		Weight::from_ref_time(45_000_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+}
@@ -0,0 +1,170 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # HRMP Manager
+//!
+//! Lets governance open and accept HRMP channels with other parachains. Each call sends the
+//! relay chain an XCM `Transact` of the matching `Hrmp` call, dispatched with this parachain's
+//! origin and paid for from its sovereign account on the relay chain, which therefore needs to
+//! hold enough for the fee and the channel deposit.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use polkadot_parachain::primitives::Id as ParaId;
+use scale_info::TypeInfo;
+use sp_std::prelude::*;
+use xcm::latest::prelude::*;
+
+mod mock;
+mod tests;
+pub mod weights;
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// Calls of the relay chain's `Hrmp` pallet, encoded like the relay chain does
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum HrmpCall {
+	/// `hrmp_init_open_channel(recipient, proposed_max_capacity, proposed_max_message_size)`
+	#[codec(index = 0)]
+	InitOpenChannel(ParaId, u32, u32),
+	/// `hrmp_accept_open_channel(sender)`
+	#[codec(index = 1)]
+	AcceptOpenChannel(ParaId),
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin which may open and accept channels.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// How messages are sent to the relay chain.
+		type XcmSender: SendXcm;
+
+		/// This parachain's id, where unspent fees are refunded to on the relay chain.
+		type SelfParaId: Get<ParaId>;
+
+		/// Index of the `Hrmp` pallet in the relay chain runtime.
+		#[pallet::constant]
+		type RelayHrmpPalletIndex: Get<u8>;
+
+		/// Amount of the relay chain token withdrawn from the sovereign account to pay for
+		/// executing each message.
+		#[pallet::constant]
+		type RelayFee: Get<u128>;
+
+		/// Weight the relay chain may use to dispatch each `Hrmp` call.
+		#[pallet::constant]
+		type RelayCallWeight: Get<u64>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The message could not be sent to the relay chain
+		SendFailed,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Requested a channel to `recipient` from the relay chain
+		OpenChannelRequested { recipient: ParaId, max_capacity: u32, max_message_size: u32 },
+		/// Accepted the channel requested by `sender` on the relay chain
+		OpenChannelAccepted { sender: ParaId },
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Request a channel from this parachain to `recipient`, which `recipient` then has to
+		/// accept.
+		#[pallet::weight(T::WeightInfo::open_channel())]
+		pub fn open_channel(
+			origin: OriginFor<T>,
+			recipient: ParaId,
+			max_capacity: u32,
+			max_message_size: u32,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::send_to_relay(HrmpCall::InitOpenChannel(
+				recipient,
+				max_capacity,
+				max_message_size,
+			))?;
+			Self::deposit_event(Event::OpenChannelRequested {
+				recipient,
+				max_capacity,
+				max_message_size,
+			});
+			Ok(())
+		}
+
+		/// Accept the channel `sender` requested to this parachain.
+		#[pallet::weight(T::WeightInfo::accept_channel())]
+		pub fn accept_channel(origin: OriginFor<T>, sender: ParaId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::send_to_relay(HrmpCall::AcceptOpenChannel(sender))?;
+			Self::deposit_event(Event::OpenChannelAccepted { sender });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The message dispatching `call` on the relay chain as this parachain
+	pub fn relay_message(call: HrmpCall) -> Xcm<()> {
+		let fee =
+			MultiAsset { id: Concrete(MultiLocation::here()), fun: Fungible(T::RelayFee::get()) };
+		Xcm(vec![
+			WithdrawAsset(fee.clone().into()),
+			BuyExecution { fees: fee, weight_limit: Unlimited },
+			Transact {
+				origin_type: OriginKind::Native,
+				require_weight_at_most: T::RelayCallWeight::get(),
+				call: (T::RelayHrmpPalletIndex::get(), call).encode().into(),
+			},
+			RefundSurplus,
+			DepositAsset {
+				assets: Wild(All),
+				max_assets: 1,
+				beneficiary: Parachain(T::SelfParaId::get().into()).into(),
+			},
+		])
+	}
+
+	fn send_to_relay(call: HrmpCall) -> DispatchResult {
+		T::XcmSender::send_xcm(MultiLocation::parent(), Self::relay_message(call))
+			.map_err(|_| Error::<T>::SendFailed)?;
+		Ok(())
+	}
+}
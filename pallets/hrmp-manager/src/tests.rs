@@ -0,0 +1,93 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit tests for the hrmp manager module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{RuntimeEvent, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn open_channel_requires_update_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			HrmpManager::open_channel(RuntimeOrigin::signed(2), 2001.into(), 8, 1024),
+			BadOrigin
+		);
+		assert_noop!(HrmpManager::accept_channel(RuntimeOrigin::signed(2), 2001.into()), BadOrigin);
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn open_channel_transacts_on_relay_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HrmpManager::open_channel(RuntimeOrigin::signed(ALICE), 2001.into(), 8, 1024));
+		System::assert_last_event(RuntimeEvent::HrmpManager(crate::Event::OpenChannelRequested {
+			recipient: 2001.into(),
+			max_capacity: 8,
+			max_message_size: 1024,
+		}));
+
+		let fee = MultiAsset { id: Concrete(MultiLocation::here()), fun: Fungible(1_000) };
+		// `Hrmp` pallet index, call index, then the call arguments
+		let mut call = vec![60u8, 0];
+		call.extend((2001u32, 8u32, 1024u32).encode());
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				MultiLocation::parent(),
+				Xcm(vec![
+					WithdrawAsset(fee.clone().into()),
+					BuyExecution { fees: fee, weight_limit: Unlimited },
+					Transact {
+						origin_type: OriginKind::Native,
+						require_weight_at_most: 1_000_000_000,
+						call: call.into(),
+					},
+					RefundSurplus,
+					DepositAsset {
+						assets: Wild(All),
+						max_assets: 1,
+						beneficiary: Parachain(SELF_PARA_ID).into(),
+					},
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn accept_channel_transacts_on_relay_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HrmpManager::accept_channel(RuntimeOrigin::signed(ALICE), 2001.into()));
+		System::assert_last_event(RuntimeEvent::HrmpManager(crate::Event::OpenChannelAccepted {
+			sender: 2001.into(),
+		}));
+
+		let sent = sent_xcm();
+		assert_eq!(sent.len(), 1);
+		let mut expected = vec![60u8, 1];
+		expected.extend(2001u32.encode());
+		assert!(matches!(
+			&sent[0].1 .0[2],
+			Transact { call, .. } if call.clone().into_encoded() == expected
+		));
+	});
+}
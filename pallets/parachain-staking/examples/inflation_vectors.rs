@@ -0,0 +1,61 @@
+//! Print per-round issuance vectors for an inflation config as CSV:
+//!
+//! cargo run -p pallet-parachain-staking --example inflation_vectors -- \
+//!     <annual min,ideal,max in percent> <expected stake min,ideal,max> <blocks per round> \
+//!     <circulating>
+//!
+//! Without arguments it prints the reference case used by the unit tests.
+use pallet_parachain_staking::{
+	inflation::{perbill_annual_to_perbill_round, BLOCKS_PER_YEAR},
+	inflation_vectors::{issuance_vectors, to_csv},
+	InflationInfo, Range,
+};
+use sp_runtime::Perbill;
+use std::{env, process};
+
+fn parse_range<T: std::str::FromStr>(arg: &str) -> Option<Range<T>> {
+	let mut parts = arg.split(',').map(|p| p.trim().parse::<T>().ok());
+	let range = Range { min: parts.next()??, ideal: parts.next()??, max: parts.next()?? };
+	parts.next().is_none().then_some(range)
+}
+
+fn usage() -> ! {
+	eprintln!(
+		"usage: inflation_vectors <annual%> <expect> <blocks_per_round> <circulating>\n\
+		 e.g.   inflation_vectors 3,4,5 100,200,300 262980 10000000"
+	);
+	process::exit(1)
+}
+
+fn main() {
+	let args: Vec<String> = env::args().skip(1).collect();
+	let args: Vec<&str> = if args.is_empty() {
+		vec!["3,4,5", "100,200,300", "262980", "10000000"]
+	} else if args.len() == 4 {
+		args.iter().map(String::as_str).collect()
+	} else {
+		usage()
+	};
+
+	let annual = parse_range::<u32>(args[0]).unwrap_or_else(|| usage());
+	let annual = Range {
+		min: Perbill::from_percent(annual.min),
+		ideal: Perbill::from_percent(annual.ideal),
+		max: Perbill::from_percent(annual.max),
+	};
+	let expect = parse_range::<u128>(args[1]).unwrap_or_else(|| usage());
+	let blocks_per_round =
+		args[2].parse::<u32>().ok().filter(|b| *b > 0).unwrap_or_else(|| usage());
+	let circulating = args[3].parse::<u128>().unwrap_or_else(|_| usage());
+	if !annual.is_valid() || !expect.is_valid() {
+		eprintln!("ranges must satisfy min <= ideal <= max");
+		process::exit(1)
+	}
+
+	let info = InflationInfo {
+		expect,
+		annual,
+		round: perbill_annual_to_perbill_round(annual, BLOCKS_PER_YEAR / blocks_per_round),
+	};
+	print!("{}", to_csv(&issuance_vectors(&info, blocks_per_round, circulating)));
+}
@@ -0,0 +1,193 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the parachain-staking pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use sp_arithmetic::{Perbill, Percent};
+use sp_std::vec::Vec;
+
+/// Breakdown of an account's balance. `free` and `reserved` add up to the account's total
+/// balance, the other amounts are parts of one of them.
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct AccountBalances<Balance> {
+	/// Free balance, including any locked part
+	pub free: Balance,
+	/// Part of `free` not frozen by any lock
+	pub transferable: Balance,
+	/// Part of `free` still locked by vesting
+	pub vesting: Balance,
+	/// Bonded as a candidate or delegator. Held out of the total balance, or locked in `free`
+	/// for accounts still on the staking locks.
+	pub staked: Balance,
+	/// Part of `staked` scheduled to be unbonded
+	pub pending_unbond: Balance,
+	/// Reserved balance other than the held stake, e.g. deposits
+	pub reserved: Balance,
+}
+
+/// Where the chain is in the current staking round
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RoundProgress<BlockNumber> {
+	/// Index of the current round
+	pub round: u32,
+	/// Blocks since the first block of the round
+	pub elapsed: BlockNumber,
+	/// Blocks until the next election ends the round
+	pub remaining: BlockNumber,
+	/// Estimated block of the next collator election, at which the next round starts
+	pub next_election: BlockNumber,
+}
+
+/// A collator candidate's standing, as shown on leaderboards
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct CandidateRanking<AccountId, Balance> {
+	/// The candidate
+	pub candidate: AccountId,
+	/// Self bond plus counted delegations
+	pub total_backing: Balance,
+	/// Points awarded to the candidate in the last round
+	pub last_round_points: u32,
+	/// Commission taken from the candidate's rewards before they are shared with delegators
+	pub commission: Perbill,
+	/// Top delegation slots still free, beyond which new delegations earn no rewards unless
+	/// they outbid the lowest top delegation
+	pub delegation_slots_remaining: u32,
+	/// Annual return of a delegation to the candidate if every round paid out like the last
+	pub estimated_apr: Perbill,
+}
+
+/// Where a delegation sits among the delegations of its candidate
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum DelegationPosition {
+	/// Counted towards the candidate's stake and rewarded
+	Top,
+	/// Neither counted nor rewarded until promoted to the top
+	Bottom,
+}
+
+/// Staking state a collator node reports to its operator's monitoring
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct CollatorMetrics {
+	/// Index of the current round
+	pub round: u32,
+	/// Collators selected to author blocks in the current round
+	pub selected_candidates: u32,
+	/// Position of the collator among the active candidates by total backing, 1 being the
+	/// most backed, if it is an active candidate
+	pub rank: Option<u32>,
+	/// Delegations to the collator, if it is a candidate
+	pub delegation_count: Option<u32>,
+	/// Collators still to be paid out across the rounds awaiting payout
+	pub pending_payouts: u32,
+}
+
+/// Funds set aside for the parachain bond out of round inflation
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ParachainBondFunds<AccountId, Balance> {
+	/// Account currently receiving the parachain bond reserve
+	pub account: AccountId,
+	/// Percent of round inflation reserved for the parachain bond
+	pub percent: Percent,
+	/// Inflation reserved for the parachain bond over all rounds paid out
+	pub total_reserved: Balance,
+	/// Funds the parachain bond account holds, until swept to the treasury
+	pub balance: Balance,
+}
+
+/// Rewards a collator, its payout split and its delegators would receive for a round if it were
+/// paid out now
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RoundPayoutEstimate<AccountId, Balance> {
+	/// The collator
+	pub collator: AccountId,
+	/// Reward kept by the collator, commission included, after the cut paid elsewhere on
+	/// collator payouts and its payout split
+	pub collator_reward: Balance,
+	/// Cut of the collator's reward paid elsewhere, e.g. to the treasury
+	pub cut: Balance,
+	/// Shares of the collator's reward paid to the accounts of its payout split
+	pub split_rewards: Vec<(AccountId, Balance)>,
+	/// Reward of each rewardable delegator of the collator
+	pub delegator_rewards: Vec<(AccountId, Balance)>,
+}
+
+/// A call waiting on-chain for approval, known to signatories only by its hash
+#[derive(Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum PendingCall {
+	/// A council proposal, whose call data is stored with it
+	Proposal(Vec<u8>),
+	/// A proxy announcement, whose call data is only known to the announcer
+	Announcement,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait ParachainStakingApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the expected author for each of the `count` slots starting at `from_slot`,
+		/// given the current collator selection and the aura-style round-robin filter.
+		fn authorship_schedule(from_slot: u64, count: u32) -> Vec<(u64, AccountId)>;
+		/// Returns the rewards `account` is due from rounds awaiting payout, followed by the
+		/// rewards paid to it in each of its most recent rewarded rounds.
+		fn pending_and_historic_rewards(account: AccountId) -> (Balance, Vec<(u32, Balance)>);
+		/// Returns the estimated block number and unix timestamp in milliseconds at which the
+		/// earliest pending unbonding of `account` from `candidate` can be executed, if any.
+		fn unbonding_eta(account: AccountId, candidate: AccountId) -> Option<(BlockNumber, u64)>;
+		/// Returns the balance of `account` broken down into free, transferable, vesting, staked,
+		/// pending unbond and reserved amounts.
+		fn account_balances(account: AccountId) -> AccountBalances<Balance>;
+		/// Returns the current round, the blocks elapsed in and remaining of it, and the estimated
+		/// block of the next collator election, using the same session schedule as `can_author`.
+		fn round_progress() -> RoundProgress<BlockNumber>;
+		/// Returns the active candidates ordered by total backing, highest first, with their
+		/// last round points, commission, free delegation slots and estimated APR.
+		fn candidate_rankings() -> Vec<CandidateRanking<AccountId, Balance>>;
+		/// Returns whether the delegation of `delegator` to `candidate` is in the top or bottom
+		/// delegations of the candidate, or `None` if there is no such delegation.
+		fn delegation_position(
+			candidate: AccountId,
+			delegator: AccountId,
+		) -> Option<DelegationPosition>;
+		/// Returns the current round, the number of selected collators and the payouts left,
+		/// with the rank and delegation count of `collator` if given.
+		fn collator_metrics(collator: Option<AccountId>) -> CollatorMetrics;
+		/// Returns the parachain bond account and reserve percent, the inflation reserved for it
+		/// so far and the funds it holds.
+		fn parachain_bond_funds() -> ParachainBondFunds<AccountId, Balance>;
+		/// Returns the rewards each collator of `round` not paid yet and its delegators would
+		/// receive if the round were paid out now, given its points so far and the current
+		/// inflation config.
+		fn estimate_round_payouts(round: u32) -> Vec<RoundPayoutEstimate<AccountId, Balance>>;
+		/// Returns the call waiting for approval with the blake2-256 hash `call_hash`, with its
+		/// call data if stored on-chain, or `None` if no such call is pending.
+		fn pending_call(call_hash: [u8; 32]) -> Option<PendingCall>;
+	}
+}
@@ -19,9 +19,10 @@
 use crate::{
 	pallet::{
 		AutoCompoundingDelegations as AutoCompoundingDelegationsStorage, BalanceOf, CandidateInfo,
-		Config, DelegatorState, Error, Event, Pallet, Total,
+		Config, DelegatorState, Error, Event, Pallet, PendingCompound, Total,
 	},
 	types::{Bond, BondAdjust, Delegator},
+	RoundIndex,
 };
 use frame_support::{dispatch::DispatchResultWithPostInfo, ensure, traits::Get, RuntimeDebug};
 use parity_scale_codec::{Decode, Encode};
@@ -34,6 +35,9 @@ use sp_std::{prelude::*, vec::Vec};
 pub struct AutoCompoundConfig<AccountId> {
 	pub delegator: AccountId,
 	pub value: Percent,
+	/// Rewards are compounded in rounds divisible by `every`, the share to compound is put
+	/// aside in `PendingCompound` in between. One compounds every round.
+	pub every: RoundIndex,
 }
 
 /// Represents the auto-compounding [Delegations] for `T: Config`
@@ -70,6 +74,32 @@ where
 		}
 	}
 
+	/// Retrieves the auto-compounding frequency for a delegation, one if it has no config. The
+	/// `delegations_config` must be a sorted vector for binary_search to work.
+	pub fn get_every_for_delegator(&self, delegator: &T::AccountId) -> RoundIndex {
+		match self.0.binary_search_by(|d| d.delegator.cmp(delegator)) {
+			Ok(index) => self.0[index].every,
+			Err(_) => 1,
+		}
+	}
+
+	/// Sets the auto-compounding frequency of an existing config. Returns `None` if the delegation
+	/// has no config, otherwise whether it changed. The `delegations_config` must be a sorted
+	/// vector for binary_search to work.
+	pub fn set_every_for_delegator(
+		&mut self,
+		delegator: &T::AccountId,
+		every: RoundIndex,
+	) -> Option<bool> {
+		let index = self.0.binary_search_by(|d| d.delegator.cmp(delegator)).ok()?;
+		if self.0[index].every == every {
+			Some(false)
+		} else {
+			self.0[index].every = every;
+			Some(true)
+		}
+	}
+
 	/// Sets the auto-compounding value for a delegation. The `delegations_config` must be a sorted
 	/// vector for binary_search to work.
 	pub fn set_for_delegator(&mut self, delegator: T::AccountId, value: Percent) -> bool {
@@ -82,7 +112,7 @@ where
 					true
 				},
 			Err(index) => {
-				self.0.insert(index, AutoCompoundConfig { delegator, value });
+				self.0.insert(index, AutoCompoundConfig { delegator, value, every: 1 });
 				true
 			},
 		}
@@ -196,9 +226,14 @@ where
 			state.set_storage(&candidate);
 		}
 
+		// delegators hold a consumer reference so the account is not reaped while staking
+		if !<DelegatorState<T>>::contains_key(&delegator) {
+			<frame_system::Pallet<T>>::inc_consumers(&delegator)?;
+		}
 		<Total<T>>::put(new_total_locked);
 		<CandidateInfo<T>>::insert(&candidate, candidate_state);
 		<DelegatorState<T>>::insert(&delegator, delegator_state);
+		<Pallet<T>>::defer_large_delegation(&candidate, &delegator, amount, amount);
 		<Pallet<T>>::deposit_event(Event::Delegation {
 			delegator,
 			locked_amount: amount,
@@ -235,6 +270,7 @@ where
 			<Error<T>>::TooLowCandidateAutoCompoundingDelegationCountToAutoCompound,
 		);
 		let state_updated = if value.is_zero() {
+			<PendingCompound<T>>::remove(&candidate, &delegator);
 			auto_compounding_state.remove_for_delegator(&delegator)
 		} else {
 			auto_compounding_state.set_for_delegator(delegator.clone(), value)
@@ -248,12 +284,40 @@ where
 		Ok(().into())
 	}
 
+	/// Sets how often the rewards of a delegation are compounded. Compounding put off under the
+	/// previous frequency is dropped, leaving those rewards liquid.
+	pub(crate) fn set_auto_compound_every(
+		candidate: T::AccountId,
+		delegator: T::AccountId,
+		every: RoundIndex,
+		candidate_auto_compounding_delegation_count_hint: u32,
+	) -> DispatchResultWithPostInfo {
+		ensure!(!every.is_zero(), <Error<T>>::InvalidCompoundFrequency);
+		let mut auto_compounding_state = Self::get_storage(&candidate);
+		ensure!(
+			auto_compounding_state.len() <= candidate_auto_compounding_delegation_count_hint,
+			<Error<T>>::TooLowCandidateAutoCompoundingDelegationCountToAutoCompound,
+		);
+		let state_updated = auto_compounding_state
+			.set_every_for_delegator(&delegator, every)
+			.ok_or(<Error<T>>::AutoCompoundDNE)?;
+		if state_updated {
+			auto_compounding_state.set_storage(&candidate);
+			<PendingCompound<T>>::remove(&candidate, &delegator);
+		}
+
+		<Pallet<T>>::deposit_event(Event::AutoCompoundFrequencySet { candidate, delegator, every });
+
+		Ok(().into())
+	}
+
 	/// Removes the auto-compounding value for a delegation. This should be called when the
 	/// delegation is revoked to cleanup storage. Storage is only written iff the entry existed.
 	pub(crate) fn remove_auto_compound(candidate: &T::AccountId, delegator: &T::AccountId) {
 		let mut auto_compounding_state = Self::get_storage(candidate);
 		if auto_compounding_state.remove_for_delegator(delegator) {
 			auto_compounding_state.set_storage(candidate);
+			<PendingCompound<T>>::remove(candidate, delegator);
 		}
 	}
 
@@ -274,7 +338,7 @@ mod tests {
 		let mut delegations_config = AutoCompoundDelegations::<Test>::new(vec![]);
 		assert_eq!(true, delegations_config.set_for_delegator(1, Percent::from_percent(50)));
 		assert_eq!(
-			vec![AutoCompoundConfig { delegator: 1, value: Percent::from_percent(50) }],
+			vec![AutoCompoundConfig { delegator: 1, value: Percent::from_percent(50), every: 1 }],
 			delegations_config.into_inner(),
 		);
 	}
@@ -285,10 +349,11 @@ mod tests {
 			AutoCompoundDelegations::<Test>::new(vec![AutoCompoundConfig {
 				delegator: 1,
 				value: Percent::from_percent(10),
+				every: 1,
 			}]);
 		assert_eq!(true, delegations_config.set_for_delegator(1, Percent::from_percent(50)));
 		assert_eq!(
-			vec![AutoCompoundConfig { delegator: 1, value: Percent::from_percent(50) }],
+			vec![AutoCompoundConfig { delegator: 1, value: Percent::from_percent(50), every: 1 }],
 			delegations_config.into_inner(),
 		);
 	}
@@ -299,10 +364,11 @@ mod tests {
 			AutoCompoundDelegations::<Test>::new(vec![AutoCompoundConfig {
 				delegator: 1,
 				value: Percent::from_percent(10),
+				every: 1,
 			}]);
 		assert_eq!(false, delegations_config.set_for_delegator(1, Percent::from_percent(10)));
 		assert_eq!(
-			vec![AutoCompoundConfig { delegator: 1, value: Percent::from_percent(10) }],
+			vec![AutoCompoundConfig { delegator: 1, value: Percent::from_percent(10), every: 1 }],
 			delegations_config.into_inner(),
 		);
 	}
@@ -319,6 +385,7 @@ mod tests {
 			AutoCompoundDelegations::<Test>::new(vec![AutoCompoundConfig {
 				delegator: 1,
 				value: Percent::from_percent(10),
+				every: 1,
 			}]);
 		assert_eq!(true, delegations_config.remove_for_delegator(&1));
 	}
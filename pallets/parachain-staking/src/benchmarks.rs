@@ -18,13 +18,14 @@
 
 //! Benchmarking
 use crate::{
-	AwardedPts, BalanceOf, Call, CandidateBondLessRequest, Config, DelegationAction, Pallet,
-	Points, Range, Round, ScheduledRequest,
+	AwardedPts, BalanceOf, Call, CandidateBondLessRequest, Config, DelegationAction, KeysOf,
+	Pallet, Points, Range, Round, ScheduledRequest, SetSessionKeys,
 };
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, vec};
-use frame_support::traits::{Currency, Get, OnFinalize, OnInitialize};
+use frame_support::traits::{Currency, EnsureOrigin, Get, OnFinalize, OnInitialize};
 use frame_system::RawOrigin;
-use sp_runtime::{Perbill, Percent};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::{traits::TrailingZeroInput, Perbill, Percent};
 use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 /// Minimum collator candidate stake
@@ -188,6 +189,47 @@ benchmarks! {
 		assert_eq!(Pallet::<T>::round().length, 1200u32);
 	}
 
+	set_invulnerables {
+		let x in 1..<<T as Config>::MaxInvulnerables as Get<u32>>::get();
+		let mut invulnerables = Vec::new();
+		for i in 0..x {
+			let (account, _) = create_funded_user::<T>("invulnerable", i, 0u32.into());
+			// distinct keys for each invulnerable
+			let keys = KeysOf::<T>::decode(&mut TrailingZeroInput::new(&i.encode()))
+				.expect("keys decode from an infinite input");
+			T::SessionKeys::set_keys(&account, keys, Vec::new())?;
+			invulnerables.push(account);
+		}
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		Pallet::<T>::set_invulnerables(origin, invulnerables.clone())?;
+	}
+	verify {
+		assert_eq!(Pallet::<T>::invulnerable_candidates(), invulnerables);
+	}
+
+	set_successor_invulnerables {
+		let x in 1..<<T as Config>::MaxInvulnerables as Get<u32>>::get();
+		let mut invulnerables = Vec::new();
+		for i in 0..x {
+			let (account, _) = create_funded_user::<T>("successor", i, 0u32.into());
+			// distinct keys for each successor
+			let keys = KeysOf::<T>::decode(&mut TrailingZeroInput::new(&i.encode()))
+				.expect("keys decode from an infinite input");
+			T::SessionKeys::set_keys(&account, keys, Vec::new())?;
+			invulnerables.push(account);
+		}
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		Pallet::<T>::set_successor_invulnerables(origin, invulnerables.clone())?;
+	}
+	verify {
+		assert_eq!(
+			Pallet::<T>::successor_invulnerables().map(|successors| successors.into_inner()),
+			Some(invulnerables)
+		);
+	}
+
 	// USER DISPATCHABLES
 
 	join_candidates {
@@ -604,6 +646,86 @@ benchmarks! {
 		assert!(Pallet::<T>::delegator_state(&caller).unwrap().is_active());
 	}
 
+	schedule_leave_all {
+		let x in 1..<<T as Config>::MaxDelegationsPerDelegator as Get<u32>>::get();
+		let mut collators: Vec<T::AccountId> = Vec::new();
+		for i in 0..x {
+			let seed = USER_SEED - i - 1;
+			let collator = create_funded_collator::<T>(
+				"collator",
+				seed,
+				0u32.into(),
+				true,
+				collators.len() as u32 + 1u32
+			)?;
+			collators.push(collator.clone());
+		}
+		let bond = <<T as Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+		let need = bond * (collators.len() as u32).into();
+		let default_minted = min_candidate_stk::<T>();
+		let need: BalanceOf<T> = if need > default_minted {
+			need - default_minted
+		} else {
+			0u32.into()
+		};
+		let (caller, _) = create_funded_user::<T>("caller", USER_SEED, need);
+		for (delegation_count, col) in collators.into_iter().enumerate() {
+			Pallet::<T>::delegate(
+				RawOrigin::Signed(caller.clone()).into(),
+				col,
+				bond,
+				0u32,
+				delegation_count as u32
+			)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(
+			Pallet::<T>::delegator_state(&caller).unwrap().less_total,
+			bond * x.into()
+		);
+	}
+
+	execute_leave_all {
+		let x in 1..<<T as Config>::MaxDelegationsPerDelegator as Get<u32>>::get();
+		let mut collators: Vec<T::AccountId> = Vec::new();
+		for i in 0..x {
+			let seed = USER_SEED - i - 1;
+			let collator = create_funded_collator::<T>(
+				"collator",
+				seed,
+				0u32.into(),
+				true,
+				collators.len() as u32 + 1u32
+			)?;
+			collators.push(collator.clone());
+		}
+		let bond = <<T as Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+		let need = bond * (collators.len() as u32).into();
+		let default_minted = min_candidate_stk::<T>();
+		let need: BalanceOf<T> = if need > default_minted {
+			need - default_minted
+		} else {
+			0u32.into()
+		};
+		let (caller, _) = create_funded_user::<T>("caller", USER_SEED, need);
+		let author = collators[0].clone();
+		for (delegation_count, col) in collators.into_iter().enumerate() {
+			Pallet::<T>::delegate(
+				RawOrigin::Signed(caller.clone()).into(),
+				col,
+				bond,
+				0u32,
+				delegation_count as u32
+			)?;
+		}
+		Pallet::<T>::schedule_leave_all(RawOrigin::Signed(caller.clone()).into())?;
+		roll_to_and_author::<T>(T::LeaveDelegatorsDelay::get(), author);
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone())
+	verify {
+		assert!(Pallet::<T>::delegator_state(&caller).is_none());
+	}
+
 	schedule_revoke_delegation {
 		let collator: T::AccountId = create_funded_collator::<T>(
 			"collator",
@@ -1095,6 +1217,16 @@ benchmarks! {
 		assert_eq!(start + 1u32.into(), end);
 	}
 
+	on_finalize {
+		let now = <Round<T>>::get().current;
+		let points_before = <Points<T>>::get(now);
+		let block = <frame_system::Pallet<T>>::block_number();
+	}: { Pallet::<T>::on_finalize(block); }
+	verify {
+		// the block author was awarded points
+		assert!(<Points<T>>::get(now) > points_before);
+	}
+
 	set_auto_compound {
 		// x controls number of distinct auto-compounding delegations the prime collator will have
 		// y controls number of distinct delegations the prime delegator will have
@@ -1290,20 +1422,6 @@ mod tests {
 		TestExternalities::new(t)
 	}
 
-	#[test]
-	fn bench_hotfix_remove_delegation_requests() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Pallet::<Test>::test_benchmark_hotfix_remove_delegation_requests());
-		});
-	}
-
-	#[test]
-	fn bench_hotfix_update_candidate_pool_value() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Pallet::<Test>::test_benchmark_hotfix_update_candidate_pool_value());
-		});
-	}
-
 	#[test]
 	fn bench_set_staking_expectations() {
 		new_test_ext().execute_with(|| {
@@ -1451,6 +1569,20 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn bench_schedule_leave_all() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_schedule_leave_all());
+		});
+	}
+
+	#[test]
+	fn bench_execute_leave_all() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_execute_leave_all());
+		});
+	}
+
 	#[test]
 	fn bench_schedule_revoke_delegation() {
 		new_test_ext().execute_with(|| {
@@ -1513,6 +1645,27 @@ mod tests {
 			assert_ok!(Pallet::<Test>::test_benchmark_base_on_initialize());
 		});
 	}
+
+	#[test]
+	fn bench_on_finalize() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_on_finalize());
+		});
+	}
+
+	#[test]
+	fn bench_set_invulnerables() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_set_invulnerables());
+		});
+	}
+
+	#[test]
+	fn bench_set_successor_invulnerables() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_set_successor_invulnerables());
+		});
+	}
 }
 
 impl_benchmark_test_suite!(Pallet, crate::benchmarks::tests::new_test_ext(), crate::mock::Test);
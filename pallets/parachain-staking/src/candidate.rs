@@ -0,0 +1,493 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Collator candidate functionality: joining, leaving and adjusting the self bond
+
+use crate::{
+	auto_compound::AutoCompoundDelegations,
+	pallet::{
+		AutoCompoundingDelegations, BalanceOf, BondFunders, BottomDelegations, CandidateInfo,
+		CandidatePool, CandidatePoolCount, CollatorPayoutSplit, Config, ConsecutiveMissedRounds,
+		DeferredStake, DelegationMemos, DelegationScheduledRequests, DelegatorState, Error, Event,
+		HeldStake, KeysOf, LockedStake, Pallet, Round, TopDelegations, Total, UnappliedSlashes,
+		VestedStake,
+	},
+	traits::SetSessionKeys,
+	types::{Bond, CandidateMetadata, Delegations, UnappliedSlash},
+	RoundIndex,
+};
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	ensure,
+	traits::{Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, ReservableCurrency},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchResult, PerThing, Perbill,
+};
+use sp_std::vec::Vec;
+
+impl<T: Config> Pallet<T> {
+	/// Caller must ensure candidate is active before calling
+	pub(crate) fn update_active(candidate: T::AccountId, total: BalanceOf<T>) {
+		<CandidatePool<T>>::insert(&candidate, total);
+		Self::note_election_change(&candidate);
+	}
+
+	/// Add `candidate` to the candidate pool, returning false if it is already in it
+	pub(crate) fn candidate_pool_insert(candidate: &T::AccountId, total: BalanceOf<T>) -> bool {
+		if <CandidatePool<T>>::contains_key(candidate) {
+			return false
+		}
+		<CandidatePool<T>>::insert(candidate, total);
+		<CandidatePoolCount<T>>::mutate(|count| *count = count.saturating_add(1));
+		Self::note_election_change(candidate);
+		true
+	}
+
+	/// Remove `candidate` from the candidate pool, returning false if it was not in it
+	pub(crate) fn candidate_pool_remove(candidate: &T::AccountId) -> bool {
+		if <CandidatePool<T>>::take(candidate).is_none() {
+			return false
+		}
+		<CandidatePoolCount<T>>::mutate(|count| *count = count.saturating_sub(1));
+		Self::note_election_change(candidate);
+		true
+	}
+
+	/// Join the set of collator candidates with a self bond of `bond`
+	pub(crate) fn candidate_join(
+		acc: T::AccountId,
+		bond: BalanceOf<T>,
+		candidate_count: u32,
+	) -> DispatchResultWithPostInfo {
+		ensure!(!Self::is_candidate(&acc), Error::<T>::CandidateExists);
+		ensure!(!Self::is_delegator(&acc), Error::<T>::DelegatorExists);
+		ensure!(bond >= T::MinCandidateStk::get(), Error::<T>::CandidateBondBelowMin);
+		ensure!(
+			candidate_count >= <CandidatePoolCount<T>>::get(),
+			Error::<T>::TooLowCandidateCountWeightHintJoinCandidates
+		);
+		ensure!(!<CandidatePool<T>>::contains_key(&acc), Error::<T>::CandidateExists);
+		ensure!(
+			Self::get_collator_stakable_free_balance(&acc) >= bond,
+			Error::<T>::InsufficientBalance,
+		);
+		// candidates hold a consumer reference so the account is not reaped while staking
+		<frame_system::Pallet<T>>::inc_consumers(&acc)?;
+		Self::hold_stake(&acc, bond)?;
+		let candidate = CandidateMetadata::new(bond);
+		<CandidateInfo<T>>::insert(&acc, candidate);
+		let empty_delegations: Delegations<T::AccountId, BalanceOf<T>> = Default::default();
+		// insert empty top delegations
+		<TopDelegations<T>>::insert(&acc, empty_delegations.clone());
+		// insert empty bottom delegations
+		<BottomDelegations<T>>::insert(&acc, empty_delegations);
+		Self::candidate_pool_insert(&acc, bond);
+		let new_total = <Total<T>>::get().saturating_add(bond);
+		<Total<T>>::put(new_total);
+		Self::deposit_event(Event::JoinedCollatorCandidates {
+			account: acc,
+			amount_locked: bond,
+			new_total_amt_locked: new_total,
+		});
+		Ok(().into())
+	}
+
+	/// Schedule the exit of `collator` and remove it from the candidate pool immediately
+	pub(crate) fn candidate_schedule_leave(
+		collator: T::AccountId,
+		candidate_count: u32,
+	) -> DispatchResultWithPostInfo {
+		let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
+		let (now, when) = state.schedule_leave::<T>()?;
+		ensure!(
+			candidate_count >= <CandidatePoolCount<T>>::get(),
+			Error::<T>::TooLowCandidateCountToLeaveCandidates
+		);
+		Self::candidate_pool_remove(&collator);
+		<CandidateInfo<T>>::insert(&collator, state);
+		Self::deposit_event(Event::CandidateScheduledExit {
+			exit_allowed_round: now,
+			candidate: collator,
+			scheduled_exit: when,
+		});
+		Ok(().into())
+	}
+
+	/// Execute the scheduled exit of `candidate`, returning the stake of it and its delegators
+	pub(crate) fn candidate_execute_leave(
+		candidate: T::AccountId,
+		candidate_delegation_count: u32,
+	) -> DispatchResultWithPostInfo {
+		let state = <CandidateInfo<T>>::get(&candidate).ok_or(Error::<T>::CandidateDNE)?;
+		ensure!(
+			state.delegation_count <= candidate_delegation_count,
+			Error::<T>::TooLowCandidateDelegationCountToLeaveCandidates
+		);
+		state.can_leave::<T>()?;
+		let return_stake = |bond: Bond<T::AccountId, BalanceOf<T>>| -> DispatchResult {
+			// remove delegation from delegator state
+			let mut delegator = DelegatorState::<T>::get(&bond.owner).expect(
+				"Collator state and delegator state are consistent.
+					Collator state has a record of this delegation. Therefore,
+					Delegator state also has a record. qed.",
+			);
+
+			if let Some(remaining) = delegator.rm_delegation::<T>(&candidate)? {
+				Self::delegation_remove_request_with_state(&candidate, &bond.owner, &mut delegator);
+				<AutoCompoundDelegations<T>>::remove_auto_compound(&candidate, &bond.owner);
+				<DelegationMemos<T>>::remove(&bond.owner, &candidate);
+
+				if remaining.is_zero() {
+					// we do not remove the scheduled delegation requests from other collators
+					// since it is assumed that they were removed incrementally before only the
+					// last delegation was left.
+					<DelegatorState<T>>::remove(&bond.owner);
+					Self::clear_reward_destination(&bond.owner);
+					<frame_system::Pallet<T>>::dec_consumers(&bond.owner);
+				} else {
+					<DelegatorState<T>>::insert(&bond.owner, delegator);
+				}
+			}
+			Ok(())
+		};
+		// total backing stake is at least the candidate self bond
+		let mut total_backing = state.bond;
+		// return all top delegations
+		let top_delegations =
+			<TopDelegations<T>>::take(&candidate).expect("CandidateInfo existence checked");
+		for bond in top_delegations.delegations {
+			return_stake(bond)?;
+		}
+		total_backing = total_backing.saturating_add(top_delegations.total);
+		// return all bottom delegations
+		let bottom_delegations =
+			<BottomDelegations<T>>::take(&candidate).expect("CandidateInfo existence checked");
+		for bond in bottom_delegations.delegations {
+			return_stake(bond)?;
+		}
+		total_backing = total_backing.saturating_add(bottom_delegations.total);
+		// return stake to collator
+		Self::release_stake(&candidate, state.bond)?;
+		<CandidateInfo<T>>::remove(&candidate);
+		<frame_system::Pallet<T>>::dec_consumers(&candidate);
+		<DelegationScheduledRequests<T>>::remove(&candidate);
+		<AutoCompoundingDelegations<T>>::remove(&candidate);
+		<TopDelegations<T>>::remove(&candidate);
+		<BottomDelegations<T>>::remove(&candidate);
+		<ConsecutiveMissedRounds<T>>::remove(&candidate);
+		<DeferredStake<T>>::remove(&candidate);
+		<CollatorPayoutSplit<T>>::remove(&candidate);
+		<BondFunders<T>>::remove(&candidate);
+		let new_total_staked = <Total<T>>::get().saturating_sub(total_backing);
+		<Total<T>>::put(new_total_staked);
+		Self::deposit_event(Event::CandidateLeft {
+			ex_candidate: candidate,
+			unlocked_amount: total_backing,
+			new_total_amt_locked: new_total_staked,
+		});
+		Ok(().into())
+	}
+
+	/// Cancel the scheduled exit of `collator` and return it to the candidate pool
+	pub(crate) fn candidate_cancel_leave(
+		collator: T::AccountId,
+		candidate_count: u32,
+	) -> DispatchResultWithPostInfo {
+		let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
+		state.can_cancel_leave::<T>()?;
+		ensure!(state.bond >= T::MinCandidateStk::get(), Error::<T>::CandidateBondBelowMin);
+		state.go_online();
+		ensure!(
+			<CandidatePoolCount<T>>::get() <= candidate_count,
+			Error::<T>::TooLowCandidateCountWeightHintCancelLeaveCandidates
+		);
+		ensure!(
+			Self::candidate_pool_insert(&collator, state.total_counted),
+			Error::<T>::AlreadyActive
+		);
+		<CandidateInfo<T>>::insert(&collator, state);
+		Self::deposit_event(Event::CancelledCandidateExit { candidate: collator });
+		Ok(().into())
+	}
+
+	/// Remove `collator` from the candidate pool without unbonding
+	pub(crate) fn candidate_go_offline(collator: T::AccountId) -> DispatchResultWithPostInfo {
+		let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
+		ensure!(state.is_active(), Error::<T>::AlreadyOffline);
+		state.go_offline();
+		Self::candidate_pool_remove(&collator);
+		<CandidateInfo<T>>::insert(&collator, state);
+		Self::deposit_event(Event::CandidateWentOffline { candidate: collator });
+		Ok(().into())
+	}
+
+	/// Return an offline `collator` to the candidate pool
+	pub(crate) fn candidate_go_online(collator: T::AccountId) -> DispatchResultWithPostInfo {
+		let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
+		ensure!(!state.is_active(), Error::<T>::AlreadyActive);
+		ensure!(!state.is_leaving(), Error::<T>::CannotGoOnlineIfLeaving);
+		ensure!(state.bond >= T::MinCandidateStk::get(), Error::<T>::CandidateBondBelowMin);
+		state.go_online();
+		ensure!(
+			Self::candidate_pool_insert(&collator, state.total_counted),
+			Error::<T>::AlreadyActive
+		);
+		<CandidateInfo<T>>::insert(&collator, state);
+		<ConsecutiveMissedRounds<T>>::remove(&collator);
+		Self::deposit_event(Event::CandidateBackOnline { candidate: collator });
+		Ok(().into())
+	}
+
+	/// Increase the self bond of `collator` by `more`
+	pub(crate) fn candidate_increase_bond(
+		collator: T::AccountId,
+		more: BalanceOf<T>,
+	) -> DispatchResultWithPostInfo {
+		let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
+		state.bond_more::<T>(collator.clone(), more)?;
+		let (is_active, total_counted) = (state.is_active(), state.total_counted);
+		<CandidateInfo<T>>::insert(&collator, state);
+		if is_active {
+			Self::update_active(collator, total_counted);
+		}
+		Ok(().into())
+	}
+
+	/// Set or remove the account allowed to top up the self bond of `collator`
+	pub(crate) fn candidate_set_bond_funder(
+		collator: T::AccountId,
+		funder: Option<T::AccountId>,
+	) -> DispatchResultWithPostInfo {
+		ensure!(Self::is_candidate(&collator), Error::<T>::CandidateDNE);
+		match &funder {
+			Some(funder) => <BondFunders<T>>::insert(&collator, funder),
+			None => <BondFunders<T>>::remove(&collator),
+		}
+		Self::deposit_event(Event::BondFunderSet { candidate: collator, funder });
+		Ok(().into())
+	}
+
+	/// Move `more` from `funder` to `candidate` and add it to the candidate's self bond
+	pub(crate) fn candidate_funded_bond_more(
+		candidate: T::AccountId,
+		funder: T::AccountId,
+		more: BalanceOf<T>,
+	) -> DispatchResultWithPostInfo {
+		ensure!(
+			<BondFunders<T>>::get(&candidate).as_ref() == Some(&funder),
+			Error::<T>::NotBondFunder
+		);
+		<T::Currency as Currency<_>>::transfer(
+			&funder,
+			&candidate,
+			more,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		Self::candidate_increase_bond(candidate, more)
+	}
+
+	/// Schedule a decrease of the self bond of `collator` by `less`
+	pub(crate) fn candidate_schedule_bond_less(
+		collator: T::AccountId,
+		less: BalanceOf<T>,
+	) -> DispatchResultWithPostInfo {
+		let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
+		let when = state.schedule_bond_less::<T>(less)?;
+		<CandidateInfo<T>>::insert(&collator, state);
+		Self::deposit_event(Event::CandidateBondLessRequested {
+			candidate: collator,
+			amount_to_decrease: less,
+			execute_round: when,
+		});
+		Ok(().into())
+	}
+
+	/// Execute the pending self bond decrease of `candidate`
+	pub(crate) fn candidate_execute_bond_less(
+		candidate: T::AccountId,
+	) -> DispatchResultWithPostInfo {
+		let mut state = <CandidateInfo<T>>::get(&candidate).ok_or(Error::<T>::CandidateDNE)?;
+		state.execute_bond_less::<T>(candidate.clone())?;
+		<CandidateInfo<T>>::insert(&candidate, state);
+		Ok(().into())
+	}
+
+	/// Cancel the pending self bond decrease of `collator`
+	pub(crate) fn candidate_cancel_bond_less(collator: T::AccountId) -> DispatchResultWithPostInfo {
+		let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
+		state.cancel_bond_less::<T>(collator.clone())?;
+		<CandidateInfo<T>>::insert(&collator, state);
+		Ok(().into())
+	}
+
+	/// Slash `fraction` of the self bond of `candidate`, if it is one, now or once
+	/// `SlashDeferDuration` rounds have passed
+	pub(crate) fn candidate_report_slash(candidate: &T::AccountId, fraction: Perbill) -> Weight {
+		let amount = match <CandidateInfo<T>>::get(candidate) {
+			Some(state) => fraction * state.bond,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let defer = T::SlashDeferDuration::get();
+		if defer.is_zero() {
+			return Self::candidate_slash(candidate, amount)
+		}
+		let apply_round = <Round<T>>::get().current.saturating_add(defer);
+		let mut slashes = <UnappliedSlashes<T>>::get(apply_round);
+		// a candidate reported again before its slash applies is slashed for its worst offence
+		let amount = match slashes.iter_mut().find(|slash| &slash.candidate == candidate) {
+			Some(slash) => {
+				slash.amount = slash.amount.max(amount);
+				slash.amount
+			},
+			None => {
+				if slashes
+					.try_push(UnappliedSlash { candidate: candidate.clone(), amount })
+					.is_err()
+				{
+					log::warn!(
+						"too many deferred slashes in round {}, slashing on report",
+						apply_round
+					);
+					return Self::candidate_slash(candidate, amount)
+						.saturating_add(T::DbWeight::get().reads(3))
+				}
+				amount
+			},
+		};
+		<UnappliedSlashes<T>>::insert(apply_round, slashes);
+		Self::deposit_event(Event::SlashDeferred {
+			candidate: candidate.clone(),
+			amount,
+			apply_round,
+		});
+		T::DbWeight::get().reads_writes(3, 1)
+	}
+
+	/// Apply the slashes deferred until `round`
+	pub(crate) fn apply_deferred_slashes(round: RoundIndex) -> Weight {
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		for slash in <UnappliedSlashes<T>>::take(round) {
+			weight = weight.saturating_add(Self::candidate_slash(&slash.candidate, slash.amount));
+		}
+		weight
+	}
+
+	/// Slash up to `amount` of the self bond of `candidate`, if it is one
+	pub(crate) fn candidate_slash(candidate: &T::AccountId, amount: BalanceOf<T>) -> Weight {
+		let mut state = match <CandidateInfo<T>>::get(candidate) {
+			Some(state) => state,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let amount = amount.min(state.bond);
+		let held = <HeldStake<T>>::get(candidate);
+		let (mut imbalance, _) = T::Currency::slash_reserved(candidate, amount.min(held));
+		Self::set_held_stake(candidate, held.saturating_sub(imbalance.peek()));
+		let locked = <LockedStake<T>>::get(candidate);
+		let unheld = locked.map_or_else(|| <VestedStake<T>>::get(candidate), |(_, locked)| locked);
+		let from_unheld = amount.saturating_sub(imbalance.peek()).min(unheld);
+		if !from_unheld.is_zero() {
+			// stake locked while vesting, or left on a staking lock, is slashed from the free
+			// balance its lock covers
+			let (slashed, _) = <T::Currency as Currency<_>>::slash(candidate, from_unheld);
+			match locked {
+				Some((id, locked)) =>
+					Self::set_locked_stake(candidate, id, locked.saturating_sub(slashed.peek())),
+				None => {
+					Self::unlock_vested_stake(candidate, slashed.peek());
+				},
+			}
+			imbalance.subsume(slashed);
+		}
+		let amount = imbalance.peek();
+		T::Slash::on_unbalanced(imbalance);
+		state.bond = state.bond.saturating_sub(amount);
+		state.total_counted = state.total_counted.saturating_sub(amount);
+		<Total<T>>::mutate(|total| *total = total.saturating_sub(amount));
+		let new_bond = state.bond;
+		Self::deposit_event(Event::CandidateSlashed {
+			candidate: candidate.clone(),
+			amount,
+			new_bond,
+		});
+		// a pending bond decrease may only take the bond down to the minimum
+		let decreasable = new_bond.saturating_sub(T::MinCandidateStk::get());
+		match state.request {
+			Some(_) if decreasable.is_zero() => {
+				let _ = state.cancel_bond_less::<T>(candidate.clone());
+			},
+			Some(ref mut request) if request.amount > decreasable => request.amount = decreasable,
+			_ => (),
+		}
+		// a candidate slashed below the minimum bond is made to leave, with its delegations
+		if new_bond < T::MinCandidateStk::get() && !state.is_leaving() {
+			let was_active = state.is_active();
+			if let Ok((now, when)) = state.schedule_leave::<T>() {
+				if was_active {
+					Self::candidate_pool_remove(candidate);
+				}
+				Self::deposit_event(Event::CandidateScheduledExit {
+					exit_allowed_round: now,
+					candidate: candidate.clone(),
+					scheduled_exit: when,
+				});
+			}
+		} else if state.is_active() {
+			Self::update_active(candidate.clone(), state.total_counted);
+		}
+		<CandidateInfo<T>>::insert(candidate, state);
+		T::DbWeight::get().reads_writes(6, 8)
+	}
+
+	/// Replace the payout split of `collator`, removing it if `split` is empty
+	pub(crate) fn candidate_set_payout_split(
+		collator: T::AccountId,
+		split: Vec<(T::AccountId, Perbill)>,
+	) -> DispatchResultWithPostInfo {
+		ensure!(Self::is_candidate(&collator), Error::<T>::CandidateDNE);
+		let total_parts = split
+			.iter()
+			.fold(0u64, |acc, (_, share)| acc.saturating_add(share.deconstruct() as u64));
+		ensure!(total_parts <= Perbill::ACCURACY as u64, Error::<T>::PayoutSplitExceedsWhole);
+		if split.is_empty() {
+			<CollatorPayoutSplit<T>>::remove(&collator);
+		} else {
+			let bounded = BoundedVec::<_, T::MaxPayoutSplitShares>::try_from(split.clone())
+				.map_err(|_| Error::<T>::TooManyPayoutSplitShares)?;
+			<CollatorPayoutSplit<T>>::insert(&collator, bounded);
+		}
+		Self::deposit_event(Event::CollatorPayoutSplitSet { candidate: collator, split });
+		Ok(().into())
+	}
+
+	/// Set the session keys of `candidate`, failing unless `ValidatorRegistration` then sees it
+	/// as registered. Dispatch rolls back the keys on failure.
+	pub(crate) fn candidate_rotate_and_set_keys(
+		candidate: T::AccountId,
+		keys: KeysOf<T>,
+		proof: Vec<u8>,
+	) -> DispatchResultWithPostInfo {
+		ensure!(Self::is_candidate(&candidate), Error::<T>::CandidateDNE);
+		T::SessionKeys::set_keys(&candidate, keys, proof)?;
+		ensure!(Self::has_registered_keys(&candidate), Error::<T>::ValidatorNotRegistered);
+		Self::note_election_change(&candidate);
+		Self::deposit_event(Event::CandidateKeysRotated { candidate });
+		Ok(().into())
+	}
+}
@@ -19,15 +19,21 @@
 use crate::{
 	auto_compound::AutoCompoundDelegations,
 	pallet::{
-		BalanceOf, CandidateInfo, Config, DelegationScheduledRequests, DelegatorState, Error,
-		Event, Pallet, Round, RoundIndex, Total,
+		BalanceOf, CandidateInfo, Config, DelegationMemos, DelegationScheduledRequests,
+		DelegatorState, Error, Event, Pallet, Round, RoundIndex, Total,
 	},
+	placeholder_weights::PlaceholderWeightInfo,
 	Delegator,
 };
-use frame_support::{dispatch::DispatchResultWithPostInfo, ensure, traits::Get, RuntimeDebug};
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	ensure,
+	traits::{Currency, Get, Imbalance, OnUnbalanced},
+	RuntimeDebug,
+};
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
-use sp_runtime::traits::Saturating;
+use sp_runtime::traits::{Saturating, Zero};
 use sp_std::vec::Vec;
 
 /// An action that can be performed upon a delegation
@@ -188,6 +194,84 @@ impl<T: Config> Pallet<T> {
 		Some(request)
 	}
 
+	/// Schedules a [DelegationAction::Revoke] of every delegation of the delegator, executable
+	/// after `LeaveDelegatorsDelay`.
+	pub(crate) fn delegator_schedule_leave_all(
+		delegator: T::AccountId,
+	) -> DispatchResultWithPostInfo {
+		let mut state = <DelegatorState<T>>::get(&delegator).ok_or(<Error<T>>::DelegatorDNE)?;
+		let delegations = state.delegations.0.clone();
+		for bond in delegations.iter() {
+			ensure!(
+				!Self::delegation_request_exists(&bond.owner, &delegator),
+				<Error<T>>::PendingDelegationRequestAlreadyExists,
+			);
+		}
+
+		let now = <Round<T>>::get().current;
+		let when = now.saturating_add(T::LeaveDelegatorsDelay::get());
+		for bond in delegations.iter() {
+			<DelegationScheduledRequests<T>>::mutate(&bond.owner, |scheduled_requests| {
+				scheduled_requests.push(ScheduledRequest {
+					delegator: delegator.clone(),
+					action: DelegationAction::Revoke(bond.amount),
+					when_executable: when,
+				})
+			});
+			state.less_total = state.less_total.saturating_add(bond.amount);
+		}
+		<DelegatorState<T>>::insert(delegator.clone(), state);
+
+		Self::deposit_event(Event::DelegatorExitScheduled {
+			round: now,
+			delegator,
+			scheduled_exit: when,
+		});
+		Ok(Some(T::WeightInfo::schedule_leave_all(delegations.len() as u32)).into())
+	}
+
+	/// Revokes every delegation of the delegator once each has a due [DelegationAction::Revoke],
+	/// without the minimum stake checks of revoking them one by one.
+	pub(crate) fn delegator_execute_leave_all(
+		delegator: T::AccountId,
+	) -> DispatchResultWithPostInfo {
+		let mut state = <DelegatorState<T>>::get(&delegator).ok_or(<Error<T>>::DelegatorDNE)?;
+		let delegations = state.delegations.0.clone();
+		let now = <Round<T>>::get().current;
+		for bond in delegations.iter() {
+			let request = <DelegationScheduledRequests<T>>::get(&bond.owner)
+				.into_iter()
+				.find(|req| req.delegator == delegator)
+				.filter(|req| matches!(req.action, DelegationAction::Revoke(_)))
+				.ok_or(<Error<T>>::DelegatorNotLeaving)?;
+			ensure!(request.when_executable <= now, <Error<T>>::DelegatorCannotLeaveYet);
+		}
+
+		let mut unstaked_amount = BalanceOf::<T>::zero();
+		for bond in delegations.iter() {
+			let collator = bond.owner.clone();
+			<DelegationScheduledRequests<T>>::mutate(&collator, |scheduled_requests| {
+				scheduled_requests.retain(|req| req.delegator != delegator)
+			});
+			state.rm_delegation::<T>(&collator)?;
+			<AutoCompoundDelegations<T>>::remove_auto_compound(&collator, &delegator);
+			<DelegationMemos<T>>::remove(&delegator, &collator);
+			Self::delegator_leaves_candidate(collator.clone(), delegator.clone(), bond.amount)?;
+			Self::deposit_event(Event::DelegationRevoked {
+				delegator: delegator.clone(),
+				candidate: collator,
+				unstaked_amount: bond.amount,
+			});
+			unstaked_amount = unstaked_amount.saturating_add(bond.amount);
+		}
+
+		<DelegatorState<T>>::remove(&delegator);
+		Self::clear_reward_destination(&delegator);
+		<frame_system::Pallet<T>>::dec_consumers(&delegator);
+		Self::deposit_event(Event::DelegatorLeft { delegator, unstaked_amount });
+		Ok(Some(T::WeightInfo::execute_leave_all(delegations.len() as u32)).into())
+	}
+
 	/// Executes the delegator's existing [ScheduledRequest] towards a given collator.
 	pub(crate) fn delegation_execute_scheduled_request(
 		collator: T::AccountId,
@@ -222,10 +306,11 @@ impl<T: Config> Pallet<T> {
 				state.less_total = state.less_total.saturating_sub(amount);
 
 				// remove delegation from delegator state
-				state.rm_delegation::<T>(&collator);
+				state.rm_delegation::<T>(&collator)?;
 
 				// remove delegation from auto-compounding info
 				<AutoCompoundDelegations<T>>::remove_auto_compound(&collator, &delegator);
+				<DelegationMemos<T>>::remove(&delegator, &collator);
 
 				// remove delegation from collator state delegations
 				Self::delegator_leaves_candidate(collator.clone(), delegator.clone(), amount)?;
@@ -238,6 +323,8 @@ impl<T: Config> Pallet<T> {
 				<DelegationScheduledRequests<T>>::insert(collator, scheduled_requests);
 				if leaving {
 					<DelegatorState<T>>::remove(&delegator);
+					Self::clear_reward_destination(&delegator);
+					<frame_system::Pallet<T>>::dec_consumers(&delegator);
 					Self::deposit_event(Event::DelegatorLeft {
 						delegator,
 						unstaked_amount: amount,
@@ -309,6 +396,56 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Revokes the delegation towards a given collator without a [ScheduledRequest], charging
+	/// `EmergencyUnbondPenalty` of it once released.
+	pub(crate) fn delegation_force_unbond_with_penalty(
+		collator: T::AccountId,
+		delegator: T::AccountId,
+	) -> DispatchResultWithPostInfo {
+		let mut state = <DelegatorState<T>>::get(&delegator).ok_or(<Error<T>>::DelegatorDNE)?;
+		let amount = state.get_bond_amount(&collator).ok_or(<Error<T>>::DelegationDNE)?;
+		// revoking last delegation => leaving set of delegators
+		let leaving = if state.delegations.0.len() == 1usize {
+			true
+		} else {
+			ensure!(
+				state.total().saturating_sub(T::MinDelegatorStk::get()) >= amount,
+				<Error<T>>::DelegatorBondBelowMin
+			);
+			false
+		};
+
+		Self::delegation_remove_request_with_state(&collator, &delegator, &mut state);
+		state.rm_delegation::<T>(&collator)?;
+		<AutoCompoundDelegations<T>>::remove_auto_compound(&collator, &delegator);
+		<DelegationMemos<T>>::remove(&delegator, &collator);
+		Self::delegator_leaves_candidate(collator.clone(), delegator.clone(), amount)?;
+
+		// the stake is free once released from the delegation
+		let (imbalance, _) = <T::Currency as Currency<_>>::slash(
+			&delegator,
+			T::EmergencyUnbondPenalty::get() * amount,
+		);
+		let penalty = imbalance.peek();
+		T::Slash::on_unbalanced(imbalance);
+		Self::deposit_event(Event::DelegationForceUnbonded {
+			delegator: delegator.clone(),
+			candidate: collator,
+			unstaked_amount: amount,
+			penalty,
+		});
+
+		if leaving {
+			<DelegatorState<T>>::remove(&delegator);
+			Self::clear_reward_destination(&delegator);
+			<frame_system::Pallet<T>>::dec_consumers(&delegator);
+			Self::deposit_event(Event::DelegatorLeft { delegator, unstaked_amount: amount });
+		} else {
+			<DelegatorState<T>>::insert(&delegator, state);
+		}
+		Ok(().into())
+	}
+
 	/// Removes the delegator's existing [ScheduledRequest] towards a given collator, if exists.
 	/// The state needs to be persisted by the caller of this function.
 	pub(crate) fn delegation_remove_request_with_state(
@@ -329,6 +466,36 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Cancels the requests towards `collator` left unexecuted for `RequestExpiryRounds` rounds
+	/// since they became executable, so that the delegations are rewarded again. Called for the
+	/// selected collators at each round change, whose weight already accounts for one request per
+	/// delegation.
+	pub(crate) fn expire_delegation_requests(collator: &T::AccountId, now: RoundIndex) {
+		let expiry = T::RequestExpiryRounds::get();
+		if expiry == 0 {
+			return
+		}
+		let (expired, pending): (Vec<_>, Vec<_>) = <DelegationScheduledRequests<T>>::get(collator)
+			.into_iter()
+			.partition(|req| req.when_executable.saturating_add(expiry) <= now);
+		if expired.is_empty() {
+			return
+		}
+		<DelegationScheduledRequests<T>>::insert(collator, pending);
+		for request in expired {
+			let delegator = request.delegator.clone();
+			if let Some(mut state) = <DelegatorState<T>>::get(&delegator) {
+				state.less_total = state.less_total.saturating_sub(request.action.amount());
+				<DelegatorState<T>>::insert(&delegator, state);
+			}
+			Self::deposit_event(Event::DelegationRequestExpired {
+				delegator,
+				collator: collator.clone(),
+				cancelled_request: request.into(),
+			});
+		}
+	}
+
 	/// Returns true if a [ScheduledRequest] exists for a given delegation
 	pub fn delegation_request_exists(collator: &T::AccountId, delegator: &T::AccountId) -> bool {
 		<DelegationScheduledRequests<T>>::get(collator)
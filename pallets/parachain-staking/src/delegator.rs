@@ -0,0 +1,222 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Delegator functionality shared by the delegation extrinsics and auto-compounding
+
+use crate::{
+	auto_compound::AutoCompoundDelegations,
+	pallet::{
+		AwardedPts, BalanceOf, CandidateInfo, CandidatePool, CandidatePoolCount, Config,
+		DelegationMemos, DelegatorState, Error, Event, Pallet, RemoteDestinationOf,
+		RemoteRewardDestinations, Round, Total,
+	},
+	traits::OnDelegationKicked,
+	types::{Bond, CapacityStatus, RemoteRewardDestination, SpreadStrategy},
+};
+use frame_support::{dispatch::DispatchResultWithPostInfo, ensure, traits::Get};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchResult, Percent,
+};
+use sp_std::{cmp::Ordering, vec::Vec};
+
+impl<T: Config> Pallet<T> {
+	/// Increase the delegation of `delegator` to `candidate` by `more`
+	pub(crate) fn delegation_bond_more(
+		delegator: T::AccountId,
+		candidate: T::AccountId,
+		more: BalanceOf<T>,
+	) -> DispatchResultWithPostInfo {
+		let in_top =
+			Self::delegation_bond_more_without_event(delegator.clone(), candidate.clone(), more)?;
+		let delegation = <DelegatorState<T>>::get(&delegator)
+			.and_then(|state| state.delegations.0.into_iter().find(|bond| bond.owner == candidate))
+			.map_or_else(Zero::zero, |bond| bond.amount);
+		Self::defer_large_delegation(&candidate, &delegator, more, delegation);
+		Self::deposit_event(Event::DelegationIncreased {
+			delegator,
+			candidate,
+			amount: more,
+			in_top,
+		});
+		Ok(().into())
+	}
+
+	/// This function exists as a helper to delegator_bond_more & auto_compound functionality.
+	/// Any changes to this function must align with both user-initiated bond increases and
+	/// auto-compounding bond increases.
+	/// Any feature-specific preconditions should be validated before this function is invoked.
+	/// Any feature-specific events must be emitted after this function is invoked.
+	pub fn delegation_bond_more_without_event(
+		delegator: T::AccountId,
+		candidate: T::AccountId,
+		more: BalanceOf<T>,
+	) -> Result<bool, sp_runtime::DispatchError> {
+		ensure!(
+			!Self::delegation_request_revoke_exists(&candidate, &delegator),
+			Error::<T>::PendingDelegationRevoke
+		);
+		let mut state = <DelegatorState<T>>::get(&delegator).ok_or(Error::<T>::DelegatorDNE)?;
+		state.increase_delegation::<T>(candidate, more)
+	}
+
+	/// Split `amount` evenly over up to `max_targets` active candidates picked by `strategy`,
+	/// skipping those already delegated to and those whose top delegations are full. Any
+	/// remainder of the split goes to the first candidate.
+	pub(crate) fn delegation_spread(
+		delegator: T::AccountId,
+		amount: BalanceOf<T>,
+		max_targets: u32,
+		strategy: SpreadStrategy,
+		candidate_count: u32,
+	) -> DispatchResultWithPostInfo {
+		ensure!(
+			candidate_count >= <CandidatePoolCount<T>>::get(),
+			Error::<T>::TooLowCandidateCountWeightHintDelegateSpread
+		);
+		let delegated: Vec<T::AccountId> = <DelegatorState<T>>::get(&delegator)
+			.map(|state| state.delegations.0.into_iter().map(|bond| bond.owner).collect())
+			.unwrap_or_default();
+		let free_slots =
+			T::MaxDelegationsPerDelegator::get().saturating_sub(delegated.len() as u32);
+		let mut candidates: Vec<(T::AccountId, BalanceOf<T>)> = <CandidatePool<T>>::iter()
+			.filter(|(candidate, _)| !delegated.contains(candidate))
+			.filter(|(candidate, _)| {
+				<CandidateInfo<T>>::get(candidate).map_or(false, |info| {
+					info.is_active() && info.top_capacity != CapacityStatus::Full
+				})
+			})
+			.collect();
+		// the pool is iterated in key hash order, so ties fall back to account order
+		candidates.sort_by(|a, b| a.0.cmp(&b.0));
+		match strategy {
+			SpreadStrategy::LowestBacked => candidates.sort_by(|a, b| a.1.cmp(&b.1)),
+			SpreadStrategy::HighestApr => {
+				let previous_round = <Round<T>>::get().current.saturating_sub(1);
+				let points = |candidate: &T::AccountId| -> BalanceOf<T> {
+					<AwardedPts<T>>::get(previous_round, candidate).into()
+				};
+				// compare points per stake by cross-multiplying, highest first
+				candidates.sort_by(|a, b| -> Ordering {
+					points(&b.0).saturating_mul(a.1).cmp(&points(&a.0).saturating_mul(b.1))
+				});
+			},
+		}
+		let targets = max_targets.min(free_slots).min(candidates.len() as u32);
+		ensure!(!targets.is_zero(), Error::<T>::NoDelegationSpreadTargets);
+		let share = amount / targets.into();
+		let remainder = amount.saturating_sub(share.saturating_mul(targets.into()));
+		for (index, (candidate, _)) in candidates.into_iter().take(targets as usize).enumerate() {
+			let amount = if index == 0 { share.saturating_add(remainder) } else { share };
+			let candidate_delegation_count =
+				<CandidateInfo<T>>::get(&candidate).map_or(0, |info| info.delegation_count);
+			let delegation_count = <DelegatorState<T>>::get(&delegator)
+				.map_or(0, |state| state.delegations.0.len() as u32);
+			<AutoCompoundDelegations<T>>::delegate_with_auto_compound(
+				candidate,
+				delegator.clone(),
+				amount,
+				Percent::zero(),
+				candidate_delegation_count,
+				0,
+				delegation_count,
+			)?;
+		}
+		Ok(().into())
+	}
+
+	/// Remove the delegation `kicked` out of the full bottom delegations of `candidate`,
+	/// releasing its funds straight away rather than after `RevokeDelegationDelay`. Its pending
+	/// requests, auto-compound config and memo go with it. The caller updates `Total`.
+	pub(crate) fn delegation_kick(
+		candidate: &T::AccountId,
+		kicked: Bond<T::AccountId, BalanceOf<T>>,
+	) {
+		let mut delegator_state = <DelegatorState<T>>::get(&kicked.owner)
+			.expect("Delegation existence => DelegatorState existence");
+		let leaving = delegator_state.delegations.0.len() == 1usize;
+		if let Err(e) = delegator_state.rm_delegation::<T>(candidate) {
+			log::error!("failed to release the stake of kicked {:?}: {:?}", kicked.owner, e);
+		}
+		Self::delegation_remove_request_with_state(candidate, &kicked.owner, &mut delegator_state);
+		<AutoCompoundDelegations<T>>::remove_auto_compound(candidate, &kicked.owner);
+		<DelegationMemos<T>>::remove(&kicked.owner, candidate);
+
+		Self::deposit_event(Event::DelegationKicked {
+			delegator: kicked.owner.clone(),
+			candidate: candidate.clone(),
+			unstaked_amount: kicked.amount,
+		});
+		T::OnDelegationKicked::on_delegation_kicked(&kicked.owner, candidate, kicked.amount);
+		if leaving {
+			<DelegatorState<T>>::remove(&kicked.owner);
+			<frame_system::Pallet<T>>::dec_consumers(&kicked.owner);
+			Self::deposit_event(Event::DelegatorLeft {
+				delegator: kicked.owner,
+				unstaked_amount: kicked.amount,
+			});
+		} else {
+			<DelegatorState<T>>::insert(&kicked.owner, delegator_state);
+		}
+	}
+
+	/// Remove delegation from candidate state
+	/// Amount input should be retrieved from delegator and it informs the storage lookups
+	pub(crate) fn delegator_leaves_candidate(
+		candidate: T::AccountId,
+		delegator: T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let mut state = <CandidateInfo<T>>::get(&candidate).ok_or(Error::<T>::CandidateDNE)?;
+		state.rm_delegation_if_exists::<T>(&candidate, delegator.clone(), amount)?;
+		let new_total_locked = <Total<T>>::get().saturating_sub(amount);
+		<Total<T>>::put(new_total_locked);
+		let new_total = state.total_counted;
+		<CandidateInfo<T>>::insert(&candidate, state);
+		Self::deposit_event(Event::DelegatorLeftCandidate {
+			delegator,
+			candidate,
+			unstaked_amount: amount,
+			total_candidate_staked: new_total,
+		});
+		Ok(())
+	}
+
+	/// Set or clear the destination on another chain the rewards of `delegator` are sent to.
+	/// Changing the destination keeps the rewards already batched for the next remittance,
+	/// clearing it releases them to the local account.
+	pub(crate) fn delegator_set_reward_destination(
+		delegator: T::AccountId,
+		destination: Option<RemoteDestinationOf<T>>,
+	) -> DispatchResultWithPostInfo {
+		ensure!(Self::is_delegator(&delegator), Error::<T>::DelegatorDNE);
+		match destination.clone() {
+			Some(destination) =>
+				<RemoteRewardDestinations<T>>::mutate(&delegator, |remote| match remote {
+					Some(remote) => remote.destination = destination,
+					None =>
+						*remote = Some(RemoteRewardDestination {
+							destination,
+							pending: Zero::zero(),
+							last_remitted: <Round<T>>::get().current,
+						}),
+				}),
+			None => Self::clear_reward_destination(&delegator),
+		}
+		Self::deposit_event(Event::RewardDestinationSet { delegator, destination });
+		Ok(().into())
+	}
+}
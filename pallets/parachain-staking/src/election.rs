@@ -0,0 +1,202 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Election of the next round's collators over the blocks before the round ends
+
+use crate::{
+	pallet::{
+		BalanceOf, CandidatePool, CollatorSelectionMode, Config, ElectionRescore, OngoingElection,
+		Pallet, Round, TotalSelected,
+	},
+	placeholder_weights::PlaceholderWeightInfo,
+	types::{Bond, PagedElection},
+	RoundIndex,
+};
+use frame_support::{traits::Get, weights::Weight};
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::{cmp::Ordering, collections::btree_set::BTreeSet, vec::Vec};
+
+type Scored<T> = (u128, Bond<<T as frame_system::Config>::AccountId, BalanceOf<T>>);
+
+impl<T: Config> Pallet<T> {
+	/// Begin the election closing the current round once it is `ElectionLookahead` blocks from
+	/// its expected end, then score `ElectionPageSize` candidates per block until the whole pool
+	/// is scored
+	pub(crate) fn advance_election(now: T::BlockNumber) -> Weight {
+		let lookahead = T::ElectionLookahead::get();
+		if lookahead.is_zero() {
+			return Weight::zero()
+		}
+		let round = <Round<T>>::get();
+		let mut election = match <OngoingElection<T>>::get() {
+			Some(election) if election.round == round.current => election,
+			stale => {
+				if stale.is_some() {
+					// a round ended without closing the election begun for it
+					<OngoingElection<T>>::kill();
+					<ElectionRescore<T>>::drain().for_each(drop);
+				}
+				let expected_end = round.first.saturating_add(round.length.into());
+				if now.saturating_add(lookahead) < expected_end {
+					return T::DbWeight::get().reads(2)
+				}
+				PagedElection {
+					round: round.current,
+					total_selected: <TotalSelected<T>>::get(),
+					mode: <CollatorSelectionMode<T>>::get(),
+					last_key: None,
+					complete: false,
+					leaders: Vec::new(),
+					best_left_out: None,
+				}
+			},
+		};
+		if election.complete {
+			return T::DbWeight::get().reads(2)
+		}
+		let scored = Self::score_election_page(&mut election, T::ElectionPageSize::get().max(1));
+		<OngoingElection<T>>::put(election);
+		T::WeightInfo::election_page(scored)
+	}
+
+	/// Score up to `limit` candidates of the pool after those already scored by `election`,
+	/// returning how many were read. Candidates changed during the election are skipped, being
+	/// scored when it ends.
+	fn score_election_page(
+		election: &mut PagedElection<T::AccountId, BalanceOf<T>>,
+		limit: u32,
+	) -> u32 {
+		let mut candidates = match election.last_key.take() {
+			Some(key) => <CandidatePool<T>>::iter_from(key),
+			None => <CandidatePool<T>>::iter(),
+		};
+		let mut scored = 0u32;
+		while scored < limit {
+			let (owner, amount) = match candidates.next() {
+				Some(candidate) => candidate,
+				None => {
+					election.complete = true;
+					return scored
+				},
+			};
+			scored = scored.saturating_add(1);
+			if <ElectionRescore<T>>::contains_key(&owner) {
+				continue
+			}
+			let qualified = Self::has_registered_keys(&owner);
+			let entry = Self::selection_score(election.round, election.mode, owner, amount);
+			if !qualified {
+				Self::leave_out(&mut election.best_left_out, entry);
+				continue
+			}
+			// leaders are kept best first
+			let at = election.leaders.partition_point(|leader| {
+				Self::selection_order(leader, &entry) == Ordering::Greater
+			});
+			election.leaders.insert(at, entry);
+			if election.leaders.len() > election.total_selected as usize {
+				if let Some(last) = election.leaders.pop() {
+					Self::leave_out(&mut election.best_left_out, last);
+				}
+			}
+		}
+		election.last_key = Some(candidates.last_raw_key().to_vec());
+		scored
+	}
+
+	/// Keep the better of `entry` and the best candidate left out so far
+	fn leave_out(best_left_out: &mut Option<Scored<T>>, entry: Scored<T>) {
+		let better = best_left_out
+			.as_ref()
+			.map_or(true, |best| Self::selection_order(&entry, best) == Ordering::Greater);
+		if better {
+			*best_left_out = Some(entry);
+		}
+	}
+
+	/// Mark `candidate` to be scored again when the ongoing election ends
+	pub(crate) fn note_election_change(candidate: &T::AccountId) {
+		if <OngoingElection<T>>::exists() {
+			<ElectionRescore<T>>::insert(candidate, ());
+		}
+	}
+
+	/// Collators chosen by the election begun ahead of the round boundary. None if there was no
+	/// such election or its result could differ from scoring the whole pool now, in which case
+	/// the whole pool must be scored instead.
+	pub(crate) fn paged_top_candidates() -> Option<Vec<T::AccountId>> {
+		let mut election = <OngoingElection<T>>::take()?;
+		let now: RoundIndex = <Round<T>>::get().current;
+		let mode = <CollatorSelectionMode<T>>::get();
+		let top_n = <TotalSelected<T>>::get();
+		let holds =
+			election.round == now && election.mode == mode && election.total_selected == top_n;
+		// score the pages left if the round ended earlier than expected
+		while holds && !election.complete {
+			Self::score_election_page(&mut election, u32::MAX);
+		}
+		let rescore = <ElectionRescore<T>>::drain().map(|(candidate, _)| candidate);
+		let rescore = rescore.collect::<BTreeSet<T::AccountId>>();
+		if !holds {
+			return None
+		}
+		let PagedElection { leaders, best_left_out, .. } = election;
+		let mut candidates = Vec::with_capacity(leaders.len().saturating_add(rescore.len()));
+		for (score, bond) in leaders {
+			if rescore.contains(&bond.owner) {
+				continue
+			}
+			let live = <CandidatePool<T>>::get(&bond.owner)
+				.filter(|_| Self::has_registered_keys(&bond.owner))
+				.map(|amount| Self::selection_score(now, mode, bond.owner.clone(), amount));
+			let unchanged = live.as_ref().map_or(false, |live| {
+				Self::selection_order(live, &(score, bond)) == Ordering::Equal
+			});
+			// a leader lost its score outside the pool, e.g. its session keys were purged, so a
+			// candidate left out may now rank above it
+			if !unchanged && best_left_out.is_some() {
+				return None
+			}
+			candidates.extend(live);
+		}
+		candidates.extend(rescore.into_iter().filter_map(|owner| {
+			let amount = <CandidatePool<T>>::get(&owner)?;
+			Self::has_registered_keys(&owner)
+				.then(|| Self::selection_score(now, mode, owner, amount))
+		}));
+		candidates.sort_by(|a, b| Self::selection_order(b, a));
+		// candidates left out kept the score they were left out with, so they rank at most as
+		// the best of them and stay out if it ranks below the last candidate chosen
+		if let Some(best_left_out) = best_left_out {
+			let last_chosen = (top_n as usize).checked_sub(1).and_then(|at| candidates.get(at));
+			match last_chosen {
+				Some(last) if Self::selection_order(last, &best_left_out) == Ordering::Greater =>
+					(),
+				_ if top_n.is_zero() => (),
+				_ => return None,
+			}
+		}
+		let mut collators = candidates
+			.into_iter()
+			.map(|(_, x)| x)
+			.take(top_n as usize)
+			.filter(|x| x.amount >= T::MinCollatorStk::get())
+			.map(|x| x.owner)
+			.collect::<Vec<T::AccountId>>();
+		collators.sort();
+		Some(collators)
+	}
+}
@@ -15,20 +15,21 @@
 // along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Helper methods for computing issuance based on inflation
-use crate::pallet::{BalanceOf, Config, Pallet};
+use crate::pallet::{BalanceOf, Config, Pallet, RoundIndex};
 use frame_support::traits::Currency;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{PerThing, Perbill, RuntimeDebug};
+use sp_std::vec::Vec;
 use substrate_fixed::{transcendental::pow as floatpow, types::I64F64};
 
 const SECONDS_PER_YEAR: u32 = 31557600;
 const SECONDS_PER_BLOCK: u32 = 12;
 pub const BLOCKS_PER_YEAR: u32 = SECONDS_PER_YEAR / SECONDS_PER_BLOCK;
 
-fn rounds_per_year<T: Config>() -> u32 {
+pub(crate) fn rounds_per_year<T: Config>() -> u32 {
 	let blocks_per_round = <Pallet<T>>::round().length;
 	BLOCKS_PER_YEAR / blocks_per_round
 }
@@ -85,7 +86,15 @@ pub fn annual_to_round<T: Config>(annual: Range<Perbill>) -> Range<Perbill> {
 
 /// Compute round issuance range from round inflation range and current total issuance
 pub fn round_issuance_range<T: Config>(round: Range<Perbill>) -> Range<BalanceOf<T>> {
-	let circulating = T::Currency::total_issuance();
+	let circulating = <T::Currency as Currency<T::AccountId>>::total_issuance();
+	issuance_range_of(round, circulating)
+}
+
+/// Round issuance range minted on top of `circulating` by the round inflation range
+pub fn issuance_range_of<Balance>(round: Range<Perbill>, circulating: Balance) -> Range<Balance>
+where
+	Balance: sp_runtime::traits::AtLeast32BitUnsigned + Copy,
+{
 	Range {
 		min: round.min * circulating,
 		ideal: round.ideal * circulating,
@@ -93,6 +102,23 @@ pub fn round_issuance_range<T: Config>(round: Range<Perbill>) -> Range<BalanceOf
 	}
 }
 
+/// Pick from `range` for `staked`: the minimum below the staking expectations, the maximum
+/// above them and the ideal within them
+pub fn pick_for_staked<Balance: Ord, V>(
+	expect: Range<Balance>,
+	range: Range<V>,
+	staked: Balance,
+) -> V {
+	// TODO: consider interpolation instead of bounded range
+	if staked < expect.min {
+		range.min
+	} else if staked > expect.max {
+		range.max
+	} else {
+		range.ideal
+	}
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Eq, PartialEq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct InflationInfo<Balance> {
@@ -126,6 +152,54 @@ impl<Balance> InflationInfo<Balance> {
 	}
 }
 
+/// Most steps of a piecewise inflation decay schedule
+pub const MAX_INFLATION_DECAY_STEPS: usize = 32;
+
+/// Automatic decrease of the annual inflation range over time
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum InflationDecay {
+	/// Every `every` rounds, e.g. yearly, decrease each bound of the annual inflation by `rate`
+	/// of itself, without taking it below `floor`
+	Proportional { every: RoundIndex, rate: Perbill, floor: Perbill },
+	/// Switch to the annual inflation of each step once its round starts. Steps are sorted by
+	/// round.
+	Piecewise(Vec<(RoundIndex, Range<Perbill>)>),
+}
+
+impl InflationDecay {
+	pub fn is_valid(&self) -> bool {
+		match self {
+			InflationDecay::Proportional { every, .. } => *every > 0,
+			InflationDecay::Piecewise(steps) =>
+				!steps.is_empty() &&
+					steps.len() <= MAX_INFLATION_DECAY_STEPS &&
+					steps.windows(2).all(|pair| pair[0].0 < pair[1].0) &&
+					steps.iter().all(|(_, annual)| annual.is_valid()),
+		}
+	}
+	/// Annual inflation taking effect when `round` starts, if it changes from `annual`
+	pub fn annual_at(&self, round: RoundIndex, annual: Range<Perbill>) -> Option<Range<Perbill>> {
+		let decayed = match self {
+			InflationDecay::Proportional { every, rate, floor } => {
+				if *every == 0 || round % every != 0 {
+					return None
+				}
+				let decay = |bound: Perbill| {
+					if bound <= *floor {
+						bound
+					} else {
+						(rate.left_from_one() * bound).max(*floor)
+					}
+				};
+				Range { min: decay(annual.min), ideal: decay(annual.ideal), max: decay(annual.max) }
+			},
+			InflationDecay::Piecewise(steps) =>
+				steps.iter().find(|(start, _)| *start == round).map(|(_, annual)| *annual)?,
+		};
+		(decayed != annual).then_some(decayed)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -138,11 +212,7 @@ mod tests {
 		// Round inflation range
 		round: Range<Perbill>,
 	) -> Range<u128> {
-		Range {
-			min: round.min * circulating,
-			ideal: round.ideal * circulating,
-			max: round.max * circulating,
-		}
+		issuance_range_of(round, circulating)
 	}
 	#[test]
 	fn simple_issuance_conversion() {
@@ -192,6 +262,72 @@ mod tests {
 		);
 	}
 	#[test]
+	fn issuance_vectors_probe_staking_expectations() {
+		use crate::inflation_vectors::{issuance_vectors, to_csv};
+		// the schedule of `range_issuance_conversion`, with 10 rounds per year
+		let annual = Range {
+			min: Perbill::from_percent(3),
+			ideal: Perbill::from_percent(4),
+			max: Perbill::from_percent(5),
+		};
+		let info = InflationInfo {
+			expect: Range { min: 100, ideal: 200, max: 300 },
+			annual,
+			round: mock_annual_to_round(annual, 10),
+		};
+		let vectors = issuance_vectors(&info, BLOCKS_PER_YEAR / 10, 10_000_000);
+		let issuance: Vec<(&str, u128, u128)> =
+			vectors.iter().map(|v| (v.case, v.staked, v.round_issuance)).collect();
+		assert_eq!(
+			issuance,
+			vec![
+				("zero", 0, 29_603),
+				("below_min", 99, 29_603),
+				("min", 100, 39_298),
+				("ideal", 200, 39_298),
+				("max", 300, 39_298),
+				("above_max", 301, 48_909),
+				("circulating", 10_000_000, 48_909),
+			]
+		);
+		assert!(vectors.iter().all(|v| v.round_inflation * 10_000_000u128 == v.round_issuance));
+		assert!(
+			to_csv(&vectors).starts_with("case,staked,round_inflation_ppb,round_issuance\nzero,0,")
+		);
+	}
+	#[test]
+	fn inflation_decay_steps_annual_inflation_down() {
+		let annual = Range {
+			min: Perbill::from_percent(4),
+			ideal: Perbill::from_percent(5),
+			max: Perbill::from_percent(10),
+		};
+		let proportional = InflationDecay::Proportional {
+			every: 10,
+			rate: Perbill::from_percent(50),
+			floor: Perbill::from_percent(3),
+		};
+		assert!(proportional.is_valid());
+		assert_eq!(proportional.annual_at(15, annual), None);
+		assert_eq!(
+			proportional.annual_at(20, annual),
+			Some(Range {
+				min: Perbill::from_percent(3),
+				ideal: Perbill::from_percent(3),
+				max: Perbill::from_percent(5),
+			})
+		);
+		let floored = Range::from(Perbill::from_percent(3));
+		assert_eq!(proportional.annual_at(30, floored), None);
+
+		let piecewise = InflationDecay::Piecewise(vec![(5, floored), (8, annual)]);
+		assert!(piecewise.is_valid());
+		assert_eq!(piecewise.annual_at(5, annual), Some(floored));
+		assert_eq!(piecewise.annual_at(6, annual), None);
+		assert_eq!(piecewise.annual_at(8, annual), None);
+		assert!(!InflationDecay::Piecewise(vec![(8, annual), (5, floored)]).is_valid());
+	}
+	#[test]
 	fn inflation_does_not_panic_at_round_number_limit() {
 		let schedule = Range {
 			min: Perbill::from_percent(100),
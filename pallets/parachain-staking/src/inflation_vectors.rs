@@ -0,0 +1,80 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Deterministic per-round issuance tables for an `InflationInfo`, to check the math in
+//! `inflation.rs` against a spreadsheet. Print them with
+//! `cargo run -p pallet-parachain-staking --example inflation_vectors`.
+use crate::inflation::{
+	issuance_range_of, perbill_annual_to_perbill_round, pick_for_staked, InflationInfo,
+	BLOCKS_PER_YEAR,
+};
+use sp_runtime::Perbill;
+
+/// Issuance of one round for a given total stake
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IssuanceVector {
+	/// Which edge of the staking expectations `staked` probes
+	pub case: &'static str,
+	pub staked: u128,
+	/// Round inflation applied to the circulating supply for `staked`
+	pub round_inflation: Perbill,
+	pub round_issuance: u128,
+}
+
+/// Issuance vectors probing the staking expectations of `info`, at and around their min, ideal
+/// and max, with rounds of `blocks_per_round` blocks and `circulating` issued. The round
+/// inflation is derived from `info.annual` like `InflationInfo::reset_round` does.
+pub fn issuance_vectors(
+	info: &InflationInfo<u128>,
+	blocks_per_round: u32,
+	circulating: u128,
+) -> Vec<IssuanceVector> {
+	let round = perbill_annual_to_perbill_round(info.annual, BLOCKS_PER_YEAR / blocks_per_round);
+	let issuance = issuance_range_of(round, circulating);
+	let expect = info.expect;
+	[
+		("zero", 0),
+		("below_min", expect.min.saturating_sub(1)),
+		("min", expect.min),
+		("ideal", expect.ideal),
+		("max", expect.max),
+		("above_max", expect.max.saturating_add(1)),
+		("circulating", circulating),
+	]
+	.into_iter()
+	.map(|(case, staked)| IssuanceVector {
+		case,
+		staked,
+		round_inflation: pick_for_staked(expect, round, staked),
+		round_issuance: pick_for_staked(expect, issuance, staked),
+	})
+	.collect()
+}
+
+/// Render vectors as CSV, with the round inflation in parts per billion
+pub fn to_csv(vectors: &[IssuanceVector]) -> String {
+	let mut csv = String::from("case,staked,round_inflation_ppb,round_issuance\n");
+	for v in vectors {
+		csv.push_str(&format!(
+			"{},{},{},{}\n",
+			v.case,
+			v.staked,
+			v.round_inflation.deconstruct(),
+			v.round_issuance
+		));
+	}
+	csv
+}
@@ -28,9 +28,16 @@
 //! `T::RewardPaymentDelay` rounds ago
 //! * a new set of collators is chosen from the candidates
 //!
+//! With a non-zero `T::ElectionLookahead`, that set is elected over the blocks before the round
+//! ends, scoring `T::ElectionPageSize` candidates per block. Candidates changed meanwhile are
+//! scored again at the boundary, and the whole pool is scored then if the paged result could be
+//! stale.
+//!
 //! Immediately following a round change, payments are made once-per-block until all payments have
 //! been made. In each such block, one collator is chosen for a rewards payment and is paid along
 //! with each of its top `T::MaxTopDelegationsPerCandidate` delegators.
+//! Rounds whose payments were left unfinished once payments moved on to a later round can be
+//! closed by anyone with `sweep_unpaid_rounds`, which issues their unpaid rewards to the treasury.
 //!
 //! To join the set of candidates, call `join_candidates` with `bond >= MinCandidateStk`.
 //! To leave the set of candidates, call `schedule_leave_candidates`. If the call succeeds,
@@ -44,22 +51,40 @@
 //! `T::MaxDelegationsPerDelegator` collator candidates by calling `delegate`.
 //!
 //! To revoke a delegation, call `revoke_delegation` with the collator candidate's account.
-//! To leave the set of delegators and revoke all delegations, call `leave_delegators`.
+//! To leave the set of delegators and revoke all delegations, call `schedule_leave_all` and
+//! then `execute_leave_all` once `LeaveDelegatorsDelay` has passed.
+//!
+//! Other pallets should stake through the [`StakingInterface`] implemented by the pallet rather
+//! than its storage.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 mod auto_compound;
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod benchmarks;
+mod candidate;
 mod delegation_requests;
+mod delegator;
+mod election;
 pub mod inflation;
+#[cfg(feature = "std")]
+pub mod inflation_vectors;
+pub mod migrations;
 #[cfg(test)]
 pub mod mock;
+pub mod placeholder_weights;
+mod restore;
 pub mod set;
+mod staking_interface;
+#[cfg(test)]
+mod tests;
 pub mod traits;
 pub mod types;
 pub mod weights;
 
 use frame_support::pallet;
-pub use inflation::{InflationInfo, Range};
+pub use inflation::{InflationDecay, InflationInfo, Range};
+use placeholder_weights::PlaceholderWeightInfo;
 use weights::WeightInfo;
 
 pub use auto_compound::{AutoCompoundConfig, AutoCompoundDelegations};
@@ -73,58 +98,101 @@ pub use RoundIndex;
 pub mod pallet {
 	use crate::{
 		delegation_requests::{CancelledScheduledRequest, DelegationAction, ScheduledRequest},
-		set::OrderedSet,
 		traits::*,
 		types::*,
-		AutoCompoundConfig, AutoCompoundDelegations, InflationInfo, Range, WeightInfo,
+		AutoCompoundConfig, AutoCompoundDelegations, InflationDecay, InflationInfo,
+		PlaceholderWeightInfo, Range, WeightInfo,
 	};
 	use frame_support::{
 		pallet_prelude::*,
 		traits::{
-			tokens::WithdrawReasons, Currency, Get, Imbalance, LockIdentifier, LockableCurrency,
-			ReservableCurrency, ValidatorRegistration,
+			fungible::MutateHold, Currency, ExistenceRequirement, Get, Imbalance, LockIdentifier,
+			LockableCurrency, OnUnbalanced, ReservableCurrency, ValidatorRegistration,
+			WithdrawReasons,
 		},
 	};
 	use frame_system::pallet_prelude::*;
 	use nimbus_primitives::{AccountLookup, NimbusId};
-	use pallet_session::SessionManager;
+	use pallet_session::{SessionManager, ShouldEndSession};
 	use sp_runtime::{
-		traits::{Convert, Saturating, Zero},
-		Perbill, Percent, RuntimeAppPublic,
+		traits::{BlockNumberProvider, Convert, IntegerSquareRoot, Saturating, Zero},
+		Perbill, Percent, RuntimeAppPublic, SaturatedConversion,
+	};
+	use sp_staking::{
+		offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
+		SessionIndex,
 	};
-	use sp_staking::SessionIndex;
-	use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+	use sp_std::{
+		collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+		prelude::*,
+	};
+
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
 
 	/// Pallet for parachain staking
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	pub type RoundIndex = u32;
-	type RewardPoint = u32;
+	/// Most parameter changes that can be scheduled for the same round
+	const MAX_PARAMETER_CHANGES_PER_ROUND: usize = 8;
+	/// Most `AtStake` snapshots cleared at the round boundary, the rest are pruned lazily
+	const AT_STAKE_PRUNE_BATCH: u32 = 20;
+	/// Most unpaid collator entries cleared by one `sweep_unpaid_rounds` call
+	const UNPAID_SWEEP_BATCH: u32 = 100;
+	pub type RewardPoint = u32;
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+	pub type KeysOf<T> = <<T as Config>::SessionKeys as SetSessionKeys<
+		<T as frame_system::Config>::AccountId,
+	>>::Keys;
+	pub type RemoteDestinationOf<T> = <<T as Config>::RemitReward as RemitReward<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+	>>::Destination;
 
+	/// Lock ids staked funds were locked under before they were held, see
+	/// `migrations::HoldStakedFunds`
 	pub const COLLATOR_LOCK_ID: LockIdentifier = *b"stkngcol";
 	pub const DELEGATOR_LOCK_ID: LockIdentifier = *b"stkngdel";
+	/// Lock id of the stake locked rather than held because it is still vesting, see
+	/// `VestedStake`
+	pub const VESTING_STAKE_LOCK_ID: LockIdentifier = *b"stkngvst";
 
 	/// Configuration trait of this pallet.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Overarching event type
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-		/// The currency type
+		/// The currency type. Staked funds are held through `MutateHold` so they cannot
+		/// overlap with funds locked by other pallets, e.g. vesting or democracy.
 		type Currency: Currency<Self::AccountId>
 			+ ReservableCurrency<Self::AccountId>
-			+ LockableCurrency<Self::AccountId>;
+			+ LockableCurrency<Self::AccountId>
+			+ MutateHold<Self::AccountId, Balance = BalanceOf<Self>>;
 		/// The origin for monetary governance
 		type MonetaryGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Cap on the total issuance. Round issuance tapers off to a share of the room left
+		/// under it, so the total issuance approaches the cap without reaching it.
+		#[pallet::constant]
+		type MaxTotalIssuance: Get<BalanceOf<Self>>;
 		/// Minimum number of blocks per round
 		#[pallet::constant]
 		type MinBlocksPerRound: Get<u32>;
 		/// Number of rounds that candidates remain bonded before exit request is executable
 		#[pallet::constant]
 		type LeaveCandidatesDelay: Get<RoundIndex>;
+		/// Number of rounds before a scheduled candidate exit in which it can no longer be
+		/// cancelled, so delegators who revoked in reaction to it are not left unbonding from a
+		/// candidate that stays. Set to zero to allow cancelling until the exit is executed.
+		#[pallet::constant]
+		type LeaveCancellationCutoff: Get<RoundIndex>;
 		/// Number of rounds candidate requests to decrease self-bond must wait to be executable
 		#[pallet::constant]
 		type CandidateBondLessDelay: Get<RoundIndex>;
@@ -178,6 +246,99 @@ pub mod pallet {
 		/// Handler to notify the runtime when a new round begin.
 		/// If you don't need it, you can specify the type `()`.
 		type OnNewRound: OnNewRound;
+		/// Handler to notify the runtime when a delegation is kicked out of a full bottom set.
+		/// If you don't need it, you can specify the type `()`.
+		type OnDelegationKicked: OnDelegationKicked<Self::AccountId, BalanceOf<Self>>;
+		/// Number of consecutive rounds a selected collator may author no blocks before it is
+		/// marked offline. Set to zero to never mark collators offline.
+		#[pallet::constant]
+		type MaxOfflineRounds: Get<RoundIndex>;
+		/// How the stake of scheduled revoke and decrease requests is rewarded until executed
+		type PendingRequestRewards: Get<PendingRequestRewards>;
+		/// Rounds a scheduled delegation request may stay unexecuted once executable before it is
+		/// cancelled, restoring the reward eligibility of the delegation. Set to zero to never
+		/// expire requests.
+		#[pallet::constant]
+		type RequestExpiryRounds: Get<RoundIndex>;
+		/// Liveness source checked for every selected collator at the end of a round.
+		/// `AuthoredBlocks<Self>` uses block authorship points and needs no offchain heartbeats.
+		type CollatorLiveness: CollatorLiveness<Self::AccountId>;
+		/// Delegations of at least this amount are locked immediately but only counted towards
+		/// collator selection after the next round boundary. Set to zero to count all
+		/// delegations immediately.
+		#[pallet::constant]
+		type DeferredDelegationThreshold: Get<BalanceOf<Self>>;
+		/// Number of most recent rewarded rounds kept in `RewardHistory` per account
+		#[pallet::constant]
+		type MaxRewardHistoryRounds: Get<u32>;
+		/// Most rewardable delegations in one page of a collator's round snapshot. The first
+		/// page is kept in `AtStake`, the rest in `AtStakePages`, and each page is paid as one
+		/// payout.
+		#[pallet::constant]
+		type MaxExposurePageSize: Get<u32>;
+		/// Points awarded to the author of every block
+		#[pallet::constant]
+		type BasePointsPerBlock: Get<RewardPoint>;
+		/// Extra points awarded to the author of a full block, scaled down linearly with the
+		/// fraction of the maximum block weight actually used
+		#[pallet::constant]
+		type MaxBonusPointsPerBlock: Get<RewardPoint>;
+		/// Origin of delegations made over XCM by accounts on other chains, e.g. through
+		/// `Transact`. Yields the local account holding the delegated funds.
+		type RemoteDelegatorOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+		/// Maximum number of accounts a candidate may split its own rewards with
+		#[pallet::constant]
+		type MaxPayoutSplitShares: Get<u32>;
+		/// How the staking reward of a round is split between collators. `()` splits it by
+		/// points alone.
+		type RewardCurve: RewardCurve<BalanceOf<Self>>;
+		/// Handler for stake forfeited, either the self bond slashed from candidates reported for
+		/// an offence or the penalty of delegations unbonded with `force_unbond_with_penalty`
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Share of a delegation forfeited to `Slash` when `force_unbond_with_penalty` unbonds it
+		/// without waiting for `RevokeDelegationDelay`
+		#[pallet::constant]
+		type EmergencyUnbondPenalty: Get<Perbill>;
+		/// Rounds between an offence report and the slash it causes, during which
+		/// `SlashCancelOrigin` may cancel the slash. Set to zero to slash on report.
+		#[pallet::constant]
+		type SlashDeferDuration: Get<RoundIndex>;
+		/// Origin that can cancel deferred slashes
+		type SlashCancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Most slashes deferred to the same round. Further offences that round are slashed on
+		/// report, as are all offences when `SlashDeferDuration` is zero.
+		#[pallet::constant]
+		type MaxDeferredSlashesPerRound: Get<u32>;
+		/// Largest share of `Total` that may leave staking over one block before the circuit
+		/// breaker trips. Set to zero to never trip on a drop of `Total`.
+		#[pallet::constant]
+		type MaxTotalDropPerBlock: Get<Percent>;
+		/// Called once when an anomaly trips the circuit breaker
+		type CircuitBreaker: CircuitBreaker;
+		/// Sends the rewards of delegators with a remote reward destination to other chains
+		type RemitReward: RemitReward<Self::AccountId, BalanceOf<Self>>;
+		/// Rounds the rewards of a delegator with a remote reward destination are batched over
+		#[pallet::constant]
+		type RemitRewardsEvery: Get<RoundIndex>;
+		/// Blocks before the expected end of a round at which the election of the next round's
+		/// collators begins, scoring `ElectionPageSize` candidates per block. Set to zero to elect
+		/// all at once at the round boundary.
+		#[pallet::constant]
+		type ElectionLookahead: Get<Self::BlockNumber>;
+		/// Most candidates scored per block by an ongoing election
+		#[pallet::constant]
+		type ElectionPageSize: Get<u32>;
+		/// Funds of an account yet to vest. Stake is drawn from them first and locked under
+		/// `VESTING_STAKE_LOCK_ID` alongside the vesting lock instead of held, so funds claimed
+		/// or transferred with a vesting schedule can be staked before they vest.
+		type VestingBalance: VestingBalance<Self::AccountId, BalanceOf<Self>>;
+		/// Sets the session keys of candidates rotating them through staking
+		type SessionKeys: SetSessionKeys<Self::AccountId>;
+		/// Relay chain block number, the clock of rounds once `RelayRoundLength` is set and the
+		/// sessions end with `RelayAlignedRounds`
+		type RelayBlockNumber: BlockNumberProvider<BlockNumber = u32>;
+		/// Account the funds reserved for parachain bond are swept to by `sweep_parachain_bond`
+		type TreasuryAccount: Get<Self::AccountId>;
 		/// A conversion from account ID to validator ID.
 		///
 		/// Its cost must be at most one storage read.
@@ -240,6 +401,26 @@ pub mod pallet {
 		TooManyInvulnerables,
 		NoAssociatedValidatorId,
 		ValidatorNotRegistered,
+		RoundRewardsNotPaid,
+		TooManyPayoutSplitShares,
+		PayoutSplitExceedsWhole,
+		EmptySlashIndices,
+		SlashIndicesNotSortedAndUnique,
+		InvalidSlashIndex,
+		CircuitBreakerNotTripped,
+		AutoCompoundDNE,
+		InvalidCompoundFrequency,
+		NotBondFunder,
+		CandidateExitCancellationClosed,
+		ParameterChangeNotInFuture,
+		TooManyParameterChanges,
+		InvalidInflationDecay,
+		TooLowCandidateCountWeightHintDelegateSpread,
+		NoDelegationSpreadTargets,
+		NothingToSweep,
+		RoundStillPaying,
+		StakeNotHeld,
+		InflationSharesExceedWhole,
 	}
 
 	#[pallet::event]
@@ -264,6 +445,15 @@ pub mod pallet {
 			collator_account: T::AccountId,
 			total_exposed_amount: BalanceOf<T>,
 		},
+		/// A collator of the last round was not selected for this one. `stake_gap` is the stake
+		/// it lacks to match the least backed selected collator, zero when it missed selection
+		/// for another reason, e.g. being idle or having no session keys.
+		CandidateBelowSelectionThreshold {
+			round: RoundIndex,
+			candidate: T::AccountId,
+			total_counted: BalanceOf<T>,
+			stake_gap: BalanceOf<T>,
+		},
 		/// Candidate requested to decrease a self bond.
 		CandidateBondLessRequested {
 			candidate: T::AccountId,
@@ -312,7 +502,8 @@ pub mod pallet {
 			unlocked_amount: BalanceOf<T>,
 			new_total_amt_locked: BalanceOf<T>,
 		},
-		/// Delegator requested to decrease a bond for the collator candidate.
+		/// Delegator requested to decrease a bond for the collator candidate. The amount still
+		/// backs the collator until executed and is rewarded as set by `PendingRequestRewards`.
 		DelegationDecreaseScheduled {
 			delegator: T::AccountId,
 			candidate: T::AccountId,
@@ -339,7 +530,8 @@ pub mod pallet {
 			delegator: T::AccountId,
 			scheduled_exit: RoundIndex,
 		},
-		/// Delegator requested to revoke delegation.
+		/// Delegator requested to revoke delegation. The delegation still backs the collator until
+		/// executed and is rewarded as set by `PendingRequestRewards`.
 		DelegationRevocationScheduled {
 			round: RoundIndex,
 			delegator: T::AccountId,
@@ -357,12 +549,33 @@ pub mod pallet {
 			candidate: T::AccountId,
 			unstaked_amount: BalanceOf<T>,
 		},
+		/// Delegation unbonded immediately, forfeiting `penalty` of `unstaked_amount`.
+		DelegationForceUnbonded {
+			delegator: T::AccountId,
+			candidate: T::AccountId,
+			unstaked_amount: BalanceOf<T>,
+			penalty: BalanceOf<T>,
+		},
 		/// Delegation kicked.
 		DelegationKicked {
 			delegator: T::AccountId,
 			candidate: T::AccountId,
 			unstaked_amount: BalanceOf<T>,
 		},
+		/// Delegation displaced from the top delegations of the candidate, it no longer counts
+		/// towards the candidate's stake nor earns rewards.
+		DelegationMovedToBottom {
+			delegator: T::AccountId,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Delegation promoted to the top delegations of the candidate, it counts towards the
+		/// candidate's stake and earns rewards again.
+		DelegationMovedToTop {
+			delegator: T::AccountId,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 		/// Cancelled a pending request to exit the set of delegators.
 		DelegatorExitCancelled {
 			delegator: T::AccountId,
@@ -373,6 +586,13 @@ pub mod pallet {
 			cancelled_request: CancelledScheduledRequest<BalanceOf<T>>,
 			collator: T::AccountId,
 		},
+		/// Request to change an existing delegation cancelled after `RequestExpiryRounds`
+		/// rounds left unexecuted.
+		DelegationRequestExpired {
+			delegator: T::AccountId,
+			cancelled_request: CancelledScheduledRequest<BalanceOf<T>>,
+			collator: T::AccountId,
+		},
 		/// New delegation (increase of the existing one).
 		Delegation {
 			delegator: T::AccountId,
@@ -381,6 +601,18 @@ pub mod pallet {
 			delegator_position: DelegatorAdded<BalanceOf<T>>,
 			auto_compound: Percent,
 		},
+		/// A delegation was made over XCM on behalf of an account on another chain.
+		RemoteDelegation {
+			delegator: T::AccountId,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A memo was attached to a new delegation.
+		DelegationMemoSet {
+			delegator: T::AccountId,
+			candidate: T::AccountId,
+			memo: DelegationMemo,
+		},
 		/// Delegation from candidate state has been remove.
 		DelegatorLeftCandidate {
 			delegator: T::AccountId,
@@ -393,6 +625,13 @@ pub mod pallet {
 			account: T::AccountId,
 			rewards: BalanceOf<T>,
 		},
+		/// Issuance for `round` reduced from `uncapped` to `issued` to stay under
+		/// `MaxTotalIssuance`.
+		IssuanceCapped {
+			round: RoundIndex,
+			uncapped: BalanceOf<T>,
+			issued: BalanceOf<T>,
+		},
 		/// Transferred to account which holds funds reserved for parachain bond.
 		ReservedForParachainBond {
 			account: T::AccountId,
@@ -408,6 +647,132 @@ pub mod pallet {
 			old: Percent,
 			new: Percent,
 		},
+		/// Funds held by the parachain bond account were transferred to the treasury.
+		ParachainBondSwept {
+			account: T::AccountId,
+			treasury: T::AccountId,
+			value: BalanceOf<T>,
+		},
+		/// A round whose payouts were left unfinished was closed, and the part of its staking
+		/// reward that was never paid was issued to the treasury instead.
+		UnpaidRoundSwept {
+			round: RoundIndex,
+			unpaid_collators: u32,
+			treasury: T::AccountId,
+			value: BalanceOf<T>,
+		},
+		/// Candidate set how its own rewards are split; an empty split pays everything to the
+		/// candidate.
+		CollatorPayoutSplitSet {
+			candidate: T::AccountId,
+			split: Vec<(T::AccountId, Perbill)>,
+		},
+		/// Candidate set or removed the account allowed to top up its self bond.
+		BondFunderSet {
+			candidate: T::AccountId,
+			funder: Option<T::AccountId>,
+		},
+		/// Delegator set or cleared the destination on another chain its rewards are sent to.
+		RewardDestinationSet {
+			delegator: T::AccountId,
+			destination: Option<RemoteDestinationOf<T>>,
+		},
+		/// Batched rewards of a delegator were sent to its remote reward destination.
+		RewardsRemitted {
+			delegator: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Batched rewards of a delegator could not be sent to its remote reward destination and
+		/// were released to its local account instead.
+		RewardRemittanceFailed {
+			delegator: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Candidate set new session keys, registered for the next selection.
+		CandidateKeysRotated {
+			candidate: T::AccountId,
+		},
+		/// Part of the candidate's self bond was slashed for an offence.
+		CandidateSlashed {
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+			new_bond: BalanceOf<T>,
+		},
+		/// Slash of the candidate's self bond deferred until `apply_round`.
+		SlashDeferred {
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+			apply_round: RoundIndex,
+		},
+		/// Deferred slash due in `round` was cancelled before being applied.
+		DeferredSlashCancelled {
+			round: RoundIndex,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Anomaly detected, the circuit breaker tripped until governance resets it.
+		CircuitBreakerTripped {
+			anomaly: StakingAnomaly<BalanceOf<T>>,
+		},
+		/// Governance reset the circuit breaker.
+		CircuitBreakerReset,
+		/// Parameter change scheduled to be applied at the start of `round`.
+		ParameterChangeScheduled {
+			round: RoundIndex,
+			change: ParameterChange,
+		},
+		/// Scheduled parameter change applied at the start of `round`.
+		ParameterChangeApplied {
+			round: RoundIndex,
+			change: ParameterChange,
+		},
+		/// Percent of each collator payout routed to the treasury (re)set.
+		CollatorPayoutTreasuryCutSet {
+			old: Percent,
+			new: Percent,
+		},
+		/// Portion of inflation reserved for rewarding protocol usage (re)set, or cleared.
+		UsageRewardsReserveSet {
+			reserve: Option<ParachainBondConfig<T::AccountId>>,
+		},
+		/// Inflation reserved for rewarding protocol usage, paid to `account`.
+		ReservedForUsageRewards {
+			account: T::AccountId,
+			value: BalanceOf<T>,
+		},
+		/// Percent of distributed fees that boosts the staking rewards (re)set.
+		StakerFeeShareSet {
+			old: Percent,
+			new: Percent,
+		},
+		/// Fees collected by a fee pot were distributed at the start of `round`: `to_treasury`
+		/// went to the treasury and `to_stakers` boosts the issuance of the next round paid out.
+		FeesDistributed {
+			round: RoundIndex,
+			to_treasury: BalanceOf<T>,
+			to_stakers: BalanceOf<T>,
+		},
+		/// Percent of round inflation set aside as a bonus to authorities by their score (re)set.
+		AuthorityBonusShareSet {
+			old: Percent,
+			new: Percent,
+		},
+		/// Authorities were paid `total` as a bonus for their score in `round`.
+		AuthorityBonusPaid {
+			round: RoundIndex,
+			total: BalanceOf<T>,
+		},
+		/// Compact payout events were turned on or off.
+		CompactPayoutEventsSet {
+			enabled: bool,
+		},
+		/// Rewards paid for a collator's points in `round`, to the collator, its payout split
+		/// shares and its delegators, in place of their `Rewarded` events.
+		CollatorPayoutSummary {
+			collator: T::AccountId,
+			round: RoundIndex,
+			rewards: BoundedVec<(T::AccountId, BalanceOf<T>), MaxPayoutSummaryRewards<T>>,
+		},
 		/// Annual inflation input (first 3) was used to derive new per-round inflation (last 3)
 		InflationSet {
 			annual_min: Perbill,
@@ -417,6 +782,10 @@ pub mod pallet {
 			round_ideal: Perbill,
 			round_max: Perbill,
 		},
+		/// Inflation decay schedule set, or removed if `None`.
+		InflationDecaySet {
+			decay: Option<InflationDecay>,
+		},
 		/// Staking expectations set.
 		StakeExpectationsSet {
 			expect_min: BalanceOf<T>,
@@ -428,11 +797,31 @@ pub mod pallet {
 			old: u32,
 			new: u32,
 		},
+		/// Set how candidates are ranked for selection.
+		SelectionModeSet {
+			old: SelectionMode,
+			new: SelectionMode,
+		},
+		/// Set the auto-compound percent of the delegations made with `delegate`.
+		DefaultAutoCompoundSet {
+			old: Percent,
+			new: Percent,
+		},
+		/// Set how collator rewards are split between collators and their delegators.
+		DelegatorRewardCurveSet {
+			old: DelegatorRewardCurve,
+			new: DelegatorRewardCurve,
+		},
 		/// Set collator commission to this value.
 		CollatorCommissionSet {
 			old: Perbill,
 			new: Perbill,
 		},
+		/// Set the relay chain blocks per round, or cleared to count rounds in parachain blocks.
+		RelayRoundLengthSet {
+			old: Option<u32>,
+			new: Option<u32>,
+		},
 		/// Set blocks per round
 		BlocksPerRoundSet {
 			current_round: RoundIndex,
@@ -449,6 +838,12 @@ pub mod pallet {
 			delegator: T::AccountId,
 			value: Percent,
 		},
+		/// Rewards of a delegation are now compounded every `every` rounds.
+		AutoCompoundFrequencySet {
+			candidate: T::AccountId,
+			delegator: T::AccountId,
+			every: RoundIndex,
+		},
 		/// Compounded a portion of rewards towards the delegation.
 		Compounded {
 			candidate: T::AccountId,
@@ -458,13 +853,73 @@ pub mod pallet {
 		NewInvulnerables {
 			invulnerables: Vec<T::AccountId>,
 		},
+		/// Invulnerables chosen to replace the current ones at the next rotation.
+		SuccessorInvulnerablesSet {
+			invulnerables: Vec<T::AccountId>,
+		},
+		/// Selected collator did not author any block in the round.
+		CollatorMissedRound {
+			collator: T::AccountId,
+			round: RoundIndex,
+			consecutive_missed: RoundIndex,
+		},
+		/// Candidate was marked offline after earning no points in `MaxOfflineRounds`
+		/// consecutive rounds it was selected for, freeing its slot in the next selection.
+		CandidateKickedForInactivity {
+			candidate: T::AccountId,
+			round: RoundIndex,
+		},
+		/// Delegation above `DeferredDelegationThreshold` is locked but will not be counted for
+		/// selection until after `round` ends.
+		DelegationDeferred {
+			delegator: T::AccountId,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+			round: RoundIndex,
+		},
+		/// Staking state was restored from a snapshot of another chain.
+		StakingStateRestored {
+			candidates: u32,
+			delegations: u32,
+			requests: u32,
+		},
+		/// Inflation set aside for the authority bonus, minted once it is paid.
+		ReservedForAuthorityBonus {
+			value: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			// every block pays at least one collator, `on_idle` pays more if the block has room
+			let (_, payout) = Self::handle_delayed_payout(<Round<T>>::get().current);
+			// scoring the block author in `on_finalize` is charged up front
+			Self::check_total_drop()
+				.saturating_add(Self::advance_election(n))
+				.saturating_add(payout)
+				.saturating_add(T::DbWeight::get().reads(1))
+				.saturating_add(T::WeightInfo::on_finalize())
+		}
 		fn on_finalize(_n: T::BlockNumber) {
 			Self::award_points_to_block_author();
 		}
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			// further payouts take the leftover block space first, then the reward remittances
+			// due, pruning whatever remains of it
+			let paid = Self::handle_delayed_payouts(remaining_weight);
+			let remitted = Self::idle_remit_rewards(remaining_weight.saturating_sub(paid));
+			let pruned = Self::idle_prune_at_stake(
+				remaining_weight.saturating_sub(paid).saturating_sub(remitted),
+			);
+			let consumed = paid.saturating_add(remitted).saturating_add(pruned);
+			#[cfg(feature = "debug-invariants")]
+			if let Err(e) = Self::do_try_state() {
+				log::error!("parachain-staking invariant violated: {}", e);
+				debug_assert!(false, "parachain-staking invariant violated: {}", e);
+			}
+			consumed
+		}
 	}
 
 	#[pallet::storage]
@@ -477,12 +932,83 @@ pub mod pallet {
 	/// The total candidates selected every round
 	type TotalSelected<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn selection_mode)]
+	/// How candidates are ranked for selection
+	pub type CollatorSelectionMode<T: Config> = StorageValue<_, SelectionMode, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn relay_round_length)]
+	/// Relay chain blocks per round, rounds then starting on multiples of it whatever the
+	/// parachain block production. Only used when sessions end with `RelayAlignedRounds`.
+	type RelayRoundLength<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn round_relay_start)]
+	/// Relay chain block number when the current round started
+	type RoundRelayStart<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn default_auto_compound)]
+	/// Percent of rewards auto-compounded for the delegations made with `delegate`
+	type DefaultAutoCompound<T: Config> = StorageValue<_, Percent, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn delegator_reward_curve)]
+	/// How the reward of a collator left after commission is split with its delegators
+	type DelegatorRewardCurveInfo<T: Config> = StorageValue<_, DelegatorRewardCurve, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn parachain_bond_info)]
 	/// Parachain bond config info { account, percent_of_inflation }
 	type ParachainBondInfo<T: Config> =
 		StorageValue<_, ParachainBondConfig<T::AccountId>, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn total_reserved_for_parachain_bond)]
+	/// Inflation reserved for parachain bond over all rounds paid out, whatever account held it
+	type TotalReservedForParachainBond<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn compact_payout_events)]
+	/// Whether payouts emit one `CollatorPayoutSummary` per collator instead of a `Rewarded`
+	/// event per account
+	type CompactPayoutEvents<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collator_payout_treasury_cut)]
+	/// Percent of every collator payout routed to the treasury by `OnCollatorPayout`
+	type CollatorPayoutTreasuryCut<T: Config> = StorageValue<_, Percent, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn usage_rewards_reserve)]
+	/// Percent of round inflation paid to an account rewarding protocol usage, e.g. the claims
+	/// pot of privacy pool users, before the rest goes to stakers
+	type UsageRewardsReserve<T: Config> =
+		StorageValue<_, ParachainBondConfig<T::AccountId>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn authority_bonus_share)]
+	/// Percent of round inflation set aside out of the staking rewards for `pay_authority_bonus`
+	type AuthorityBonusShare<T: Config> = StorageValue<_, Percent, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn authority_bonus_reserve)]
+	/// Inflation set aside for `pay_authority_bonus` and not paid yet, minted once it is paid
+	type AuthorityBonusReserve<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn staker_fee_share)]
+	/// Percent of the fees distributed by `distribute_fees` that boosts the staking rewards, the
+	/// rest going to the treasury
+	type StakerFeeShare<T: Config> = StorageValue<_, Percent, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn issuance_boost)]
+	/// Fees burned by `distribute_fees` and reissued on top of the inflation of the next round
+	/// paid out
+	pub(crate) type IssuanceBoost<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn round)]
 	/// Current round index and next round scheduled transition
@@ -527,6 +1053,102 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Share of rewards put aside to compound at the next scheduled round of a delegation
+	/// compounding less often than every round, keyed by candidate then delegator.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_compound)]
+	pub type PendingCompound<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Optional memo attached to a delegation at creation time, keyed by delegator then candidate.
+	#[pallet::storage]
+	#[pallet::getter(fn delegation_memo)]
+	pub type DelegationMemos<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		DelegationMemo,
+		OptionQuery,
+	>;
+
+	/// Delegators sending their rewards to an account on another chain, with the rewards batched
+	/// since the last remittance
+	#[pallet::storage]
+	#[pallet::getter(fn remote_reward_destination)]
+	pub type RemoteRewardDestinations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		RemoteRewardDestination<RemoteDestinationOf<T>, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Delegators whose batched rewards are due to be remitted, which `on_idle` does as block
+	/// space allows
+	#[pallet::storage]
+	pub(crate) type DueRemittances<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// `Total` at the start of the current block
+	#[pallet::storage]
+	pub(crate) type TotalAtBlockStart<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Rewards paid so far for a round being paid out
+	#[pallet::storage]
+	pub(crate) type RoundPaidOut<T: Config> =
+		StorageMap<_, Twox64Concat, RoundIndex, BalanceOf<T>, ValueQuery>;
+
+	/// Anomaly that tripped the circuit breaker, until governance resets it
+	#[pallet::storage]
+	#[pallet::getter(fn circuit_breaker_anomaly)]
+	pub type CircuitBreakerAnomaly<T: Config> =
+		StorageValue<_, StakingAnomaly<BalanceOf<T>>, OptionQuery>;
+
+	/// Slashes reported for an offence, by the round they are applied at, at most one per
+	/// candidate
+	#[pallet::storage]
+	#[pallet::getter(fn unapplied_slashes)]
+	pub type UnappliedSlashes<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		RoundIndex,
+		BoundedVec<UnappliedSlash<T::AccountId, BalanceOf<T>>, T::MaxDeferredSlashesPerRound>,
+		ValueQuery,
+	>;
+
+	/// Parameter changes waiting for the round they are applied at
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_parameter_changes)]
+	pub type ScheduledParameterChanges<T: Config> =
+		StorageMap<_, Twox64Concat, RoundIndex, Vec<ParameterChange>, ValueQuery>;
+
+	/// Account allowed to top up a candidate's self bond with its own balance
+	#[pallet::storage]
+	#[pallet::getter(fn bond_funder)]
+	pub type BondFunders<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// Accounts sharing in a candidate's commission and self bond rewards. Whatever is not
+	/// shared is paid to the candidate.
+	#[pallet::storage]
+	#[pallet::getter(fn payout_split)]
+	pub type CollatorPayoutSplit<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(T::AccountId, Perbill), T::MaxPayoutSplitShares>,
+		OptionQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn top_delegations)]
 	/// Top delegations for collator candidate
@@ -557,7 +1179,14 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn invulnerable_candidates)]
 	/// The invulnerable candidates
-	type InvulnerableCandidates<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+	pub(crate) type InvulnerableCandidates<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxInvulnerables>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn successor_invulnerables)]
+	/// The invulnerable candidates chosen to replace the current ones at the next rotation
+	pub(crate) type SuccessorInvulnerables<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxInvulnerables>, OptionQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn total)]
@@ -568,7 +1197,23 @@ pub mod pallet {
 	#[pallet::getter(fn candidate_pool)]
 	/// The pool of collator candidates, each with their total backing stake
 	pub(crate) type CandidatePool<T: Config> =
-		StorageValue<_, OrderedSet<Bond<T::AccountId, BalanceOf<T>>>, ValueQuery>;
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ongoing_election)]
+	/// Election of the next round's collators begun ahead of the round boundary
+	pub(crate) type OngoingElection<T: Config> =
+		StorageValue<_, PagedElection<T::AccountId, BalanceOf<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	/// Candidates whose score changed during the ongoing election, scored again when it ends
+	pub(crate) type ElectionRescore<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_pool_count)]
+	/// Number of collator candidates in the candidate pool
+	pub(crate) type CandidatePoolCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn at_stake)]
@@ -583,6 +1228,38 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn at_stake_page)]
+	/// Rewardable delegations of a collator in a round beyond the first page kept in `AtStake`,
+	/// by collator and page index starting at 1
+	pub type AtStakePages<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		RoundIndex,
+		Twox64Concat,
+		(T::AccountId, u32),
+		Vec<BondWithAutoCompound<T::AccountId, BalanceOf<T>>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn paged_payout)]
+	/// Collator of the round whose delegators on `AtStakePages` are still being paid
+	pub type PagedPayouts<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		RoundIndex,
+		PagedPayout<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn stale_at_stake)]
+	/// Rounds whose `AtStake` snapshots are no longer needed but were not fully cleared yet,
+	/// mapped to the cursor clearing resumes from (empty if clearing has not started)
+	pub type StaleAtStake<T: Config> =
+		StorageMap<_, Twox64Concat, RoundIndex, Vec<u8>, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn delayed_payouts)]
 	/// Delayed payouts
@@ -599,11 +1276,23 @@ pub mod pallet {
 	/// Inflation configuration
 	pub type InflationConfig<T: Config> = StorageValue<_, InflationInfo<BalanceOf<T>>, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn inflation_decay)]
+	/// Decay applied to the annual inflation at the start of rounds, if any
+	pub type InflationDecaySchedule<T: Config> = StorageValue<_, InflationDecay, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn points)]
 	/// Total points awarded to collators for block production in the round
 	pub type Points<T: Config> = StorageMap<_, Twox64Concat, RoundIndex, RewardPoint, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn round_reward_weight)]
+	/// Sum of the `RewardCurve` weights of the collators awarded points in the round, set when
+	/// the round's payouts are prepared
+	pub type RoundRewardWeight<T: Config> =
+		StorageMap<_, Twox64Concat, RoundIndex, u128, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn awarded_pts)]
 	/// Points for each collator per round
@@ -617,17 +1306,62 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
-	#[pallet::genesis_config]
-	pub struct GenesisConfig<T: Config> {
-		/// Initialize balance and register all as collators: `(collator AccountId, balance
-		/// Amount)`
-		pub candidates: Vec<(T::AccountId, BalanceOf<T>)>,
-		/// Initialize balance and make delegations:
-		/// `(delegator AccountId, collator AccountId, delegation Amount, auto-compounding
-		/// Percent)`
-		pub delegations: Vec<(T::AccountId, T::AccountId, BalanceOf<T>, Percent)>,
-		/// Inflation configuration
-		pub inflation_config: InflationInfo<BalanceOf<T>>,
+	#[pallet::storage]
+	#[pallet::getter(fn consecutive_missed_rounds)]
+	/// Number of consecutive rounds a selected collator did not author any block
+	pub type ConsecutiveMissedRounds<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, RoundIndex, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn deferred_stake)]
+	/// Stake from large delegations to a candidate made in the given round, which is not counted
+	/// for the selection at the end of that round
+	pub type DeferredStake<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (RoundIndex, BalanceOf<T>), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn vested_stake)]
+	/// Part of the stake of an account locked under `VESTING_STAKE_LOCK_ID` rather than held,
+	/// because it was still vesting when staked. It stays in the account's free balance.
+	pub type VestedStake<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn held_stake)]
+	/// Part of the stake of an account held from its free balance. Other pallets reserve from the
+	/// same balance, so no more than this is ever released or slashed as stake.
+	pub type HeldStake<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn locked_stake)]
+	/// Stake of an account the storage version 2 migration could not hold, left under the
+	/// staking lock it was under. Its later stake joins the lock rather than being held.
+	pub type LockedStake<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (LockIdentifier, BalanceOf<T>), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reward_history)]
+	/// Rewards paid to an account for each of its last `MaxRewardHistoryRounds` rewarded rounds
+	pub type RewardHistory<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<(RoundIndex, BalanceOf<T>), T::MaxRewardHistoryRounds>,
+		ValueQuery,
+	>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Initialize balance and register all as collators: `(collator AccountId, balance
+		/// Amount)`
+		pub candidates: Vec<(T::AccountId, BalanceOf<T>)>,
+		/// Initialize balance and make delegations:
+		/// `(delegator AccountId, collator AccountId, delegation Amount, auto-compounding
+		/// Percent)`
+		pub delegations: Vec<(T::AccountId, T::AccountId, BalanceOf<T>, Percent)>,
+		/// Inflation configuration
+		pub inflation_config: InflationInfo<BalanceOf<T>>,
 		/// Default fixed percent a collator takes off the top of due rewards
 		pub collator_commission: Perbill,
 		/// Default percent of inflation set aside for parachain bond every round
@@ -650,27 +1384,77 @@ pub mod pallet {
 		}
 	}
 
+	#[cfg(feature = "std")]
+	impl<T: Config> GenesisConfig<T> {
+		/// Check the candidates and delegations against the pallet's configured limits, so
+		/// chain spec authors can catch a bad config before building genesis. Balances are
+		/// checked when genesis is built.
+		pub fn validate(&self) -> Result<(), String> {
+			if self.blocks_per_round.is_zero() {
+				return Err("blocks per round must be > 0".into())
+			}
+			let mut candidates = BTreeSet::new();
+			for (candidate, bond) in &self.candidates {
+				if *bond < T::MinCandidateStk::get() {
+					return Err(format!("candidate {:?} bonds less than the minimum", candidate))
+				}
+				if !candidates.insert(candidate.clone()) {
+					return Err(format!("candidate {:?} is listed more than once", candidate))
+				}
+			}
+			let mut delegations: BTreeMap<T::AccountId, Vec<T::AccountId>> = BTreeMap::new();
+			for (delegator, target, amount, _) in &self.delegations {
+				if candidates.contains(delegator) {
+					return Err(format!("delegator {:?} is also a candidate", delegator))
+				}
+				if !candidates.contains(target) {
+					return Err(format!(
+						"delegator {:?} delegates to {:?}, which is not a candidate",
+						delegator, target
+					))
+				}
+				if *amount < T::MinDelegation::get() {
+					return Err(format!(
+						"delegation of {:?} to {:?} is less than the minimum",
+						delegator, target
+					))
+				}
+				let targets = delegations.entry(delegator.clone()).or_default();
+				if targets.contains(target) {
+					return Err(format!(
+						"delegator {:?} delegates to {:?} more than once",
+						delegator, target
+					))
+				}
+				targets.push(target.clone());
+				if targets.len() as u32 > T::MaxDelegationsPerDelegator::get() {
+					return Err(format!("delegator {:?} has too many delegations", delegator))
+				}
+			}
+			Ok(())
+		}
+	}
+
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
 			assert!(self.blocks_per_round > 0, "Blocks per round must be > 0");
 			<InflationConfig<T>>::put(self.inflation_config.clone());
-			let mut candidate_count = 0u32;
+			if let Err(error) = self.validate() {
+				panic!("Invalid parachain-staking genesis config: {}", error);
+			}
 			// Initialize the candidates
-			for &(ref candidate, balance) in &self.candidates {
+			for (candidate_count, &(ref candidate, balance)) in self.candidates.iter().enumerate() {
 				assert!(
 					<Pallet<T>>::get_collator_stakable_free_balance(candidate) >= balance,
 					"Account does not have enough balance to bond as a candidate."
 				);
-				candidate_count = candidate_count.saturating_add(1u32);
 				if let Err(error) = <Pallet<T>>::join_candidates(
 					T::RuntimeOrigin::from(Some(candidate.clone()).into()),
 					balance,
-					candidate_count,
+					candidate_count as u32,
 				) {
-					log::warn!("Join candidates failed in genesis with error {:?}", error);
-				} else {
-					candidate_count = candidate_count.saturating_add(1u32);
+					panic!("Genesis candidate {:?} failed to join: {:?}", candidate, error);
 				}
 			}
 
@@ -684,39 +1468,28 @@ pub mod pallet {
 					<Pallet<T>>::get_delegator_stakable_free_balance(delegator) >= balance,
 					"Account does not have enough balance to place delegation."
 				);
-				let cd_count =
-					if let Some(x) = col_delegator_count.get(target) { *x } else { 0u32 };
-				let dd_count =
-					if let Some(x) = del_delegation_count.get(delegator) { *x } else { 0u32 };
+				let cd_count = col_delegator_count.entry(target.clone()).or_insert(0);
+				let dd_count = del_delegation_count.entry(delegator.clone()).or_insert(0);
 				let cd_auto_compound_count =
-					col_auto_compound_delegator_count.get(target).cloned().unwrap_or_default();
+					col_auto_compound_delegator_count.entry(target.clone()).or_insert(0);
 				if let Err(error) = <Pallet<T>>::delegate_with_auto_compound(
 					T::RuntimeOrigin::from(Some(delegator.clone()).into()),
 					target.clone(),
 					balance,
 					auto_compound,
-					cd_count,
-					cd_auto_compound_count,
-					dd_count,
+					*cd_count,
+					*cd_auto_compound_count,
+					*dd_count,
 				) {
-					log::warn!("Delegate failed in genesis with error {:?}", error);
-				} else {
-					if let Some(x) = col_delegator_count.get_mut(target) {
-						*x = x.saturating_add(1u32);
-					} else {
-						col_delegator_count.insert(target.clone(), 1u32);
-					};
-					if let Some(x) = del_delegation_count.get_mut(delegator) {
-						*x = x.saturating_add(1u32);
-					} else {
-						del_delegation_count.insert(delegator.clone(), 1u32);
-					};
-					if !auto_compound.is_zero() {
-						col_auto_compound_delegator_count
-							.entry(target.clone())
-							.and_modify(|x| *x = x.saturating_add(1))
-							.or_insert(1);
-					}
+					panic!(
+						"Genesis delegation of {:?} to {:?} failed: {:?}",
+						delegator, target, error
+					);
+				}
+				*cd_count = cd_count.saturating_add(1);
+				*dd_count = dd_count.saturating_add(1);
+				if !auto_compound.is_zero() {
+					*cd_auto_compound_count = cd_auto_compound_count.saturating_add(1);
 				}
 			}
 			// Set collator commission to default config
@@ -792,6 +1565,22 @@ pub mod pallet {
 			<InflationConfig<T>>::put(config);
 			Ok(().into())
 		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_inflation_decay())]
+		/// Set how the annual inflation decays at the start of rounds, or stop the decay with
+		/// `None`. Each decay step sets the inflation like `set_inflation` would.
+		pub fn set_inflation_decay(
+			origin: OriginFor<T>,
+			decay: Option<InflationDecay>,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				decay.as_ref().map_or(true, |decay| decay.is_valid()),
+				Error::<T>::InvalidInflationDecay
+			);
+			<InflationDecaySchedule<T>>::set(decay.clone());
+			Self::deposit_event(Event::InflationDecaySet { decay });
+			Ok(().into())
+		}
 		#[pallet::weight(<T as Config>::WeightInfo::set_parachain_bond_account())]
 		/// Set the account that will hold funds set aside for parachain bond
 		pub fn set_parachain_bond_account(
@@ -814,10 +1603,228 @@ pub mod pallet {
 			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
 			let ParachainBondConfig { account, percent: old } = <ParachainBondInfo<T>>::get();
 			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			Self::ensure_inflation_shares(
+				new,
+				<UsageRewardsReserve<T>>::get()
+					.map_or_else(Percent::zero, |reserve| reserve.percent),
+				<AuthorityBonusShare<T>>::get(),
+			)?;
 			<ParachainBondInfo<T>>::put(ParachainBondConfig { account, percent: new });
 			Self::deposit_event(Event::ParachainBondReservePercentSet { old, new });
 			Ok(().into())
 		}
+		#[pallet::weight(<T as Config>::WeightInfo::sweep_parachain_bond())]
+		/// Transfer the funds held by the parachain bond account to the treasury, leaving the
+		/// existential deposit so that it keeps receiving its share of inflation
+		pub fn sweep_parachain_bond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			let account = <ParachainBondInfo<T>>::get().account;
+			let value =
+				T::Currency::free_balance(&account).saturating_sub(T::Currency::minimum_balance());
+			ensure!(!value.is_zero(), Error::<T>::NothingToSweep);
+			let treasury = T::TreasuryAccount::get();
+			T::Currency::transfer(&account, &treasury, value, ExistenceRequirement::KeepAlive)?;
+			Self::deposit_event(Event::ParachainBondSwept { account, treasury, value });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_default_auto_compound())]
+		/// Set the percent of rewards auto-compounded for the delegations made with `delegate`
+		pub fn set_default_auto_compound(
+			origin: OriginFor<T>,
+			new: Percent,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			let old = <DefaultAutoCompound<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<DefaultAutoCompound<T>>::put(new);
+			Self::deposit_event(Event::DefaultAutoCompoundSet { old, new });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_delegator_reward_curve())]
+		/// Set how the reward of a collator left after commission is split with its delegators
+		/// - applies to the rounds paid out from the next block
+		pub fn set_delegator_reward_curve(
+			origin: OriginFor<T>,
+			new: DelegatorRewardCurve,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			let old = <DelegatorRewardCurveInfo<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<DelegatorRewardCurveInfo<T>>::put(new);
+			Self::deposit_event(Event::DelegatorRewardCurveSet { old, new });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_collator_payout_treasury_cut())]
+		/// Set the percent of every collator payout routed to the treasury
+		pub fn set_collator_payout_treasury_cut(
+			origin: OriginFor<T>,
+			new: Percent,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			let old = <CollatorPayoutTreasuryCut<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<CollatorPayoutTreasuryCut<T>>::put(new);
+			Self::deposit_event(Event::CollatorPayoutTreasuryCutSet { old, new });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_usage_rewards_reserve())]
+		/// Set the account paid a percent of round inflation for rewarding protocol usage, or
+		/// stop paying it with `None`
+		pub fn set_usage_rewards_reserve(
+			origin: OriginFor<T>,
+			reserve: Option<ParachainBondConfig<T::AccountId>>,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			ensure!(<UsageRewardsReserve<T>>::get() != reserve, Error::<T>::NoWritingSameValue);
+			Self::ensure_inflation_shares(
+				<ParachainBondInfo<T>>::get().percent,
+				reserve.as_ref().map_or_else(Percent::zero, |reserve| reserve.percent),
+				<AuthorityBonusShare<T>>::get(),
+			)?;
+			<UsageRewardsReserve<T>>::set(reserve.clone());
+			Self::deposit_event(Event::UsageRewardsReserveSet { reserve });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_staker_fee_share())]
+		/// Set the percent of distributed fees that boosts the staking rewards rather than going
+		/// to the treasury
+		pub fn set_staker_fee_share(
+			origin: OriginFor<T>,
+			new: Percent,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			let old = <StakerFeeShare<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<StakerFeeShare<T>>::put(new);
+			Self::deposit_event(Event::StakerFeeShareSet { old, new });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_authority_bonus_share())]
+		/// Set the percent of round inflation set aside out of the staking rewards as a bonus to
+		/// authorities by their score
+		pub fn set_authority_bonus_share(
+			origin: OriginFor<T>,
+			new: Percent,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			let old = <AuthorityBonusShare<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			Self::ensure_inflation_shares(
+				<ParachainBondInfo<T>>::get().percent,
+				<UsageRewardsReserve<T>>::get()
+					.map_or_else(Percent::zero, |reserve| reserve.percent),
+				new,
+			)?;
+			<AuthorityBonusShare<T>>::put(new);
+			Self::deposit_event(Event::AuthorityBonusShareSet { old, new });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_compact_payout_events())]
+		/// Emit one `CollatorPayoutSummary` per paid collator instead of a `Rewarded` event for
+		/// every rewarded account, shrinking the events of payout blocks
+		pub fn set_compact_payout_events(
+			origin: OriginFor<T>,
+			enabled: bool,
+		) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			ensure!(<CompactPayoutEvents<T>>::get() != enabled, Error::<T>::NoWritingSameValue);
+			<CompactPayoutEvents<T>>::put(enabled);
+			Self::deposit_event(Event::CompactPayoutEventsSet { enabled });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32))]
+		/// Cancel deferred slashes due in `round` before they are applied, e.g. for an erroneous
+		/// offence report. `slash_indices` index `UnappliedSlashes` of `round`, sorted ascending.
+		pub fn cancel_deferred_slash(
+			origin: OriginFor<T>,
+			round: RoundIndex,
+			slash_indices: Vec<u32>,
+		) -> DispatchResultWithPostInfo {
+			T::SlashCancelOrigin::ensure_origin(origin)?;
+			ensure!(!slash_indices.is_empty(), Error::<T>::EmptySlashIndices);
+			ensure!(
+				slash_indices.windows(2).all(|pair| pair[0] < pair[1]),
+				Error::<T>::SlashIndicesNotSortedAndUnique
+			);
+			let mut slashes = <UnappliedSlashes<T>>::get(round);
+			ensure!(
+				slash_indices.iter().all(|index| (*index as usize) < slashes.len()),
+				Error::<T>::InvalidSlashIndex
+			);
+			// remove from the back so the remaining indices stay valid
+			for index in slash_indices.into_iter().rev() {
+				let slash = slashes.remove(index as usize);
+				Self::deposit_event(Event::DeferredSlashCancelled {
+					round,
+					candidate: slash.candidate,
+					amount: slash.amount,
+				});
+			}
+			if slashes.is_empty() {
+				<UnappliedSlashes<T>>::remove(round);
+			} else {
+				<UnappliedSlashes<T>>::insert(round, slashes);
+			}
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::force_restore_staking_state(
+			snapshot.candidates.len() as u32,
+			snapshot.delegations.len() as u32,
+			snapshot.requests.len() as u32,
+		))]
+		/// Restore candidates, delegations with their auto-compound configs and pending requests
+		/// exported from another chain, e.g. to relaunch it, without every account staking again.
+		/// The accounts must hold the funds they bond. Pending requests keep the rounds they had
+		/// left. Entries that cannot be restored are skipped.
+		pub fn force_restore_staking_state(
+			origin: OriginFor<T>,
+			snapshot: StakingSnapshot<T::AccountId, BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			frame_system::ensure_root(origin)?;
+			let (candidates, delegations, requests) = Self::restore_staking_state(snapshot);
+			Self::deposit_event(Event::StakingStateRestored { candidates, delegations, requests });
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::reset_circuit_breaker())]
+		/// Reset the circuit breaker once the anomaly that tripped it is dealt with. Whatever
+		/// `CircuitBreaker` did when it tripped, e.g. pausing calls, is undone separately.
+		pub fn reset_circuit_breaker(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				<CircuitBreakerAnomaly<T>>::take().is_some(),
+				Error::<T>::CircuitBreakerNotTripped
+			);
+			Self::deposit_event(Event::CircuitBreakerReset);
+			Ok(().into())
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_parameter_change())]
+		/// Schedule `change` to be applied at the start of `round`, before the collators of that
+		/// round are selected, rather than timing the inclusion of the matching setter.
+		pub fn schedule_parameter_change(
+			origin: OriginFor<T>,
+			round: RoundIndex,
+			change: ParameterChange,
+		) -> DispatchResultWithPostInfo {
+			frame_system::ensure_root(origin)?;
+			ensure!(round > <Round<T>>::get().current, Error::<T>::ParameterChangeNotInFuture);
+			match change {
+				ParameterChange::Inflation(schedule) =>
+					ensure!(schedule.is_valid(), Error::<T>::InvalidSchedule),
+				ParameterChange::CollatorCommission(_) => (),
+				ParameterChange::TotalSelected(new) =>
+					ensure!(new >= T::MinSelectedCandidates::get(), Error::<T>::CannotSetBelowMin),
+			}
+			<ScheduledParameterChanges<T>>::try_mutate(round, |changes| -> DispatchResult {
+				ensure!(
+					changes.len() < MAX_PARAMETER_CHANGES_PER_ROUND,
+					Error::<T>::TooManyParameterChanges
+				);
+				changes.push(change.clone());
+				Ok(())
+			})?;
+			Self::deposit_event(Event::ParameterChangeScheduled { round, change });
+			Ok(().into())
+		}
 		#[pallet::weight(<T as Config>::WeightInfo::set_total_selected())]
 		/// Set the total number of collator candidates selected per round
 		/// - changes are not applied until the start of the next round
@@ -834,6 +1841,20 @@ pub mod pallet {
 			Self::deposit_event(Event::TotalSelectedSet { old, new });
 			Ok(().into())
 		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_selection_mode())]
+		/// Set how candidates are ranked for selection
+		/// - changes are not applied until the start of the next round
+		pub fn set_selection_mode(
+			origin: OriginFor<T>,
+			new: SelectionMode,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let old = <CollatorSelectionMode<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<CollatorSelectionMode<T>>::put(new);
+			Self::deposit_event(Event::SelectionModeSet { old, new });
+			Ok(().into())
+		}
 		#[pallet::weight(<T as Config>::WeightInfo::set_collator_commission())]
 		/// Set the commission for all collators
 		pub fn set_collator_commission(
@@ -879,6 +1900,22 @@ pub mod pallet {
 			<InflationConfig<T>>::put(inflation_config);
 			Ok(().into())
 		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_relay_round_length())]
+		/// Set the relay chain blocks per round, so that rounds keep to real time when parachain
+		/// blocks are produced late, or clear it to end rounds with the inner session schedule
+		/// - the current round ends at the next multiple of `new` relay chain blocks
+		pub fn set_relay_round_length(
+			origin: OriginFor<T>,
+			new: Option<u32>,
+		) -> DispatchResultWithPostInfo {
+			frame_system::ensure_root(origin)?;
+			ensure!(new != Some(0), Error::<T>::CannotSetBelowMin);
+			let old = <RelayRoundLength<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<RelayRoundLength<T>>::set(new);
+			Self::deposit_event(Event::RelayRoundLengthSet { old, new });
+			Ok(().into())
+		}
 		#[pallet::weight(<T as Config>::WeightInfo::join_candidates(*candidate_count))]
 		/// Join the set of collator candidates
 		pub fn join_candidates(
@@ -887,40 +1924,7 @@ pub mod pallet {
 			candidate_count: u32,
 		) -> DispatchResultWithPostInfo {
 			let acc = ensure_signed(origin)?;
-			ensure!(!Self::is_candidate(&acc), Error::<T>::CandidateExists);
-			ensure!(!Self::is_delegator(&acc), Error::<T>::DelegatorExists);
-			ensure!(bond >= T::MinCandidateStk::get(), Error::<T>::CandidateBondBelowMin);
-			let mut candidates = <CandidatePool<T>>::get();
-			let old_count = candidates.0.len() as u32;
-			ensure!(
-				candidate_count >= old_count,
-				Error::<T>::TooLowCandidateCountWeightHintJoinCandidates
-			);
-			ensure!(
-				candidates.insert(Bond { owner: acc.clone(), amount: bond }),
-				Error::<T>::CandidateExists
-			);
-			ensure!(
-				Self::get_collator_stakable_free_balance(&acc) >= bond,
-				Error::<T>::InsufficientBalance,
-			);
-			T::Currency::set_lock(COLLATOR_LOCK_ID, &acc, bond, WithdrawReasons::all());
-			let candidate = CandidateMetadata::new(bond);
-			<CandidateInfo<T>>::insert(&acc, candidate);
-			let empty_delegations: Delegations<T::AccountId, BalanceOf<T>> = Default::default();
-			// insert empty top delegations
-			<TopDelegations<T>>::insert(&acc, empty_delegations.clone());
-			// insert empty bottom delegations
-			<BottomDelegations<T>>::insert(&acc, empty_delegations);
-			<CandidatePool<T>>::put(candidates);
-			let new_total = <Total<T>>::get().saturating_add(bond);
-			<Total<T>>::put(new_total);
-			Self::deposit_event(Event::JoinedCollatorCandidates {
-				account: acc,
-				amount_locked: bond,
-				new_total_amt_locked: new_total,
-			});
-			Ok(().into())
+			Self::candidate_join(acc, bond, candidate_count)
 		}
 		#[pallet::weight(<T as Config>::WeightInfo::schedule_leave_candidates(*candidate_count))]
 		/// Request to leave the set of candidates. If successful, the account is immediately
@@ -930,23 +1934,7 @@ pub mod pallet {
 			candidate_count: u32,
 		) -> DispatchResultWithPostInfo {
 			let collator = ensure_signed(origin)?;
-			let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
-			let (now, when) = state.schedule_leave::<T>()?;
-			let mut candidates = <CandidatePool<T>>::get();
-			ensure!(
-				candidate_count >= candidates.0.len() as u32,
-				Error::<T>::TooLowCandidateCountToLeaveCandidates
-			);
-			if candidates.remove(&Bond::from_owner(collator.clone())) {
-				<CandidatePool<T>>::put(candidates);
-			}
-			<CandidateInfo<T>>::insert(&collator, state);
-			Self::deposit_event(Event::CandidateScheduledExit {
-				exit_allowed_round: now,
-				candidate: collator,
-				scheduled_exit: when,
-			});
-			Ok(().into())
+			Self::candidate_schedule_leave(collator, candidate_count)
 		}
 
 		#[pallet::weight(
@@ -959,75 +1947,7 @@ pub mod pallet {
 			candidate_delegation_count: u32,
 		) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
-			let state = <CandidateInfo<T>>::get(&candidate).ok_or(Error::<T>::CandidateDNE)?;
-			ensure!(
-				state.delegation_count <= candidate_delegation_count,
-				Error::<T>::TooLowCandidateDelegationCountToLeaveCandidates
-			);
-			state.can_leave::<T>()?;
-			let return_stake = |bond: Bond<T::AccountId, BalanceOf<T>>| -> DispatchResult {
-				// remove delegation from delegator state
-				let mut delegator = DelegatorState::<T>::get(&bond.owner).expect(
-					"Collator state and delegator state are consistent. 
-						Collator state has a record of this delegation. Therefore, 
-						Delegator state also has a record. qed.",
-				);
-
-				if let Some(remaining) = delegator.rm_delegation::<T>(&candidate) {
-					Self::delegation_remove_request_with_state(
-						&candidate,
-						&bond.owner,
-						&mut delegator,
-					);
-					<AutoCompoundDelegations<T>>::remove_auto_compound(&candidate, &bond.owner);
-
-					if remaining.is_zero() {
-						// we do not remove the scheduled delegation requests from other collators
-						// since it is assumed that they were removed incrementally before only the
-						// last delegation was left.
-						<DelegatorState<T>>::remove(&bond.owner);
-						T::Currency::remove_lock(DELEGATOR_LOCK_ID, &bond.owner);
-					} else {
-						<DelegatorState<T>>::insert(&bond.owner, delegator);
-					}
-				} else {
-					// TODO: review. we assume here that this delegator has no remaining staked
-					// balance, so we ensure the lock is cleared
-					T::Currency::remove_lock(DELEGATOR_LOCK_ID, &bond.owner);
-				}
-				Ok(())
-			};
-			// total backing stake is at least the candidate self bond
-			let mut total_backing = state.bond;
-			// return all top delegations
-			let top_delegations =
-				<TopDelegations<T>>::take(&candidate).expect("CandidateInfo existence checked");
-			for bond in top_delegations.delegations {
-				return_stake(bond)?;
-			}
-			total_backing = total_backing.saturating_add(top_delegations.total);
-			// return all bottom delegations
-			let bottom_delegations =
-				<BottomDelegations<T>>::take(&candidate).expect("CandidateInfo existence checked");
-			for bond in bottom_delegations.delegations {
-				return_stake(bond)?;
-			}
-			total_backing = total_backing.saturating_add(bottom_delegations.total);
-			// return stake to collator
-			T::Currency::remove_lock(COLLATOR_LOCK_ID, &candidate);
-			<CandidateInfo<T>>::remove(&candidate);
-			<DelegationScheduledRequests<T>>::remove(&candidate);
-			<AutoCompoundingDelegations<T>>::remove(&candidate);
-			<TopDelegations<T>>::remove(&candidate);
-			<BottomDelegations<T>>::remove(&candidate);
-			let new_total_staked = <Total<T>>::get().saturating_sub(total_backing);
-			<Total<T>>::put(new_total_staked);
-			Self::deposit_event(Event::CandidateLeft {
-				ex_candidate: candidate,
-				unlocked_amount: total_backing,
-				new_total_amt_locked: new_total_staked,
-			});
-			Ok(().into())
+			Self::candidate_execute_leave(candidate, candidate_delegation_count)
 		}
 		#[pallet::weight(<T as Config>::WeightInfo::cancel_leave_candidates(*candidate_count))]
 		/// Cancel open request to leave candidates
@@ -1038,55 +1958,19 @@ pub mod pallet {
 			candidate_count: u32,
 		) -> DispatchResultWithPostInfo {
 			let collator = ensure_signed(origin)?;
-			let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
-			ensure!(state.is_leaving(), Error::<T>::CandidateNotLeaving);
-			state.go_online();
-			let mut candidates = <CandidatePool<T>>::get();
-			ensure!(
-				candidates.0.len() as u32 <= candidate_count,
-				Error::<T>::TooLowCandidateCountWeightHintCancelLeaveCandidates
-			);
-			ensure!(
-				candidates.insert(Bond { owner: collator.clone(), amount: state.total_counted }),
-				Error::<T>::AlreadyActive
-			);
-			<CandidatePool<T>>::put(candidates);
-			<CandidateInfo<T>>::insert(&collator, state);
-			Self::deposit_event(Event::CancelledCandidateExit { candidate: collator });
-			Ok(().into())
+			Self::candidate_cancel_leave(collator, candidate_count)
 		}
 		#[pallet::weight(<T as Config>::WeightInfo::go_offline())]
 		/// Temporarily leave the set of collator candidates without unbonding
 		pub fn go_offline(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let collator = ensure_signed(origin)?;
-			let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
-			ensure!(state.is_active(), Error::<T>::AlreadyOffline);
-			state.go_offline();
-			let mut candidates = <CandidatePool<T>>::get();
-			if candidates.remove(&Bond::from_owner(collator.clone())) {
-				<CandidatePool<T>>::put(candidates);
-			}
-			<CandidateInfo<T>>::insert(&collator, state);
-			Self::deposit_event(Event::CandidateWentOffline { candidate: collator });
-			Ok(().into())
+			Self::candidate_go_offline(collator)
 		}
 		#[pallet::weight(<T as Config>::WeightInfo::go_online())]
 		/// Rejoin the set of collator candidates if previously had called `go_offline`
 		pub fn go_online(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let collator = ensure_signed(origin)?;
-			let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
-			ensure!(!state.is_active(), Error::<T>::AlreadyActive);
-			ensure!(!state.is_leaving(), Error::<T>::CannotGoOnlineIfLeaving);
-			state.go_online();
-			let mut candidates = <CandidatePool<T>>::get();
-			ensure!(
-				candidates.insert(Bond { owner: collator.clone(), amount: state.total_counted }),
-				Error::<T>::AlreadyActive
-			);
-			<CandidatePool<T>>::put(candidates);
-			<CandidateInfo<T>>::insert(&collator, state);
-			Self::deposit_event(Event::CandidateBackOnline { candidate: collator });
-			Ok(().into())
+			Self::candidate_go_online(collator)
 		}
 		#[pallet::weight(<T as Config>::WeightInfo::candidate_bond_more())]
 		/// Increase collator candidate self bond by `more`
@@ -1095,14 +1979,28 @@ pub mod pallet {
 			more: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let collator = ensure_signed(origin)?;
-			let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
-			state.bond_more::<T>(collator.clone(), more)?;
-			let (is_active, total_counted) = (state.is_active(), state.total_counted);
-			<CandidateInfo<T>>::insert(&collator, state);
-			if is_active {
-				Self::update_active(collator, total_counted);
-			}
-			Ok(().into())
+			Self::candidate_increase_bond(collator, more)
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_bond_funder())]
+		/// Let `funder` top up the self bond of the calling candidate with its own balance through
+		/// `candidate_bond_more_for`, e.g. for custodial setups. `None` removes the funder.
+		pub fn set_bond_funder(
+			origin: OriginFor<T>,
+			funder: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let collator = ensure_signed(origin)?;
+			Self::candidate_set_bond_funder(collator, funder)
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::candidate_bond_more_for())]
+		/// Increase the self bond of `candidate` by `more`, paid by the calling account, which must
+		/// be the bond funder set by the candidate
+		pub fn candidate_bond_more_for(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			more: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let funder = ensure_signed(origin)?;
+			Self::candidate_funded_bond_more(candidate, funder, more)
 		}
 		#[pallet::weight(<T as Config>::WeightInfo::schedule_candidate_bond_less())]
 		/// Request by collator candidate to decrease self bond by `less`
@@ -1111,15 +2009,7 @@ pub mod pallet {
 			less: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let collator = ensure_signed(origin)?;
-			let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
-			let when = state.schedule_bond_less::<T>(less)?;
-			<CandidateInfo<T>>::insert(&collator, state);
-			Self::deposit_event(Event::CandidateBondLessRequested {
-				candidate: collator,
-				amount_to_decrease: less,
-				execute_round: when,
-			});
-			Ok(().into())
+			Self::candidate_schedule_bond_less(collator, less)
 		}
 		#[pallet::weight(<T as Config>::WeightInfo::execute_candidate_bond_less())]
 		/// Execute pending request to adjust the collator candidate self bond
@@ -1128,28 +2018,48 @@ pub mod pallet {
 			candidate: T::AccountId,
 		) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?; // we may want to reward this if caller != candidate
-			let mut state = <CandidateInfo<T>>::get(&candidate).ok_or(Error::<T>::CandidateDNE)?;
-			state.execute_bond_less::<T>(candidate.clone())?;
-			<CandidateInfo<T>>::insert(&candidate, state);
-			Ok(().into())
+			Self::candidate_execute_bond_less(candidate)
 		}
 		#[pallet::weight(<T as Config>::WeightInfo::cancel_candidate_bond_less())]
 		/// Cancel pending request to adjust the collator candidate self bond
 		pub fn cancel_candidate_bond_less(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let collator = ensure_signed(origin)?;
-			let mut state = <CandidateInfo<T>>::get(&collator).ok_or(Error::<T>::CandidateDNE)?;
-			state.cancel_bond_less::<T>(collator.clone())?;
-			<CandidateInfo<T>>::insert(&collator, state);
-			Ok(().into())
+			Self::candidate_cancel_bond_less(collator)
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::set_payout_split(split.len() as u32))]
+		/// Share the caller's commission and self bond rewards with other accounts, e.g. members
+		/// of a collator syndicate or a hosting provider. Each account is paid its share of every
+		/// collator payout and the remainder goes to the candidate. Shared rewards are only paid
+		/// into accounts that exist. An empty `split` removes the current split.
+		pub fn set_payout_split(
+			origin: OriginFor<T>,
+			split: Vec<(T::AccountId, Perbill)>,
+		) -> DispatchResultWithPostInfo {
+			let collator = ensure_signed(origin)?;
+			Self::candidate_set_payout_split(collator, split)
+		}
+		#[pallet::weight(<T as Config>::WeightInfo::rotate_and_set_keys())]
+		/// Set the session keys of the calling candidate and check `ValidatorRegistration` sees
+		/// them in the same call, failing without setting them otherwise. Candidates without
+		/// registered keys are passed over when selecting collators, so use this rather than
+		/// `Session::set_keys` to be sure the new keys count.
+		pub fn rotate_and_set_keys(
+			origin: OriginFor<T>,
+			keys: KeysOf<T>,
+			proof: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let candidate = ensure_signed(origin)?;
+			Self::candidate_rotate_and_set_keys(candidate, keys, proof)
 		}
 		#[pallet::weight(
 			<T as Config>::WeightInfo::delegate(
 				*candidate_delegation_count,
 				*delegation_count
-			)
+			).saturating_add(T::DbWeight::get().reads_writes(2, 1))
 		)]
 		/// If caller is not a delegator and not a collator, then join the set of delegators
 		/// If caller is a delegator, then makes delegation to change their delegation state
+		/// Auto-compounds the `DefaultAutoCompound` percent of the delegation's rewards
 		pub fn delegate(
 			origin: OriginFor<T>,
 			candidate: T::AccountId,
@@ -1158,13 +2068,21 @@ pub mod pallet {
 			delegation_count: u32,
 		) -> DispatchResultWithPostInfo {
 			let delegator = ensure_signed(origin)?;
+			let auto_compound = <DefaultAutoCompound<T>>::get();
+			// no hint is taken for the auto-compounding delegations of the candidate, the default
+			// being set by governance rather than the caller
+			let candidate_auto_compounding_delegation_count = if auto_compound.is_zero() {
+				0
+			} else {
+				<AutoCompoundDelegations<T>>::get_storage(&candidate).len()
+			};
 			<AutoCompoundDelegations<T>>::delegate_with_auto_compound(
 				candidate,
 				delegator,
 				amount,
-				Percent::zero(),
+				auto_compound,
 				candidate_delegation_count,
-				0,
+				candidate_auto_compounding_delegation_count,
 				delegation_count,
 			)
 		}
@@ -1200,38 +2118,126 @@ pub mod pallet {
 			)
 		}
 
-		#[pallet::weight(<T as Config>::WeightInfo::schedule_revoke_delegation())]
-		/// Request to revoke an existing delegation. If successful, the delegation is scheduled
-		/// to be allowed to be revoked via the `execute_delegation_request` extrinsic.
-		pub fn schedule_revoke_delegation(
+		#[pallet::weight(
+			<T as Config>::WeightInfo::delegate_with_auto_compound(
+				*candidate_delegation_count,
+				0,
+				*delegation_count
+			).saturating_add(T::DbWeight::get().writes(1))
+		)]
+		/// If caller is not a delegator and not a collator, then join the set of delegators
+		/// If caller is a delegator, then makes delegation to change their delegation state
+		/// Attaches `memo` to the new delegation so it can be identified on-chain
+		pub fn delegate_with_memo(
 			origin: OriginFor<T>,
-			collator: T::AccountId,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+			memo: DelegationMemo,
+			candidate_delegation_count: u32,
+			delegation_count: u32,
 		) -> DispatchResultWithPostInfo {
 			let delegator = ensure_signed(origin)?;
-			Self::delegation_schedule_revoke(collator, delegator)
+			let post_info = <AutoCompoundDelegations<T>>::delegate_with_auto_compound(
+				candidate.clone(),
+				delegator.clone(),
+				amount,
+				Percent::zero(),
+				candidate_delegation_count,
+				0,
+				delegation_count,
+			)?;
+			<DelegationMemos<T>>::insert(&delegator, &candidate, memo.clone());
+			Self::deposit_event(Event::DelegationMemoSet { delegator, candidate, memo });
+			Ok(post_info)
 		}
 
-		#[pallet::weight(<T as Config>::WeightInfo::delegator_bond_more())]
-		/// Bond more for delegators wrt a specific collator candidate.
-		pub fn delegator_bond_more(
+		#[pallet::weight(
+			<T as Config>::WeightInfo::delegate(
+				*candidate_delegation_count,
+				*delegation_count
+			)
+		)]
+		/// Delegate on behalf of an account on the relay chain or a sibling parachain. The
+		/// delegation is made from the local account `RemoteDelegatorOrigin` maps the XCM origin
+		/// to, which is where reserve transfers from that account deposit their funds.
+		pub fn delegate_via_xcm(
 			origin: OriginFor<T>,
 			candidate: T::AccountId,
-			more: BalanceOf<T>,
+			amount: BalanceOf<T>,
+			candidate_delegation_count: u32,
+			delegation_count: u32,
 		) -> DispatchResultWithPostInfo {
-			let delegator = ensure_signed(origin)?;
-			let in_top = Self::delegation_bond_more_without_event(
-				delegator.clone(),
+			let delegator = T::RemoteDelegatorOrigin::ensure_origin(origin)?;
+			let post_info = <AutoCompoundDelegations<T>>::delegate_with_auto_compound(
 				candidate.clone(),
-				more,
+				delegator.clone(),
+				amount,
+				Percent::zero(),
+				candidate_delegation_count,
+				0,
+				delegation_count,
 			)?;
-			Pallet::<T>::deposit_event(Event::DelegationIncreased {
-				delegator,
-				candidate,
-				amount: more,
-				in_top,
-			});
+			Self::deposit_event(Event::RemoteDelegation { delegator, candidate, amount });
+			Ok(post_info)
+		}
 
-			Ok(().into())
+		#[pallet::weight(
+			<T as Config>::WeightInfo::delegate_spread(*max_targets, *candidate_count)
+		)]
+		/// Split a delegation of `amount` evenly over up to `max_targets` candidates picked by
+		/// `strategy`, as separate `delegate` calls would. `candidate_count` is a weight hint for
+		/// the size of the candidate pool.
+		pub fn delegate_spread(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+			max_targets: u32,
+			strategy: SpreadStrategy,
+			candidate_count: u32,
+		) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			ensure!(
+				max_targets <= T::MaxDelegationsPerDelegator::get(),
+				Error::<T>::ExceedMaxDelegationsPerDelegator
+			);
+			Self::delegation_spread(delegator, amount, max_targets, strategy, candidate_count)
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_revoke_delegation())]
+		/// Request to revoke an existing delegation. If successful, the delegation is scheduled
+		/// to be allowed to be revoked via the `execute_delegation_request` extrinsic.
+		pub fn schedule_revoke_delegation(
+			origin: OriginFor<T>,
+			collator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			Self::delegation_schedule_revoke(collator, delegator)
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::force_unbond_with_penalty())]
+		/// Revoke a delegation right away instead of after `RevokeDelegationDelay`, forfeiting
+		/// `EmergencyUnbondPenalty` of it. Any request pending for the delegation is dropped.
+		pub fn force_unbond_with_penalty(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			Self::delegation_force_unbond_with_penalty(candidate, delegator)
+		}
+
+		#[pallet::weight(
+			<T as Config>::WeightInfo::delegator_bond_more()
+				.saturating_add(T::DbWeight::get().reads_writes(4, 2))
+		)]
+		/// Bond more for delegators wrt a specific collator candidate.
+		/// Deferred from selection as a new delegation if it brings the delegation to at least
+		/// `DeferredDelegationThreshold`.
+		pub fn delegator_bond_more(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			more: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			Self::delegation_bond_more(delegator, candidate, more)
 		}
 
 		#[pallet::weight(<T as Config>::WeightInfo::schedule_delegator_bond_less())]
@@ -1256,6 +2262,30 @@ pub mod pallet {
 			Self::delegation_execute_scheduled_request(candidate, delegator)
 		}
 
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_leave_all(
+			T::MaxDelegationsPerDelegator::get()
+		))]
+		/// Request to revoke every delegation of the caller, all executable with one
+		/// `execute_leave_all` after `LeaveDelegatorsDelay`. Fails if any of the delegations
+		/// already has a pending request.
+		pub fn schedule_leave_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			Self::delegator_schedule_leave_all(delegator)
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::execute_leave_all(
+			T::MaxDelegationsPerDelegator::get()
+		))]
+		/// Revoke every delegation of `delegator` once all its revocations are due, leaving the
+		/// set of delegators.
+		pub fn execute_leave_all(
+			origin: OriginFor<T>,
+			delegator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::delegator_execute_leave_all(delegator)
+		}
+
 		#[pallet::weight(<T as Config>::WeightInfo::cancel_delegator_bond_less())]
 		/// Cancel request to change an existing delegation.
 		pub fn cancel_delegation_request(
@@ -1288,17 +2318,162 @@ pub mod pallet {
 			)
 		}
 
+		/// Compound the rewards of a delegation only in rounds divisible by `every`, putting the
+		/// share to compound aside in between, to save small delegators dust bonds. The delegation
+		/// must already auto-compound.
+		#[pallet::weight(<T as Config>::WeightInfo::set_auto_compound_every(
+			*candidate_auto_compounding_delegation_count_hint,
+		))]
+		pub fn set_auto_compound_every(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			every: RoundIndex,
+			candidate_auto_compounding_delegation_count_hint: u32,
+		) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			<AutoCompoundDelegations<T>>::set_auto_compound_every(
+				candidate,
+				delegator,
+				every,
+				candidate_auto_compounding_delegation_count_hint,
+			)
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::set_reward_destination())]
+		/// Send the rewards of the calling delegator to `destination` on another chain, batched
+		/// over `RemitRewardsEvery` rounds. Rewards compounded into delegations are not sent.
+		/// Delegators on other chains may call this over XCM like `delegate_via_xcm`. `None`
+		/// keeps rewards in the local account again.
+		pub fn set_reward_destination(
+			origin: OriginFor<T>,
+			destination: Option<RemoteDestinationOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let delegator = match T::RemoteDelegatorOrigin::try_origin(origin) {
+				Ok(delegator) => delegator,
+				Err(origin) => ensure_signed(origin)?,
+			};
+			Self::delegator_set_reward_destination(delegator, destination)
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::prune_stale_at_stake(*limit))]
+		/// Clear up to `limit` leftover `AtStake` snapshots of a `round` whose rewards have all
+		/// been paid. Any signed account may call this to speed up cleanup.
+		pub fn prune_stale_at_stake(
+			origin: OriginFor<T>,
+			round: RoundIndex,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(
+				round.saturating_add(T::RewardPaymentDelay::get()) <= <Round<T>>::get().current &&
+					!<DelayedPayouts<T>>::contains_key(round),
+				Error::<T>::RoundRewardsNotPaid
+			);
+			let removed = Self::prune_at_stake(round, limit);
+			Ok(Some(<T as Config>::WeightInfo::prune_stale_at_stake(removed)).into())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::sweep_unpaid_rounds(UNPAID_SWEEP_BATCH))]
+		/// Close the rounds up to `up_to_round` whose payouts were left unfinished once payouts
+		/// moved on to a later round, issuing the staking reward they did not pay to the
+		/// treasury. Rounds are closed oldest first, clearing up to `UNPAID_SWEEP_BATCH` unpaid
+		/// collators per call. Delegators on the later pages of a collator being paid when the
+		/// round is closed are not paid. Any signed account may call this.
+		pub fn sweep_unpaid_rounds(
+			origin: OriginFor<T>,
+			up_to_round: RoundIndex,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let paying = <Round<T>>::get().current.saturating_sub(T::RewardPaymentDelay::get());
+			ensure!(up_to_round < paying, Error::<T>::RoundStillPaying);
+			let mut rounds: Vec<RoundIndex> =
+				<DelayedPayouts<T>>::iter_keys().filter(|round| *round <= up_to_round).collect();
+			ensure!(!rounds.is_empty(), Error::<T>::NothingToSweep);
+			rounds.sort_unstable();
+
+			let treasury = T::TreasuryAccount::get();
+			let mut cleared = 0u32;
+			for round in rounds {
+				let unpaid = <AwardedPts<T>>::clear_prefix(
+					round,
+					UNPAID_SWEEP_BATCH.saturating_sub(cleared),
+					None,
+				);
+				cleared = cleared.saturating_add(unpaid.unique);
+				if unpaid.maybe_cursor.is_some() {
+					break
+				}
+				let payout = match <DelayedPayouts<T>>::take(round) {
+					Some(payout) => payout,
+					None => continue,
+				};
+				let paid = <RoundPaidOut<T>>::take(round);
+				<Points<T>>::remove(round);
+				<RoundRewardWeight<T>>::remove(round);
+				<PagedPayouts<T>>::remove(round);
+				if !<StaleAtStake<T>>::contains_key(round) {
+					<StaleAtStake<T>>::insert(round, Vec::<u8>::new());
+				}
+				let residual = payout.total_staking_reward.saturating_sub(paid);
+				let value = T::Currency::deposit_creating(&treasury, residual).peek();
+				Self::deposit_event(Event::UnpaidRoundSwept {
+					round,
+					unpaid_collators: unpaid.unique,
+					treasury: treasury.clone(),
+					value,
+				});
+				if cleared >= UNPAID_SWEEP_BATCH {
+					break
+				}
+			}
+			Ok(Some(<T as Config>::WeightInfo::sweep_unpaid_rounds(cleared)).into())
+		}
+
 		/// Set the list of invulnerable (fixed) collators.
-		#[pallet::weight(<T as Config>::WeightInfo::schedule_delegator_bond_less())]
+		#[pallet::weight(<T as Config>::WeightInfo::set_invulnerables(new.len() as u32))]
 		pub fn set_invulnerables(
 			origin: OriginFor<T>,
 			new: Vec<T::AccountId>,
 		) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
+			let invulnerables = Self::ensure_valid_invulnerables(new)?;
+			<InvulnerableCandidates<T>>::put(invulnerables.clone());
+			Self::deposit_event(Event::NewInvulnerables {
+				invulnerables: invulnerables.into_inner(),
+			});
+			Ok(().into())
+		}
+
+		/// Choose the invulnerables that replace the current ones when
+		/// `rotate_invulnerables` is next called, e.g. by a runtime upgrade.
+		#[pallet::weight(<T as Config>::WeightInfo::set_successor_invulnerables(new.len() as u32))]
+		pub fn set_successor_invulnerables(
+			origin: OriginFor<T>,
+			new: Vec<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let invulnerables = Self::ensure_valid_invulnerables(new)?;
+			<SuccessorInvulnerables<T>>::put(invulnerables.clone());
+			Self::deposit_event(Event::SuccessorInvulnerablesSet {
+				invulnerables: invulnerables.into_inner(),
+			});
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `account` has session keys registered with `ValidatorRegistration`
+		pub(crate) fn has_registered_keys(account: &T::AccountId) -> bool {
+			T::ValidatorIdOf::convert(account.clone())
+				.map_or(false, |validator| T::ValidatorRegistration::is_registered(&validator))
+		}
+
+		/// Checks the invulnerables are within bounds and have registered validator keys
+		fn ensure_valid_invulnerables(
+			new: Vec<T::AccountId>,
+		) -> Result<BoundedVec<T::AccountId, T::MaxInvulnerables>, DispatchError> {
 			let bounded_invulnerables = BoundedVec::<_, T::MaxInvulnerables>::try_from(new)
 				.map_err(|_| Error::<T>::TooManyInvulnerables)?;
-
-			// check if the invulnerables have associated validator keys before they are set
 			for account_id in bounded_invulnerables.iter() {
 				let validator_key = T::ValidatorIdOf::convert(account_id.clone())
 					.ok_or(Error::<T>::NoAssociatedValidatorId)?;
@@ -1307,16 +2482,23 @@ pub mod pallet {
 					Error::<T>::ValidatorNotRegistered
 				);
 			}
+			Ok(bounded_invulnerables)
+		}
 
-			<InvulnerableCandidates<T>>::put(bounded_invulnerables.clone());
+		/// Replaces the invulnerables with the successors chosen through
+		/// `set_successor_invulnerables`, keeping the current ones if no successors were chosen.
+		pub fn rotate_invulnerables() -> Weight {
+			let invulnerables = match <SuccessorInvulnerables<T>>::take() {
+				Some(invulnerables) => invulnerables,
+				None => return T::DbWeight::get().reads(1),
+			};
+			<InvulnerableCandidates<T>>::put(invulnerables.clone());
 			Self::deposit_event(Event::NewInvulnerables {
-				invulnerables: bounded_invulnerables.to_vec(),
+				invulnerables: invulnerables.into_inner(),
 			});
-			Ok(().into())
+			T::DbWeight::get().reads_writes(1, 2)
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
 		pub fn is_delegator(acc: &T::AccountId) -> bool {
 			<DelegatorState<T>>::get(acc).is_some()
 		}
@@ -1326,21 +2508,392 @@ pub mod pallet {
 		pub fn is_selected_candidate(acc: &T::AccountId) -> bool {
 			<SelectedCandidates<T>>::get().binary_search(acc).is_ok()
 		}
-		/// Returns an account's free balance which is not locked in delegation staking
-		pub fn get_delegator_stakable_free_balance(acc: &T::AccountId) -> BalanceOf<T> {
-			let mut balance = T::Currency::free_balance(acc);
-			if let Some(state) = <DelegatorState<T>>::get(acc) {
-				balance = balance.saturating_sub(state.total());
+		/// Block at which the earliest pending unbonding of `account` from `candidate` becomes
+		/// executable: the exit of the candidate, which unbonds its delegations too, and the
+		/// candidate's bond decrease or the scheduled delegation request of `account`. Assumes the
+		/// remaining rounds last as long as the current one.
+		pub fn unbonding_eta(
+			account: &T::AccountId,
+			candidate: &T::AccountId,
+		) -> Option<T::BlockNumber> {
+			let info = <CandidateInfo<T>>::get(candidate)?;
+			let exit = match info.status {
+				CollatorStatus::Leaving(when) => Some(when),
+				_ => None,
+			};
+			let request = if account == candidate {
+				info.request.map(|request| request.when_executable)
+			} else {
+				<DelegationScheduledRequests<T>>::get(candidate)
+					.into_iter()
+					.find(|request| &request.delegator == account)
+					.map(|request| request.when_executable)
+			};
+			let when_executable = exit.into_iter().chain(request).min()?;
+			let round = <Round<T>>::get();
+			let remaining_blocks =
+				when_executable.saturating_sub(round.current).saturating_mul(round.length);
+			Some(
+				round
+					.first
+					.saturating_add(remaining_blocks.into())
+					.max(<frame_system::Pallet<T>>::block_number()),
+			)
+		}
+
+		/// Amount `who` has bonded as a candidate and delegator, and the part of it scheduled to
+		/// be unbonded: a pending bond decrease or exit of the candidate, plus the scheduled
+		/// delegation decreases and revocations
+		pub fn staked_and_unbonding(who: &T::AccountId) -> (BalanceOf<T>, BalanceOf<T>) {
+			let (mut staked, mut unbonding) = (BalanceOf::<T>::zero(), BalanceOf::<T>::zero());
+			if let Some(info) = <CandidateInfo<T>>::get(who) {
+				staked = info.bond;
+				unbonding = match info.status {
+					CollatorStatus::Leaving(_) => info.bond,
+					_ => info.request.map_or_else(Zero::zero, |request| request.amount),
+				};
+			}
+			if let Some(state) = <DelegatorState<T>>::get(who) {
+				staked = staked.saturating_add(state.total);
+				unbonding = unbonding.saturating_add(state.less_total);
+			}
+			(staked, unbonding)
+		}
+
+		/// Amount each candidate and delegator has bonded, unbonding parts included
+		pub fn staked_amounts() -> Vec<(T::AccountId, BalanceOf<T>)> {
+			<CandidateInfo<T>>::iter()
+				.map(|(candidate, info)| (candidate, info.bond))
+				.chain(
+					<DelegatorState<T>>::iter().map(|(delegator, state)| (delegator, state.total)),
+				)
+				.collect()
+		}
+
+		/// Collators still to be paid out across the rounds awaiting payout
+		pub fn pending_payouts() -> u32 {
+			<DelayedPayouts<T>>::iter_keys()
+				.map(|round| <AwardedPts<T>>::iter_prefix(round).count() as u32)
+				.sum()
+		}
+
+		/// Whether the delegation of `delegator` to `candidate` is in the top or bottom
+		/// delegations of the candidate, or `None` if there is no such delegation
+		pub fn delegation_position(
+			candidate: &T::AccountId,
+			delegator: &T::AccountId,
+		) -> Option<DelegationPosition> {
+			let contains = |delegations: Option<Delegations<T::AccountId, BalanceOf<T>>>| {
+				delegations
+					.map_or(false, |d| d.delegations.iter().any(|bond| &bond.owner == delegator))
+			};
+			if contains(<TopDelegations<T>>::get(candidate)) {
+				Some(DelegationPosition::Top)
+			} else if contains(<BottomDelegations<T>>::get(candidate)) {
+				Some(DelegationPosition::Bottom)
+			} else {
+				None
+			}
+		}
+
+		/// Active candidates ordered by total backing, highest first, each with its points from
+		/// the last round and the number of its top delegation slots still free
+		pub fn candidate_rankings() -> Vec<(T::AccountId, BalanceOf<T>, RewardPoint, u32)> {
+			let previous_round = <Round<T>>::get().current.saturating_sub(1);
+			let max_top = T::MaxTopDelegationsPerCandidate::get();
+			let mut rankings: Vec<(T::AccountId, BalanceOf<T>, RewardPoint, u32)> =
+				<CandidatePool<T>>::iter()
+					.map(|(candidate, backing)| {
+						let points = <AwardedPts<T>>::get(previous_round, &candidate);
+						let top = <TopDelegations<T>>::get(&candidate)
+							.map_or(0, |top| top.delegations.len() as u32);
+						(candidate, backing, points, max_top.saturating_sub(top))
+					})
+					.collect();
+			rankings.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+			rankings
+		}
+
+		/// Annual return a delegation to `candidate` would earn if every round paid out like
+		/// the last one: the candidate's share of the last round's points applied to the
+		/// staking rewards of a round at the current stake, net of commission, over its backing
+		pub fn estimated_apr(candidate: &T::AccountId) -> Perbill {
+			let previous_round = <Round<T>>::get().current.saturating_sub(1);
+			let total_points = <Points<T>>::get(previous_round);
+			let backing = <CandidateInfo<T>>::get(candidate)
+				.map_or_else(Zero::zero, |info| info.total_counted);
+			if total_points.is_zero() || backing.is_zero() {
+				return Perbill::zero()
+			}
+			let (_, issuance) = Self::round_issuance(<Total<T>>::get());
+			let staking_reward =
+				issuance.saturating_sub(<ParachainBondInfo<T>>::get().percent * issuance);
+			let points = <AwardedPts<T>>::get(previous_round, candidate);
+			let candidate_reward = Perbill::from_rational(points, total_points) * staking_reward;
+			let delegator_reward =
+				candidate_reward.saturating_sub(<CollatorCommission<T>>::get() * candidate_reward);
+			let annual =
+				delegator_reward.saturating_mul(crate::inflation::rounds_per_year::<T>().into());
+			Perbill::from_rational(annual.min(backing), backing)
+		}
+
+		/// Rewards the collators of `round` not paid yet would receive if the round were paid out
+		/// now, each with the rewards of its payout split and its rewardable delegators. Rounds
+		/// not prepared for payout yet are estimated from their points so far and the current
+		/// inflation config. A collator paid over several blocks is left out.
+		pub fn estimate_round_payouts(
+			round: RoundIndex,
+		) -> Vec<CollatorRoundPayout<T::AccountId, BalanceOf<T>>> {
+			let total_points = <Points<T>>::get(round);
+			if total_points.is_zero() {
+				return Vec::new()
+			}
+			let prepared = <DelayedPayouts<T>>::get(round);
+			let total_reward_weight = if prepared.is_some() {
+				<RoundRewardWeight<T>>::get(round)
+			} else {
+				<AwardedPts<T>>::iter_prefix(round)
+					.map(|(collator, pts)| {
+						let exposure = <AtStake<T>>::get(round, &collator).total;
+						T::RewardCurve::reward_weight(pts, exposure)
+					})
+					.fold(0u128, |total, weight| total.saturating_add(weight))
+			};
+			let payout_info = prepared.unwrap_or_else(|| {
+				let (_, inflation) = Self::round_issuance(<Staked<T>>::get(round));
+				let reserved = <ParachainBondInfo<T>>::get().percent * inflation;
+				let reserved = reserved
+					.saturating_add(
+						<UsageRewardsReserve<T>>::get()
+							.map_or_else(Zero::zero, |reserve| reserve.percent * inflation),
+					)
+					.saturating_add(<AuthorityBonusShare<T>>::get() * inflation);
+				let round_issuance = inflation.saturating_add(<IssuanceBoost<T>>::get());
+				DelayedPayout {
+					round_issuance,
+					total_staking_reward: round_issuance.saturating_sub(reserved),
+					collator_commission: <CollatorCommission<T>>::get(),
+				}
+			});
+			let collator_issuance = payout_info.collator_commission * payout_info.round_issuance;
+
+			// mirrors `pay_one_collator_reward`
+			<AwardedPts<T>>::iter_prefix(round)
+				.map(|(collator, pts)| {
+					let state = <AtStake<T>>::get(round, &collator);
+					let pct_due =
+						Self::collator_pct_due(pts, total_points, total_reward_weight, state.total);
+					let amt_due = pct_due * payout_info.total_staking_reward;
+					let (collator_due, delegator_rewards) = if state.delegations.is_empty() {
+						(amt_due, Vec::new())
+					} else {
+						let (collator_reward, amt_due, total) = Self::split_collator_reward(
+							amt_due,
+							pct_due * collator_issuance,
+							state.bond,
+							state.total,
+						);
+						let delegator_rewards = state
+							.delegations
+							.into_iter()
+							.chain(Self::at_stake_overflow(round, &collator))
+							.map(|bond| {
+								(bond.owner, Self::delegation_reward(bond.amount, total, amt_due))
+							})
+							.collect();
+						(collator_reward, delegator_rewards)
+					};
+					let cut = T::OnCollatorPayout::cut(round, &collator, collator_due);
+					let (split_rewards, collator_reward) =
+						Self::payout_split_shares(&collator, collator_due.saturating_sub(cut));
+					CollatorRoundPayout {
+						collator,
+						collator_reward,
+						cut,
+						split_rewards,
+						delegator_rewards,
+					}
+				})
+				.collect()
+		}
+
+		/// Verify the cross-consistency of candidate, delegation and scheduled request storage.
+		/// Iterates all staking storage, so it is only meant for tests and debug builds.
+		pub fn do_try_state() -> Result<(), &'static str> {
+			for (candidate, info) in <CandidateInfo<T>>::iter() {
+				let top = <TopDelegations<T>>::get(&candidate).ok_or("candidate has no top")?;
+				let bottom =
+					<BottomDelegations<T>>::get(&candidate).ok_or("candidate has no bottom")?;
+				let sum = |d: &Delegations<T::AccountId, BalanceOf<T>>| {
+					d.delegations
+						.iter()
+						.fold(BalanceOf::<T>::zero(), |acc, bond| acc.saturating_add(bond.amount))
+				};
+				ensure!(sum(&top) == top.total, "top delegations do not add up to their total");
+				ensure!(
+					sum(&bottom) == bottom.total,
+					"bottom delegations do not add up to their total"
+				);
+				ensure!(
+					info.total_counted == info.bond.saturating_add(top.total),
+					"total_counted is not self bond plus top delegations"
+				);
+				ensure!(
+					info.delegation_count as usize ==
+						top.delegations.len().saturating_add(bottom.delegations.len()),
+					"delegation_count does not match top and bottom delegations"
+				);
+				ensure!(
+					Self::staked_balance(&candidate) >= info.bond,
+					"candidate holds less than its self bond"
+				);
+			}
+			for (delegator, state) in <DelegatorState<T>>::iter() {
+				let sum = state
+					.delegations
+					.0
+					.iter()
+					.fold(BalanceOf::<T>::zero(), |acc, bond| acc.saturating_add(bond.amount));
+				ensure!(sum == state.total, "delegator total does not match its delegations");
+				ensure!(
+					Self::staked_balance(&delegator) >= state.total,
+					"delegator holds less than its total"
+				);
+				for bond in state.delegations.0.iter() {
+					ensure!(
+						<CandidateInfo<T>>::contains_key(&bond.owner),
+						"delegation to a candidate that does not exist"
+					);
+				}
+				ensure!(state.id == delegator, "delegator state stored under another account");
+			}
+			for (collator, requests) in <DelegationScheduledRequests<T>>::iter() {
+				for request in requests {
+					let state = <DelegatorState<T>>::get(&request.delegator)
+						.ok_or("scheduled request from an account that is not a delegator")?;
+					ensure!(
+						state.delegations.0.iter().any(|bond| bond.owner == collator),
+						"scheduled request for a delegation that does not exist"
+					);
+				}
 			}
-			balance
+			Ok(())
+		}
+
+		/// Returns an account's free balance that can still be staked as a delegator. Funds
+		/// still vesting count, except those already staked under `VESTING_STAKE_LOCK_ID`.
+		pub fn get_delegator_stakable_free_balance(acc: &T::AccountId) -> BalanceOf<T> {
+			Self::stakable_free_balance(acc)
 		}
-		/// Returns an account's free balance which is not locked in collator staking
+		/// Returns an account's free balance that can still be staked as a collator. Funds still
+		/// vesting count, except those already staked under `VESTING_STAKE_LOCK_ID`.
 		pub fn get_collator_stakable_free_balance(acc: &T::AccountId) -> BalanceOf<T> {
-			let mut balance = T::Currency::free_balance(acc);
-			if let Some(info) = <CandidateInfo<T>>::get(acc) {
-				balance = balance.saturating_sub(info.bond);
+			Self::stakable_free_balance(acc)
+		}
+		fn stakable_free_balance(acc: &T::AccountId) -> BalanceOf<T> {
+			let locked = <LockedStake<T>>::get(acc).map_or(Zero::zero(), |(_, locked)| locked);
+			T::Currency::free_balance(acc)
+				.saturating_sub(<VestedStake<T>>::get(acc))
+				.saturating_sub(locked)
+		}
+		/// Funds of `who` still vesting that are not staked yet
+		fn unstaked_vesting_balance(who: &T::AccountId) -> BalanceOf<T> {
+			T::VestingBalance::vesting_balance(who)
+				.min(T::Currency::free_balance(who))
+				.saturating_sub(<VestedStake<T>>::get(who))
+		}
+		/// Funds of `who` backing its stake, held, locked while vesting or left on a staking lock
+		pub(crate) fn staked_balance(who: &T::AccountId) -> BalanceOf<T> {
+			let locked = <LockedStake<T>>::get(who).map_or(Zero::zero(), |(_, locked)| locked);
+			<HeldStake<T>>::get(who)
+				.min(T::Currency::reserved_balance(who))
+				.saturating_add(<VestedStake<T>>::get(who))
+				.saturating_add(locked)
+		}
+		/// Stake `amount` of `who`'s free balance. Funds still vesting are staked first, by
+		/// extending `VESTING_STAKE_LOCK_ID` over them since the vesting lock already keeps them
+		/// from being held, and the rest is held. Accounts with `LockedStake` extend its lock.
+		pub(crate) fn hold_stake(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			if let Some((id, locked)) = <LockedStake<T>>::get(who) {
+				let locked = locked.saturating_add(amount);
+				T::Currency::set_lock(id, who, locked, WithdrawReasons::all());
+				<LockedStake<T>>::insert(who, (id, locked));
+				return Ok(())
+			}
+			let locked = amount.min(Self::unstaked_vesting_balance(who));
+			let held = amount.saturating_sub(locked);
+			<T::Currency as MutateHold<T::AccountId>>::hold(who, held)?;
+			if !held.is_zero() {
+				<HeldStake<T>>::mutate(who, |stake| *stake = stake.saturating_add(held));
+			}
+			if !locked.is_zero() {
+				let vested = <VestedStake<T>>::get(who).saturating_add(locked);
+				T::Currency::set_lock(VESTING_STAKE_LOCK_ID, who, vested, WithdrawReasons::all());
+				<VestedStake<T>>::insert(who, vested);
+			}
+			Ok(())
+		}
+		/// Release `amount` of `who`'s stake back into its free balance, unlocking stake locked
+		/// while vesting before releasing held stake. Fails rather than release more than
+		/// `who` has staked, which would release funds other pallets reserved.
+		pub(crate) fn release_stake(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			if let Some((id, locked)) = <LockedStake<T>>::get(who) {
+				ensure!(amount <= locked, Error::<T>::StakeNotHeld);
+				Self::set_locked_stake(who, id, locked.saturating_sub(amount));
+				return Ok(())
+			}
+			let unlocked = Self::unlock_vested_stake(who, amount);
+			let amount = amount.saturating_sub(unlocked);
+			if amount.is_zero() {
+				return Ok(())
+			}
+			let held = <HeldStake<T>>::get(who);
+			ensure!(amount <= held, Error::<T>::StakeNotHeld);
+			<T::Currency as MutateHold<T::AccountId>>::release(who, amount, false)?;
+			Self::set_held_stake(who, held.saturating_sub(amount));
+			Ok(())
+		}
+		/// Set the stake of `who` held from its free balance
+		pub(crate) fn set_held_stake(who: &T::AccountId, held: BalanceOf<T>) {
+			if held.is_zero() {
+				<HeldStake<T>>::remove(who);
+			} else {
+				<HeldStake<T>>::insert(who, held);
+			}
+		}
+		/// Set the stake of `who` left under the staking lock `id`
+		pub(crate) fn set_locked_stake(
+			who: &T::AccountId,
+			id: LockIdentifier,
+			locked: BalanceOf<T>,
+		) {
+			if locked.is_zero() {
+				T::Currency::remove_lock(id, who);
+				<LockedStake<T>>::remove(who);
+			} else {
+				T::Currency::set_lock(id, who, locked, WithdrawReasons::all());
+				<LockedStake<T>>::insert(who, (id, locked));
+			}
+		}
+		/// Unlock up to `amount` of `who`'s stake locked while vesting, returning how much
+		pub(crate) fn unlock_vested_stake(
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let vested = <VestedStake<T>>::get(who);
+			let unlocked = amount.min(vested);
+			if unlocked.is_zero() {
+				return unlocked
+			}
+			let vested = vested.saturating_sub(unlocked);
+			if vested.is_zero() {
+				T::Currency::remove_lock(VESTING_STAKE_LOCK_ID, who);
+				<VestedStake<T>>::remove(who);
+			} else {
+				T::Currency::set_lock(VESTING_STAKE_LOCK_ID, who, vested, WithdrawReasons::all());
+				<VestedStake<T>>::insert(who, vested);
 			}
-			balance
+			unlocked
 		}
 		/// Returns a delegations auto-compound value.
 		pub fn delegation_auto_compound(
@@ -1349,48 +2902,105 @@ pub mod pallet {
 		) -> Percent {
 			<AutoCompoundDelegations<T>>::auto_compound(candidate, delegator)
 		}
-		/// Caller must ensure candidate is active before calling
-		pub(crate) fn update_active(candidate: T::AccountId, total: BalanceOf<T>) {
-			let mut candidates = <CandidatePool<T>>::get();
-			candidates.remove(&Bond::from_owner(candidate.clone()));
-			candidates.insert(Bond { owner: candidate, amount: total });
-			<CandidatePool<T>>::put(candidates);
+		/// Compute round issuance based on total staked for the given round. Once the room left
+		/// under `MaxTotalIssuance` is what binds, the round inflation applies to that room instead
+		/// of the circulating supply.
+		fn compute_issuance(round: RoundIndex, staked: BalanceOf<T>) -> BalanceOf<T> {
+			let (uncapped, issued) = Self::round_issuance(staked);
+			if issued < uncapped {
+				Self::deposit_event(Event::IssuanceCapped { round, uncapped, issued });
+			}
+			issued
 		}
-		/// Compute round issuance based on total staked for the given round
-		fn compute_issuance(staked: BalanceOf<T>) -> BalanceOf<T> {
+		/// Issuance of a round with `staked` at stake, before and after the `MaxTotalIssuance` cap
+		fn round_issuance(staked: BalanceOf<T>) -> (BalanceOf<T>, BalanceOf<T>) {
 			let config = <InflationConfig<T>>::get();
-			let round_issuance = crate::inflation::round_issuance_range::<T>(config.round);
-			// TODO: consider interpolation instead of bounded range
-			if staked < config.expect.min {
-				round_issuance.min
-			} else if staked > config.expect.max {
-				round_issuance.max
-			} else {
-				round_issuance.ideal
+			let round_inflation =
+				crate::inflation::pick_for_staked(config.expect, config.round, staked);
+			let circulating = T::Currency::total_issuance();
+			let uncapped = round_inflation * circulating;
+			let capped = round_inflation * T::MaxTotalIssuance::get().saturating_sub(circulating);
+			(uncapped, uncapped.min(capped))
+		}
+		/// Mint the `AuthorityBonusReserve` set aside out of round inflation as a bonus to
+		/// `scores`' accounts, e.g. DKG authorities by their reputation, pro rata to their score.
+		/// Without scores the reserve waits for the next call. The bonus is paid as a collator
+		/// reward for `round`, so it follows the payout split of the account. Returns the weight
+		/// consumed.
+		pub fn pay_authority_bonus(round: RoundIndex, scores: Vec<(T::AccountId, u128)>) -> Weight {
+			let bonus = <AuthorityBonusReserve<T>>::get();
+			let total_score =
+				scores.iter().fold(0u128, |total, (_, score)| total.saturating_add(*score));
+			if bonus.is_zero() || total_score.is_zero() {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut total = BalanceOf::<T>::zero();
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			for (account, score) in scores {
+				let amount = Perbill::from_rational(score, total_score) * bonus;
+				if amount.is_zero() {
+					continue
+				}
+				let (_, split_weight) =
+					Self::mint_collator_reward(round, amount, &account, &mut None);
+				total = total.saturating_add(amount);
+				// the account and its reward history
+				weight = weight
+					.saturating_add(split_weight)
+					.saturating_add(T::DbWeight::get().reads_writes(2, 2));
 			}
+			// rounding dust stays reserved
+			<AuthorityBonusReserve<T>>::put(bonus.saturating_sub(total));
+			Self::deposit_event(Event::AuthorityBonusPaid { round, total });
+			weight
 		}
-		/// Remove delegation from candidate state
-		/// Amount input should be retrieved from delegator and it informs the storage lookups
-		pub(crate) fn delegator_leaves_candidate(
-			candidate: T::AccountId,
-			delegator: T::AccountId,
-			amount: BalanceOf<T>,
-		) -> DispatchResult {
-			let mut state = <CandidateInfo<T>>::get(&candidate).ok_or(Error::<T>::CandidateDNE)?;
-			state.rm_delegation_if_exists::<T>(&candidate, delegator.clone(), amount)?;
-			let new_total_locked = <Total<T>>::get().saturating_sub(amount);
-			<Total<T>>::put(new_total_locked);
-			let new_total = state.total_counted;
-			<CandidateInfo<T>>::insert(&candidate, state);
-			Self::deposit_event(Event::DelegatorLeftCandidate {
-				delegator,
-				candidate,
-				unstaked_amount: amount,
-				total_candidate_staked: new_total,
-			});
-			Ok(())
+		/// Distribute the free balance `pot` holds above the existential deposit, e.g. token
+		/// wrapping fees: `StakerFeeShare` of it is burned and reissued as a boost of the next
+		/// round paid out, the rest is transferred to `treasury`. Returns the weight consumed.
+		pub fn distribute_fees(
+			round: RoundIndex,
+			pot: &T::AccountId,
+			treasury: &T::AccountId,
+		) -> Weight {
+			let available =
+				T::Currency::free_balance(pot).saturating_sub(T::Currency::minimum_balance());
+			if available.is_zero() {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut to_stakers = <StakerFeeShare<T>>::get() * available;
+			let mut to_treasury = available.saturating_sub(to_stakers);
+			if !to_stakers.is_zero() {
+				match T::Currency::withdraw(
+					pot,
+					to_stakers,
+					WithdrawReasons::TRANSFER,
+					ExistenceRequirement::KeepAlive,
+				) {
+					// dropping the imbalance burns it until it is reissued with the rewards
+					Ok(burned) => <IssuanceBoost<T>>::mutate(|boost| {
+						*boost = boost.saturating_add(burned.peek())
+					}),
+					Err(e) => {
+						log::warn!("failed to burn {:?} of fees for stakers: {:?}", to_stakers, e);
+						to_stakers = Zero::zero();
+					},
+				}
+			}
+			if !to_treasury.is_zero() {
+				if let Err(e) = T::Currency::transfer(
+					pot,
+					treasury,
+					to_treasury,
+					ExistenceRequirement::KeepAlive,
+				) {
+					log::warn!("failed to transfer {:?} of fees to treasury: {:?}", to_treasury, e);
+					to_treasury = Zero::zero();
+				}
+			}
+			Self::deposit_event(Event::FeesDistributed { round, to_treasury, to_stakers });
+			T::DbWeight::get().reads_writes(4, 4)
 		}
-		fn prepare_staking_payouts(now: RoundIndex) {
+		pub(crate) fn prepare_staking_payouts(now: RoundIndex) {
 			// payout is now - delay rounds ago => now - delay > 0 else return early
 			let delay = T::RewardPaymentDelay::get();
 			if now <= delay {
@@ -1399,24 +3009,52 @@ pub mod pallet {
 			let round_to_payout = now.saturating_sub(delay);
 			let total_points = <Points<T>>::get(round_to_payout);
 			if total_points.is_zero() {
+				// nothing will be paid for the round, so its snapshots can go straight away
+				<StaleAtStake<T>>::insert(round_to_payout, Vec::<u8>::new());
 				return
 			}
 			let total_staked = <Staked<T>>::take(round_to_payout);
-			let total_issuance = Self::compute_issuance(total_staked);
+			let inflation = Self::compute_issuance(round_to_payout, total_staked);
+			// fees recycled by `distribute_fees` are paid to stakers on top of the inflation
+			let total_issuance = inflation.saturating_add(<IssuanceBoost<T>>::take());
 			let mut left_issuance = total_issuance;
-			// reserve portion of issuance for parachain bond account
+			// reserve portion of inflation for parachain bond account
 			let bond_config = <ParachainBondInfo<T>>::get();
-			let parachain_bond_reserve = bond_config.percent * total_issuance;
+			let parachain_bond_reserve = bond_config.percent * inflation;
 			if let Ok(imb) =
 				T::Currency::deposit_into_existing(&bond_config.account, parachain_bond_reserve)
 			{
 				// update round issuance iff transfer succeeds
 				left_issuance = left_issuance.saturating_sub(imb.peek());
+				<TotalReservedForParachainBond<T>>::mutate(|total| {
+					*total = total.saturating_add(imb.peek())
+				});
 				Self::deposit_event(Event::ReservedForParachainBond {
 					account: bond_config.account,
 					value: imb.peek(),
 				});
 			}
+			// and for the account rewarding protocol usage, if any
+			if let Some(reserve) = <UsageRewardsReserve<T>>::get() {
+				let imb =
+					T::Currency::deposit_creating(&reserve.account, reserve.percent * inflation);
+				if !imb.peek().is_zero() {
+					left_issuance = left_issuance.saturating_sub(imb.peek());
+					Self::deposit_event(Event::ReservedForUsageRewards {
+						account: reserve.account,
+						value: imb.peek(),
+					});
+				}
+			}
+			// and for the authority bonus, minted once the authorities are scored
+			let authority_bonus = <AuthorityBonusShare<T>>::get() * inflation;
+			if !authority_bonus.is_zero() {
+				left_issuance = left_issuance.saturating_sub(authority_bonus);
+				<AuthorityBonusReserve<T>>::mutate(|reserve| {
+					*reserve = reserve.saturating_add(authority_bonus)
+				});
+				Self::deposit_event(Event::ReservedForAuthorityBonus { value: authority_bonus });
+			}
 
 			let payout = DelayedPayout {
 				round_issuance: total_issuance,
@@ -1425,46 +3063,175 @@ pub mod pallet {
 			};
 
 			<DelayedPayouts<T>>::insert(round_to_payout, payout);
+
+			let reward_weight = <AwardedPts<T>>::iter_prefix(round_to_payout)
+				.map(|(collator, pts)| {
+					let exposure = <AtStake<T>>::get(round_to_payout, &collator).total;
+					T::RewardCurve::reward_weight(pts, exposure)
+				})
+				.fold(0u128, |total, weight| total.saturating_add(weight));
+			<RoundRewardWeight<T>>::insert(round_to_payout, reward_weight);
+		}
+
+		/// Pay out collators of the round due for payment out of the `remaining_weight` left in
+		/// the block, for as long as it fits another worst case payout. These come on top of the
+		/// one payout `on_initialize` makes every block, and so never take space from
+		/// transactions. Returns the weight consumed.
+		fn handle_delayed_payouts(remaining_weight: Weight) -> Weight {
+			// worst case payout: a full page of delegators, each with its reward history
+			let page_size =
+				T::MaxExposurePageSize::get().min(T::MaxTopDelegationsPerCandidate::get());
+			let max_payout = T::WeightInfo::pay_one_collator_reward(page_size).saturating_add(
+				T::DbWeight::get().reads_writes(page_size as u64 + 1, page_size as u64 + 1),
+			);
+			let now = <Round<T>>::get().current;
+			let mut consumed = T::DbWeight::get().reads(1);
+			while consumed.saturating_add(max_payout).ref_time() <= remaining_weight.ref_time() {
+				let (paid, weight) = Self::handle_delayed_payout(now);
+				consumed = consumed.saturating_add(weight);
+				if !paid {
+					break
+				}
+			}
+			consumed
 		}
 
 		/// Wrapper around pay_one_collator_reward which handles the following logic:
 		/// * whether or not a payout needs to be made
 		/// * cleaning up when payouts are done
-		/// * returns the weight consumed by pay_one_collator_reward if applicable
-		fn handle_delayed_payouts(now: RoundIndex) -> Weight {
+		/// * returns whether a payout was made, and the weight consumed
+		fn handle_delayed_payout(now: RoundIndex) -> (bool, Weight) {
 			let delay = T::RewardPaymentDelay::get();
 
 			// don't underflow uint
 			if now < delay {
-				return Weight::from_ref_time(0u64)
+				return (false, Weight::from_ref_time(0u64))
 			}
 
 			let paid_for_round = now.saturating_sub(delay);
 
 			if let Some(payout_info) = <DelayedPayouts<T>>::get(paid_for_round) {
+				let staking_reward = payout_info.total_staking_reward;
 				let result = Self::pay_one_collator_reward(paid_for_round, payout_info);
-				if result.0.is_none() {
+				if let Some((_, paid)) = result.0 {
+					let paid_out = <RoundPaidOut<T>>::mutate(paid_for_round, |paid_out| {
+						*paid_out = paid_out.saturating_add(paid);
+						*paid_out
+					});
+					let mut weight = result.1.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+					if paid_out > staking_reward {
+						weight = weight.saturating_add(Self::trip_circuit_breaker(
+							StakingAnomaly::PayoutExceededIssuance {
+								round: paid_for_round,
+								paid: paid_out,
+								issuance: staking_reward,
+							},
+						));
+					}
+					(true, weight)
+				} else {
 					// result.0 indicates whether or not a payout was made
 					// clean up storage items that we no longer need
 					<DelayedPayouts<T>>::remove(paid_for_round);
 					<Points<T>>::remove(paid_for_round);
+					<RoundRewardWeight<T>>::remove(paid_for_round);
+					<RoundPaidOut<T>>::remove(paid_for_round);
 
-					// remove all candidates that did not produce any blocks for
-					// the given round. The weight is added based on the number of backend
-					// items removed.
-					let remove_result = <AtStake<T>>::clear_prefix(paid_for_round, 20, None);
-					result.1.saturating_add(T::DbWeight::get().writes(remove_result.backend as u64))
-				} else {
-					result.1 // weight consumed by pay_one_collator_reward
+					// remove candidates that did not produce any blocks for the given round.
+					// Whatever does not fit in this batch is left to `on_idle` and
+					// `prune_stale_at_stake`. The weight is added based on the number of
+					// backend items removed.
+					let removed = Self::prune_at_stake(paid_for_round, AT_STAKE_PRUNE_BATCH);
+					let cleanup = T::DbWeight::get().reads_writes(1, removed as u64 + 2);
+					(false, result.1.saturating_add(cleanup))
 				}
 			} else {
-				Weight::from_ref_time(0u64)
+				(false, T::DbWeight::get().reads(1))
+			}
+		}
+
+		/// Trip the circuit breaker if `Total` dropped by more than `MaxTotalDropPerBlock` since
+		/// the start of the previous block
+		fn check_total_drop() -> Weight {
+			let total = <Total<T>>::get();
+			let previous = <TotalAtBlockStart<T>>::get();
+			<TotalAtBlockStart<T>>::put(total);
+			let weight = T::DbWeight::get().reads_writes(2, 1);
+			let max_drop = T::MaxTotalDropPerBlock::get();
+			if max_drop.is_zero() || previous.saturating_sub(total) <= max_drop * previous {
+				return weight
+			}
+			weight.saturating_add(Self::trip_circuit_breaker(StakingAnomaly::TotalDropped {
+				previous,
+				current: total,
+			}))
+		}
+
+		/// Record `anomaly` and call `CircuitBreaker`, unless the breaker already tripped
+		pub(crate) fn trip_circuit_breaker(anomaly: StakingAnomaly<BalanceOf<T>>) -> Weight {
+			if <CircuitBreakerAnomaly<T>>::exists() {
+				return T::DbWeight::get().reads(1)
+			}
+			log::error!("parachain-staking circuit breaker tripped: {:?}", anomaly);
+			<CircuitBreakerAnomaly<T>>::put(anomaly.clone());
+			Self::deposit_event(Event::CircuitBreakerTripped { anomaly });
+			T::CircuitBreaker::trip().saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		}
+
+		/// Clear up to `limit` `AtStake` snapshots and `AtStakePages` of `round`, resuming from the
+		/// cursor left by the previous call. Returns the number of snapshots and pages removed.
+		pub(crate) fn prune_at_stake(round: RoundIndex, limit: u32) -> u32 {
+			let cursor = <StaleAtStake<T>>::get(round).unwrap_or_default();
+			let result = <AtStake<T>>::clear_prefix(
+				round,
+				limit,
+				(!cursor.is_empty()).then(|| cursor.as_slice()),
+			);
+			if let Some(cursor) = result.maybe_cursor {
+				<StaleAtStake<T>>::insert(round, cursor);
+				return result.backend
+			}
+			// pages of collators that were never paid go once the snapshots are cleared. The
+			// cursor is left empty while pages remain, as `AtStake` is cleared by then.
+			let limit = limit.saturating_sub(result.backend);
+			if limit.is_zero() {
+				<StaleAtStake<T>>::insert(round, Vec::<u8>::new());
+				return result.backend
+			}
+			let pages = <AtStakePages<T>>::clear_prefix(round, limit, None);
+			if pages.maybe_cursor.is_some() {
+				<StaleAtStake<T>>::insert(round, Vec::<u8>::new());
+			} else {
+				<StaleAtStake<T>>::remove(round);
+			}
+			result.backend.saturating_add(pages.backend)
+		}
+
+		/// Spend the weight left in the block on clearing the snapshots of one stale round
+		fn idle_prune_at_stake(remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let lookup = db_weight.reads(1);
+			let round = match <StaleAtStake<T>>::iter_keys().next() {
+				Some(round) => round,
+				None => return lookup,
+			};
+			// the cursor is read and written once, every snapshot is one write
+			let overhead = lookup.saturating_add(db_weight.reads_writes(1, 1));
+			let per_snapshot = db_weight.writes(1).ref_time();
+			let available = remaining_weight.ref_time().saturating_sub(overhead.ref_time());
+			let limit = available.checked_div(per_snapshot).unwrap_or(u64::MAX);
+			if limit == 0 {
+				return lookup
 			}
+			let removed = Self::prune_at_stake(round, limit.min(u32::MAX as u64) as u32);
+			overhead.saturating_add(db_weight.writes(removed as u64))
 		}
 
-		/// Payout a single collator from the given round.
+		/// Payout a single collator from the given round, or the next page of delegators of a
+		/// collator paid in an earlier block.
 		///
-		/// Returns an optional tuple of (Collator's AccountId, total paid)
+		/// Returns an optional tuple of (Collator's AccountId, total paid), where the total is
+		/// counted with the collator's first page and is zero for its later pages,
 		/// or None if there were no more payouts to be made for the round.
 		pub(crate) fn pay_one_collator_reward(
 			paid_for_round: RoundIndex,
@@ -1483,6 +3250,10 @@ pub mod pallet {
 				return (None, Weight::zero())
 			}
 
+			if let Some(paged) = <PagedPayouts<T>>::get(paid_for_round) {
+				return Self::pay_delegation_page(paid_for_round, paged)
+			}
+
 			let collator_fee = payout_info.collator_commission;
 			let collator_issuance = collator_fee * payout_info.round_issuance;
 
@@ -1490,75 +3261,433 @@ pub mod pallet {
 				<AwardedPts<T>>::iter_prefix(paid_for_round).drain().next()
 			{
 				let mut extra_weight = Weight::zero();
-				let pct_due = Perbill::from_rational(pts, total_points);
-				let total_paid = pct_due * payout_info.total_staking_reward;
-				let mut amt_due = total_paid;
+				let mut summary = <CompactPayoutEvents<T>>::get().then(Vec::new);
 				// Take the snapshot of block author and delegations
-
 				let state = <AtStake<T>>::take(paid_for_round, &collator);
 
+				let pct_due = Self::collator_pct_due(
+					pts,
+					total_points,
+					<RoundRewardWeight<T>>::get(paid_for_round),
+					state.total,
+				);
+				let total_paid = pct_due * payout_info.total_staking_reward;
+				let amt_due = total_paid;
+
 				let num_delegators = state.delegations.len();
 				if state.delegations.is_empty() {
 					// solo collator with no delegators
-					Self::mint(amt_due, collator.clone());
-					extra_weight =
-						extra_weight.saturating_add(T::OnCollatorPayout::on_collator_payout(
+					let (kept, split_weight) = Self::mint_collator_reward(
+						paid_for_round,
+						amt_due,
+						&collator,
+						&mut summary,
+					);
+					extra_weight = extra_weight.saturating_add(split_weight).saturating_add(
+						T::OnCollatorPayout::on_collator_payout(
 							paid_for_round,
 							collator.clone(),
-							amt_due,
-						));
+							kept,
+						),
+					);
 				} else {
 					// pay collator first; commission + due_portion
-					let collator_pct = Perbill::from_rational(state.bond, state.total);
-					let commission = pct_due * collator_issuance;
-					amt_due = amt_due.saturating_sub(commission);
-					let collator_reward = (collator_pct * amt_due).saturating_add(commission);
-					Self::mint(collator_reward, collator.clone());
-					extra_weight =
-						extra_weight.saturating_add(T::OnCollatorPayout::on_collator_payout(
+					let (collator_reward, amt_due, total) = Self::split_collator_reward(
+						amt_due,
+						pct_due * collator_issuance,
+						state.bond,
+						state.total,
+					);
+					let (kept, split_weight) = Self::mint_collator_reward(
+						paid_for_round,
+						collator_reward,
+						&collator,
+						&mut summary,
+					);
+					extra_weight = extra_weight.saturating_add(split_weight).saturating_add(
+						T::OnCollatorPayout::on_collator_payout(
 							paid_for_round,
 							collator.clone(),
-							collator_reward,
-						));
+							kept,
+						),
+					);
 
 					// pay delegators due portion
-					for BondWithAutoCompound { owner, amount, auto_compound } in state.delegations {
-						let percent = Perbill::from_rational(amount, state.total);
-						let due = percent * amt_due;
-						if !due.is_zero() {
-							Self::mint_and_compound(
-								due,
-								auto_compound,
-								collator.clone(),
-								owner.clone(),
-							);
-						}
+					extra_weight = extra_weight.saturating_add(Self::pay_delegations(
+						paid_for_round,
+						&collator,
+						state.delegations,
+						amt_due,
+						total,
+						&mut summary,
+					));
+					// the delegators on later pages are paid over the next blocks
+					if <AtStakePages<T>>::contains_key(paid_for_round, (collator.clone(), 1)) {
+						<PagedPayouts<T>>::insert(
+							paid_for_round,
+							PagedPayout {
+								collator: collator.clone(),
+								next_page: 1,
+								amt_due,
+								total,
+							},
+						);
 					}
+					extra_weight =
+						extra_weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
+				}
+
+				if let Some(mut rewards) = summary {
+					rewards.truncate(MaxPayoutSummaryRewards::<T>::get() as usize);
+					Self::deposit_event(Event::CollatorPayoutSummary {
+						collator: collator.clone(),
+						round: paid_for_round,
+						rewards: BoundedVec::try_from(rewards).unwrap_or_default(),
+					});
 				}
 
+				// reward history of the collator and each delegator
+				let history_weight = T::DbWeight::get()
+					.reads_writes(num_delegators as u64 + 1, num_delegators as u64 + 1);
 				(
 					Some((collator, total_paid)),
 					T::WeightInfo::pay_one_collator_reward(num_delegators as u32)
-						.saturating_add(extra_weight),
+						.saturating_add(extra_weight)
+						.saturating_add(history_weight),
 				)
 			} else {
 				// Note that we don't clean up storage here; it is cleaned up in
-				// handle_delayed_payouts()
+				// handle_delayed_payout()
 				(None, Weight::from_ref_time(0u64))
 			}
 		}
 
+		/// Pay the delegators on the next page of the collator whose payout is in progress for
+		/// `paid_for_round`, and move on to the following page or finish the collator's payout
+		fn pay_delegation_page(
+			paid_for_round: RoundIndex,
+			mut paged: PagedPayout<T::AccountId, BalanceOf<T>>,
+		) -> (Option<(T::AccountId, BalanceOf<T>)>, Weight) {
+			let collator = paged.collator.clone();
+			let page = (collator.clone(), paged.next_page);
+			let delegations = <AtStakePages<T>>::take(paid_for_round, page).unwrap_or_default();
+			let num_delegators = delegations.len();
+			let mut summary = <CompactPayoutEvents<T>>::get().then(Vec::new);
+			let extra_weight = Self::pay_delegations(
+				paid_for_round,
+				&collator,
+				delegations,
+				paged.amt_due,
+				paged.total,
+				&mut summary,
+			);
+			if let Some(mut rewards) = summary {
+				rewards.truncate(MaxPayoutSummaryRewards::<T>::get() as usize);
+				Self::deposit_event(Event::CollatorPayoutSummary {
+					collator: collator.clone(),
+					round: paid_for_round,
+					rewards: BoundedVec::try_from(rewards).unwrap_or_default(),
+				});
+			}
+			paged.next_page = paged.next_page.saturating_add(1);
+			let next_page = (collator.clone(), paged.next_page);
+			if <AtStakePages<T>>::contains_key(paid_for_round, next_page) {
+				<PagedPayouts<T>>::insert(paid_for_round, paged);
+			} else {
+				<PagedPayouts<T>>::remove(paid_for_round);
+			}
+			// reward history of each delegator, the page and the payout cursor
+			let history_weight = T::DbWeight::get()
+				.reads_writes(num_delegators as u64 + 3, num_delegators as u64 + 2);
+			(
+				Some((collator, BalanceOf::<T>::zero())),
+				T::WeightInfo::pay_one_collator_reward(num_delegators as u32)
+					.saturating_add(extra_weight)
+					.saturating_add(history_weight),
+			)
+		}
+
+		/// Share of a round's staking reward due to a collator awarded `pts` of the round's
+		/// `total_points` and backed by `exposure`. Rounds prepared without reward weights, or
+		/// where all of them are zero, are split by points.
+		fn collator_pct_due(
+			pts: RewardPoint,
+			total_points: RewardPoint,
+			total_reward_weight: u128,
+			exposure: BalanceOf<T>,
+		) -> Perbill {
+			if total_reward_weight.is_zero() {
+				Perbill::from_rational(pts, total_points)
+			} else {
+				Perbill::from_rational(
+					T::RewardCurve::reward_weight(pts, exposure),
+					total_reward_weight,
+				)
+			}
+		}
+
+		/// Reward of a delegation of `amount` out of the `amt_due` to the delegators of a
+		/// collator, split by their stake `total`
+		fn delegation_reward(
+			amount: BalanceOf<T>,
+			total: BalanceOf<T>,
+			amt_due: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			Perbill::from_rational(amount, total) * amt_due
+		}
+
+		/// Splits `amt` paid to `collator` between the shares of its `CollatorPayoutSplit` and the
+		/// part the collator keeps. Returns the shares and the part kept.
+		fn payout_split_shares(
+			collator: &T::AccountId,
+			amt: BalanceOf<T>,
+		) -> (Vec<(T::AccountId, BalanceOf<T>)>, BalanceOf<T>) {
+			let mut kept = amt;
+			let shares = <CollatorPayoutSplit<T>>::get(collator)
+				.map(|split| {
+					split
+						.into_iter()
+						.map(|(account, share)| {
+							let due = share * amt;
+							kept = kept.saturating_sub(due);
+							(account, due)
+						})
+						.collect()
+				})
+				.unwrap_or_default();
+			(shares, kept)
+		}
+
+		/// Splits `amt_due` to a collator with delegations between the collator, which also takes
+		/// `commission` out of it, and its delegators. Returns the collator's reward, the amount
+		/// left to the delegators and the stake they split it by.
+		fn split_collator_reward(
+			amt_due: BalanceOf<T>,
+			commission: BalanceOf<T>,
+			bond: BalanceOf<T>,
+			total: BalanceOf<T>,
+		) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
+			let amt_due = amt_due.saturating_sub(commission);
+			let bond_pct = Perbill::from_rational(bond, total);
+			let collator_pct = <DelegatorRewardCurveInfo<T>>::get().collator_share(bond_pct);
+			let collator_reward = (collator_pct * amt_due).saturating_add(commission);
+			// when the curve raises the collator share, the delegators split the rest by stake
+			// rather than taking their share of the collator's total
+			if collator_pct == bond_pct {
+				(collator_reward, amt_due, total)
+			} else {
+				(
+					collator_reward,
+					amt_due.saturating_sub(collator_pct * amt_due),
+					total.saturating_sub(bond),
+				)
+			}
+		}
+
+		/// Pay each of `delegations` its share by stake of the `amt_due` of a collator backed by
+		/// `total`, compounding it as configured. Returns the weight of compounding and remitting.
+		fn pay_delegations(
+			paid_for_round: RoundIndex,
+			collator: &T::AccountId,
+			delegations: Vec<BondWithAutoCompound<T::AccountId, BalanceOf<T>>>,
+			amt_due: BalanceOf<T>,
+			total: BalanceOf<T>,
+			summary: &mut Option<Vec<(T::AccountId, BalanceOf<T>)>>,
+		) -> Weight {
+			let compound_schedule = <AutoCompoundDelegations<T>>::get_storage(collator);
+			let mut weight = T::DbWeight::get().reads(1);
+			for BondWithAutoCompound { owner, amount, auto_compound } in delegations {
+				let due = Self::delegation_reward(amount, total, amt_due);
+				if !due.is_zero() {
+					weight = weight.saturating_add(Self::mint_and_compound(
+						paid_for_round,
+						due,
+						auto_compound,
+						compound_schedule.get_every_for_delegator(&owner),
+						collator.clone(),
+						owner.clone(),
+						summary,
+					));
+				}
+			}
+			weight
+		}
+
+		/// Defers counting `amount` newly delegated to `candidate` towards selection if it brings
+		/// the delegation to `delegation`, at or above `DeferredDelegationThreshold`. Bonding more
+		/// to a small delegation is deferred as a large delegation would be. Genesis delegations
+		/// are always counted immediately.
+		pub(crate) fn defer_large_delegation(
+			candidate: &T::AccountId,
+			delegator: &T::AccountId,
+			amount: BalanceOf<T>,
+			delegation: BalanceOf<T>,
+		) {
+			let threshold = T::DeferredDelegationThreshold::get();
+			if threshold.is_zero() ||
+				delegation < threshold ||
+				<frame_system::Pallet<T>>::block_number().is_zero()
+			{
+				return
+			}
+			let now = <Round<T>>::get().current;
+			<DeferredStake<T>>::mutate(candidate, |deferred| {
+				*deferred = match deferred.take() {
+					Some((round, stake)) if round == now =>
+						Some((now, stake.saturating_add(amount))),
+					_ => Some((now, amount)),
+				}
+			});
+			Self::note_election_change(candidate);
+			Self::deposit_event(Event::DelegationDeferred {
+				delegator: delegator.clone(),
+				candidate: candidate.clone(),
+				amount,
+				round: now,
+			});
+		}
+
+		/// Apply the parameter changes scheduled for the round that just started. A total selected
+		/// above the round length is capped to it, as `set_total_selected` would refuse it.
+		fn apply_parameter_changes(round: &RoundInfo<T::BlockNumber>) -> Weight {
+			let changes = <ScheduledParameterChanges<T>>::take(round.current);
+			let weight = T::DbWeight::get().reads_writes(1, 1).saturating_add(
+				T::DbWeight::get().reads_writes(1, 1).saturating_mul(changes.len() as u64),
+			);
+			for change in changes {
+				match change {
+					ParameterChange::Inflation(schedule) => {
+						let mut config = <InflationConfig<T>>::get();
+						config.annual = schedule;
+						config.set_round_from_annual::<T>(schedule);
+						<InflationConfig<T>>::put(config);
+					},
+					ParameterChange::CollatorCommission(new) => <CollatorCommission<T>>::put(new),
+					ParameterChange::TotalSelected(new) =>
+						<TotalSelected<T>>::put(new.min(round.length)),
+				}
+				Self::deposit_event(Event::ParameterChangeApplied { round: round.current, change });
+			}
+			weight
+		}
+
+		/// Ensure the shares of round inflation set aside for the parachain bond, usage rewards
+		/// and the authority bonus leave nothing over-allocated, as each is taken out of the
+		/// whole round inflation
+		fn ensure_inflation_shares(
+			parachain_bond: Percent,
+			usage_rewards: Percent,
+			authority_bonus: Percent,
+		) -> DispatchResult {
+			let total = [parachain_bond, usage_rewards, authority_bonus]
+				.iter()
+				.map(|share| share.deconstruct() as u32)
+				.sum::<u32>();
+			ensure!(total <= 100, Error::<T>::InflationSharesExceedWhole);
+			Ok(())
+		}
+
+		/// Decay the annual inflation if the decay schedule has a step at `round`
+		fn apply_inflation_decay(round: RoundIndex) -> Weight {
+			let decay = match <InflationDecaySchedule<T>>::get() {
+				Some(decay) => decay,
+				None => return T::DbWeight::get().reads(1),
+			};
+			let mut config = <InflationConfig<T>>::get();
+			let annual = match decay.annual_at(round, config.annual) {
+				Some(annual) => annual,
+				None => return T::DbWeight::get().reads(2),
+			};
+			config.annual = annual;
+			config.set_round_from_annual::<T>(annual);
+			Self::deposit_event(Event::InflationSet {
+				annual_min: config.annual.min,
+				annual_ideal: config.annual.ideal,
+				annual_max: config.annual.max,
+				round_min: config.round.min,
+				round_ideal: config.round.ideal,
+				round_max: config.round.max,
+			});
+			<InflationConfig<T>>::put(config);
+			T::DbWeight::get().reads_writes(2, 1)
+		}
+
+		/// Track the selected collators which did not author any block in `round`. A collator is
+		/// warned for every consecutive round it misses and is marked offline once it has missed
+		/// `T::MaxOfflineRounds` rounds in a row. Invulnerables are never marked offline.
+		fn handle_missed_rounds(round: RoundIndex) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let max_offline_rounds = T::MaxOfflineRounds::get();
+			if max_offline_rounds.is_zero() {
+				return Weight::zero()
+			}
+			// nobody authored in the round (e.g. stalled chain), do not penalize the collators
+			if <Points<T>>::get(round).is_zero() {
+				return db_weight.reads(1)
+			}
+			let invulnerables = <InvulnerableCandidates<T>>::get();
+			let selected = <SelectedCandidates<T>>::get();
+			// liveness, missed rounds, and the candidate and the pool when it is marked offline
+			let weight = db_weight
+				.reads(3)
+				.saturating_add(db_weight.reads_writes(4, 3).saturating_mul(selected.len() as u64));
+			for collator in selected {
+				if invulnerables.contains(&collator) ||
+					T::CollatorLiveness::is_live(round, &collator)
+				{
+					<ConsecutiveMissedRounds<T>>::remove(&collator);
+					continue
+				}
+				let consecutive_missed =
+					<ConsecutiveMissedRounds<T>>::get(&collator).saturating_add(1);
+				if consecutive_missed < max_offline_rounds {
+					<ConsecutiveMissedRounds<T>>::insert(&collator, consecutive_missed);
+					Self::deposit_event(Event::CollatorMissedRound {
+						collator,
+						round,
+						consecutive_missed,
+					});
+					continue
+				}
+				<ConsecutiveMissedRounds<T>>::remove(&collator);
+				if let Some(mut state) = <CandidateInfo<T>>::get(&collator) {
+					if !state.is_active() {
+						continue
+					}
+					state.go_offline();
+					Self::candidate_pool_remove(&collator);
+					<CandidateInfo<T>>::insert(&collator, state);
+					Self::deposit_event(Event::CandidateKickedForInactivity {
+						candidate: collator,
+						round,
+					});
+				}
+			}
+			weight
+		}
+
 		/// Compute the top `TotalSelected` candidates in the CandidatePool and return
 		/// a vec of their AccountIds (in the order of selection)
 		pub fn compute_top_candidates() -> Vec<T::AccountId> {
-			let mut candidates = <CandidatePool<T>>::get().0;
-			// order candidates by stake (least to greatest so requires `rev()`)
-			candidates.sort_by(|a, b| a.amount.cmp(&b.amount));
+			// stake deferred during the round being closed does not count for this selection
+			let now = <Round<T>>::get().current;
+			let mode = <CollatorSelectionMode<T>>::get();
+			let mut candidates = <CandidatePool<T>>::iter()
+				.map(|(owner, amount)| Self::selection_score(now, mode, owner, amount))
+				.collect::<Vec<(u128, _)>>();
+			// order candidates by score, then stake, then account (least to greatest so requires
+			// `rev()`)
+			candidates.sort_by(Self::selection_order);
 			let top_n = <TotalSelected<T>>::get() as usize;
-			// choose the top TotalSelected qualified candidates, ordered by stake
+			// choose the top TotalSelected qualified candidates, ordered by stake. Candidates
+			// without session keys could not author, so the next ones take their place. Genesis
+			// candidates get their keys from the session genesis config, built after this one.
+			let at_genesis = <frame_system::Pallet<T>>::block_number().is_zero();
 			let mut collators = candidates
 				.into_iter()
 				.rev()
+				.map(|(_, x)| x)
+				.filter(|x| at_genesis || Self::has_registered_keys(&x.owner))
 				.take(top_n)
 				.filter(|x| x.amount >= T::MinCollatorStk::get())
 				.map(|x| x.owner)
@@ -1566,25 +3695,73 @@ pub mod pallet {
 			collators.sort();
 			collators
 		}
+		/// Selection score of a candidate backed by `amount` in the pool, with the bond counted
+		/// towards selection. Stake deferred during round `now` is left out of both.
+		pub(crate) fn selection_score(
+			now: RoundIndex,
+			mode: SelectionMode,
+			owner: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> (u128, Bond<T::AccountId, BalanceOf<T>>) {
+			let mut bond = Bond { owner, amount };
+			let mut deferred_now = BalanceOf::<T>::zero();
+			if let Some((round, deferred)) = <DeferredStake<T>>::get(&bond.owner) {
+				if round == now {
+					bond.amount = bond.amount.saturating_sub(deferred);
+					deferred_now = deferred;
+				}
+			}
+			let score = match mode {
+				SelectionMode::Stake => bond.amount.saturated_into::<u128>(),
+				// the deferred stake is taken as a single delegation
+				SelectionMode::SqrtPerBond => Self::sqrt_per_bond_score(&bond.owner)
+					.saturating_sub(deferred_now.saturated_into::<u128>().integer_sqrt()),
+			};
+			(score, bond)
+		}
+		/// Order of scored candidates by score, then stake, then account
+		pub(crate) fn selection_order(
+			a: &(u128, Bond<T::AccountId, BalanceOf<T>>),
+			b: &(u128, Bond<T::AccountId, BalanceOf<T>>),
+		) -> sp_std::cmp::Ordering {
+			a.0.cmp(&b.0)
+				.then_with(|| a.1.amount.cmp(&b.1.amount))
+				.then_with(|| a.1.owner.cmp(&b.1.owner))
+		}
+		/// Sum of the square roots of the self bond and of each top delegation of `candidate`
+		fn sqrt_per_bond_score(candidate: &T::AccountId) -> u128 {
+			let sqrt = |amount: BalanceOf<T>| amount.saturated_into::<u128>().integer_sqrt();
+			let self_bond = <CandidateInfo<T>>::get(candidate).map_or(0, |info| sqrt(info.bond));
+			<TopDelegations<T>>::get(candidate)
+				.map_or(0, |top| {
+					top.delegations
+						.iter()
+						.fold(0u128, |score, bond| score.saturating_add(sqrt(bond.amount)))
+				})
+				.saturating_add(self_bond)
+		}
 		/// Best as in most cumulatively supported in terms of stake
 		/// Returns [collator_count, delegation_count, total staked]
 		fn select_top_candidates(now: RoundIndex) -> (u32, u32, BalanceOf<T>, Vec<T::AccountId>) {
 			let (mut collator_count, mut delegation_count, mut total) =
 				(0u32, 0u32, BalanceOf::<T>::zero());
-			// choose the top TotalSelected qualified candidates, ordered by stake
-			let collators = Self::compute_top_candidates();
+			// choose the top TotalSelected qualified candidates, ordered by stake, finishing the
+			// election begun ahead of the boundary if it still holds
+			let collators =
+				Self::paged_top_candidates().unwrap_or_else(Self::compute_top_candidates);
 			if collators.is_empty() {
 				// SELECTION FAILED TO SELECT >=1 COLLATOR => select collators from previous round
 				let last_round = now.saturating_sub(1u32);
 				let mut total_per_candidate: BTreeMap<T::AccountId, BalanceOf<T>> = BTreeMap::new();
 				// set this round AtStake to last round AtStake
-				for (account, snapshot) in <AtStake<T>>::iter_prefix(last_round) {
+				for (account, mut snapshot) in <AtStake<T>>::iter_prefix(last_round) {
+					snapshot.delegations.extend(Self::at_stake_overflow(last_round, &account));
 					collator_count = collator_count.saturating_add(1u32);
 					delegation_count =
 						delegation_count.saturating_add(snapshot.delegations.len() as u32);
 					total = total.saturating_add(snapshot.total);
 					total_per_candidate.insert(account.clone(), snapshot.total);
-					<AtStake<T>>::insert(now, account, snapshot);
+					Self::insert_at_stake(now, &account, snapshot);
 				}
 				// `SelectedCandidates` remains unchanged from last round
 				// emit CollatorChosen event for tools that use this event
@@ -1602,15 +3779,19 @@ pub mod pallet {
 			}
 
 			// snapshot exposure for round for weighting reward distribution
+			let mut threshold: Option<BalanceOf<T>> = None;
 			for account in collators.iter() {
 				let state = <CandidateInfo<T>>::get(account)
 					.expect("all members of CandidateQ must be candidates");
 
+				threshold =
+					Some(threshold.map_or(state.total_counted, |t| t.min(state.total_counted)));
 				collator_count = collator_count.saturating_add(1u32);
 				delegation_count = delegation_count.saturating_add(state.delegation_count);
 				total = total.saturating_add(state.total_counted);
+				Self::expire_delegation_requests(account, now);
 				let CountedDelegations { uncounted_stake, rewardable_delegations } =
-					Self::get_rewardable_delegators(account);
+					Self::get_rewardable_delegators(account, now);
 				let total_counted = state.total_counted.saturating_sub(uncounted_stake);
 
 				let auto_compounding_delegations = <AutoCompoundingDelegations<T>>::get(&account)
@@ -1634,31 +3815,86 @@ pub mod pallet {
 					delegations: rewardable_delegations,
 					total: total_counted,
 				};
-				<AtStake<T>>::insert(now, account, snapshot);
+				Self::insert_at_stake(now, account, snapshot);
 				Self::deposit_event(Event::CollatorChosen {
 					round: now,
 					collator_account: account.clone(),
 					total_exposed_amount: state.total_counted,
 				});
 			}
+			// warn the collators of the last round that are still candidates but missed this one
+			let threshold = threshold.unwrap_or_else(Zero::zero);
+			for candidate in <SelectedCandidates<T>>::get() {
+				if collators.contains(&candidate) {
+					continue
+				}
+				if let Some(state) = <CandidateInfo<T>>::get(&candidate) {
+					Self::deposit_event(Event::CandidateBelowSelectionThreshold {
+						round: now,
+						candidate,
+						total_counted: state.total_counted,
+						stake_gap: threshold.saturating_sub(state.total_counted),
+					});
+				}
+			}
 			// insert canonical collator set
 			<SelectedCandidates<T>>::put(collators.clone());
 			(collator_count, delegation_count, total, collators)
 		}
 
+		/// Store the snapshot of `collator` for `round`, keeping its first `MaxExposurePageSize`
+		/// delegations in `AtStake` and paging the rest into `AtStakePages`
+		fn insert_at_stake(
+			round: RoundIndex,
+			collator: &T::AccountId,
+			mut snapshot: CollatorSnapshot<T::AccountId, BalanceOf<T>>,
+		) {
+			let page_size = T::MaxExposurePageSize::get().max(1) as usize;
+			if snapshot.delegations.len() > page_size {
+				let overflow = snapshot.delegations.split_off(page_size);
+				for (index, page) in overflow.chunks(page_size).enumerate() {
+					let key = (collator.clone(), index as u32 + 1);
+					<AtStakePages<T>>::insert(round, key, page.to_vec());
+				}
+			}
+			<AtStake<T>>::insert(round, collator, snapshot);
+		}
+
+		/// Delegations of `collator` in the `round` snapshot beyond its first page
+		fn at_stake_overflow(
+			round: RoundIndex,
+			collator: &T::AccountId,
+		) -> Vec<BondWithAutoCompound<T::AccountId, BalanceOf<T>>> {
+			Self::at_stake_pages_from(round, collator, 1).flatten().collect()
+		}
+
+		/// Pages of the `round` snapshot of `collator` from `first_page` on
+		fn at_stake_pages_from(
+			round: RoundIndex,
+			collator: &T::AccountId,
+			first_page: u32,
+		) -> impl Iterator<Item = Vec<BondWithAutoCompound<T::AccountId, BalanceOf<T>>>> + '_ {
+			(first_page..)
+				.map_while(move |page| <AtStakePages<T>>::get(round, (collator.clone(), page)))
+		}
+
 		/// Apply the delegator intent for revoke and decrease in order to build the
 		/// effective list of delegators with their intended bond amount.
 		///
-		/// This will:
-		/// - if [DelegationChange::Revoke] is outstanding, set the bond amount to 0.
-		/// - if [DelegationChange::Decrease] is outstanding, subtract the bond by specified amount.
-		/// - else, do nothing
+		/// If a [DelegationChange::Revoke] or [DelegationChange::Decrease] is outstanding, the
+		/// revoked bond or the decreased amount is not rewarded, rewarded in full, or rewarded
+		/// in proportion to the rounds left until it is executable, as set by
+		/// `PendingRequestRewards`.
 		///
 		/// The intended bond amounts will be used while calculating rewards.
-		fn get_rewardable_delegators(collator: &T::AccountId) -> CountedDelegations<T> {
+		fn get_rewardable_delegators(
+			collator: &T::AccountId,
+			now: RoundIndex,
+		) -> CountedDelegations<T> {
+			let policy = T::PendingRequestRewards::get();
 			let requests = <DelegationScheduledRequests<T>>::get(collator)
 				.into_iter()
-				.map(|x| (x.delegator, x.action))
+				.map(|x| (x.delegator, (x.action, x.when_executable)))
 				.collect::<BTreeMap<_, _>>();
 			let mut uncounted_stake = BalanceOf::<T>::zero();
 			let rewardable_delegations = <TopDelegations<T>>::get(collator)
@@ -1666,27 +3902,30 @@ pub mod pallet {
 				.delegations
 				.into_iter()
 				.map(|mut bond| {
-					bond.amount = match requests.get(&bond.owner) {
-						None => bond.amount,
-						Some(DelegationAction::Revoke(_)) => {
-							log::warn!(
-								"reward for delegator '{:?}' set to zero due to pending \
-								revoke request",
-								bond.owner
-							);
-							uncounted_stake = uncounted_stake.saturating_add(bond.amount);
-							BalanceOf::<T>::zero()
-						},
-						Some(DelegationAction::Decrease(amount)) => {
-							log::warn!(
-								"reward for delegator '{:?}' reduced by set amount due to pending \
-								decrease request",
-								bond.owner
-							);
-							uncounted_stake = uncounted_stake.saturating_add(*amount);
-							bond.amount.saturating_sub(*amount)
+					let (pending, delay, when) = match requests.get(&bond.owner) {
+						None => return bond,
+						Some((DelegationAction::Revoke(_), when)) =>
+							(bond.amount, T::RevokeDelegationDelay::get(), *when),
+						Some((DelegationAction::Decrease(amount), when)) =>
+							(*amount, T::DelegationBondLessDelay::get(), *when),
+					};
+					let uncounted = match policy {
+						PendingRequestRewards::Forfeit => pending,
+						PendingRequestRewards::UntilExecution => Zero::zero(),
+						PendingRequestRewards::ProRata => {
+							let left = Perbill::from_rational(when.saturating_sub(now), delay);
+							pending.saturating_sub(left * pending)
 						},
 					};
+					if !uncounted.is_zero() {
+						log::warn!(
+							"reward for delegator '{:?}' reduced by {:?} due to pending request",
+							bond.owner,
+							uncounted
+						);
+					}
+					uncounted_stake = uncounted_stake.saturating_add(uncounted);
+					bond.amount = bond.amount.saturating_sub(uncounted);
 
 					bond
 				})
@@ -1694,75 +3933,284 @@ pub mod pallet {
 			CountedDelegations { uncounted_stake, rewardable_delegations }
 		}
 
-		/// This function exists as a helper to delegator_bond_more & auto_compound functionality.
-		/// Any changes to this function must align with both user-initiated bond increases and
-		/// auto-compounding bond increases.
-		/// Any feature-specific preconditions should be validated before this function is invoked.
-		/// Any feature-specific events must be emitted after this function is invoked.
-		pub fn delegation_bond_more_without_event(
-			delegator: T::AccountId,
-			candidate: T::AccountId,
-			more: BalanceOf<T>,
-		) -> Result<bool, sp_runtime::DispatchError> {
-			ensure!(
-				!Self::delegation_request_revoke_exists(&candidate, &delegator),
-				Error::<T>::PendingDelegationRevoke
-			);
-			let mut state = <DelegatorState<T>>::get(&delegator).ok_or(Error::<T>::DelegatorDNE)?;
-			state.increase_delegation::<T>(candidate, more)
+		/// Mint a specified reward amount to the beneficiary account. Emits the [Rewarded] event,
+		/// or adds the reward to `summary` when payout events are compact.
+		fn mint(
+			for_round: RoundIndex,
+			amt: BalanceOf<T>,
+			to: T::AccountId,
+			summary: &mut Option<Vec<(T::AccountId, BalanceOf<T>)>>,
+		) {
+			if let Ok(amount_transferred) = T::Currency::deposit_into_existing(&to, amt) {
+				Self::record_reward(&to, for_round, amount_transferred.peek());
+				Self::note_reward(to, amount_transferred.peek(), summary);
+			}
 		}
 
-		/// Mint a specified reward amount to the beneficiary account. Emits the [Rewarded] event.
-		fn mint(amt: BalanceOf<T>, to: T::AccountId) {
-			if let Ok(amount_transferred) = T::Currency::deposit_into_existing(&to, amt) {
-				Self::deposit_event(Event::Rewarded {
-					account: to.clone(),
-					rewards: amount_transferred.peek(),
-				});
+		/// Emit [Rewarded] for `account`, or add it to the payout summary if there is one
+		fn note_reward(
+			account: T::AccountId,
+			rewards: BalanceOf<T>,
+			summary: &mut Option<Vec<(T::AccountId, BalanceOf<T>)>>,
+		) {
+			match summary {
+				Some(summary) => summary.push((account, rewards)),
+				None => Self::deposit_event(Event::Rewarded { account, rewards }),
+			}
+		}
+
+		/// Mint a collator's own reward, less the cut `T::OnCollatorPayout` takes, paying the
+		/// shares of its `CollatorPayoutSplit` first. Returns the part kept by the collator and the
+		/// weight of paying the cut and the shares.
+		fn mint_collator_reward(
+			for_round: RoundIndex,
+			amt: BalanceOf<T>,
+			collator: &T::AccountId,
+			summary: &mut Option<Vec<(T::AccountId, BalanceOf<T>)>>,
+		) -> (BalanceOf<T>, Weight) {
+			let (cut, cut_weight) = T::OnCollatorPayout::take_cut(for_round, collator, amt);
+			let (split, kept) = Self::payout_split_shares(collator, amt.saturating_sub(cut));
+			for (account, due) in split.iter() {
+				if !due.is_zero() {
+					Self::mint(for_round, *due, account.clone(), summary);
+				}
+			}
+			Self::mint(for_round, kept, collator.clone(), summary);
+			// the account and reward history of every share holder
+			let shares = split.len() as u64;
+			(
+				kept,
+				T::DbWeight::get()
+					.reads_writes(1 + 2 * shares, 2 * shares)
+					.saturating_add(cut_weight),
+			)
+		}
+
+		/// Add `amount` to the reward history of `who` for `round`, dropping the oldest round once
+		/// `MaxRewardHistoryRounds` rounds are stored.
+		fn record_reward(who: &T::AccountId, round: RoundIndex, amount: BalanceOf<T>) {
+			if T::MaxRewardHistoryRounds::get().is_zero() {
+				return
+			}
+			<RewardHistory<T>>::mutate(who, |history| {
+				if let Some((last_round, total)) = history.last_mut() {
+					if *last_round == round {
+						*total = total.saturating_add(amount);
+						return
+					}
+				}
+				if history.len() as u32 >= T::MaxRewardHistoryRounds::get() {
+					history.remove(0);
+				}
+				let _ = history.try_push((round, amount));
+			});
+		}
+
+		/// Rewards `who` is due from rounds whose payouts are computed but not yet fully paid out,
+		/// as a collator, payout split share holder or delegator
+		pub fn pending_rewards(who: &T::AccountId) -> BalanceOf<T> {
+			let mut pending = BalanceOf::<T>::zero();
+			for (round, _) in <DelayedPayouts<T>>::iter() {
+				// delegators on the pages left of a collator paid in an earlier block
+				if let Some(paged) = <PagedPayouts<T>>::get(round) {
+					for bond in Self::at_stake_pages_from(round, &paged.collator, paged.next_page)
+						.flatten()
+						.filter(|bond| &bond.owner == who)
+					{
+						pending = pending.saturating_add(Self::delegation_reward(
+							bond.amount,
+							paged.total,
+							paged.amt_due,
+						));
+					}
+				}
+				for payout in Self::estimate_round_payouts(round) {
+					if &payout.collator == who {
+						pending = pending.saturating_add(payout.collator_reward);
+					}
+					for (_, reward) in payout
+						.split_rewards
+						.iter()
+						.chain(payout.delegator_rewards.iter())
+						.filter(|(account, _)| account == who)
+					{
+						pending = pending.saturating_add(*reward);
+					}
+				}
 			}
+			pending
 		}
 
 		/// Mint and compound delegation rewards. The function mints the amount towards the
 		/// delegator and tries to compound a specified percent of it back towards the delegation.
 		/// If a scheduled delegation revoke exists, then the amount is only minted, and nothing is
-		/// compounded. Emits the [Compounded] event.
+		/// compounded. Emits the [Compounded] event. Whatever is not compounded is batched for
+		/// the delegator's remote reward destination, if it has one. Returns the weight of
+		/// batching and remitting.
 		fn mint_and_compound(
+			for_round: RoundIndex,
 			amt: BalanceOf<T>,
 			compound_percent: Percent,
+			compound_every: RoundIndex,
 			candidate: T::AccountId,
 			delegator: T::AccountId,
-		) {
+			summary: &mut Option<Vec<(T::AccountId, BalanceOf<T>)>>,
+		) -> Weight {
 			if let Ok(amount_transferred) = T::Currency::deposit_into_existing(&delegator, amt) {
-				Self::deposit_event(Event::Rewarded {
-					account: delegator.clone(),
-					rewards: amount_transferred.peek(),
-				});
+				Self::record_reward(&delegator, for_round, amount_transferred.peek());
+				Self::note_reward(delegator.clone(), amount_transferred.peek(), summary);
 
-				let compound_amount = compound_percent.mul_ceil(amount_transferred.peek());
-				if compound_amount.is_zero() {
-					return
+				let mut weight = Weight::zero();
+				let mut remittable = amount_transferred.peek();
+				let mut compound_amount = compound_percent.mul_ceil(amount_transferred.peek());
+				if compound_every > 1 && !compound_percent.is_zero() {
+					// the share put aside is not remitted, whether it can be compounded or not
+					remittable = remittable.saturating_sub(compound_amount);
+					let due = <PendingCompound<T>>::get(&candidate, &delegator)
+						.saturating_add(compound_amount);
+					if for_round % compound_every == 0 {
+						<PendingCompound<T>>::remove(&candidate, &delegator);
+						compound_amount = due;
+					} else {
+						<PendingCompound<T>>::insert(&candidate, &delegator, due);
+						compound_amount = Zero::zero();
+					}
+					weight = T::DbWeight::get().reads_writes(1, 1);
 				}
-
-				if let Err(err) = Self::delegation_bond_more_without_event(
-					delegator.clone(),
-					candidate.clone(),
-					compound_amount,
-				) {
-					log::error!(
-								"Error compounding staking reward towards candidate '{:?}' for delegator '{:?}': {:?}",
+				if !compound_amount.is_zero() {
+					match Self::delegation_bond_more_without_event(
+						delegator.clone(),
+						candidate.clone(),
+						compound_amount,
+					) {
+						Ok(_) => {
+							if compound_every <= 1 {
+								remittable = remittable.saturating_sub(compound_amount);
+							}
+							Pallet::<T>::deposit_event(Event::Compounded {
+								delegator: delegator.clone(),
 								candidate,
-								delegator,
-								err
-							);
-					return
-				};
+								amount: compound_amount,
+							});
+						},
+						Err(err) => log::error!(
+							"Error compounding staking reward towards candidate '{:?}' for delegator '{:?}': {:?}",
+							candidate,
+							delegator,
+							err
+						),
+					}
+				}
+
+				return weight
+					.saturating_add(Self::batch_remote_reward(for_round, &delegator, remittable))
+			};
+			Weight::zero()
+		}
 
-				Pallet::<T>::deposit_event(Event::Compounded {
+		/// Reserve `amount` in the local account of `delegator` and add it to the rewards batched
+		/// for its remote reward destination, if it has one. The batch is queued for remittance
+		/// once `RemitRewardsEvery` rounds passed since the last one, and remitted in `on_idle`
+		/// rather than while paying out.
+		fn batch_remote_reward(
+			for_round: RoundIndex,
+			delegator: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Weight {
+			let mut remote = match <RemoteRewardDestinations<T>>::get(delegator) {
+				Some(remote) => remote,
+				None => return T::DbWeight::get().reads(1),
+			};
+			let mut weight = T::DbWeight::get().reads_writes(2, 2);
+			match T::Currency::reserve(delegator, amount) {
+				Ok(()) => remote.pending = remote.pending.saturating_add(amount),
+				// e.g. a lock covers the reward, which is then kept in the local account
+				Err(err) => log::warn!(
+					"Error batching {:?} of staking rewards for delegator '{:?}': {:?}",
+					amount,
 					delegator,
-					candidate,
-					amount: compound_amount,
-				});
+					err
+				),
+			}
+			if for_round.saturating_sub(remote.last_remitted) >= T::RemitRewardsEvery::get() &&
+				!remote.pending.is_zero()
+			{
+				remote.last_remitted = for_round;
+				<DueRemittances<T>>::insert(delegator, ());
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+			<RemoteRewardDestinations<T>>::insert(delegator, remote);
+			weight
+		}
+
+		/// Remit the due reward batches that fit in `remaining_weight`, returning the weight used
+		fn idle_remit_rewards(remaining_weight: Weight) -> Weight {
+			let lookup = T::DbWeight::get().reads(1);
+			let per_remittance =
+				T::RemitReward::weight().saturating_add(T::DbWeight::get().reads_writes(3, 4));
+			let available = remaining_weight.ref_time().saturating_sub(lookup.ref_time());
+			let limit = available.checked_div(per_remittance.ref_time()).unwrap_or(u64::MAX);
+			let due = <DueRemittances<T>>::iter_keys()
+				.take(limit.min(u32::MAX as u64) as usize)
+				.collect::<Vec<_>>();
+			due.iter().fold(lookup, |weight, delegator| {
+				weight.saturating_add(Self::remit_rewards(delegator))
+			})
+		}
+
+		/// Send the rewards batched for `delegator` to its remote reward destination. Rewards that
+		/// cannot be sent are released to its local account rather than retried.
+		pub(crate) fn remit_rewards(delegator: &T::AccountId) -> Weight {
+			<DueRemittances<T>>::remove(delegator);
+			let mut remote = match <RemoteRewardDestinations<T>>::get(delegator) {
+				Some(remote) if !remote.pending.is_zero() => remote,
+				_ => return T::DbWeight::get().reads_writes(1, 1),
 			};
+			let mut weight = T::DbWeight::get().reads_writes(3, 4);
+			let amount = Self::release_batched_rewards(delegator, remote.pending);
+			remote.pending = Zero::zero();
+			match T::RemitReward::remit(delegator, &remote.destination, amount) {
+				Ok(remit_weight) => {
+					weight = weight.saturating_add(remit_weight);
+					Self::deposit_event(Event::RewardsRemitted {
+						delegator: delegator.clone(),
+						amount,
+					});
+				},
+				Err(err) => {
+					log::warn!(
+						"Error remitting {:?} of staking rewards for delegator '{:?}': {:?}",
+						amount,
+						delegator,
+						err
+					);
+					weight = weight.saturating_add(T::RemitReward::weight());
+					Self::deposit_event(Event::RewardRemittanceFailed {
+						delegator: delegator.clone(),
+						amount,
+					});
+				},
+			}
+			<RemoteRewardDestinations<T>>::insert(delegator, remote);
+			weight
+		}
+
+		/// Unreserve up to `amount` of the rewards batched for `delegator`, never touching the
+		/// reserve backing its stake, returning the amount released
+		fn release_batched_rewards(delegator: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+			let batched = T::Currency::reserved_balance(delegator)
+				.saturating_sub(<HeldStake<T>>::get(delegator))
+				.min(amount);
+			batched.saturating_sub(T::Currency::unreserve(delegator, batched))
+		}
+
+		/// Remove the remote reward destination of `delegator`, releasing the rewards batched for
+		/// it to its local account
+		pub(crate) fn clear_reward_destination(delegator: &T::AccountId) {
+			if let Some(remote) = <RemoteRewardDestinations<T>>::take(delegator) {
+				Self::release_batched_rewards(delegator, remote.pending);
+				<DueRemittances<T>>::remove(delegator);
+			}
 		}
 	}
 
@@ -1772,9 +4220,74 @@ pub mod pallet {
 		fn award_points_to_block_author() {
 			let author = T::BlockAuthor::get();
 			let now = <Round<T>>::get().current;
-			let score_plus_20 = <AwardedPts<T>>::get(now, &author).saturating_add(20);
-			<AwardedPts<T>>::insert(now, author, score_plus_20);
-			<Points<T>>::mutate(now, |x| *x = x.saturating_add(20));
+			let points = Self::block_author_points();
+			let score = <AwardedPts<T>>::get(now, &author).saturating_add(points);
+			<AwardedPts<T>>::insert(now, author, score);
+			<Points<T>>::mutate(now, |x| *x = x.saturating_add(points));
+		}
+		/// Base points plus a bonus proportional to the block weight used so far
+		fn block_author_points() -> RewardPoint {
+			let used = <frame_system::Pallet<T>>::block_weight().total().ref_time();
+			let max = T::BlockWeights::get().max_block.ref_time();
+			let fullness = Perbill::from_rational(used.min(max), max);
+			T::BasePointsPerBlock::get().saturating_add(fullness * T::MaxBonusPointsPerBlock::get())
+		}
+	}
+
+	/// Most rewards in a `CollatorPayoutSummary`: the collator, its payout split shares and its
+	/// top delegations
+	pub struct MaxPayoutSummaryRewards<T>(PhantomData<T>);
+	impl<T: Config> Get<u32> for MaxPayoutSummaryRewards<T> {
+		fn get() -> u32 {
+			T::MaxTopDelegationsPerCandidate::get()
+				.saturating_add(T::MaxPayoutSplitShares::get())
+				.saturating_add(1)
+		}
+	}
+
+	/// Session keys set through `pallet_session`
+	pub struct PalletSessionKeys<T>(PhantomData<T>);
+	impl<T: pallet_session::Config> SetSessionKeys<T::AccountId> for PalletSessionKeys<T> {
+		type Keys = T::Keys;
+
+		fn set_keys(who: &T::AccountId, keys: T::Keys, proof: Vec<u8>) -> DispatchResult {
+			<pallet_session::Pallet<T>>::set_keys(
+				frame_system::RawOrigin::Signed(who.clone()).into(),
+				keys,
+				proof,
+			)
+		}
+	}
+
+	/// Ends sessions, and so rounds, on multiples of `RelayRoundLength` relay chain blocks when it
+	/// is set, and as `Inner` does otherwise
+	pub struct RelayAlignedRounds<T, Inner>(PhantomData<(T, Inner)>);
+	impl<T: Config, Inner: ShouldEndSession<T::BlockNumber>> ShouldEndSession<T::BlockNumber>
+		for RelayAlignedRounds<T, Inner>
+	{
+		fn should_end_session(now: T::BlockNumber) -> bool {
+			match <RelayRoundLength<T>>::get() {
+				// a round missing whole periods ends at once, the next one realigned
+				Some(length) =>
+					T::RelayBlockNumber::current_block_number() / length >
+						<RoundRelayStart<T>>::get() / length,
+				None => Inner::should_end_session(now),
+			}
+		}
+	}
+
+	/// Staking-native liveness: a collator is live in a round if it authored at least one block
+	pub struct AuthoredBlocks<T>(PhantomData<T>);
+	impl<T: Config> CollatorLiveness<T::AccountId> for AuthoredBlocks<T> {
+		fn is_live(round: RoundIndex, collator: &T::AccountId) -> bool {
+			!<AwardedPts<T>>::get(round, collator).is_zero()
+		}
+	}
+
+	impl<T: Config> CollatorBacking<T::AccountId, BalanceOf<T>> for Pallet<T> {
+		fn backing(round: RoundIndex, collator: &T::AccountId) -> Option<BalanceOf<T>> {
+			<AtStake<T>>::contains_key(round, collator)
+				.then(|| <AtStake<T>>::get(round, collator).total)
 		}
 	}
 
@@ -1790,6 +4303,24 @@ pub mod pallet {
 		}
 	}
 
+	/// Slashes the self bond of candidates reported through `pallet_offences`, after
+	/// `SlashDeferDuration` rounds. Delegations are not slashed.
+	impl<T: Config> OnOffenceHandler<T::AccountId, (T::AccountId, T::AccountId), Weight> for Pallet<T> {
+		fn on_offence(
+			offenders: &[OffenceDetails<T::AccountId, (T::AccountId, T::AccountId)>],
+			slash_fraction: &[Perbill],
+			_session: SessionIndex,
+			_disable_strategy: DisableStrategy,
+		) -> Weight {
+			let mut weight = Weight::zero();
+			for (details, fraction) in offenders.iter().zip(slash_fraction) {
+				weight = weight
+					.saturating_add(Self::candidate_report_slash(&details.offender.0, *fraction));
+			}
+			weight
+		}
+	}
+
 	/// Play the role of the session manager.
 	impl<T: Config> SessionManager<T::AccountId> for Pallet<T> {
 		fn new_session(index: SessionIndex) -> Option<Vec<T::AccountId>> {
@@ -1805,18 +4336,46 @@ pub mod pallet {
 			// mutate round
 			round.update(current_block_number);
 
+			// governance changes due this round take effect before payouts and selection
+			let mut weight = Self::apply_parameter_changes(&round)
+				.saturating_add(Self::apply_inflation_decay(round.current));
+
 			// pay all stakers for T::RewardPaymentDelay rounds ago
 			Self::prepare_staking_payouts(round.current);
 
+			// slashes due this round count against the stake selected for it
+			weight = weight.saturating_add(Self::apply_deferred_slashes(round.current));
+
+			// mark collators which keep missing their rounds offline before selection
+			let missed_rounds_weight = Self::handle_missed_rounds(round.current.saturating_sub(1));
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+				missed_rounds_weight,
+				DispatchClass::Mandatory,
+			);
+
 			// select top collator candidates for next round
-			let (collator_count, _, total_staked, collators) =
+			let (collator_count, delegation_count, total_staked, collators) =
 				Self::select_top_candidates(round.current);
+			// the round transition benchmark covers preparing the payouts and the selection
+			weight = weight.saturating_add(T::WeightInfo::round_transition_on_initialize(
+				collator_count,
+				delegation_count,
+			));
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+				weight,
+				DispatchClass::Mandatory,
+			);
 			// start next round
 			<Round<T>>::put(round);
+			<RoundRelayStart<T>>::put(T::RelayBlockNumber::current_block_number());
 			// snapshot total stake
 			<Staked<T>>::insert(round.current, <Total<T>>::get());
-
-			Self::handle_delayed_payouts(round.current);
+			// hooks see the collators selected for the new round
+			let weight = T::OnNewRound::on_new_round(round.current);
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+				weight,
+				DispatchClass::Mandatory,
+			);
 
 			Self::deposit_event(Event::NewRound {
 				starting_block: round.first,
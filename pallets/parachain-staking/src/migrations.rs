@@ -0,0 +1,214 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations for parachain-staking
+
+use crate::{
+	set::OrderedSet, AutoCompoundConfig, AutoCompoundingDelegations, BalanceOf, Bond,
+	CandidateInfo, CandidatePool, CandidatePoolCount, Config, DelegatorState,
+	InvulnerableCandidates, LockedStake, Pallet, SuccessorInvulnerables, VestedStake,
+	COLLATOR_LOCK_ID, DELEGATOR_LOCK_ID,
+};
+use frame_support::{
+	storage::{unhashed, StoragePrefixedMap},
+	traits::{
+		fungible::MutateHold, Get, GetStorageVersion, LockIdentifier, LockableCurrency,
+		OnRuntimeUpgrade, StorageVersion, WithdrawReasons,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use parity_scale_codec::Decode;
+use sp_runtime::{traits::Zero, Percent};
+use sp_std::vec::Vec;
+
+/// Adds the consumer reference every candidate and delegator account holds since storage
+/// version 1, so staking accounts cannot be reaped while they still have staking state.
+pub struct AddStakerConsumerRefs<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for AddStakerConsumerRefs<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut accounts = 0u64;
+		for account in <CandidateInfo<T>>::iter_keys().chain(<DelegatorState<T>>::iter_keys()) {
+			if let Err(e) = <frame_system::Pallet<T>>::inc_consumers_without_limit(&account) {
+				log::error!("failed to add staking consumer reference for {:?}: {:?}", account, e);
+			}
+			accounts = accounts.saturating_add(1);
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!("added staking consumer references for {} accounts", accounts);
+		T::DbWeight::get().reads_writes(accounts.saturating_mul(2) + 1, accounts + 1)
+	}
+}
+
+/// Moves staked funds from the `COLLATOR_LOCK_ID`/`DELEGATOR_LOCK_ID` locks to holds, as used
+/// since storage version 2. Accounts whose funds cannot be held, e.g. because they overlap with
+/// another lock, keep their staking lock, recorded in `LockedStake`, and are logged.
+pub struct HoldStakedFunds<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> HoldStakedFunds<T> {
+	fn lock_to_hold(who: &T::AccountId, id: LockIdentifier, amount: BalanceOf<T>) {
+		T::Currency::remove_lock(id, who);
+		if let Err(e) = <T::Currency as MutateHold<T::AccountId>>::hold(who, amount) {
+			log::error!("failed to hold {:?} staked by {:?}: {:?}", amount, who, e);
+			T::Currency::set_lock(id, who, amount, WithdrawReasons::all());
+			<LockedStake<T>>::insert(who, (id, amount));
+		}
+	}
+}
+impl<T: Config> OnRuntimeUpgrade for HoldStakedFunds<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 2 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut accounts = 0u64;
+		for (candidate, info) in <CandidateInfo<T>>::iter() {
+			Self::lock_to_hold(&candidate, COLLATOR_LOCK_ID, info.bond);
+			accounts = accounts.saturating_add(1);
+		}
+		for (delegator, state) in <DelegatorState<T>>::iter() {
+			Self::lock_to_hold(&delegator, DELEGATOR_LOCK_ID, state.total);
+			accounts = accounts.saturating_add(1);
+		}
+		StorageVersion::new(2).put::<Pallet<T>>();
+		log::info!("moved staked funds of {} accounts from locks to holds", accounts);
+		T::DbWeight::get()
+			.reads_writes(accounts.saturating_mul(3) + 1, accounts.saturating_mul(4) + 1)
+	}
+}
+
+/// `AutoCompoundConfig` before storage version 3
+#[derive(Decode)]
+struct OldAutoCompoundConfig<AccountId> {
+	delegator: AccountId,
+	value: Percent,
+}
+
+/// Adds the compounding frequency to every auto-compounding config, as stored since storage
+/// version 3. Existing configs keep compounding every round.
+pub struct AddAutoCompoundFrequency<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for AddAutoCompoundFrequency<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 3 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut candidates = 0u64;
+		<AutoCompoundingDelegations<T>>::translate_values::<
+			Vec<OldAutoCompoundConfig<T::AccountId>>,
+			_,
+		>(|configs| {
+			candidates = candidates.saturating_add(1);
+			Some(
+				configs
+					.into_iter()
+					.map(|OldAutoCompoundConfig { delegator, value }| AutoCompoundConfig {
+						delegator,
+						value,
+						every: 1,
+					})
+					.collect(),
+			)
+		});
+		StorageVersion::new(3).put::<Pallet<T>>();
+		log::info!("added a compounding frequency to the configs of {} candidates", candidates);
+		T::DbWeight::get().reads_writes(candidates + 1, candidates + 1)
+	}
+}
+
+/// Splits the `CandidatePool` value, a single `OrderedSet` of every candidate and its backing
+/// before storage version 4, into one entry per candidate plus `CandidatePoolCount`.
+pub struct SplitCandidatePool<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for SplitCandidatePool<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 4 {
+			return T::DbWeight::get().reads(1)
+		}
+		// the old value lives at the prefix of the new map
+		let pool: OrderedSet<Bond<T::AccountId, BalanceOf<T>>> =
+			unhashed::take(&<CandidatePool<T>>::final_prefix()).unwrap_or_default();
+		let candidates = pool.0.len() as u64;
+		for bond in pool.0 {
+			<CandidatePool<T>>::insert(&bond.owner, bond.amount);
+		}
+		<CandidatePoolCount<T>>::put(candidates as u32);
+		StorageVersion::new(4).put::<Pallet<T>>();
+		log::info!("split the candidate pool into entries for {} candidates", candidates);
+		T::DbWeight::get().reads_writes(2, candidates + 3)
+	}
+}
+
+/// Bounds `InvulnerableCandidates` and `SuccessorInvulnerables` by `MaxInvulnerables`, as stored
+/// since storage version 5. Both were only ever set within the bound, so the values are kept as
+/// they are, save for any invulnerables past the bound, which are dropped and logged.
+pub struct BoundInvulnerables<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> BoundInvulnerables<T> {
+	fn bound(invulnerables: Vec<T::AccountId>) -> BoundedVec<T::AccountId, T::MaxInvulnerables> {
+		if invulnerables.len() > T::MaxInvulnerables::get() as usize {
+			log::warn!(
+				"dropping invulnerables {:?} past MaxInvulnerables",
+				&invulnerables[T::MaxInvulnerables::get() as usize..]
+			);
+		}
+		BoundedVec::truncate_from(invulnerables)
+	}
+}
+impl<T: Config> OnRuntimeUpgrade for BoundInvulnerables<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 5 {
+			return T::DbWeight::get().reads(1)
+		}
+		let _ = <InvulnerableCandidates<T>>::translate::<Vec<T::AccountId>, _>(|old| {
+			old.map(Self::bound)
+		});
+		let _ = <SuccessorInvulnerables<T>>::translate::<Vec<T::AccountId>, _>(|old| {
+			old.map(Self::bound)
+		});
+		StorageVersion::new(5).put::<Pallet<T>>();
+		log::info!("bounded the invulnerables by MaxInvulnerables");
+		T::DbWeight::get().reads_writes(3, 3)
+	}
+}
+
+/// Records the stake every account holds in `HeldStake`, as tracked since storage version 6.
+/// The stake not locked while vesting or left on a staking lock is the stake held.
+pub struct TrackHeldStake<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> TrackHeldStake<T> {
+	fn track(who: &T::AccountId, stake: BalanceOf<T>) {
+		let locked = <LockedStake<T>>::get(who).map_or(Zero::zero(), |(_, locked)| locked);
+		let held = stake.saturating_sub(<VestedStake<T>>::get(who)).saturating_sub(locked);
+		Pallet::<T>::set_held_stake(who, held);
+	}
+}
+impl<T: Config> OnRuntimeUpgrade for TrackHeldStake<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 6 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut accounts = 0u64;
+		for (candidate, info) in <CandidateInfo<T>>::iter() {
+			Self::track(&candidate, info.bond);
+			accounts = accounts.saturating_add(1);
+		}
+		for (delegator, state) in <DelegatorState<T>>::iter() {
+			Self::track(&delegator, state.total);
+			accounts = accounts.saturating_add(1);
+		}
+		StorageVersion::new(6).put::<Pallet<T>>();
+		log::info!("recorded the held stake of {} accounts", accounts);
+		T::DbWeight::get().reads_writes(accounts.saturating_mul(3) + 1, accounts + 1)
+	}
+}
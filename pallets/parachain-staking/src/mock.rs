@@ -17,12 +17,10 @@
 //! Test utilities
 #![allow(clippy::all, dead_code)]
 use crate as pallet_parachain_staking;
-use crate::{
-	pallet, AwardedPts, Config, InflationInfo, Points, Range, COLLATOR_LOCK_ID, DELEGATOR_LOCK_ID,
-};
+use crate::{pallet, AwardedPts, Config, InflationInfo, Points, Range};
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{Everything, GenesisBuild, LockIdentifier, OnFinalize, OnInitialize},
+	traits::{Everything, GenesisBuild, OnFinalize, OnInitialize},
 	weights::Weight,
 };
 use frame_system::EnsureRoot;
@@ -49,6 +47,7 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>},
 		ParachainStaking: pallet_parachain_staking::{Pallet, Call, Storage, Config<T>, Event<T>},
 		BlockAuthor: block_author::{Pallet, Storage},
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
@@ -116,6 +115,17 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 }
+parameter_types! {
+	pub const MinVestedTransfer: Balance = 1;
+}
+impl pallet_vesting::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockNumberToBalance = sp_runtime::traits::ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = ();
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
 
 sp_runtime::impl_opaque_keys! {
 	pub struct MockSessionKeys {
@@ -141,7 +151,8 @@ impl pallet_session::SessionHandler<u64> for TestSessionHandler {
 
 parameter_types! {
 	pub const Offset: BlockNumber = 0;
-	pub const Period: BlockNumber = 10;
+	// sessions, and so rounds, change every `DefaultBlocksPerRound` blocks
+	pub const Period: BlockNumber = 5;
 }
 
 impl pallet_session::Config for Test {
@@ -149,7 +160,8 @@ impl pallet_session::Config for Test {
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	// we don't have stash and controller, thus we don't need the convert as well.
 	type ValidatorIdOf = IdentityCollator;
-	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+	type ShouldEndSession =
+		crate::RelayAlignedRounds<Test, pallet_session::PeriodicSessions<Period, Offset>>;
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
 	type SessionManager = ParachainStaking;
 	type SessionHandler = TestSessionHandler;
@@ -176,14 +188,110 @@ parameter_types! {
 	pub const MinCollatorStk: u128 = 10;
 	pub const MinDelegatorStk: u128 = 5;
 	pub const MinDelegation: u128 = 3;
+	pub static MaxOfflineRounds: u32 = 0;
+	pub static DeferredDelegationThreshold: u128 = 0;
+	pub const EmergencyUnbondPenalty: Perbill = Perbill::from_percent(10);
+	pub const MaxRewardHistoryRounds: u32 = 4;
+	pub const BasePointsPerBlock: u32 = 20;
+	pub static MaxBonusPointsPerBlock: u32 = 0;
+	pub const MaxPayoutSplitShares: u32 = 2;
+	pub static UseSqrtExposure: bool = false;
+	pub const RemitRewardsEvery: u32 = 2;
+	pub const TreasuryAccount: AccountId = 99;
+	pub static RelayBlockNumber: u32 = 0;
+	pub static SlashDeferDuration: u32 = 0;
+	pub static RequestExpiryRounds: u32 = 0;
+	pub static PendingRequestRewardPolicy: crate::PendingRequestRewards =
+		crate::PendingRequestRewards::Forfeit;
+	pub static LeaveCancellationCutoff: u32 = 0;
+	pub static MaxTotalIssuance: u128 = u128::MAX;
+	pub static MaxTotalDropPerBlock: Percent = Percent::from_percent(0);
+	pub static CircuitBreakerTrips: u32 = 0;
+	pub static Remitted: Vec<(AccountId, u32, Balance)> = vec![];
+	pub static RemitFails: bool = false;
+	pub static Kicked: Vec<(AccountId, AccountId, Balance)> = vec![];
+	pub static MaxExposurePageSize: u32 = 100;
+	pub static ElectionLookahead: BlockNumber = 0;
+	pub static ElectionPageSize: u32 = 2;
+	pub static NewRounds: Vec<u32> = vec![];
+}
+
+/// Records kicked delegations as (delegator, candidate, amount)
+pub struct MockOnDelegationKicked;
+impl crate::OnDelegationKicked<AccountId, Balance> for MockOnDelegationKicked {
+	fn on_delegation_kicked(delegator: &AccountId, candidate: &AccountId, amount: Balance) {
+		Kicked::mutate(|kicked| kicked.push((*delegator, *candidate, amount)));
+	}
+}
+
+/// Records remittances to a para id instead of sending them over XCM
+pub struct MockRemitReward;
+impl crate::RemitReward<AccountId, Balance> for MockRemitReward {
+	type Destination = u32;
+
+	fn remit(
+		who: &AccountId,
+		destination: &u32,
+		amount: Balance,
+	) -> Result<Weight, sp_runtime::DispatchError> {
+		if RemitFails::get() {
+			return Err(sp_runtime::DispatchError::Other("remittance failed"))
+		}
+		Remitted::mutate(|remitted| remitted.push((*who, *destination, amount)));
+		Ok(Weight::zero())
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Counts the trips of the circuit breaker
+pub struct MockCircuitBreaker;
+impl crate::CircuitBreaker for MockCircuitBreaker {
+	fn trip() -> Weight {
+		CircuitBreakerTrips::mutate(|trips| *trips += 1);
+		Weight::zero()
+	}
+}
+
+pub struct MockRelayBlockNumber;
+impl sp_runtime::traits::BlockNumberProvider for MockRelayBlockNumber {
+	type BlockNumber = u32;
+
+	fn current_block_number() -> u32 {
+		RelayBlockNumber::get()
+	}
+}
+
+pub struct MockOnNewRound;
+impl crate::OnNewRound for MockOnNewRound {
+	fn on_new_round(round_index: crate::RoundIndex) -> Weight {
+		NewRounds::mutate(|rounds| rounds.push(round_index));
+		Weight::zero()
+	}
+}
+
+/// Splits rewards by points, or by [`crate::SqrtExposure`] once `UseSqrtExposure` is set
+pub struct MockRewardCurve;
+impl crate::RewardCurve<Balance> for MockRewardCurve {
+	fn reward_weight(points: u32, exposure: Balance) -> u128 {
+		if UseSqrtExposure::get() {
+			<crate::SqrtExposure as crate::RewardCurve<Balance>>::reward_weight(points, exposure)
+		} else {
+			<() as crate::RewardCurve<Balance>>::reward_weight(points, exposure)
+		}
+	}
 }
 
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type MonetaryGovernanceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTotalIssuance = MaxTotalIssuance;
 	type MinBlocksPerRound = MinBlocksPerRound;
 	type LeaveCandidatesDelay = LeaveCandidatesDelay;
+	type LeaveCancellationCutoff = LeaveCancellationCutoff;
 	type CandidateBondLessDelay = CandidateBondLessDelay;
 	type LeaveDelegatorsDelay = LeaveDelegatorsDelay;
 	type RevokeDelegationDelay = RevokeDelegationDelay;
@@ -205,7 +313,35 @@ impl Config for Test {
 	type ValidatorRegistration = Session;
 	type UpdateOrigin = EnsureRoot<AccountId>;
 	type OnCollatorPayout = ();
-	type OnNewRound = ();
+	type OnNewRound = MockOnNewRound;
+	type OnDelegationKicked = MockOnDelegationKicked;
+	type MaxOfflineRounds = MaxOfflineRounds;
+	type PendingRequestRewards = PendingRequestRewardPolicy;
+	type RequestExpiryRounds = RequestExpiryRounds;
+	type CollatorLiveness = crate::AuthoredBlocks<Test>;
+	type DeferredDelegationThreshold = DeferredDelegationThreshold;
+	type MaxRewardHistoryRounds = MaxRewardHistoryRounds;
+	type MaxExposurePageSize = MaxExposurePageSize;
+	type BasePointsPerBlock = BasePointsPerBlock;
+	type MaxBonusPointsPerBlock = MaxBonusPointsPerBlock;
+	type RemoteDelegatorOrigin = frame_system::EnsureSigned<AccountId>;
+	type MaxPayoutSplitShares = MaxPayoutSplitShares;
+	type RewardCurve = MockRewardCurve;
+	type Slash = ();
+	type EmergencyUnbondPenalty = EmergencyUnbondPenalty;
+	type SlashDeferDuration = SlashDeferDuration;
+	type SlashCancelOrigin = EnsureRoot<AccountId>;
+	type MaxDeferredSlashesPerRound = ConstU32<2>;
+	type MaxTotalDropPerBlock = MaxTotalDropPerBlock;
+	type CircuitBreaker = MockCircuitBreaker;
+	type RemitReward = MockRemitReward;
+	type RemitRewardsEvery = RemitRewardsEvery;
+	type ElectionLookahead = ElectionLookahead;
+	type ElectionPageSize = ElectionPageSize;
+	type VestingBalance = crate::VestingScheduleBalance<Vesting>;
+	type SessionKeys = crate::PalletSessionKeys<Test>;
+	type RelayBlockNumber = MockRelayBlockNumber;
+	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
 }
 
@@ -265,13 +401,13 @@ impl ExtBuilder {
 		self
 	}
 
-	// pub(crate) fn with_auto_compounding_delegations(
-	// 	mut self,
-	// 	delegations: Vec<(AccountId, AccountId, Balance, Percent)>,
-	// ) -> Self {
-	// 	self.delegations = delegations;
-	// 	self
-	// }
+	pub(crate) fn with_auto_compounding_delegations(
+		mut self,
+		delegations: Vec<(AccountId, AccountId, Balance, Percent)>,
+	) -> Self {
+		self.delegations = delegations;
+		self
+	}
 
 	#[allow(dead_code)]
 	pub(crate) fn with_inflation(mut self, inflation: InflationInfo<Balance>) -> Self {
@@ -287,6 +423,7 @@ impl ExtBuilder {
 		pallet_balances::GenesisConfig::<Test> { balances: self.balances }
 			.assimilate_storage(&mut t)
 			.expect("Pallet balances storage can be assimilated");
+		let candidates = self.collators.iter().map(|(c, _)| *c).collect::<Vec<_>>();
 		pallet_parachain_staking::GenesisConfig::<Test> {
 			candidates: self.collators,
 			delegations: self.delegations,
@@ -299,7 +436,18 @@ impl ExtBuilder {
 		.expect("Parachain Staking's storage can be assimilated");
 
 		let mut ext = sp_io::TestExternalities::new(t);
-		ext.execute_with(|| System::set_block_number(1));
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			// genesis candidates are selected, which requires session keys
+			for candidate in candidates {
+				Session::set_keys(
+					RuntimeOrigin::signed(candidate),
+					UintAuthorityId(candidate).into(),
+					vec![],
+				)
+				.expect("genesis candidates are endowed");
+			}
+		});
 		ext
 	}
 }
@@ -311,6 +459,7 @@ pub(crate) fn roll_one_block() -> BlockNumber {
 	System::set_block_number(System::block_number() + 1);
 	System::on_initialize(System::block_number());
 	Balances::on_initialize(System::block_number());
+	Session::on_initialize(System::block_number());
 	ParachainStaking::on_initialize(System::block_number());
 	System::block_number()
 }
@@ -334,6 +483,12 @@ pub(crate) fn roll_to_round_begin(round: BlockNumber) -> BlockNumber {
 	roll_to(block)
 }
 
+/// Rotates the session, which is when the staking round advances. Returns the new round.
+pub(crate) fn start_next_round() -> u32 {
+	Session::rotate_session();
+	ParachainStaking::round().current
+}
+
 /// Rolls block-by-block to the end of the specified round.
 /// The block following will be the one in which the specified round change occurs.
 pub(crate) fn roll_to_round_end(round: BlockNumber) -> BlockNumber {
@@ -473,15 +628,6 @@ pub(crate) fn set_author(round: BlockNumber, acc: u64, pts: u32) {
 }
 
 /// fn to query the lock amount
-pub(crate) fn query_lock_amount(account_id: u64, id: LockIdentifier) -> Option<Balance> {
-	for lock in Balances::locks(&account_id) {
-		if lock.id == id {
-			return Some(lock.amount)
-		}
-	}
-	None
-}
-
 #[test]
 fn geneses() {
 	ExtBuilder::default()
@@ -503,29 +649,29 @@ fn geneses() {
 			assert!(System::events().is_empty());
 			// collators
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 500);
-			assert_eq!(query_lock_amount(1, COLLATOR_LOCK_ID), Some(500));
+			assert_eq!(Balances::reserved_balance(1), 500);
 			assert!(ParachainStaking::is_candidate(&1));
-			assert_eq!(query_lock_amount(2, COLLATOR_LOCK_ID), Some(200));
+			assert_eq!(Balances::reserved_balance(2), 200);
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&2), 100);
 			assert!(ParachainStaking::is_candidate(&2));
 			// delegators
 			for x in 3..7 {
 				assert!(ParachainStaking::is_delegator(&x));
 				assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&x), 0);
-				assert_eq!(query_lock_amount(x, DELEGATOR_LOCK_ID), Some(100));
+				assert_eq!(Balances::reserved_balance(x), 100);
 			}
 			// uninvolved
 			for x in 7..10 {
 				assert!(!ParachainStaking::is_delegator(&x));
 			}
-			// no delegator staking locks
-			assert_eq!(query_lock_amount(7, DELEGATOR_LOCK_ID), None);
+			// nothing held for delegator staking
+			assert_eq!(Balances::reserved_balance(7), 0);
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&7), 100);
-			assert_eq!(query_lock_amount(8, DELEGATOR_LOCK_ID), None);
+			assert_eq!(Balances::reserved_balance(8), 0);
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&8), 9);
-			assert_eq!(query_lock_amount(9, DELEGATOR_LOCK_ID), None);
+			assert_eq!(Balances::reserved_balance(9), 0);
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&9), 4);
-			// no collator staking locks
+			// nothing held for collator staking
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&7), 100);
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&8), 9);
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&9), 4);
@@ -551,16 +697,16 @@ fn geneses() {
 			// collators
 			for x in 1..5 {
 				assert!(ParachainStaking::is_candidate(&x));
-				assert_eq!(query_lock_amount(x, COLLATOR_LOCK_ID), Some(20));
+				assert_eq!(Balances::reserved_balance(x), 20);
 				assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&x), 80);
 			}
 			assert!(ParachainStaking::is_candidate(&5));
-			assert_eq!(query_lock_amount(5, COLLATOR_LOCK_ID), Some(10));
+			assert_eq!(Balances::reserved_balance(5), 10);
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&5), 90);
 			// delegators
 			for x in 6..11 {
 				assert!(ParachainStaking::is_delegator(&x));
-				assert_eq!(query_lock_amount(x, DELEGATOR_LOCK_ID), Some(10));
+				assert_eq!(Balances::reserved_balance(x), 10);
 				assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&x), 90);
 			}
 		});
@@ -0,0 +1,110 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Restoring staking state exported from another chain, e.g. when relaunching the chain
+
+use crate::{
+	auto_compound::AutoCompoundDelegations,
+	pallet::{
+		BalanceOf, CandidateInfo, CandidatePoolCount, Config, DelegationScheduledRequests,
+		DelegatorState, Pallet, Round,
+	},
+	types::StakingSnapshot,
+	DelegationAction,
+};
+use sp_runtime::traits::{Saturating, Zero};
+
+impl<T: Config> Pallet<T> {
+	/// Replays `snapshot` through the regular staking calls on behalf of its accounts, which must
+	/// hold the funds to bond. Entries that fail, e.g. for lack of funds, are logged and skipped.
+	/// Returns how many candidates, delegations and requests were restored.
+	pub(crate) fn restore_staking_state(
+		snapshot: StakingSnapshot<T::AccountId, BalanceOf<T>>,
+	) -> (u32, u32, u32) {
+		let (mut candidates, mut delegations, mut requests) = (0u32, 0u32, 0u32);
+		for (candidate, bond) in snapshot.candidates {
+			let candidate_count = <CandidatePoolCount<T>>::get();
+			if let Err(error) = Self::candidate_join(candidate, bond, candidate_count) {
+				log::warn!("Restoring candidate failed with error {:?}", error);
+			} else {
+				candidates = candidates.saturating_add(1);
+			}
+		}
+
+		for (delegator, candidate, amount, auto_compound, every) in snapshot.delegations {
+			let candidate_delegation_count =
+				<CandidateInfo<T>>::get(&candidate).map_or(0, |info| info.delegation_count);
+			let delegation_count =
+				<DelegatorState<T>>::get(&delegator).map_or(0, |state| state.delegations.0.len())
+					as u32;
+			let auto_compounding_count = <AutoCompoundDelegations<T>>::get_storage(&candidate).len();
+			let restored = <AutoCompoundDelegations<T>>::delegate_with_auto_compound(
+				candidate.clone(),
+				delegator.clone(),
+				amount,
+				auto_compound,
+				candidate_delegation_count,
+				auto_compounding_count,
+				delegation_count,
+			)
+			.and_then(|_| {
+				if auto_compound.is_zero() || every <= 1 {
+					return Ok(().into())
+				}
+				<AutoCompoundDelegations<T>>::set_auto_compound_every(
+					candidate,
+					delegator,
+					every,
+					auto_compounding_count.saturating_add(1),
+				)
+			});
+			if let Err(error) = restored {
+				log::warn!("Restoring delegation failed with error {:?}", error);
+			} else {
+				delegations = delegations.saturating_add(1);
+			}
+		}
+
+		let now = <Round<T>>::get().current;
+		for request in snapshot.requests {
+			let scheduled = match request.action {
+				DelegationAction::Revoke(_) => Self::delegation_schedule_revoke(
+					request.candidate.clone(),
+					request.delegator.clone(),
+				),
+				DelegationAction::Decrease(amount) => Self::delegation_schedule_bond_decrease(
+					request.candidate.clone(),
+					request.delegator.clone(),
+					amount,
+				),
+			};
+			if let Err(error) = scheduled {
+				log::warn!("Restoring delegation request failed with error {:?}", error);
+				continue
+			}
+			// the request keeps the rounds it had left rather than the full delay
+			<DelegationScheduledRequests<T>>::mutate(&request.candidate, |scheduled| {
+				if let Some(scheduled) =
+					scheduled.iter_mut().find(|scheduled| scheduled.delegator == request.delegator)
+				{
+					scheduled.when_executable = now.saturating_add(request.rounds_left);
+				}
+			});
+			requests = requests.saturating_add(1);
+		}
+		(candidates, delegations, requests)
+	}
+}
@@ -0,0 +1,105 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! [StakingInterface] implementation for the pallet
+
+use crate::{
+	auto_compound::AutoCompoundDelegations,
+	pallet::{BalanceOf, CandidateInfo, Config, DelegatorState, Pallet, Round, Total},
+	traits::StakingInterface,
+	types::RoundInfo,
+};
+use frame_support::{dispatch::DispatchResultWithPostInfo, traits::Get};
+use sp_runtime::{traits::Zero, DispatchResult, Percent};
+
+/// Drops the post dispatch info, which only matters to extrinsics
+fn into_dispatch_result(result: DispatchResultWithPostInfo) -> DispatchResult {
+	result.map(|_| ()).map_err(|e| e.error)
+}
+
+impl<T: Config> StakingInterface for Pallet<T> {
+	type AccountId = T::AccountId;
+	type Balance = BalanceOf<T>;
+	type BlockNumber = T::BlockNumber;
+
+	fn current_round() -> RoundInfo<T::BlockNumber> {
+		<Round<T>>::get()
+	}
+
+	fn min_delegation() -> BalanceOf<T> {
+		T::MinDelegation::get()
+	}
+
+	fn total_stake() -> BalanceOf<T> {
+		<Total<T>>::get()
+	}
+
+	fn is_candidate(who: &T::AccountId) -> bool {
+		Self::is_candidate(who)
+	}
+
+	fn is_delegator(who: &T::AccountId) -> bool {
+		Self::is_delegator(who)
+	}
+
+	fn candidate_stake(candidate: &T::AccountId) -> Option<BalanceOf<T>> {
+		<CandidateInfo<T>>::get(candidate).map(|info| info.total_counted)
+	}
+
+	fn delegation_stake(
+		delegator: &T::AccountId,
+		candidate: &T::AccountId,
+	) -> Option<BalanceOf<T>> {
+		<DelegatorState<T>>::get(delegator)?.get_bond_amount(candidate)
+	}
+
+	fn delegate(
+		delegator: T::AccountId,
+		candidate: T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let delegation_count = match <DelegatorState<T>>::get(&delegator) {
+			Some(state) if state.get_bond_amount(&candidate).is_some() =>
+				return into_dispatch_result(Self::delegation_bond_more(
+					delegator, candidate, amount,
+				)),
+			Some(state) => state.delegations.0.len() as u32,
+			None => 0,
+		};
+		// the weight hints are only needed by extrinsics, so they are taken from current state
+		let candidate_delegation_count =
+			<CandidateInfo<T>>::get(&candidate).map_or(0, |info| info.delegation_count);
+		let candidate_auto_compounding_delegation_count =
+			<AutoCompoundDelegations<T>>::get_storage(&candidate).len();
+		into_dispatch_result(<AutoCompoundDelegations<T>>::delegate_with_auto_compound(
+			candidate,
+			delegator,
+			amount,
+			Percent::zero(),
+			candidate_delegation_count,
+			candidate_auto_compounding_delegation_count,
+			delegation_count,
+		))
+	}
+
+	fn undelegate(delegator: T::AccountId, candidate: T::AccountId) -> DispatchResult {
+		into_dispatch_result(Self::delegation_schedule_revoke(candidate, delegator))
+	}
+
+	fn withdraw_unbonded(delegator: T::AccountId, candidate: T::AccountId) -> DispatchResult {
+		into_dispatch_result(Self::delegation_execute_scheduled_request(candidate, delegator))
+	}
+}
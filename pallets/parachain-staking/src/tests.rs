@@ -21,22 +21,32 @@
 //! 2. Monetary Governance
 //! 3. Public (Collator, Nominator)
 //! 4. Miscellaneous Property-Based Tests
+//! 5. Features, in the order they were added
 use crate::{
 	assert_eq_events, assert_eq_last_events, assert_event_emitted, assert_last_event,
 	assert_tail_eq,
 	auto_compound::{AutoCompoundConfig, AutoCompoundDelegations},
 	delegation_requests::{CancelledScheduledRequest, DelegationAction, ScheduledRequest},
 	mock::{
-		roll_one_block, roll_to, roll_to_round_begin, roll_to_round_end, set_author, Balances,
-		BlockNumber, Event as MetaEvent, ExtBuilder, Origin, ParachainStaking, Test,
+		events, last_event, roll_one_block, roll_to, roll_to_round_begin, roll_to_round_end,
+		set_author, start_next_round, AccountId, Balance, Balances, BlockNumber,
+		CircuitBreakerTrips, DeferredDelegationThreshold, DelegationBondLessDelay,
+		ElectionLookahead, ExtBuilder, Kicked, LeaveCancellationCutoff, MaxBonusPointsPerBlock,
+		MaxBottomDelegationsPerCandidate, MaxExposurePageSize, MaxOfflineRounds,
+		MaxTopDelegationsPerCandidate, MaxTotalDropPerBlock, MaxTotalIssuance, NewRounds,
+		ParachainStaking, PendingRequestRewardPolicy, RelayBlockNumber, RemitFails, Remitted,
+		RequestExpiryRounds, RevokeDelegationDelay, RewardPaymentDelay, RuntimeEvent,
+		RuntimeOrigin, SlashDeferDuration, System, Test, UseSqrtExposure, Vesting,
 	},
-	set::OrderedSet,
-	AtStake, Bond, BottomDelegations, CandidateInfo, CandidateMetadata, CandidatePool,
-	CapacityStatus, CollatorStatus, DelegationScheduledRequests, Delegations, DelegatorAdded,
-	DelegatorState, DelegatorStatus, Error, Event, Range, TopDelegations, DELEGATOR_LOCK_ID,
+	AtStake, Bond, CollatorStatus, DelegationScheduledRequests, DelegatorAdded, Error, Event,
+	Range, UnappliedSlash,
 };
-use frame_support::{assert_noop, assert_ok};
-use sp_runtime::{traits::Zero, DispatchError, ModuleError, Perbill, Percent};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnIdle, OnInitialize},
+	weights::Weight,
+};
+use sp_runtime::{testing::UintAuthorityId, traits::Zero, Perbill, Percent};
 
 // ~~ ROOT ~~
 
@@ -44,15 +54,18 @@ use sp_runtime::{traits::Zero, DispatchError, ModuleError, Perbill, Percent};
 fn invalid_root_origin_fails() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::set_total_selected(Origin::signed(45), 6u32),
+			ParachainStaking::set_total_selected(RuntimeOrigin::signed(45), 6u32),
 			sp_runtime::DispatchError::BadOrigin
 		);
 		assert_noop!(
-			ParachainStaking::set_collator_commission(Origin::signed(45), Perbill::from_percent(5)),
+			ParachainStaking::set_collator_commission(
+				RuntimeOrigin::signed(45),
+				Perbill::from_percent(5)
+			),
 			sp_runtime::DispatchError::BadOrigin
 		);
 		assert_noop!(
-			ParachainStaking::set_blocks_per_round(Origin::signed(45), 3u32),
+			ParachainStaking::set_blocks_per_round(RuntimeOrigin::signed(45), 3u32),
 			sp_runtime::DispatchError::BadOrigin
 		);
 	});
@@ -64,9 +77,9 @@ fn invalid_root_origin_fails() {
 fn set_total_selected_event_emits_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		// before we can bump total_selected we must bump the blocks per round
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 6u32));
-		assert_ok!(ParachainStaking::set_total_selected(Origin::root(), 6u32));
-		assert_last_event!(MetaEvent::ParachainStaking(Event::TotalSelectedSet {
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 6u32));
+		assert_ok!(ParachainStaking::set_total_selected(RuntimeOrigin::root(), 6u32));
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::TotalSelectedSet {
 			old: 5u32,
 			new: 6u32
 		}));
@@ -78,7 +91,7 @@ fn set_total_selected_fails_if_above_blocks_per_round() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(ParachainStaking::round().length, 5); // test relies on this
 		assert_noop!(
-			ParachainStaking::set_total_selected(Origin::root(), 6u32),
+			ParachainStaking::set_total_selected(RuntimeOrigin::root(), 6u32),
 			Error::<Test>::RoundLengthMustBeAtLeastTotalSelectedCollators,
 		);
 	});
@@ -87,26 +100,26 @@ fn set_total_selected_fails_if_above_blocks_per_round() {
 #[test]
 fn set_total_selected_passes_if_equal_to_blocks_per_round() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 10u32));
-		assert_ok!(ParachainStaking::set_total_selected(Origin::root(), 10u32));
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 10u32));
+		assert_ok!(ParachainStaking::set_total_selected(RuntimeOrigin::root(), 10u32));
 	});
 }
 
 #[test]
 fn set_total_selected_passes_if_below_blocks_per_round() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 10u32));
-		assert_ok!(ParachainStaking::set_total_selected(Origin::root(), 9u32));
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 10u32));
+		assert_ok!(ParachainStaking::set_total_selected(RuntimeOrigin::root(), 9u32));
 	});
 }
 
 #[test]
 fn set_blocks_per_round_fails_if_below_total_selected() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 20u32));
-		assert_ok!(ParachainStaking::set_total_selected(Origin::root(), 15u32));
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 20u32));
+		assert_ok!(ParachainStaking::set_total_selected(RuntimeOrigin::root(), 15u32));
 		assert_noop!(
-			ParachainStaking::set_blocks_per_round(Origin::root(), 14u32),
+			ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 14u32),
 			Error::<Test>::RoundLengthMustBeAtLeastTotalSelectedCollators,
 		);
 	});
@@ -115,9 +128,9 @@ fn set_blocks_per_round_fails_if_below_total_selected() {
 #[test]
 fn set_blocks_per_round_passes_if_equal_to_total_selected() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 10u32));
-		assert_ok!(ParachainStaking::set_total_selected(Origin::root(), 9u32));
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 9u32));
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 10u32));
+		assert_ok!(ParachainStaking::set_total_selected(RuntimeOrigin::root(), 9u32));
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 9u32));
 	});
 }
 
@@ -125,7 +138,7 @@ fn set_blocks_per_round_passes_if_equal_to_total_selected() {
 fn set_blocks_per_round_passes_if_above_total_selected() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(ParachainStaking::round().length, 5); // test relies on this
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 6u32));
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 6u32));
 	});
 }
 
@@ -133,10 +146,10 @@ fn set_blocks_per_round_passes_if_above_total_selected() {
 fn set_total_selected_storage_updates_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		// round length must be >= total_selected, so update that first
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 10u32));
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 10u32));
 
 		assert_eq!(ParachainStaking::total_selected(), 5u32);
-		assert_ok!(ParachainStaking::set_total_selected(Origin::root(), 6u32));
+		assert_ok!(ParachainStaking::set_total_selected(RuntimeOrigin::root(), 6u32));
 		assert_eq!(ParachainStaking::total_selected(), 6u32);
 	});
 }
@@ -145,7 +158,7 @@ fn set_total_selected_storage_updates_correctly() {
 fn cannot_set_total_selected_to_current_total_selected() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::set_total_selected(Origin::root(), 5u32),
+			ParachainStaking::set_total_selected(RuntimeOrigin::root(), 5u32),
 			Error::<Test>::NoWritingSameValue
 		);
 	});
@@ -155,7 +168,7 @@ fn cannot_set_total_selected_to_current_total_selected() {
 fn cannot_set_total_selected_below_module_min() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::set_total_selected(Origin::root(), 4u32),
+			ParachainStaking::set_total_selected(RuntimeOrigin::root(), 4u32),
 			Error::<Test>::CannotSetBelowMin
 		);
 	});
@@ -167,10 +180,10 @@ fn cannot_set_total_selected_below_module_min() {
 fn set_collator_commission_event_emits_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(ParachainStaking::set_collator_commission(
-			Origin::root(),
+			RuntimeOrigin::root(),
 			Perbill::from_percent(5)
 		));
-		assert_last_event!(MetaEvent::ParachainStaking(Event::CollatorCommissionSet {
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::CollatorCommissionSet {
 			old: Perbill::from_percent(20),
 			new: Perbill::from_percent(5),
 		}));
@@ -182,7 +195,7 @@ fn set_collator_commission_storage_updates_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(ParachainStaking::collator_commission(), Perbill::from_percent(20));
 		assert_ok!(ParachainStaking::set_collator_commission(
-			Origin::root(),
+			RuntimeOrigin::root(),
 			Perbill::from_percent(5)
 		));
 		assert_eq!(ParachainStaking::collator_commission(), Perbill::from_percent(5));
@@ -193,7 +206,42 @@ fn set_collator_commission_storage_updates_correctly() {
 fn cannot_set_collator_commission_to_current_collator_commission() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::set_collator_commission(Origin::root(), Perbill::from_percent(20)),
+			ParachainStaking::set_collator_commission(
+				RuntimeOrigin::root(),
+				Perbill::from_percent(20)
+			),
+			Error::<Test>::NoWritingSameValue
+		);
+	});
+}
+
+// SET COLLATOR PAYOUT TREASURY CUT
+
+#[test]
+fn set_collator_payout_treasury_cut_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ParachainStaking::set_collator_payout_treasury_cut(
+				RuntimeOrigin::signed(45),
+				Percent::from_percent(10)
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_eq!(ParachainStaking::collator_payout_treasury_cut(), Percent::zero());
+		assert_ok!(ParachainStaking::set_collator_payout_treasury_cut(
+			RuntimeOrigin::root(),
+			Percent::from_percent(10)
+		));
+		assert_eq!(ParachainStaking::collator_payout_treasury_cut(), Percent::from_percent(10));
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::CollatorPayoutTreasuryCutSet {
+			old: Percent::zero(),
+			new: Percent::from_percent(10),
+		}));
+		assert_noop!(
+			ParachainStaking::set_collator_payout_treasury_cut(
+				RuntimeOrigin::root(),
+				Percent::from_percent(10)
+			),
 			Error::<Test>::NoWritingSameValue
 		);
 	});
@@ -204,8 +252,8 @@ fn cannot_set_collator_commission_to_current_collator_commission() {
 #[test]
 fn set_blocks_per_round_event_emits_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 6u32));
-		assert_last_event!(MetaEvent::ParachainStaking(Event::BlocksPerRoundSet {
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 6u32));
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::BlocksPerRoundSet {
 			current_round: 1,
 			first_block: 0,
 			old: 5,
@@ -221,7 +269,7 @@ fn set_blocks_per_round_event_emits_correctly() {
 fn set_blocks_per_round_storage_updates_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(ParachainStaking::round().length, 5);
-		assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 6u32));
+		assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 6u32));
 		assert_eq!(ParachainStaking::round().length, 6);
 	});
 }
@@ -230,7 +278,7 @@ fn set_blocks_per_round_storage_updates_correctly() {
 fn cannot_set_blocks_per_round_below_module_min() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::set_blocks_per_round(Origin::root(), 2u32),
+			ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 2u32),
 			Error::<Test>::CannotSetBelowMin
 		);
 	});
@@ -240,7 +288,7 @@ fn cannot_set_blocks_per_round_below_module_min() {
 fn cannot_set_blocks_per_round_to_current_blocks_per_round() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::set_blocks_per_round(Origin::root(), 5u32),
+			ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 5u32),
 			Error::<Test>::NoWritingSameValue
 		);
 	});
@@ -256,18 +304,18 @@ fn round_immediately_jumps_if_current_duration_exceeds_new_blocks_per_round() {
 			// we can't lower the blocks per round because it must be above the number of collators,
 			// and we can't lower the number of collators because it must be above
 			// MinSelectedCandidates. so we first raise blocks per round, then lower it.
-			assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 10u32));
+			assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 10u32));
 
 			roll_to(17);
-			assert_last_event!(MetaEvent::ParachainStaking(Event::NewRound {
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::NewRound {
 				starting_block: 10,
 				round: 2,
 				selected_collators_number: 1,
 				total_balance: 20
 			}));
-			assert_ok!(ParachainStaking::set_blocks_per_round(Origin::root(), 5u32));
+			assert_ok!(ParachainStaking::set_blocks_per_round(RuntimeOrigin::root(), 5u32));
 			roll_to(18);
-			assert_last_event!(MetaEvent::ParachainStaking(Event::NewRound {
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::NewRound {
 				starting_block: 18,
 				round: 3,
 				selected_collators_number: 1,
@@ -283,14 +331,14 @@ fn invalid_monetary_origin_fails() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
 			ParachainStaking::set_staking_expectations(
-				Origin::signed(45),
+				RuntimeOrigin::signed(45),
 				Range { min: 3u32.into(), ideal: 4u32.into(), max: 5u32.into() }
 			),
 			sp_runtime::DispatchError::BadOrigin
 		);
 		assert_noop!(
 			ParachainStaking::set_inflation(
-				Origin::signed(45),
+				RuntimeOrigin::signed(45),
 				Range {
 					min: Perbill::from_percent(3),
 					ideal: Perbill::from_percent(4),
@@ -301,7 +349,7 @@ fn invalid_monetary_origin_fails() {
 		);
 		assert_noop!(
 			ParachainStaking::set_inflation(
-				Origin::signed(45),
+				RuntimeOrigin::signed(45),
 				Range {
 					min: Perbill::from_percent(3),
 					ideal: Perbill::from_percent(4),
@@ -311,12 +359,12 @@ fn invalid_monetary_origin_fails() {
 			sp_runtime::DispatchError::BadOrigin
 		);
 		assert_noop!(
-			ParachainStaking::set_parachain_bond_account(Origin::signed(45), 11),
+			ParachainStaking::set_parachain_bond_account(RuntimeOrigin::signed(45), 11),
 			sp_runtime::DispatchError::BadOrigin
 		);
 		assert_noop!(
 			ParachainStaking::set_parachain_bond_reserve_percent(
-				Origin::signed(45),
+				RuntimeOrigin::signed(45),
 				Percent::from_percent(2)
 			),
 			sp_runtime::DispatchError::BadOrigin
@@ -331,10 +379,10 @@ fn set_staking_event_emits_event_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		// valid call succeeds
 		assert_ok!(ParachainStaking::set_staking_expectations(
-			Origin::root(),
+			RuntimeOrigin::root(),
 			Range { min: 3u128, ideal: 4u128, max: 5u128 }
 		));
-		assert_last_event!(MetaEvent::ParachainStaking(Event::StakeExpectationsSet {
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::StakeExpectationsSet {
 			expect_min: 3u128,
 			expect_ideal: 4u128,
 			expect_max: 5u128,
@@ -350,7 +398,7 @@ fn set_staking_updates_storage_correctly() {
 			Range { min: 700, ideal: 700, max: 700 }
 		);
 		assert_ok!(ParachainStaking::set_staking_expectations(
-			Origin::root(),
+			RuntimeOrigin::root(),
 			Range { min: 3u128, ideal: 4u128, max: 5u128 }
 		));
 		assert_eq!(
@@ -366,7 +414,7 @@ fn cannot_set_invalid_staking_expectations() {
 		// invalid call fails
 		assert_noop!(
 			ParachainStaking::set_staking_expectations(
-				Origin::root(),
+				RuntimeOrigin::root(),
 				Range { min: 5u128, ideal: 4u128, max: 3u128 }
 			),
 			Error::<Test>::InvalidSchedule
@@ -378,12 +426,12 @@ fn cannot_set_invalid_staking_expectations() {
 fn cannot_set_same_staking_expectations() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(ParachainStaking::set_staking_expectations(
-			Origin::root(),
+			RuntimeOrigin::root(),
 			Range { min: 3u128, ideal: 4u128, max: 5u128 }
 		));
 		assert_noop!(
 			ParachainStaking::set_staking_expectations(
-				Origin::root(),
+				RuntimeOrigin::root(),
 				Range { min: 3u128, ideal: 4u128, max: 5u128 }
 			),
 			Error::<Test>::NoWritingSameValue
@@ -398,8 +446,11 @@ fn set_inflation_event_emits_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		let (min, ideal, max): (Perbill, Perbill, Perbill) =
 			(Perbill::from_percent(3), Perbill::from_percent(4), Perbill::from_percent(5));
-		assert_ok!(ParachainStaking::set_inflation(Origin::root(), Range { min, ideal, max }));
-		assert_last_event!(MetaEvent::ParachainStaking(Event::InflationSet {
+		assert_ok!(ParachainStaking::set_inflation(
+			RuntimeOrigin::root(),
+			Range { min, ideal, max }
+		));
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::InflationSet {
 			annual_min: min,
 			annual_ideal: ideal,
 			annual_max: max,
@@ -431,7 +482,10 @@ fn set_inflation_storage_updates_correctly() {
 				max: Perbill::from_percent(5)
 			}
 		);
-		assert_ok!(ParachainStaking::set_inflation(Origin::root(), Range { min, ideal, max }),);
+		assert_ok!(ParachainStaking::set_inflation(
+			RuntimeOrigin::root(),
+			Range { min, ideal, max }
+		),);
 		assert_eq!(ParachainStaking::inflation_config().annual, Range { min, ideal, max });
 		assert_eq!(
 			ParachainStaking::inflation_config().round,
@@ -449,7 +503,7 @@ fn cannot_set_invalid_inflation() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
 			ParachainStaking::set_inflation(
-				Origin::root(),
+				RuntimeOrigin::root(),
 				Range {
 					min: Perbill::from_percent(5),
 					ideal: Perbill::from_percent(4),
@@ -466,9 +520,12 @@ fn cannot_set_same_inflation() {
 	ExtBuilder::default().build().execute_with(|| {
 		let (min, ideal, max): (Perbill, Perbill, Perbill) =
 			(Perbill::from_percent(3), Perbill::from_percent(4), Perbill::from_percent(5));
-		assert_ok!(ParachainStaking::set_inflation(Origin::root(), Range { min, ideal, max }),);
+		assert_ok!(ParachainStaking::set_inflation(
+			RuntimeOrigin::root(),
+			Range { min, ideal, max }
+		),);
 		assert_noop!(
-			ParachainStaking::set_inflation(Origin::root(), Range { min, ideal, max }),
+			ParachainStaking::set_inflation(RuntimeOrigin::root(), Range { min, ideal, max }),
 			Error::<Test>::NoWritingSameValue
 		);
 	});
@@ -479,8 +536,8 @@ fn cannot_set_same_inflation() {
 #[test]
 fn set_parachain_bond_account_event_emits_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(ParachainStaking::set_parachain_bond_account(Origin::root(), 11));
-		assert_last_event!(MetaEvent::ParachainStaking(Event::ParachainBondAccountSet {
+		assert_ok!(ParachainStaking::set_parachain_bond_account(RuntimeOrigin::root(), 11));
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::ParachainBondAccountSet {
 			old: 0,
 			new: 11
 		}));
@@ -491,7 +548,7 @@ fn set_parachain_bond_account_event_emits_correctly() {
 fn set_parachain_bond_account_storage_updates_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(ParachainStaking::parachain_bond_info().account, 0);
-		assert_ok!(ParachainStaking::set_parachain_bond_account(Origin::root(), 11));
+		assert_ok!(ParachainStaking::set_parachain_bond_account(RuntimeOrigin::root(), 11));
 		assert_eq!(ParachainStaking::parachain_bond_info().account, 11);
 	});
 }
@@ -502,10 +559,10 @@ fn set_parachain_bond_account_storage_updates_correctly() {
 fn set_parachain_bond_reserve_percent_event_emits_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(ParachainStaking::set_parachain_bond_reserve_percent(
-			Origin::root(),
+			RuntimeOrigin::root(),
 			Percent::from_percent(50)
 		));
-		assert_last_event!(MetaEvent::ParachainStaking(Event::ParachainBondReservePercentSet {
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::ParachainBondReservePercentSet {
 			old: Percent::from_percent(30),
 			new: Percent::from_percent(50),
 		}));
@@ -517,7 +574,7 @@ fn set_parachain_bond_reserve_percent_storage_updates_correctly() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(ParachainStaking::parachain_bond_info().percent, Percent::from_percent(30));
 		assert_ok!(ParachainStaking::set_parachain_bond_reserve_percent(
-			Origin::root(),
+			RuntimeOrigin::root(),
 			Percent::from_percent(50)
 		));
 		assert_eq!(ParachainStaking::parachain_bond_info().percent, Percent::from_percent(50));
@@ -529,7 +586,7 @@ fn cannot_set_same_parachain_bond_reserve_percent() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
 			ParachainStaking::set_parachain_bond_reserve_percent(
-				Origin::root(),
+				RuntimeOrigin::root(),
 				Percent::from_percent(30)
 			),
 			Error::<Test>::NoWritingSameValue
@@ -544,8 +601,8 @@ fn cannot_set_same_parachain_bond_reserve_percent() {
 #[test]
 fn join_candidates_event_emits_correctly() {
 	ExtBuilder::default().with_balances(vec![(1, 10)]).build().execute_with(|| {
-		assert_ok!(ParachainStaking::join_candidates(Origin::signed(1), 10u128, 0u32));
-		assert_last_event!(MetaEvent::ParachainStaking(Event::JoinedCollatorCandidates {
+		assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 10u128, 0u32));
+		assert_last_event!(RuntimeEvent::ParachainStaking(Event::JoinedCollatorCandidates {
 			account: 1,
 			amount_locked: 10u128,
 			new_total_amt_locked: 10u128,
@@ -557,7 +614,7 @@ fn join_candidates_event_emits_correctly() {
 fn join_candidates_reserves_balance() {
 	ExtBuilder::default().with_balances(vec![(1, 10)]).build().execute_with(|| {
 		assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 10);
-		assert_ok!(ParachainStaking::join_candidates(Origin::signed(1), 10u128, 0u32));
+		assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 10u128, 0u32));
 		assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 0);
 	});
 }
@@ -566,7 +623,7 @@ fn join_candidates_reserves_balance() {
 fn join_candidates_increases_total_staked() {
 	ExtBuilder::default().with_balances(vec![(1, 10)]).build().execute_with(|| {
 		assert_eq!(ParachainStaking::total(), 0);
-		assert_ok!(ParachainStaking::join_candidates(Origin::signed(1), 10u128, 0u32));
+		assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 10u128, 0u32));
 		assert_eq!(ParachainStaking::total(), 10);
 	});
 }
@@ -575,7 +632,7 @@ fn join_candidates_increases_total_staked() {
 fn join_candidates_creates_candidate_state() {
 	ExtBuilder::default().with_balances(vec![(1, 10)]).build().execute_with(|| {
 		assert!(ParachainStaking::candidate_info(1).is_none());
-		assert_ok!(ParachainStaking::join_candidates(Origin::signed(1), 10u128, 0u32));
+		assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 10u128, 0u32));
 		let candidate_state = ParachainStaking::candidate_info(1).expect("just joined => exists");
 		assert_eq!(candidate_state.bond, 10u128);
 	});
@@ -584,11 +641,10 @@ fn join_candidates_creates_candidate_state() {
 #[test]
 fn join_candidates_adds_to_candidate_pool() {
 	ExtBuilder::default().with_balances(vec![(1, 10)]).build().execute_with(|| {
-		assert!(ParachainStaking::candidate_pool().0.is_empty());
-		assert_ok!(ParachainStaking::join_candidates(Origin::signed(1), 10u128, 0u32));
-		let candidate_pool = ParachainStaking::candidate_pool();
-		assert_eq!(candidate_pool.0[0].owner, 1);
-		assert_eq!(candidate_pool.0[0].amount, 10);
+		assert_eq!(ParachainStaking::candidate_pool_count(), 0);
+		assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 10u128, 0u32));
+		assert_eq!(ParachainStaking::candidate_pool_count(), 1);
+		assert_eq!(ParachainStaking::candidate_pool(1), Some(10));
 	});
 }
 
@@ -600,7 +656,7 @@ fn cannot_join_candidates_if_candidate() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::join_candidates(Origin::signed(1), 11u128, 100u32),
+				ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 11u128, 100u32),
 				Error::<Test>::CandidateExists
 			);
 		});
@@ -615,7 +671,7 @@ fn cannot_join_candidates_if_delegator() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::join_candidates(Origin::signed(2), 10u128, 1u32),
+				ParachainStaking::join_candidates(RuntimeOrigin::signed(2), 10u128, 1u32),
 				Error::<Test>::DelegatorExists
 			);
 		});
@@ -625,7 +681,7 @@ fn cannot_join_candidates_if_delegator() {
 fn cannot_join_candidates_without_min_bond() {
 	ExtBuilder::default().with_balances(vec![(1, 1000)]).build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::join_candidates(Origin::signed(1), 9u128, 100u32),
+			ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 9u128, 100u32),
 			Error::<Test>::CandidateBondBelowMin
 		);
 	});
@@ -635,12 +691,8 @@ fn cannot_join_candidates_without_min_bond() {
 fn cannot_join_candidates_with_more_than_available_balance() {
 	ExtBuilder::default().with_balances(vec![(1, 500)]).build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::join_candidates(Origin::signed(1), 501u128, 100u32),
-			DispatchError::Module(ModuleError {
-				index: 2,
-				error: [8, 0, 0, 0],
-				message: Some("InsufficientBalance")
-			})
+			ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 501u128, 100u32),
+			Error::<Test>::InsufficientBalance
 		);
 	});
 }
@@ -654,7 +706,7 @@ fn insufficient_join_candidates_weight_hint_fails() {
 		.execute_with(|| {
 			for i in 0..5 {
 				assert_noop!(
-					ParachainStaking::join_candidates(Origin::signed(6), 20, i),
+					ParachainStaking::join_candidates(RuntimeOrigin::signed(6), 20, i),
 					Error::<Test>::TooLowCandidateCountWeightHintJoinCandidates
 				);
 			}
@@ -680,7 +732,7 @@ fn sufficient_join_candidates_weight_hint_succeeds() {
 		.execute_with(|| {
 			let mut count = 5u32;
 			for i in 6..10 {
-				assert_ok!(ParachainStaking::join_candidates(Origin::signed(i), 20, count));
+				assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(i), 20, count));
 				count += 1u32;
 			}
 		});
@@ -695,8 +747,8 @@ fn leave_candidates_event_emits_correctly() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateScheduledExit {
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateScheduledExit {
 				exit_allowed_round: 1,
 				candidate: 1,
 				scheduled_exit: 3
@@ -711,9 +763,10 @@ fn leave_candidates_removes_candidate_from_candidate_pool() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_eq!(ParachainStaking::candidate_pool().0.len(), 1);
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
-			assert!(ParachainStaking::candidate_pool().0.is_empty());
+			assert_eq!(ParachainStaking::candidate_pool_count(), 1);
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
+			assert_eq!(ParachainStaking::candidate_pool_count(), 0);
+			assert_eq!(ParachainStaking::candidate_pool(1), None);
 		});
 }
 
@@ -721,7 +774,7 @@ fn leave_candidates_removes_candidate_from_candidate_pool() {
 fn cannot_leave_candidates_if_not_candidate() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32),
+			ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32),
 			Error::<Test>::CandidateDNE
 		);
 	});
@@ -734,9 +787,9 @@ fn cannot_leave_candidates_if_already_leaving_candidates() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			assert_noop!(
-				ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32),
+				ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32),
 				Error::<Test>::CandidateAlreadyLeaving
 			);
 		});
@@ -751,7 +804,7 @@ fn insufficient_leave_candidates_weight_hint_fails() {
 		.execute_with(|| {
 			for i in 1..6 {
 				assert_noop!(
-					ParachainStaking::schedule_leave_candidates(Origin::signed(i), 4u32),
+					ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(i), 4u32),
 					Error::<Test>::TooLowCandidateCountToLeaveCandidates
 				);
 			}
@@ -767,7 +820,10 @@ fn sufficient_leave_candidates_weight_hint_succeeds() {
 		.execute_with(|| {
 			let mut count = 5u32;
 			for i in 1..6 {
-				assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(i), count));
+				assert_ok!(ParachainStaking::schedule_leave_candidates(
+					RuntimeOrigin::signed(i),
+					count
+				));
 				count -= 1u32;
 			}
 		});
@@ -782,10 +838,10 @@ fn execute_leave_candidates_emits_event() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, 0));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateLeft {
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 0));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateLeft {
 				ex_candidate: 1,
 				unlocked_amount: 10,
 				new_total_amt_locked: 0
@@ -800,9 +856,9 @@ fn execute_leave_candidates_callable_by_any_signed() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(2), 1, 0));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(2), 1, 0));
 		});
 }
 
@@ -814,15 +870,15 @@ fn execute_leave_candidates_requires_correct_weight_hint() {
 		.with_delegations(vec![(2, 1, 10), (3, 1, 10), (4, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			roll_to(10);
 			for i in 0..3 {
 				assert_noop!(
-					ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, i),
+					ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, i),
 					Error::<Test>::TooLowCandidateDelegationCountToLeaveCandidates
 				);
 			}
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(2), 1, 3));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(2), 1, 3));
 		});
 }
 
@@ -834,9 +890,9 @@ fn execute_leave_candidates_unreserves_balance() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 0);
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, 0));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 0));
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 10);
 		});
 }
@@ -849,9 +905,9 @@ fn execute_leave_candidates_decreases_total_staked() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::total(), 10);
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, 0));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 0));
 			assert_eq!(ParachainStaking::total(), 0);
 		});
 }
@@ -863,13 +919,13 @@ fn execute_leave_candidates_removes_candidate_state() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			// candidate state is not immediately removed
 			let candidate_state =
 				ParachainStaking::candidate_info(1).expect("just left => still exists");
 			assert_eq!(candidate_state.bond, 10u128);
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, 0));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 0));
 			assert!(ParachainStaking::candidate_info(1).is_none());
 		});
 }
@@ -882,7 +938,11 @@ fn execute_leave_candidates_removes_pending_delegation_requests() {
 		.with_delegations(vec![(2, 1, 15)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			let state = ParachainStaking::delegation_scheduled_requests(&1);
 			assert_eq!(
 				state,
@@ -892,13 +952,13 @@ fn execute_leave_candidates_removes_pending_delegation_requests() {
 					action: DelegationAction::Decrease(5),
 				}],
 			);
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			// candidate state is not immediately removed
 			let candidate_state =
 				ParachainStaking::candidate_info(1).expect("just left => still exists");
 			assert_eq!(candidate_state.bond, 10u128);
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, 1));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 1));
 			assert!(ParachainStaking::candidate_info(1).is_none());
 			assert!(
 				!ParachainStaking::delegation_scheduled_requests(&1)
@@ -920,18 +980,18 @@ fn cannot_execute_leave_candidates_before_delay() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
 			assert_noop!(
-				ParachainStaking::execute_leave_candidates(Origin::signed(3), 1, 0),
+				ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(3), 1, 0),
 				Error::<Test>::CandidateCannotLeaveYet
 			);
 			roll_to(9);
 			assert_noop!(
-				ParachainStaking::execute_leave_candidates(Origin::signed(3), 1, 0),
+				ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(3), 1, 0),
 				Error::<Test>::CandidateCannotLeaveYet
 			);
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(3), 1, 0));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(3), 1, 0));
 		});
 }
 
@@ -944,9 +1004,9 @@ fn cancel_leave_candidates_emits_event() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
-			assert_ok!(ParachainStaking::cancel_leave_candidates(Origin::signed(1), 1));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CancelledCandidateExit {
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::cancel_leave_candidates(RuntimeOrigin::signed(1), 1));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CancelledCandidateExit {
 				candidate: 1
 			}));
 		});
@@ -959,8 +1019,8 @@ fn cancel_leave_candidates_updates_candidate_state() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
-			assert_ok!(ParachainStaking::cancel_leave_candidates(Origin::signed(1), 1));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::cancel_leave_candidates(RuntimeOrigin::signed(1), 1));
 			let candidate =
 				ParachainStaking::candidate_info(&1).expect("just cancelled leave so exists");
 			assert!(candidate.is_active());
@@ -974,10 +1034,9 @@ fn cancel_leave_candidates_adds_to_candidate_pool() {
 		.with_candidates(vec![(1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1u32));
-			assert_ok!(ParachainStaking::cancel_leave_candidates(Origin::signed(1), 1));
-			assert_eq!(ParachainStaking::candidate_pool().0[0].owner, 1);
-			assert_eq!(ParachainStaking::candidate_pool().0[0].amount, 10);
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1u32));
+			assert_ok!(ParachainStaking::cancel_leave_candidates(RuntimeOrigin::signed(1), 1));
+			assert_eq!(ParachainStaking::candidate_pool(1), Some(10));
 		});
 }
 
@@ -990,8 +1049,8 @@ fn go_offline_event_emits_correctly() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::go_offline(Origin::signed(1)));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateWentOffline {
+			assert_ok!(ParachainStaking::go_offline(RuntimeOrigin::signed(1)));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateWentOffline {
 				candidate: 1
 			}));
 		});
@@ -1004,9 +1063,10 @@ fn go_offline_removes_candidate_from_candidate_pool() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_eq!(ParachainStaking::candidate_pool().0.len(), 1);
-			assert_ok!(ParachainStaking::go_offline(Origin::signed(1)));
-			assert!(ParachainStaking::candidate_pool().0.is_empty());
+			assert_eq!(ParachainStaking::candidate_pool_count(), 1);
+			assert_ok!(ParachainStaking::go_offline(RuntimeOrigin::signed(1)));
+			assert_eq!(ParachainStaking::candidate_pool_count(), 0);
+			assert_eq!(ParachainStaking::candidate_pool(1), None);
 		});
 }
 
@@ -1019,7 +1079,7 @@ fn go_offline_updates_candidate_state_to_idle() {
 		.execute_with(|| {
 			let candidate_state = ParachainStaking::candidate_info(1).expect("is active candidate");
 			assert_eq!(candidate_state.status, CollatorStatus::Active);
-			assert_ok!(ParachainStaking::go_offline(Origin::signed(1)));
+			assert_ok!(ParachainStaking::go_offline(RuntimeOrigin::signed(1)));
 			let candidate_state =
 				ParachainStaking::candidate_info(1).expect("is candidate, just offline");
 			assert_eq!(candidate_state.status, CollatorStatus::Idle);
@@ -1029,7 +1089,10 @@ fn go_offline_updates_candidate_state_to_idle() {
 #[test]
 fn cannot_go_offline_if_not_candidate() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_noop!(ParachainStaking::go_offline(Origin::signed(3)), Error::<Test>::CandidateDNE);
+		assert_noop!(
+			ParachainStaking::go_offline(RuntimeOrigin::signed(3)),
+			Error::<Test>::CandidateDNE
+		);
 	});
 }
 
@@ -1040,9 +1103,9 @@ fn cannot_go_offline_if_already_offline() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::go_offline(Origin::signed(1)));
+			assert_ok!(ParachainStaking::go_offline(RuntimeOrigin::signed(1)));
 			assert_noop!(
-				ParachainStaking::go_offline(Origin::signed(1)),
+				ParachainStaking::go_offline(RuntimeOrigin::signed(1)),
 				Error::<Test>::AlreadyOffline
 			);
 		});
@@ -1057,9 +1120,9 @@ fn go_online_event_emits_correctly() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::go_offline(Origin::signed(1)));
-			assert_ok!(ParachainStaking::go_online(Origin::signed(1)));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateBackOnline {
+			assert_ok!(ParachainStaking::go_offline(RuntimeOrigin::signed(1)));
+			assert_ok!(ParachainStaking::go_online(RuntimeOrigin::signed(1)));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateBackOnline {
 				candidate: 1
 			}));
 		});
@@ -1072,11 +1135,10 @@ fn go_online_adds_to_candidate_pool() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::go_offline(Origin::signed(1)));
-			assert!(ParachainStaking::candidate_pool().0.is_empty());
-			assert_ok!(ParachainStaking::go_online(Origin::signed(1)));
-			assert_eq!(ParachainStaking::candidate_pool().0[0].owner, 1);
-			assert_eq!(ParachainStaking::candidate_pool().0[0].amount, 20);
+			assert_ok!(ParachainStaking::go_offline(RuntimeOrigin::signed(1)));
+			assert_eq!(ParachainStaking::candidate_pool(1), None);
+			assert_ok!(ParachainStaking::go_online(RuntimeOrigin::signed(1)));
+			assert_eq!(ParachainStaking::candidate_pool(1), Some(20));
 		});
 }
 
@@ -1087,11 +1149,11 @@ fn go_online_storage_updates_candidate_state() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::go_offline(Origin::signed(1)));
+			assert_ok!(ParachainStaking::go_offline(RuntimeOrigin::signed(1)));
 			let candidate_state =
 				ParachainStaking::candidate_info(1).expect("offline still exists");
 			assert_eq!(candidate_state.status, CollatorStatus::Idle);
-			assert_ok!(ParachainStaking::go_online(Origin::signed(1)));
+			assert_ok!(ParachainStaking::go_online(RuntimeOrigin::signed(1)));
 			let candidate_state = ParachainStaking::candidate_info(1).expect("online so exists");
 			assert_eq!(candidate_state.status, CollatorStatus::Active);
 		});
@@ -1100,7 +1162,10 @@ fn go_online_storage_updates_candidate_state() {
 #[test]
 fn cannot_go_online_if_not_candidate() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_noop!(ParachainStaking::go_online(Origin::signed(3)), Error::<Test>::CandidateDNE);
+		assert_noop!(
+			ParachainStaking::go_online(RuntimeOrigin::signed(3)),
+			Error::<Test>::CandidateDNE
+		);
 	});
 }
 
@@ -1112,7 +1177,7 @@ fn cannot_go_online_if_already_online() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::go_online(Origin::signed(1)),
+				ParachainStaking::go_online(RuntimeOrigin::signed(1)),
 				Error::<Test>::AlreadyActive
 			);
 		});
@@ -1125,9 +1190,9 @@ fn cannot_go_online_if_leaving() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1));
 			assert_noop!(
-				ParachainStaking::go_online(Origin::signed(1)),
+				ParachainStaking::go_online(RuntimeOrigin::signed(1)),
 				Error::<Test>::CannotGoOnlineIfLeaving
 			);
 		});
@@ -1142,8 +1207,8 @@ fn candidate_bond_more_emits_correct_event() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::candidate_bond_more(Origin::signed(1), 30));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateBondedMore {
+			assert_ok!(ParachainStaking::candidate_bond_more(RuntimeOrigin::signed(1), 30));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateBondedMore {
 				candidate: 1,
 				amount: 30,
 				new_total_bond: 50
@@ -1159,7 +1224,7 @@ fn candidate_bond_more_reserves_balance() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 30);
-			assert_ok!(ParachainStaking::candidate_bond_more(Origin::signed(1), 30));
+			assert_ok!(ParachainStaking::candidate_bond_more(RuntimeOrigin::signed(1), 30));
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 0);
 		});
 }
@@ -1172,7 +1237,7 @@ fn candidate_bond_more_increases_total() {
 		.build()
 		.execute_with(|| {
 			let mut total = ParachainStaking::total();
-			assert_ok!(ParachainStaking::candidate_bond_more(Origin::signed(1), 30));
+			assert_ok!(ParachainStaking::candidate_bond_more(RuntimeOrigin::signed(1), 30));
 			total += 30;
 			assert_eq!(ParachainStaking::total(), total);
 		});
@@ -1187,7 +1252,7 @@ fn candidate_bond_more_updates_candidate_state() {
 		.execute_with(|| {
 			let candidate_state = ParachainStaking::candidate_info(1).expect("updated => exists");
 			assert_eq!(candidate_state.bond, 20);
-			assert_ok!(ParachainStaking::candidate_bond_more(Origin::signed(1), 30));
+			assert_ok!(ParachainStaking::candidate_bond_more(RuntimeOrigin::signed(1), 30));
 			let candidate_state = ParachainStaking::candidate_info(1).expect("updated => exists");
 			assert_eq!(candidate_state.bond, 50);
 		});
@@ -1200,11 +1265,9 @@ fn candidate_bond_more_updates_candidate_pool() {
 		.with_candidates(vec![(1, 20)])
 		.build()
 		.execute_with(|| {
-			assert_eq!(ParachainStaking::candidate_pool().0[0].owner, 1);
-			assert_eq!(ParachainStaking::candidate_pool().0[0].amount, 20);
-			assert_ok!(ParachainStaking::candidate_bond_more(Origin::signed(1), 30));
-			assert_eq!(ParachainStaking::candidate_pool().0[0].owner, 1);
-			assert_eq!(ParachainStaking::candidate_pool().0[0].amount, 50);
+			assert_eq!(ParachainStaking::candidate_pool(1), Some(20));
+			assert_ok!(ParachainStaking::candidate_bond_more(RuntimeOrigin::signed(1), 30));
+			assert_eq!(ParachainStaking::candidate_pool(1), Some(50));
 		});
 }
 
@@ -1217,8 +1280,11 @@ fn schedule_candidate_bond_less_event_emits_correctly() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateBondLessRequested {
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateBondLessRequested {
 				candidate: 1,
 				amount_to_decrease: 10,
 				execute_round: 3,
@@ -1233,9 +1299,9 @@ fn cannot_schedule_candidate_bond_less_if_request_exists() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 5));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(RuntimeOrigin::signed(1), 5));
 			assert_noop!(
-				ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 5),
+				ParachainStaking::schedule_candidate_bond_less(RuntimeOrigin::signed(1), 5),
 				Error::<Test>::PendingCandidateRequestAlreadyExists
 			);
 		});
@@ -1245,7 +1311,7 @@ fn cannot_schedule_candidate_bond_less_if_request_exists() {
 fn cannot_schedule_candidate_bond_less_if_not_candidate() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::schedule_candidate_bond_less(Origin::signed(6), 50),
+			ParachainStaking::schedule_candidate_bond_less(RuntimeOrigin::signed(6), 50),
 			Error::<Test>::CandidateDNE
 		);
 	});
@@ -1259,7 +1325,7 @@ fn cannot_schedule_candidate_bond_less_if_new_total_below_min_candidate_stk() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 21),
+				ParachainStaking::schedule_candidate_bond_less(RuntimeOrigin::signed(1), 21),
 				Error::<Test>::CandidateBondBelowMin
 			);
 		});
@@ -1272,8 +1338,11 @@ fn can_schedule_candidate_bond_less_if_leaving_candidates() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1));
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
 		});
 }
 
@@ -1284,11 +1353,11 @@ fn cannot_schedule_candidate_bond_less_if_exited_candidates() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, 0));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 0));
 			assert_noop!(
-				ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10),
+				ParachainStaking::schedule_candidate_bond_less(RuntimeOrigin::signed(1), 10),
 				Error::<Test>::CandidateDNE
 			);
 		});
@@ -1303,10 +1372,13 @@ fn execute_candidate_bond_less_emits_correct_event() {
 		.with_candidates(vec![(1, 50)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 30));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				30
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_candidate_bond_less(Origin::signed(1), 1));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateBondedLess {
+			assert_ok!(ParachainStaking::execute_candidate_bond_less(RuntimeOrigin::signed(1), 1));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateBondedLess {
 				candidate: 1,
 				amount: 30,
 				new_bond: 20
@@ -1322,9 +1394,12 @@ fn execute_candidate_bond_less_unreserves_balance() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 0);
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_candidate_bond_less(Origin::signed(1), 1));
+			assert_ok!(ParachainStaking::execute_candidate_bond_less(RuntimeOrigin::signed(1), 1));
 			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 10);
 		});
 }
@@ -1337,9 +1412,12 @@ fn execute_candidate_bond_less_decreases_total() {
 		.build()
 		.execute_with(|| {
 			let mut total = ParachainStaking::total();
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_candidate_bond_less(Origin::signed(1), 1));
+			assert_ok!(ParachainStaking::execute_candidate_bond_less(RuntimeOrigin::signed(1), 1));
 			total -= 10;
 			assert_eq!(ParachainStaking::total(), total);
 		});
@@ -1354,9 +1432,12 @@ fn execute_candidate_bond_less_updates_candidate_state() {
 		.execute_with(|| {
 			let candidate_state = ParachainStaking::candidate_info(1).expect("updated => exists");
 			assert_eq!(candidate_state.bond, 30);
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_candidate_bond_less(Origin::signed(1), 1));
+			assert_ok!(ParachainStaking::execute_candidate_bond_less(RuntimeOrigin::signed(1), 1));
 			let candidate_state = ParachainStaking::candidate_info(1).expect("updated => exists");
 			assert_eq!(candidate_state.bond, 20);
 		});
@@ -1369,13 +1450,14 @@ fn execute_candidate_bond_less_updates_candidate_pool() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_eq!(ParachainStaking::candidate_pool().0[0].owner, 1);
-			assert_eq!(ParachainStaking::candidate_pool().0[0].amount, 30);
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
+			assert_eq!(ParachainStaking::candidate_pool(1), Some(30));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_candidate_bond_less(Origin::signed(1), 1));
-			assert_eq!(ParachainStaking::candidate_pool().0[0].owner, 1);
-			assert_eq!(ParachainStaking::candidate_pool().0[0].amount, 20);
+			assert_ok!(ParachainStaking::execute_candidate_bond_less(RuntimeOrigin::signed(1), 1));
+			assert_eq!(ParachainStaking::candidate_pool(1), Some(20));
 		});
 }
 
@@ -1388,9 +1470,12 @@ fn cancel_candidate_bond_less_emits_event() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
-			assert_ok!(ParachainStaking::cancel_candidate_bond_less(Origin::signed(1)));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CancelledCandidateBondLess {
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
+			assert_ok!(ParachainStaking::cancel_candidate_bond_less(RuntimeOrigin::signed(1)));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CancelledCandidateBondLess {
 				candidate: 1,
 				amount: 10,
 				execute_round: 3,
@@ -1405,8 +1490,11 @@ fn cancel_candidate_bond_less_updates_candidate_state() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
-			assert_ok!(ParachainStaking::cancel_candidate_bond_less(Origin::signed(1)));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
+			assert_ok!(ParachainStaking::cancel_candidate_bond_less(RuntimeOrigin::signed(1)));
 			assert!(ParachainStaking::candidate_info(&1).unwrap().request.is_none());
 		});
 }
@@ -1418,9 +1506,12 @@ fn only_candidate_can_cancel_candidate_bond_less_request() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_candidate_bond_less(Origin::signed(1), 10));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
 			assert_noop!(
-				ParachainStaking::cancel_candidate_bond_less(Origin::signed(2)),
+				ParachainStaking::cancel_candidate_bond_less(RuntimeOrigin::signed(2)),
 				Error::<Test>::CandidateDNE
 			);
 		});
@@ -1435,8 +1526,8 @@ fn delegate_event_emits_correctly() {
 		.with_candidates(vec![(1, 30)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 1, 10, 0, 0));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::Delegation {
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 0, 0));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::Delegation {
 				delegator: 2,
 				locked_amount: 10,
 				candidate: 1,
@@ -1454,7 +1545,7 @@ fn delegate_reserves_balance() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 10);
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 1, 10, 0, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 0, 0));
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 0);
 		});
 }
@@ -1467,7 +1558,7 @@ fn delegate_updates_delegator_state() {
 		.build()
 		.execute_with(|| {
 			assert!(ParachainStaking::delegator_state(2).is_none());
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 1, 10, 0, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 0, 0));
 			let delegator_state =
 				ParachainStaking::delegator_state(2).expect("just delegated => exists");
 			assert_eq!(delegator_state.total(), 10);
@@ -1490,7 +1581,7 @@ fn delegate_updates_collator_state() {
 				ParachainStaking::top_delegations(1).expect("registered in genesis");
 			assert!(top_delegations.delegations.is_empty());
 			assert!(top_delegations.total.is_zero());
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 1, 10, 0, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 0, 0));
 			let candidate_state =
 				ParachainStaking::candidate_info(1).expect("just delegated => exists");
 			assert_eq!(candidate_state.total_counted, 40);
@@ -1508,8 +1599,8 @@ fn can_delegate_immediately_after_other_join_candidates() {
 		.with_balances(vec![(1, 20), (2, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::join_candidates(Origin::signed(1), 20, 0));
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 1, 20, 0, 0));
+			assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 20, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 20, 0, 0));
 		});
 }
 
@@ -1521,8 +1612,8 @@ fn can_delegate_if_revoking() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 4, 10, 0, 2));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 4, 10, 0, 2));
 		});
 }
 
@@ -1556,7 +1647,7 @@ fn cannot_delegate_if_full_and_new_delegation_less_than_or_equal_lowest_bottom()
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::delegate(Origin::signed(11), 1, 10, 8, 0),
+				ParachainStaking::delegate(RuntimeOrigin::signed(11), 1, 10, 8, 0),
 				Error::<Test>::CannotDelegateLessThanOrEqualToLowestBottomWhenFull
 			);
 		});
@@ -1591,7 +1682,7 @@ fn can_delegate_if_full_and_new_delegation_greater_than_lowest_bottom() {
 		])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::delegate(Origin::signed(11), 1, 11, 8, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(11), 1, 11, 8, 0));
 			assert_event_emitted!(Event::DelegationKicked {
 				delegator: 10,
 				candidate: 1,
@@ -1609,7 +1700,7 @@ fn cannot_delegate_if_candidate() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::delegate(Origin::signed(2), 1, 10, 0, 0),
+				ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 0, 0),
 				Error::<Test>::CandidateExists
 			);
 		});
@@ -1624,7 +1715,7 @@ fn cannot_delegate_if_already_delegated() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::delegate(Origin::signed(2), 1, 10, 1, 1),
+				ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 1, 1),
 				Error::<Test>::AlreadyDelegatedCandidate
 			);
 		});
@@ -1639,7 +1730,7 @@ fn cannot_delegate_more_than_max_delegations() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::delegate(Origin::signed(2), 6, 10, 0, 4),
+				ParachainStaking::delegate(RuntimeOrigin::signed(2), 6, 10, 0, 4),
 				Error::<Test>::ExceedMaxDelegationsPerDelegator,
 			);
 		});
@@ -1666,12 +1757,24 @@ fn sufficient_delegate_weight_hint_succeeds() {
 		.execute_with(|| {
 			let mut count = 4u32;
 			for i in 7..11 {
-				assert_ok!(ParachainStaking::delegate(Origin::signed(i), 1, 10, count, 0u32));
+				assert_ok!(ParachainStaking::delegate(
+					RuntimeOrigin::signed(i),
+					1,
+					10,
+					count,
+					0u32
+				));
 				count += 1u32;
 			}
 			let mut count = 0u32;
 			for i in 3..11 {
-				assert_ok!(ParachainStaking::delegate(Origin::signed(i), 2, 10, count, 1u32));
+				assert_ok!(ParachainStaking::delegate(
+					RuntimeOrigin::signed(i),
+					2,
+					10,
+					count,
+					1u32
+				));
 				count += 1u32;
 			}
 		});
@@ -1699,20 +1802,26 @@ fn insufficient_delegate_weight_hint_fails() {
 			let mut count = 3u32;
 			for i in 7..11 {
 				assert_noop!(
-					ParachainStaking::delegate(Origin::signed(i), 1, 10, count, 0u32),
+					ParachainStaking::delegate(RuntimeOrigin::signed(i), 1, 10, count, 0u32),
 					Error::<Test>::TooLowCandidateDelegationCountToDelegate
 				);
 			}
 			// to set up for next error test
 			count = 4u32;
 			for i in 7..11 {
-				assert_ok!(ParachainStaking::delegate(Origin::signed(i), 1, 10, count, 0u32));
+				assert_ok!(ParachainStaking::delegate(
+					RuntimeOrigin::signed(i),
+					1,
+					10,
+					count,
+					0u32
+				));
 				count += 1u32;
 			}
 			count = 0u32;
 			for i in 3..11 {
 				assert_noop!(
-					ParachainStaking::delegate(Origin::signed(i), 2, 10, count, 0u32),
+					ParachainStaking::delegate(RuntimeOrigin::signed(i), 2, 10, count, 0u32),
 					Error::<Test>::TooLowDelegationCountToDelegate
 				);
 				count += 1u32;
@@ -1730,15 +1839,21 @@ fn revoke_delegation_event_emits_correctly() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationRevocationScheduled {
-				round: 1,
-				delegator: 2,
-				candidate: 1,
-				scheduled_exit: 3,
-			}));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_last_event!(RuntimeEvent::ParachainStaking(
+				Event::DelegationRevocationScheduled {
+					round: 1,
+					delegator: 2,
+					candidate: 1,
+					scheduled_exit: 3,
+				}
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_event_emitted!(Event::DelegatorLeftCandidate {
 				delegator: 2,
 				candidate: 1,
@@ -1756,9 +1871,9 @@ fn can_revoke_delegation_if_revoking_another_delegation() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			// this is an exit implicitly because last delegation revoked
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 3));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 3));
 		});
 }
 
@@ -1766,7 +1881,7 @@ fn can_revoke_delegation_if_revoking_another_delegation() {
 fn cannot_revoke_delegation_if_not_delegator() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1),
+			ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1),
 			Error::<Test>::DelegatorDNE
 		);
 	});
@@ -1781,7 +1896,7 @@ fn cannot_revoke_delegation_that_dne() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 3),
+				ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 3),
 				Error::<Test>::DelegationDNE
 			);
 		});
@@ -1797,7 +1912,7 @@ fn can_schedule_revoke_delegation_below_min_delegator_stake() {
 		.with_delegations(vec![(2, 1, 5), (2, 3, 3)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 		});
 }
 
@@ -1812,7 +1927,7 @@ fn delegator_bond_more_reserves_balance() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 5);
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5));
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 0);
 		});
 }
@@ -1826,7 +1941,7 @@ fn delegator_bond_more_increases_total_staked() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::total(), 40);
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5));
 			assert_eq!(ParachainStaking::total(), 45);
 		});
 }
@@ -1840,7 +1955,7 @@ fn delegator_bond_more_updates_delegator_state() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::delegator_state(2).expect("exists").total(), 10);
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5));
 			assert_eq!(ParachainStaking::delegator_state(2).expect("exists").total(), 15);
 		});
 }
@@ -1856,7 +1971,7 @@ fn delegator_bond_more_updates_candidate_state_top_delegations() {
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().delegations[0].owner, 2);
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().delegations[0].amount, 10);
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().total, 10);
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5));
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().delegations[0].owner, 2);
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().delegations[0].amount, 15);
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().total, 15);
@@ -1880,8 +1995,8 @@ fn delegator_bond_more_updates_candidate_state_bottom_delegations() {
 				10
 			);
 			assert_eq!(ParachainStaking::bottom_delegations(1).unwrap().total, 10);
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationIncreased {
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::DelegationIncreased {
 				delegator: 2,
 				candidate: 1,
 				amount: 5,
@@ -1908,7 +2023,7 @@ fn delegator_bond_more_increases_total() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::total(), 40);
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5));
 			assert_eq!(ParachainStaking::total(), 45);
 		});
 }
@@ -1921,8 +2036,8 @@ fn can_delegator_bond_more_for_leaving_candidate() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1));
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5));
 		});
 }
 
@@ -1934,9 +2049,9 @@ fn delegator_bond_more_disallowed_when_revoke_scheduled() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			assert_noop!(
-				ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5),
+				ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5),
 				<Error<Test>>::PendingDelegationRevoke
 			);
 		});
@@ -1950,8 +2065,12 @@ fn delegator_bond_more_allowed_when_bond_decrease_scheduled() {
 		.with_delegations(vec![(2, 1, 15)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5,));
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5,
+			));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 5));
 		});
 }
 
@@ -1965,13 +2084,19 @@ fn delegator_bond_less_event_emits_correctly() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationDecreaseScheduled {
-				delegator: 2,
-				candidate: 1,
-				amount_to_decrease: 5,
-				execute_round: 3,
-			}));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
+			assert_last_event!(RuntimeEvent::ParachainStaking(
+				Event::DelegationDecreaseScheduled {
+					delegator: 2,
+					candidate: 1,
+					amount_to_decrease: 5,
+					execute_round: 3,
+				}
+			));
 		});
 }
 
@@ -1983,7 +2108,11 @@ fn delegator_bond_less_updates_delegator_state() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			let state = ParachainStaking::delegation_scheduled_requests(&1);
 			assert_eq!(
 				state,
@@ -2004,9 +2133,9 @@ fn cannot_delegator_bond_less_if_revoking() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			assert_noop!(
-				ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 1),
+				ParachainStaking::schedule_delegator_bond_less(RuntimeOrigin::signed(2), 1, 1),
 				Error::<Test>::PendingDelegationRequestAlreadyExists
 			);
 		});
@@ -2016,7 +2145,7 @@ fn cannot_delegator_bond_less_if_revoking() {
 fn cannot_delegator_bond_less_if_not_delegator() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5),
+			ParachainStaking::schedule_delegator_bond_less(RuntimeOrigin::signed(2), 1, 5),
 			Error::<Test>::DelegatorDNE
 		);
 	});
@@ -2031,7 +2160,7 @@ fn cannot_delegator_bond_less_if_candidate_dne() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 3, 5),
+				ParachainStaking::schedule_delegator_bond_less(RuntimeOrigin::signed(2), 3, 5),
 				Error::<Test>::DelegationDNE
 			);
 		});
@@ -2046,7 +2175,7 @@ fn cannot_delegator_bond_less_if_delegation_dne() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 3, 5),
+				ParachainStaking::schedule_delegator_bond_less(RuntimeOrigin::signed(2), 3, 5),
 				Error::<Test>::DelegationDNE
 			);
 		});
@@ -2061,7 +2190,7 @@ fn cannot_delegator_bond_less_below_min_collator_stk() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 6),
+				ParachainStaking::schedule_delegator_bond_less(RuntimeOrigin::signed(2), 1, 6),
 				Error::<Test>::DelegatorBondBelowMin
 			);
 		});
@@ -2076,7 +2205,7 @@ fn cannot_delegator_bond_less_more_than_total_delegation() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 11),
+				ParachainStaking::schedule_delegator_bond_less(RuntimeOrigin::signed(2), 1, 11),
 				Error::<Test>::DelegatorBondBelowMin
 			);
 		});
@@ -2091,7 +2220,7 @@ fn cannot_delegator_bond_less_below_min_delegation() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 8),
+				ParachainStaking::schedule_delegator_bond_less(RuntimeOrigin::signed(2), 1, 8),
 				Error::<Test>::DelegationBelowMin
 			);
 		});
@@ -2110,9 +2239,13 @@ fn execute_revoke_delegation_emits_exit_event_if_exit_happens() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_event_emitted!(Event::DelegatorLeftCandidate {
 				delegator: 2,
 				candidate: 1,
@@ -2132,9 +2265,13 @@ fn revoke_delegation_executes_exit_if_last_delegation() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_event_emitted!(Event::DelegatorLeftCandidate {
 				delegator: 2,
 				candidate: 1,
@@ -2153,9 +2290,13 @@ fn execute_revoke_delegation_emits_correct_event() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_event_emitted!(Event::DelegatorLeftCandidate {
 				delegator: 2,
 				candidate: 1,
@@ -2174,9 +2315,13 @@ fn execute_revoke_delegation_unreserves_balance() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 0);
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 10);
 		});
 }
@@ -2192,7 +2337,7 @@ fn execute_revoke_delegation_adds_revocation_to_delegator_state() {
 			assert!(!ParachainStaking::delegation_scheduled_requests(&1)
 				.iter()
 				.any(|x| x.delegator == 2));
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			assert!(ParachainStaking::delegation_scheduled_requests(&1)
 				.iter()
 				.any(|x| x.delegator == 2));
@@ -2207,9 +2352,13 @@ fn execute_revoke_delegation_removes_revocation_from_delegator_state_upon_execut
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert!(!ParachainStaking::delegation_scheduled_requests(&1)
 				.iter()
 				.any(|x| x.delegator == 2));
@@ -2224,9 +2373,13 @@ fn execute_revoke_delegation_removes_revocation_from_state_for_single_delegation
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert!(
 				!ParachainStaking::delegation_scheduled_requests(&1)
 					.iter()
@@ -2245,9 +2398,13 @@ fn execute_revoke_delegation_decreases_total_staked() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::total(), 40);
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_eq!(ParachainStaking::total(), 30);
 		});
 }
@@ -2261,11 +2418,15 @@ fn execute_revoke_delegation_for_last_delegation_removes_delegator_state() {
 		.build()
 		.execute_with(|| {
 			assert!(ParachainStaking::delegator_state(2).is_some());
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
 			// this will be confusing for people
 			// if status is leaving, then execute_delegation_request works if last delegation
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert!(ParachainStaking::delegator_state(2).is_none());
 		});
 }
@@ -2279,9 +2440,13 @@ fn execute_revoke_delegation_removes_delegation_from_candidate_state() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::candidate_info(1).expect("exists").delegation_count, 1u32);
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert!(ParachainStaking::candidate_info(1)
 				.expect("exists")
 				.delegation_count
@@ -2297,11 +2462,15 @@ fn can_execute_revoke_delegation_for_leaving_candidate() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1));
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
 			// can execute delegation request for leaving candidate
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 		});
 }
 
@@ -2313,11 +2482,11 @@ fn can_execute_leave_candidates_if_revoking_candidate() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1));
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
 			// revocation executes during execute leave candidates (callable by anyone)
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, 1));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 1));
 			assert!(!ParachainStaking::is_delegator(&2));
 			assert_eq!(Balances::reserved_balance(&2), 0);
 			assert_eq!(Balances::free_balance(&2), 10);
@@ -2332,10 +2501,14 @@ fn delegator_bond_more_after_revoke_delegation_does_not_effect_exit() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(2), 3, 10));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 3, 10));
 			roll_to(100);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert!(ParachainStaking::is_delegator(&2));
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 10);
 		});
@@ -2349,22 +2522,36 @@ fn delegator_bond_less_after_revoke_delegation_does_not_effect_exit() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationRevocationScheduled {
-				round: 1,
-				delegator: 2,
-				candidate: 1,
-				scheduled_exit: 3,
-			}));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_last_event!(RuntimeEvent::ParachainStaking(
+				Event::DelegationRevocationScheduled {
+					round: 1,
+					delegator: 2,
+					candidate: 1,
+					scheduled_exit: 3,
+				}
+			));
 			assert_noop!(
-				ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 2),
+				ParachainStaking::schedule_delegator_bond_less(RuntimeOrigin::signed(2), 1, 2),
 				Error::<Test>::PendingDelegationRequestAlreadyExists
 			);
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 3, 2));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				3,
+				2
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 3));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationDecreased {
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				3
+			));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::DelegationDecreased {
 				delegator: 2,
 				candidate: 3,
 				amount: 2,
@@ -2386,9 +2573,17 @@ fn execute_delegator_bond_less_unreserves_balance() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 0);
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 5);
 		});
 }
@@ -2402,9 +2597,17 @@ fn execute_delegator_bond_less_decreases_total_staked() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::total(), 40);
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_eq!(ParachainStaking::total(), 35);
 		});
 }
@@ -2418,9 +2621,17 @@ fn execute_delegator_bond_less_updates_delegator_state() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::delegator_state(2).expect("exists").total(), 10);
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_eq!(ParachainStaking::delegator_state(2).expect("exists").total(), 5);
 		});
 }
@@ -2435,9 +2646,17 @@ fn execute_delegator_bond_less_updates_candidate_state() {
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().delegations[0].owner, 2);
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().delegations[0].amount, 10);
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().delegations[0].owner, 2);
 			assert_eq!(ParachainStaking::top_delegations(1).unwrap().delegations[0].amount, 5);
 		});
@@ -2452,9 +2671,17 @@ fn execute_delegator_bond_less_decreases_total() {
 		.build()
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::total(), 40);
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_eq!(ParachainStaking::total(), 35);
 		});
 }
@@ -2473,9 +2700,17 @@ fn execute_delegator_bond_less_updates_just_bottom_delegations() {
 				ParachainStaking::top_delegations(&1).expect("delegated by all so exists");
 			let pre_call_bottom_delegations =
 				ParachainStaking::bottom_delegations(&1).expect("delegated by all so exists");
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 2));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				2
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			let post_call_candidate_info =
 				ParachainStaking::candidate_info(&1).expect("delegated by all so exists");
 			let post_call_top_delegations =
@@ -2531,9 +2766,17 @@ fn execute_delegator_bond_less_does_not_delete_bottom_delegations() {
 				ParachainStaking::top_delegations(&1).expect("delegated by all so exists");
 			let pre_call_bottom_delegations =
 				ParachainStaking::bottom_delegations(&1).expect("delegated by all so exists");
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(6), 1, 4));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(6),
+				1,
+				4
+			));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(6), 6, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(6),
+				6,
+				1
+			));
 			let post_call_candidate_info =
 				ParachainStaking::candidate_info(&1).expect("delegated by all so exists");
 			let post_call_top_delegations =
@@ -2583,11 +2826,19 @@ fn can_execute_delegator_bond_less_for_leaving_candidate() {
 		.with_delegations(vec![(2, 1, 15)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 1));
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			roll_to(10);
 			// can execute bond more delegation request for leaving candidate
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 		});
 }
 
@@ -2602,9 +2853,9 @@ fn cancel_revoke_delegation_emits_correct_event() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
-			assert_ok!(ParachainStaking::cancel_delegation_request(Origin::signed(2), 1));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CancelledDelegationRequest {
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_ok!(ParachainStaking::cancel_delegation_request(RuntimeOrigin::signed(2), 1));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CancelledDelegationRequest {
 				delegator: 2,
 				collator: 1,
 				cancelled_request: CancelledScheduledRequest {
@@ -2623,7 +2874,7 @@ fn cancel_revoke_delegation_updates_delegator_state() {
 		.with_delegations(vec![(2, 1, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			let state = ParachainStaking::delegation_scheduled_requests(&1);
 			assert_eq!(
 				state,
@@ -2639,7 +2890,7 @@ fn cancel_revoke_delegation_updates_delegator_state() {
 					.expect("delegator state must exist"),
 				10
 			);
-			assert_ok!(ParachainStaking::cancel_delegation_request(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::cancel_delegation_request(RuntimeOrigin::signed(2), 1));
 			assert!(!ParachainStaking::delegation_scheduled_requests(&1)
 				.iter()
 				.any(|x| x.delegator == 2));
@@ -2662,9 +2913,13 @@ fn cancel_delegator_bond_less_correct_event() {
 		.with_delegations(vec![(2, 1, 15)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
-			assert_ok!(ParachainStaking::cancel_delegation_request(Origin::signed(2), 1));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CancelledDelegationRequest {
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
+			assert_ok!(ParachainStaking::cancel_delegation_request(RuntimeOrigin::signed(2), 1));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CancelledDelegationRequest {
 				delegator: 2,
 				collator: 1,
 				cancelled_request: CancelledScheduledRequest {
@@ -2683,7 +2938,11 @@ fn cancel_delegator_bond_less_updates_delegator_state() {
 		.with_delegations(vec![(2, 1, 15)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 5));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				5
+			));
 			let state = ParachainStaking::delegation_scheduled_requests(&1);
 			assert_eq!(
 				state,
@@ -2699,7 +2958,7 @@ fn cancel_delegator_bond_less_updates_delegator_state() {
 					.expect("delegator state must exist"),
 				5
 			);
-			assert_ok!(ParachainStaking::cancel_delegation_request(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::cancel_delegation_request(RuntimeOrigin::signed(2), 1));
 			assert!(!ParachainStaking::delegation_scheduled_requests(&1)
 				.iter()
 				.any(|x| x.delegator == 2));
@@ -2722,7 +2981,7 @@ fn delegator_schedule_revocation_total() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10), (2, 4, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			assert_eq!(
 				ParachainStaking::delegator_state(&2)
 					.map(|x| x.less_total)
@@ -2730,16 +2989,20 @@ fn delegator_schedule_revocation_total() {
 				10
 			);
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert_eq!(
 				ParachainStaking::delegator_state(&2)
 					.map(|x| x.less_total)
 					.expect("delegator state must exist"),
 				0
 			);
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 5, 10, 0, 2));
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 3));
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 4));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 5, 10, 0, 2));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 3));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 4));
 			assert_eq!(
 				ParachainStaking::delegator_state(&2)
 					.map(|x| x.less_total)
@@ -2747,14 +3010,22 @@ fn delegator_schedule_revocation_total() {
 				20,
 			);
 			roll_to(20);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 3));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				3
+			));
 			assert_eq!(
 				ParachainStaking::delegator_state(&2)
 					.map(|x| x.less_total)
 					.expect("delegator state must exist"),
 				10,
 			);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 4));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				4
+			));
 			assert_eq!(
 				ParachainStaking::delegator_state(&2)
 					.map(|x| x.less_total)
@@ -2787,7 +3058,7 @@ fn parachain_bond_inflation_reserve_matches_config() {
 			assert_eq!(Balances::free_balance(&11), 1);
 			// set parachain bond account so DefaultParachainBondReservePercent = 30% of inflation
 			// is allocated to this account hereafter
-			assert_ok!(ParachainStaking::set_parachain_bond_account(Origin::root(), 11));
+			assert_ok!(ParachainStaking::set_parachain_bond_account(RuntimeOrigin::root(), 11));
 			roll_to(8);
 			// chooses top TotalSelectedCandidates (5), in order
 			let mut expected = vec![
@@ -2910,7 +3181,7 @@ fn parachain_bond_inflation_reserve_matches_config() {
 			assert_eq_events!(expected.clone());
 			assert_eq!(Balances::free_balance(&11), 65);
 			assert_ok!(ParachainStaking::set_parachain_bond_reserve_percent(
-				Origin::root(),
+				RuntimeOrigin::root(),
 				Percent::from_percent(50)
 			));
 			// 6 won't be paid for this round because they left already
@@ -2965,7 +3236,7 @@ fn parachain_bond_inflation_reserve_matches_config() {
 			assert_eq_events!(expected.clone());
 			assert_eq!(Balances::free_balance(&11), 127);
 			set_author(8, 1, 100);
-			assert_ok!(ParachainStaking::delegate(Origin::signed(8), 1, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(8), 1, 10, 10, 10));
 			roll_to(45);
 			// new delegation is not rewarded yet
 			let mut new5 = vec![
@@ -3065,15 +3336,15 @@ fn paid_collator_commission_matches_config() {
 				},
 			];
 			assert_eq_events!(expected.clone());
-			assert_ok!(ParachainStaking::join_candidates(Origin::signed(4), 20u128, 100u32));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::JoinedCollatorCandidates {
+			assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(4), 20u128, 100u32));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::JoinedCollatorCandidates {
 				account: 4,
 				amount_locked: 20u128,
 				new_total_amt_locked: 60u128,
 			}));
 			roll_to(9);
-			assert_ok!(ParachainStaking::delegate(Origin::signed(5), 4, 10, 10, 10));
-			assert_ok!(ParachainStaking::delegate(Origin::signed(6), 4, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(5), 4, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(6), 4, 10, 10, 10));
 			roll_to(11);
 			let mut new = vec![
 				Event::JoinedCollatorCandidates {
@@ -3156,11 +3427,11 @@ fn collator_exit_executes_after_delay() {
 		.build()
 		.execute_with(|| {
 			roll_to(11);
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(2), 2));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(2), 2));
 			let info = ParachainStaking::candidate_info(&2).unwrap();
 			assert_eq!(info.status, CollatorStatus::Leaving(5));
 			roll_to(21);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(2), 2, 2));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(2), 2, 2));
 			// we must exclude leaving collators from rewards while
 			// holding them retroactively accountable for previous faults
 			// (within the last T::SlashingWindow blocks)
@@ -3243,16 +3514,16 @@ fn collator_selection_chooses_top_candidates() {
 				},
 			];
 			assert_eq_events!(expected.clone());
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(6), 6));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateScheduledExit {
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(6), 6));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateScheduledExit {
 				exit_allowed_round: 2,
 				candidate: 6,
 				scheduled_exit: 4
 			}));
 			roll_to(21);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(6), 6, 0));
-			assert_ok!(ParachainStaking::join_candidates(Origin::signed(6), 69u128, 100u32));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::JoinedCollatorCandidates {
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(6), 6, 0));
+			assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(6), 69u128, 100u32));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::JoinedCollatorCandidates {
 				account: 6,
 				amount_locked: 69u128,
 				new_total_amt_locked: 469u128,
@@ -3519,9 +3790,9 @@ fn multiple_delegations() {
 				},
 			];
 			assert_eq_events!(expected.clone());
-			assert_ok!(ParachainStaking::delegate(Origin::signed(6), 2, 10, 10, 10));
-			assert_ok!(ParachainStaking::delegate(Origin::signed(6), 3, 10, 10, 10));
-			assert_ok!(ParachainStaking::delegate(Origin::signed(6), 4, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(6), 2, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(6), 3, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(6), 4, 10, 10, 10));
 			roll_to(16);
 			let mut new = vec![
 				Event::Delegation {
@@ -3571,8 +3842,8 @@ fn multiple_delegations() {
 			expected.append(&mut new);
 			assert_eq_events!(expected.clone());
 			roll_to(21);
-			assert_ok!(ParachainStaking::delegate(Origin::signed(7), 2, 80, 10, 10));
-			assert_ok!(ParachainStaking::delegate(Origin::signed(10), 2, 10, 10, 10),);
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(7), 2, 80, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(10), 2, 10, 10, 10),);
 			roll_to(26);
 			let mut new2 = vec![
 				Event::CollatorChosen { round: 5, collator_account: 1, total_exposed_amount: 50 },
@@ -3614,8 +3885,8 @@ fn multiple_delegations() {
 			];
 			expected.append(&mut new2);
 			assert_eq_events!(expected.clone());
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(2), 5));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::CandidateScheduledExit {
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(2), 5));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::CandidateScheduledExit {
 				exit_allowed_round: 6,
 				candidate: 2,
 				scheduled_exit: 8
@@ -3650,7 +3921,7 @@ fn multiple_delegations() {
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&6), 60);
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&7), 10);
 			roll_to(40);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(2), 2, 5));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(2), 2, 5));
 			assert_eq!(ParachainStaking::delegator_state(7).unwrap().total(), 10);
 			assert_eq!(ParachainStaking::delegator_state(6).unwrap().total(), 30);
 			assert_eq!(ParachainStaking::delegator_state(7).unwrap().delegations.0.len(), 1usize);
@@ -3675,15 +3946,15 @@ fn execute_leave_candidate_removes_delegations() {
 				.iter()
 				.any(|x| x.delegator == 3));
 
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(2), 2));
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(3), 2));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(2), 2));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(3), 2));
 			// Verifies the revocation request is present
 			assert!(ParachainStaking::delegation_scheduled_requests(&2)
 				.iter()
 				.any(|x| x.delegator == 3));
 
 			roll_to(16);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(2), 2, 2));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(2), 2, 2));
 			// Verifies the revocation request is again empty
 			assert!(!ParachainStaking::delegation_scheduled_requests(&2)
 				.iter()
@@ -3869,7 +4140,7 @@ fn payouts_follow_delegation_changes() {
 			expected.append(&mut new4);
 			assert_eq_events!(expected.clone());
 			set_author(9, 1, 100);
-			assert_ok!(ParachainStaking::delegate(Origin::signed(8), 1, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(8), 1, 10, 10, 10));
 			roll_to(45);
 			// new delegation is not rewarded yet
 			let mut new5 = vec![
@@ -3956,25 +4227,25 @@ fn bottom_delegations_are_empty_when_top_delegations_not_full() {
 			assert!(top_delegations.delegations.is_empty());
 			assert!(bottom_delegations.delegations.is_empty());
 			// 1 delegator => 1 top delegator, 0 bottom delegators
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 1, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 10, 10));
 			let top_delegations = ParachainStaking::top_delegations(1).unwrap();
 			let bottom_delegations = ParachainStaking::bottom_delegations(1).unwrap();
 			assert_eq!(top_delegations.delegations.len(), 1usize);
 			assert!(bottom_delegations.delegations.is_empty());
 			// 2 delegators => 2 top delegators, 0 bottom delegators
-			assert_ok!(ParachainStaking::delegate(Origin::signed(3), 1, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(3), 1, 10, 10, 10));
 			let top_delegations = ParachainStaking::top_delegations(1).unwrap();
 			let bottom_delegations = ParachainStaking::bottom_delegations(1).unwrap();
 			assert_eq!(top_delegations.delegations.len(), 2usize);
 			assert!(bottom_delegations.delegations.is_empty());
 			// 3 delegators => 3 top delegators, 0 bottom delegators
-			assert_ok!(ParachainStaking::delegate(Origin::signed(4), 1, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(4), 1, 10, 10, 10));
 			let top_delegations = ParachainStaking::top_delegations(1).unwrap();
 			let bottom_delegations = ParachainStaking::bottom_delegations(1).unwrap();
 			assert_eq!(top_delegations.delegations.len(), 3usize);
 			assert!(bottom_delegations.delegations.is_empty());
 			// 4 delegators => 4 top delegators, 0 bottom delegators
-			assert_ok!(ParachainStaking::delegate(Origin::signed(5), 1, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(5), 1, 10, 10, 10));
 			let top_delegations = ParachainStaking::top_delegations(1).unwrap();
 			let bottom_delegations = ParachainStaking::bottom_delegations(1).unwrap();
 			assert_eq!(top_delegations.delegations.len(), 4usize);
@@ -4010,36 +4281,48 @@ fn candidate_pool_updates_when_total_counted_changes() {
 		.build()
 		.execute_with(|| {
 			fn is_candidate_pool_bond(account: u64, bond: u128) {
-				let pool = ParachainStaking::candidate_pool();
-				for candidate in pool.0 {
-					if candidate.owner == account {
-						assert_eq!(
-							candidate.amount, bond,
-							"Candidate Bond {:?} is Not Equal to Expected: {:?}",
-							candidate.amount, bond
-						);
-					}
-				}
+				assert_eq!(
+					ParachainStaking::candidate_pool(account),
+					Some(bond),
+					"Candidate Bond is Not Equal to Expected: {:?}",
+					bond
+				);
 			}
 			// 15 + 16 + 17 + 18 + 20 = 86 (top 4 + self bond)
 			is_candidate_pool_bond(1, 86);
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(3), 1, 8));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(3), 1, 8));
 			// 3: 11 -> 19 => 3 is in top, bumps out 7
 			// 16 + 17 + 18 + 19 + 20 = 90 (top 4 + self bond)
 			is_candidate_pool_bond(1, 90);
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(4), 1, 8));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(4), 1, 8));
 			// 4: 12 -> 20 => 4 is in top, bumps out 8
 			// 17 + 18 + 19 + 20 + 20 = 94 (top 4 + self bond)
 			is_candidate_pool_bond(1, 94);
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(10), 1, 3));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(10),
+				1,
+				3
+			));
 			roll_to(30);
 			// 10: 18 -> 15 => 10 bumped to bottom, 8 bumped to top (- 18 + 16 = -2 for count)
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(10), 10, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(10),
+				10,
+				1
+			));
 			// 16 + 17 + 19 + 20 + 20 = 92 (top 4 + self bond)
 			is_candidate_pool_bond(1, 92);
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(9), 1, 4));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(9),
+				1,
+				4
+			));
 			roll_to(40);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(9), 9, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(9),
+				9,
+				1
+			));
 			// 15 + 16 + 19 + 20 + 20 = 90 (top 4 + self bond)
 			is_candidate_pool_bond(1, 90);
 		});
@@ -4080,7 +4363,7 @@ fn only_top_collators_are_counted() {
 			// 15 + 16 + 17 + 18 + 20 = 86 (top 4 + self bond)
 			assert_eq!(collator_state.total_counted, 86);
 			// bump bottom to the top
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(3), 1, 8));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(3), 1, 8));
 			assert_event_emitted!(Event::DelegationIncreased {
 				delegator: 3,
 				candidate: 1,
@@ -4091,7 +4374,7 @@ fn only_top_collators_are_counted() {
 			// 16 + 17 + 18 + 19 + 20 = 90 (top 4 + self bond)
 			assert_eq!(collator_state.total_counted, 90);
 			// bump bottom to the top
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(4), 1, 8));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(4), 1, 8));
 			assert_event_emitted!(Event::DelegationIncreased {
 				delegator: 4,
 				candidate: 1,
@@ -4102,7 +4385,7 @@ fn only_top_collators_are_counted() {
 			// 17 + 18 + 19 + 20 + 20 = 94 (top 4 + self bond)
 			assert_eq!(collator_state.total_counted, 94);
 			// bump bottom to the top
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(5), 1, 8));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(5), 1, 8));
 			assert_event_emitted!(Event::DelegationIncreased {
 				delegator: 5,
 				candidate: 1,
@@ -4113,7 +4396,7 @@ fn only_top_collators_are_counted() {
 			// 18 + 19 + 20 + 21 + 20 = 98 (top 4 + self bond)
 			assert_eq!(collator_state.total_counted, 98);
 			// bump bottom to the top
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(6), 1, 8));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(6), 1, 8));
 			assert_event_emitted!(Event::DelegationIncreased {
 				delegator: 6,
 				candidate: 1,
@@ -4149,7 +4432,7 @@ fn delegation_events_convey_correct_position() {
 			// 11 + 12 + 13 + 14 + 20 = 70 (top 4 + self bond)
 			assert_eq!(collator1_state.total_counted, 70);
 			// Top delegations are full, new highest delegation is made
-			assert_ok!(ParachainStaking::delegate(Origin::signed(7), 1, 15, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(7), 1, 15, 10, 10));
 			assert_event_emitted!(Event::Delegation {
 				delegator: 7,
 				locked_amount: 15,
@@ -4161,7 +4444,7 @@ fn delegation_events_convey_correct_position() {
 			// 12 + 13 + 14 + 15 + 20 = 70 (top 4 + self bond)
 			assert_eq!(collator1_state.total_counted, 74);
 			// New delegation is added to the bottom
-			assert_ok!(ParachainStaking::delegate(Origin::signed(8), 1, 10, 10, 10));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(8), 1, 10, 10, 10));
 			assert_event_emitted!(Event::Delegation {
 				delegator: 8,
 				locked_amount: 10,
@@ -4173,7 +4456,7 @@ fn delegation_events_convey_correct_position() {
 			// 12 + 13 + 14 + 15 + 20 = 70 (top 4 + self bond)
 			assert_eq!(collator1_state.total_counted, 74);
 			// 8 increases delegation to the top
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(8), 1, 3));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(8), 1, 3));
 			assert_event_emitted!(Event::DelegationIncreased {
 				delegator: 8,
 				candidate: 1,
@@ -4184,7 +4467,7 @@ fn delegation_events_convey_correct_position() {
 			// 13 + 13 + 14 + 15 + 20 = 75 (top 4 + self bond)
 			assert_eq!(collator1_state.total_counted, 75);
 			// 3 increases delegation but stays in bottom
-			assert_ok!(ParachainStaking::delegator_bond_more(Origin::signed(3), 1, 1));
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(3), 1, 1));
 			assert_event_emitted!(Event::DelegationIncreased {
 				delegator: 3,
 				candidate: 1,
@@ -4195,7 +4478,11 @@ fn delegation_events_convey_correct_position() {
 			// 13 + 13 + 14 + 15 + 20 = 75 (top 4 + self bond)
 			assert_eq!(collator1_state.total_counted, 75);
 			// 6 decreases delegation but stays in top
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(6), 1, 2));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(6),
+				1,
+				2
+			));
 			assert_event_emitted!(Event::DelegationDecreaseScheduled {
 				delegator: 6,
 				candidate: 1,
@@ -4203,7 +4490,11 @@ fn delegation_events_convey_correct_position() {
 				execute_round: 3,
 			});
 			roll_to(30);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(6), 6, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(6),
+				6,
+				1
+			));
 			assert_event_emitted!(Event::DelegationDecreased {
 				delegator: 6,
 				candidate: 1,
@@ -4214,7 +4505,11 @@ fn delegation_events_convey_correct_position() {
 			// 12 + 13 + 13 + 15 + 20 = 73 (top 4 + self bond)ƒ
 			assert_eq!(collator1_state.total_counted, 73);
 			// 6 decreases delegation and is bumped to bottom
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(6), 1, 1));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(6),
+				1,
+				1
+			));
 			assert_event_emitted!(Event::DelegationDecreaseScheduled {
 				delegator: 6,
 				candidate: 1,
@@ -4222,7 +4517,11 @@ fn delegation_events_convey_correct_position() {
 				execute_round: 9,
 			});
 			roll_to(40);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(6), 6, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(6),
+				6,
+				1
+			));
 			assert_event_emitted!(Event::DelegationDecreased {
 				delegator: 6,
 				candidate: 1,
@@ -4681,9 +4980,9 @@ fn delegation_kicked_from_bottom_removes_pending_request() {
 		])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			// 10 delegates to full 1 => kicks lowest delegation (2, 19)
-			assert_ok!(ParachainStaking::delegate(Origin::signed(10), 1, 20, 8, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(10), 1, 20, 8, 0));
 			// check the event
 			assert_event_emitted!(Event::DelegationKicked {
 				delegator: 2,
@@ -4707,7 +5006,10 @@ fn no_selected_candidates_defaults_to_last_round_collators() {
 			roll_to_round_begin(1);
 			// schedule to leave
 			for i in 1..6 {
-				assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(i), 5));
+				assert_ok!(ParachainStaking::schedule_leave_candidates(
+					RuntimeOrigin::signed(i),
+					5
+				));
 			}
 			let old_round = ParachainStaking::round().current;
 			let old_selected_candidates = ParachainStaking::selected_candidates();
@@ -4718,7 +5020,11 @@ fn no_selected_candidates_defaults_to_last_round_collators() {
 			roll_to_round_begin(3);
 			// execute leave
 			for i in 1..6 {
-				assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(i), i, 0,));
+				assert_ok!(ParachainStaking::execute_leave_candidates(
+					RuntimeOrigin::signed(i),
+					i,
+					0,
+				));
 			}
 			// next round
 			roll_to_round_begin(4);
@@ -4745,13 +5051,15 @@ fn test_delegator_scheduled_for_revoke_is_rewarded_for_previous_rounds_but_not_f
 			// preset rewards for rounds 1, 2 and 3
 			(1..=3).for_each(|round| set_author(round, 1, 1));
 
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationRevocationScheduled {
-				round: 1,
-				delegator: 2,
-				candidate: 1,
-				scheduled_exit: 3,
-			}));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_last_event!(RuntimeEvent::ParachainStaking(
+				Event::DelegationRevocationScheduled {
+					round: 1,
+					delegator: 2,
+					candidate: 1,
+					scheduled_exit: 3,
+				}
+			));
 			let collator = ParachainStaking::candidate_info(1).expect("candidate must exist");
 			assert_eq!(
 				1, collator.delegation_count,
@@ -4798,13 +5106,15 @@ fn test_delegator_scheduled_for_revoke_is_rewarded_when_request_cancelled() {
 			// preset rewards for rounds 2, 3 and 4
 			(2..=4).for_each(|round| set_author(round, 1, 1));
 
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationRevocationScheduled {
-				round: 1,
-				delegator: 2,
-				candidate: 1,
-				scheduled_exit: 3,
-			}));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_last_event!(RuntimeEvent::ParachainStaking(
+				Event::DelegationRevocationScheduled {
+					round: 1,
+					delegator: 2,
+					candidate: 1,
+					scheduled_exit: 3,
+				}
+			));
 			let collator = ParachainStaking::candidate_info(1).expect("candidate must exist");
 			assert_eq!(
 				1, collator.delegation_count,
@@ -4813,7 +5123,7 @@ fn test_delegator_scheduled_for_revoke_is_rewarded_when_request_cancelled() {
 			assert_eq!(30, collator.total_counted, "collator's total was reduced unexpectedly");
 
 			roll_to_round_begin(2);
-			assert_ok!(ParachainStaking::cancel_delegation_request(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::cancel_delegation_request(RuntimeOrigin::signed(2), 1));
 
 			roll_to_round_begin(4);
 			assert_eq_last_events!(
@@ -4855,13 +5165,19 @@ fn test_delegator_scheduled_for_bond_decrease_is_rewarded_for_previous_rounds_bu
 			// preset rewards for rounds 1, 2 and 3
 			(1..=3).for_each(|round| set_author(round, 1, 1));
 
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 10,));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationDecreaseScheduled {
-				execute_round: 3,
-				delegator: 2,
-				candidate: 1,
-				amount_to_decrease: 10,
-			}));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				10,
+			));
+			assert_last_event!(RuntimeEvent::ParachainStaking(
+				Event::DelegationDecreaseScheduled {
+					execute_round: 3,
+					delegator: 2,
+					candidate: 1,
+					amount_to_decrease: 10,
+				}
+			));
 			let collator = ParachainStaking::candidate_info(1).expect("candidate must exist");
 			assert_eq!(
 				1, collator.delegation_count,
@@ -4911,13 +5227,19 @@ fn test_delegator_scheduled_for_bond_decrease_is_rewarded_when_request_cancelled
 			// preset rewards for rounds 2, 3 and 4
 			(2..=4).for_each(|round| set_author(round, 1, 1));
 
-			assert_ok!(ParachainStaking::schedule_delegator_bond_less(Origin::signed(2), 1, 10,));
-			assert_last_event!(MetaEvent::ParachainStaking(Event::DelegationDecreaseScheduled {
-				execute_round: 3,
-				delegator: 2,
-				candidate: 1,
-				amount_to_decrease: 10,
-			}));
+			assert_ok!(ParachainStaking::schedule_delegator_bond_less(
+				RuntimeOrigin::signed(2),
+				1,
+				10,
+			));
+			assert_last_event!(RuntimeEvent::ParachainStaking(
+				Event::DelegationDecreaseScheduled {
+					execute_round: 3,
+					delegator: 2,
+					candidate: 1,
+					amount_to_decrease: 10,
+				}
+			));
 			let collator = ParachainStaking::candidate_info(1).expect("candidate must exist");
 			assert_eq!(
 				1, collator.delegation_count,
@@ -4926,7 +5248,7 @@ fn test_delegator_scheduled_for_bond_decrease_is_rewarded_when_request_cancelled
 			assert_eq!(40, collator.total_counted, "collator's total was reduced unexpectedly");
 
 			roll_to_round_begin(2);
-			assert_ok!(ParachainStaking::cancel_delegation_request(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::cancel_delegation_request(RuntimeOrigin::signed(2), 1));
 
 			roll_to_round_begin(4);
 			assert_eq_last_events!(
@@ -5064,45 +5386,34 @@ fn test_delegation_request_revoke_exists_returns_true_when_revoke_exists() {
 }
 
 #[test]
-fn locking_zero_amount_is_ignored() {
-	use frame_support::traits::{LockableCurrency, WithdrawReasons};
-
-	// this test demonstrates the behavior of pallet Balance's `LockableCurrency` implementation of
-	// `set_locks()` when an amount of 0 is provided: it is a no-op
-
-	ExtBuilder::default().with_balances(vec![(1, 100)]).build().execute_with(|| {
-		assert_eq!(crate::mock::query_lock_amount(1, DELEGATOR_LOCK_ID), None);
-
-		Balances::set_lock(DELEGATOR_LOCK_ID, &1, 1, WithdrawReasons::all());
-		assert_eq!(crate::mock::query_lock_amount(1, DELEGATOR_LOCK_ID), Some(1));
-
-		Balances::set_lock(DELEGATOR_LOCK_ID, &1, 0, WithdrawReasons::all());
-		// Note that we tried to call `set_lock(0)` and it ignored it, we still have our lock
-		assert_eq!(crate::mock::query_lock_amount(1, DELEGATOR_LOCK_ID), Some(1));
-	});
-}
-
-#[test]
-fn revoke_last_removes_lock() {
+fn revoke_last_releases_held_stake() {
 	ExtBuilder::default()
 		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
 		.with_candidates(vec![(1, 25), (2, 25)])
 		.with_delegations(vec![(3, 1, 30), (3, 2, 25)])
 		.build()
 		.execute_with(|| {
-			assert_eq!(crate::mock::query_lock_amount(3, DELEGATOR_LOCK_ID), Some(55));
+			assert_eq!(Balances::reserved_balance(3), 55);
 
 			// schedule and remove one...
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(3), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(3), 1));
 			roll_to_round_begin(3);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(3), 3, 1));
-			assert_eq!(crate::mock::query_lock_amount(3, DELEGATOR_LOCK_ID), Some(25));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(3),
+				3,
+				1
+			));
+			assert_eq!(Balances::reserved_balance(3), 25);
 
 			// schedule and remove the other...
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(3), 2));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(3), 2));
 			roll_to_round_begin(5);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(3), 3, 2));
-			assert_eq!(crate::mock::query_lock_amount(3, DELEGATOR_LOCK_ID), None);
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(3),
+				3,
+				2
+			));
+			assert_eq!(Balances::reserved_balance(3), 0);
 		});
 }
 
@@ -5119,7 +5430,7 @@ fn test_set_auto_compound_fails_if_invalid_delegation_hint() {
 
 			assert_noop!(
 				ParachainStaking::set_auto_compound(
-					Origin::signed(2),
+					RuntimeOrigin::signed(2),
 					1,
 					Percent::from_percent(50),
 					candidate_auto_compounding_delegation_count_hint,
@@ -5141,6 +5452,7 @@ fn test_set_auto_compound_fails_if_invalid_candidate_auto_compounding_hint() {
 			<AutoCompoundDelegations<Test>>::new(vec![AutoCompoundConfig {
 				delegator: 2,
 				value: Percent::from_percent(10),
+				every: 1,
 			}])
 			.set_storage(&1);
 			let candidate_auto_compounding_delegation_count_hint = 0; // is however, 1
@@ -5148,7 +5460,7 @@ fn test_set_auto_compound_fails_if_invalid_candidate_auto_compounding_hint() {
 
 			assert_noop!(
 				ParachainStaking::set_auto_compound(
-					Origin::signed(2),
+					RuntimeOrigin::signed(2),
 					1,
 					Percent::from_percent(50),
 					candidate_auto_compounding_delegation_count_hint,
@@ -5168,7 +5480,7 @@ fn test_set_auto_compound_inserts_if_not_exists() {
 		.build()
 		.execute_with(|| {
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				Percent::from_percent(50),
 				0,
@@ -5180,7 +5492,11 @@ fn test_set_auto_compound_inserts_if_not_exists() {
 				value: Percent::from_percent(50),
 			});
 			assert_eq!(
-				vec![AutoCompoundConfig { delegator: 2, value: Percent::from_percent(50) }],
+				vec![AutoCompoundConfig {
+					delegator: 2,
+					value: Percent::from_percent(50),
+					every: 1
+				}],
 				ParachainStaking::auto_compounding_delegations(&1),
 			);
 		});
@@ -5197,11 +5513,12 @@ fn test_set_auto_compound_updates_if_existing() {
 			<AutoCompoundDelegations<Test>>::new(vec![AutoCompoundConfig {
 				delegator: 2,
 				value: Percent::from_percent(10),
+				every: 1,
 			}])
 			.set_storage(&1);
 
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				Percent::from_percent(50),
 				1,
@@ -5213,7 +5530,11 @@ fn test_set_auto_compound_updates_if_existing() {
 				value: Percent::from_percent(50),
 			});
 			assert_eq!(
-				vec![AutoCompoundConfig { delegator: 2, value: Percent::from_percent(50) }],
+				vec![AutoCompoundConfig {
+					delegator: 2,
+					value: Percent::from_percent(50),
+					every: 1
+				}],
 				ParachainStaking::auto_compounding_delegations(&1),
 			);
 		});
@@ -5230,11 +5551,12 @@ fn test_set_auto_compound_removes_if_auto_compound_zero_percent() {
 			<AutoCompoundDelegations<Test>>::new(vec![AutoCompoundConfig {
 				delegator: 2,
 				value: Percent::from_percent(10),
+				every: 1,
 			}])
 			.set_storage(&1);
 
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				Percent::zero(),
 				1,
@@ -5258,22 +5580,26 @@ fn test_execute_revoke_delegation_removes_auto_compounding_from_state_for_delega
 		.build()
 		.execute_with(|| {
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				Percent::from_percent(50),
 				0,
 				2,
 			));
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				3,
 				Percent::from_percent(50),
 				0,
 				2,
 			));
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_delegation_request(Origin::signed(2), 2, 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
 			assert!(
 				!ParachainStaking::auto_compounding_delegations(&1)
 					.iter()
@@ -5298,23 +5624,23 @@ fn test_execute_leave_candidates_removes_auto_compounding_state() {
 		.build()
 		.execute_with(|| {
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				Percent::from_percent(50),
 				0,
 				2,
 			));
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				3,
 				Percent::from_percent(50),
 				0,
 				2,
 			));
 
-			assert_ok!(ParachainStaking::schedule_leave_candidates(Origin::signed(1), 2));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 2));
 			roll_to(10);
-			assert_ok!(ParachainStaking::execute_leave_candidates(Origin::signed(1), 1, 1,));
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 1,));
 
 			assert!(
 				!ParachainStaking::auto_compounding_delegations(&1)
@@ -5362,7 +5688,7 @@ fn test_delegation_kicked_from_bottom_delegation_removes_auto_compounding_state(
 		.build()
 		.execute_with(|| {
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				Percent::from_percent(50),
 				0,
@@ -5370,7 +5696,7 @@ fn test_delegation_kicked_from_bottom_delegation_removes_auto_compounding_state(
 			));
 
 			// kicks lowest delegation (2, 19)
-			assert_ok!(ParachainStaking::delegate(Origin::signed(10), 1, 20, 8, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(10), 1, 20, 8, 0));
 
 			assert!(
 				!ParachainStaking::auto_compounding_delegations(&1)
@@ -5391,14 +5717,14 @@ fn test_rewards_do_not_auto_compound_on_payment_if_delegation_scheduled_revoke_e
 		.execute_with(|| {
 			(2..=5).for_each(|round| set_author(round, 1, 1));
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				Percent::from_percent(50),
 				0,
 				1,
 			));
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(3),
+				RuntimeOrigin::signed(3),
 				1,
 				Percent::from_percent(50),
 				1,
@@ -5407,7 +5733,7 @@ fn test_rewards_do_not_auto_compound_on_payment_if_delegation_scheduled_revoke_e
 			roll_to_round_begin(3);
 
 			// schedule revoke for delegator 2; no rewards should be compounded
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			roll_to_round_begin(4);
 
 			assert_eq_last_events!(vec![
@@ -5430,21 +5756,21 @@ fn test_rewards_auto_compound_on_payment_as_per_auto_compound_config() {
 		.execute_with(|| {
 			(2..=6).for_each(|round| set_author(round, 1, 1));
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				Percent::from_percent(0),
 				0,
 				1,
 			));
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(3),
+				RuntimeOrigin::signed(3),
 				1,
 				Percent::from_percent(50),
 				1,
 				1,
 			));
 			assert_ok!(ParachainStaking::set_auto_compound(
-				Origin::signed(4),
+				RuntimeOrigin::signed(4),
 				1,
 				Percent::from_percent(100),
 				2,
@@ -5481,7 +5807,7 @@ fn test_delegate_with_auto_compound_fails_if_invalid_delegation_hint() {
 
 			assert_noop!(
 				ParachainStaking::delegate_with_auto_compound(
-					Origin::signed(2),
+					RuntimeOrigin::signed(2),
 					1,
 					10,
 					Percent::from_percent(50),
@@ -5508,7 +5834,7 @@ fn test_delegate_with_auto_compound_fails_if_invalid_candidate_delegation_count_
 
 			assert_noop!(
 				ParachainStaking::delegate_with_auto_compound(
-					Origin::signed(2),
+					RuntimeOrigin::signed(2),
 					1,
 					10,
 					Percent::from_percent(50),
@@ -5535,7 +5861,7 @@ fn test_delegate_with_auto_compound_fails_if_invalid_candidate_auto_compounding_
 
 			assert_noop!(
 				ParachainStaking::delegate_with_auto_compound(
-					Origin::signed(2),
+					RuntimeOrigin::signed(2),
 					1,
 					10,
 					Percent::from_percent(50),
@@ -5556,7 +5882,7 @@ fn test_delegate_with_auto_compound_sets_auto_compound_config() {
 		.build()
 		.execute_with(|| {
 			assert_ok!(ParachainStaking::delegate_with_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				10,
 				Percent::from_percent(50),
@@ -5572,7 +5898,11 @@ fn test_delegate_with_auto_compound_sets_auto_compound_config() {
 				auto_compound: Percent::from_percent(50),
 			});
 			assert_eq!(
-				vec![AutoCompoundConfig { delegator: 2, value: Percent::from_percent(50) }],
+				vec![AutoCompoundConfig {
+					delegator: 2,
+					value: Percent::from_percent(50),
+					every: 1
+				}],
 				ParachainStaking::auto_compounding_delegations(&1),
 			);
 		});
@@ -5586,7 +5916,7 @@ fn test_delegate_with_auto_compound_skips_storage_but_emits_event_for_zero_auto_
 		.build()
 		.execute_with(|| {
 			assert_ok!(ParachainStaking::delegate_with_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				10,
 				Percent::zero(),
@@ -5595,7 +5925,7 @@ fn test_delegate_with_auto_compound_skips_storage_but_emits_event_for_zero_auto_
 				0,
 			));
 			assert_eq!(0, ParachainStaking::auto_compounding_delegations(&1).len(),);
-			assert_last_event!(MetaEvent::ParachainStaking(Event::Delegation {
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::Delegation {
 				delegator: 2,
 				locked_amount: 10,
 				candidate: 1,
@@ -5614,7 +5944,7 @@ fn test_delegate_with_auto_compound_reserves_balance() {
 		.execute_with(|| {
 			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 10);
 			assert_ok!(ParachainStaking::delegate_with_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				10,
 				Percent::from_percent(50),
@@ -5635,7 +5965,7 @@ fn test_delegate_with_auto_compound_updates_delegator_state() {
 		.execute_with(|| {
 			assert!(ParachainStaking::delegator_state(2).is_none());
 			assert_ok!(ParachainStaking::delegate_with_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				10,
 				Percent::from_percent(50),
@@ -5666,7 +5996,7 @@ fn test_delegate_with_auto_compound_updates_collator_state() {
 			assert!(top_delegations.delegations.is_empty());
 			assert!(top_delegations.total.is_zero());
 			assert_ok!(ParachainStaking::delegate_with_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				10,
 				Percent::from_percent(50),
@@ -5691,9 +6021,9 @@ fn test_delegate_with_auto_compound_can_delegate_immediately_after_other_join_ca
 		.with_balances(vec![(1, 20), (2, 20)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::join_candidates(Origin::signed(1), 20, 0));
+			assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 20, 0));
 			assert_ok!(ParachainStaking::delegate_with_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				1,
 				20,
 				Percent::from_percent(50),
@@ -5712,9 +6042,9 @@ fn test_delegate_with_auto_compound_can_delegate_to_other_if_revoking() {
 		.with_delegations(vec![(2, 1, 10), (2, 3, 10)])
 		.build()
 		.execute_with(|| {
-			assert_ok!(ParachainStaking::schedule_revoke_delegation(Origin::signed(2), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
 			assert_ok!(ParachainStaking::delegate_with_auto_compound(
-				Origin::signed(2),
+				RuntimeOrigin::signed(2),
 				4,
 				10,
 				Percent::from_percent(50),
@@ -5756,7 +6086,7 @@ fn test_delegate_with_auto_compound_cannot_delegate_if_less_than_or_equal_lowest
 		.execute_with(|| {
 			assert_noop!(
 				ParachainStaking::delegate_with_auto_compound(
-					Origin::signed(11),
+					RuntimeOrigin::signed(11),
 					1,
 					10,
 					Percent::from_percent(50),
@@ -5799,7 +6129,7 @@ fn test_delegate_with_auto_compound_can_delegate_if_greater_than_lowest_bottom()
 		.build()
 		.execute_with(|| {
 			assert_ok!(ParachainStaking::delegate_with_auto_compound(
-				Origin::signed(11),
+				RuntimeOrigin::signed(11),
 				1,
 				11,
 				Percent::from_percent(50),
@@ -5825,7 +6155,7 @@ fn test_delegate_with_auto_compound_cannot_delegate_if_candidate() {
 		.execute_with(|| {
 			assert_noop!(
 				ParachainStaking::delegate_with_auto_compound(
-					Origin::signed(2),
+					RuntimeOrigin::signed(2),
 					1,
 					10,
 					Percent::from_percent(50),
@@ -5848,7 +6178,7 @@ fn test_delegate_with_auto_compound_cannot_delegate_if_already_delegated() {
 		.execute_with(|| {
 			assert_noop!(
 				ParachainStaking::delegate_with_auto_compound(
-					Origin::signed(2),
+					RuntimeOrigin::signed(2),
 					1,
 					10,
 					Percent::from_percent(50),
@@ -5871,7 +6201,7 @@ fn test_delegate_with_auto_compound_cannot_delegate_more_than_max_delegations()
 		.execute_with(|| {
 			assert_noop!(
 				ParachainStaking::delegate_with_auto_compound(
-					Origin::signed(2),
+					RuntimeOrigin::signed(2),
 					6,
 					10,
 					Percent::from_percent(50),
@@ -5894,9 +6224,9 @@ fn test_delegate_skips_auto_compound_storage_but_emits_event_for_zero_auto_compo
 		.execute_with(|| {
 			// We already have an auto-compounding delegation from 3 -> 1, so the hint validation
 			// would cause a failure if the auto-compounding isn't skipped properly.
-			assert_ok!(ParachainStaking::delegate(Origin::signed(2), 1, 10, 1, 0,));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 1, 0,));
 			assert_eq!(1, ParachainStaking::auto_compounding_delegations(&1).len(),);
-			assert_last_event!(MetaEvent::ParachainStaking(Event::Delegation {
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::Delegation {
 				delegator: 2,
 				locked_amount: 10,
 				candidate: 1,
@@ -5905,3 +6235,2643 @@ fn test_delegate_skips_auto_compound_storage_but_emits_event_for_zero_auto_compo
 			}));
 		});
 }
+
+// ~~ FEATURES ~~
+
+#[test]
+fn try_state_holds_for_genesis_staking() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.with_delegations(vec![(3, 1, 10), (4, 1, 10), (5, 2, 10)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(ParachainStaking::do_try_state(), Ok(()));
+			for (delegator, state) in <crate::DelegatorState<Test>>::iter() {
+				assert_eq!(Balances::reserved_balance(delegator), state.total);
+			}
+		});
+}
+
+#[test]
+fn staking_interface_delegates_and_undelegates() {
+	use crate::StakingInterface;
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<ParachainStaking as StakingInterface>::delegate(2, 1, 10));
+			assert_ok!(<ParachainStaking as StakingInterface>::delegate(2, 1, 5));
+			assert_eq!(ParachainStaking::delegation_stake(&2, &1), Some(15));
+			assert_eq!(ParachainStaking::candidate_stake(&1), Some(35));
+			assert_eq!(ParachainStaking::total_stake(), 35);
+
+			assert_ok!(ParachainStaking::undelegate(2, 1));
+			start_next_round();
+			start_next_round();
+			assert_ok!(ParachainStaking::withdraw_unbonded(2, 1));
+			assert!(!<ParachainStaking as StakingInterface>::is_delegator(&2));
+			assert_eq!(ParachainStaking::candidate_stake(&1), Some(20));
+			assert_eq!(Balances::reserved_balance(2), 0);
+		});
+}
+
+#[test]
+fn stale_at_stake_is_pruned_across_calls() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20), (2, 20), (3, 20)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(<crate::AtStake<Test>>::iter_prefix(1).count(), 3);
+			assert_noop!(
+				ParachainStaking::prune_stale_at_stake(RuntimeOrigin::signed(1), 1, 10),
+				Error::<Test>::RoundRewardsNotPaid
+			);
+
+			start_next_round();
+			start_next_round();
+			// no points were awarded in round 1, so nothing is paid and its snapshots are stale
+			assert!(ParachainStaking::stale_at_stake(1).is_some());
+
+			assert_ok!(ParachainStaking::prune_stale_at_stake(RuntimeOrigin::signed(1), 1, 2));
+			assert_eq!(<crate::AtStake<Test>>::iter_prefix(1).count(), 1);
+			assert!(ParachainStaking::stale_at_stake(1).is_some());
+
+			assert_ok!(ParachainStaking::prune_stale_at_stake(RuntimeOrigin::signed(1), 1, 2));
+			assert_eq!(<crate::AtStake<Test>>::iter_prefix(1).count(), 0);
+			assert_eq!(ParachainStaking::stale_at_stake(1), None);
+		});
+}
+
+#[test]
+fn collator_reward_is_paid_out_according_to_payout_split() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let split = |shares: &[(AccountId, u32)]| {
+				shares.iter().map(|(who, pct)| (*who, Perbill::from_percent(*pct))).collect()
+			};
+			assert_noop!(
+				ParachainStaking::set_payout_split(RuntimeOrigin::signed(2), split(&[(3, 10)])),
+				Error::<Test>::CandidateDNE
+			);
+			assert_noop!(
+				ParachainStaking::set_payout_split(
+					RuntimeOrigin::signed(1),
+					split(&[(2, 10), (3, 10), (4, 10)])
+				),
+				Error::<Test>::TooManyPayoutSplitShares
+			);
+			assert_noop!(
+				ParachainStaking::set_payout_split(
+					RuntimeOrigin::signed(1),
+					split(&[(2, 60), (3, 50)])
+				),
+				Error::<Test>::PayoutSplitExceedsWhole
+			);
+			assert_ok!(ParachainStaking::set_payout_split(
+				RuntimeOrigin::signed(1),
+				split(&[(2, 20), (3, 30)])
+			));
+
+			set_author(1, 1, 20);
+			ParachainStaking::pay_one_collator_reward(
+				1,
+				crate::DelayedPayout {
+					round_issuance: 100,
+					total_staking_reward: 100,
+					collator_commission: Perbill::from_percent(20),
+				},
+			);
+			assert_eq!(Balances::free_balance(2), 120);
+			assert_eq!(Balances::free_balance(3), 130);
+			// 80 free after the self bond is held, plus the remaining half of the reward
+			assert_eq!(Balances::free_balance(1), 130);
+		});
+}
+
+#[test]
+fn sqrt_exposure_curve_dampens_rewards_of_larger_collators() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 200), (2, 200)])
+		.with_candidates(vec![(1, 16), (2, 144)])
+		.build()
+		.execute_with(|| {
+			UseSqrtExposure::set(true);
+			set_author(1, 1, 20);
+			set_author(1, 2, 20);
+			// pays out round 1
+			ParachainStaking::prepare_staking_payouts(1 + RewardPaymentDelay::get());
+			// 20 * sqrt(16) + 20 * sqrt(144)
+			assert_eq!(ParachainStaking::round_reward_weight(1), 320);
+
+			for _ in 0..2 {
+				ParachainStaking::pay_one_collator_reward(
+					1,
+					crate::DelayedPayout {
+						round_issuance: 100,
+						total_staking_reward: 100,
+						collator_commission: Perbill::zero(),
+					},
+				);
+			}
+			// nine times the stake earns three times the reward
+			assert_eq!(Balances::free_balance(1), 200 - 16 + 25);
+			assert_eq!(Balances::free_balance(2), 200 - 144 + 75);
+		});
+}
+
+#[test]
+fn staked_and_unbonding_includes_scheduled_requests() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(ParachainStaking::staked_and_unbonding(&1), (20, 0));
+			assert_eq!(ParachainStaking::staked_and_unbonding(&2), (10, 0));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(RuntimeOrigin::signed(1), 5));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_eq!(ParachainStaking::staked_and_unbonding(&1), (20, 5));
+			assert_eq!(ParachainStaking::staked_and_unbonding(&2), (10, 10));
+			assert_eq!(ParachainStaking::staked_and_unbonding(&3), (0, 0));
+		});
+}
+
+#[test]
+fn offence_slashes_candidate_self_bond() {
+	use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10)])
+		.build()
+		.execute_with(|| {
+			<ParachainStaking as OnOffenceHandler<_, _, _>>::on_offence(
+				&[OffenceDetails { offender: (1, 1), reporters: vec![] }],
+				&[Perbill::from_percent(50)],
+				0,
+				DisableStrategy::WhenSlashed,
+			);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::CandidateSlashed {
+					candidate: 1,
+					amount: 10,
+					new_bond: 10,
+				})
+			);
+			assert_eq!(Balances::reserved_balance(1), 10);
+			assert_eq!(Balances::total_balance(&1), 90);
+			assert_eq!(ParachainStaking::total(), 20);
+			// delegations are untouched
+			assert_eq!(ParachainStaking::staked_and_unbonding(&2), (10, 0));
+		});
+}
+
+#[test]
+fn deferred_slash_is_applied_after_defer_duration_unless_cancelled() {
+	use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+	SlashDeferDuration::set(2);
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			<ParachainStaking as OnOffenceHandler<_, _, _>>::on_offence(
+				&[
+					OffenceDetails { offender: (1, 1), reporters: vec![] },
+					OffenceDetails { offender: (2, 2), reporters: vec![] },
+				],
+				&[Perbill::from_percent(50), Perbill::from_percent(25)],
+				0,
+				DisableStrategy::WhenSlashed,
+			);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::SlashDeferred {
+					candidate: 2,
+					amount: 5,
+					apply_round: 3,
+				})
+			);
+			// nothing is slashed on report
+			assert_eq!(Balances::reserved_balance(1), 20);
+			assert_eq!(ParachainStaking::unapplied_slashes(3).len(), 2);
+
+			assert_noop!(
+				ParachainStaking::cancel_deferred_slash(RuntimeOrigin::signed(1), 3, vec![1]),
+				sp_runtime::DispatchError::BadOrigin
+			);
+			assert_noop!(
+				ParachainStaking::cancel_deferred_slash(RuntimeOrigin::root(), 3, vec![]),
+				Error::<Test>::EmptySlashIndices
+			);
+			assert_noop!(
+				ParachainStaking::cancel_deferred_slash(RuntimeOrigin::root(), 3, vec![1, 1]),
+				Error::<Test>::SlashIndicesNotSortedAndUnique
+			);
+			assert_noop!(
+				ParachainStaking::cancel_deferred_slash(RuntimeOrigin::root(), 3, vec![2]),
+				Error::<Test>::InvalidSlashIndex
+			);
+			assert_ok!(ParachainStaking::cancel_deferred_slash(RuntimeOrigin::root(), 3, vec![1]));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::DeferredSlashCancelled {
+					round: 3,
+					candidate: 2,
+					amount: 5,
+				})
+			);
+
+			roll_to_round_begin(2);
+			assert_eq!(Balances::reserved_balance(1), 20);
+			roll_to_round_begin(3);
+			assert_eq!(Balances::reserved_balance(1), 10);
+			assert_eq!(Balances::reserved_balance(2), 20);
+			assert_eq!(ParachainStaking::total(), 30);
+			assert!(ParachainStaking::unapplied_slashes(3).is_empty());
+		});
+}
+
+#[test]
+fn deferred_slashes_keep_the_worst_offence_and_slash_on_report_once_full() {
+	use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+	SlashDeferDuration::set(2);
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 40), (2, 40), (3, 40)])
+		.build()
+		.execute_with(|| {
+			let report = |offender: AccountId, fraction: u32| {
+				<ParachainStaking as OnOffenceHandler<_, _, _>>::on_offence(
+					&[OffenceDetails { offender: (offender, offender), reporters: vec![] }],
+					&[Perbill::from_percent(fraction)],
+					0,
+					DisableStrategy::WhenSlashed,
+				);
+			};
+			report(1, 50);
+			report(1, 25);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::SlashDeferred {
+					candidate: 1,
+					amount: 20,
+					apply_round: 3,
+				})
+			);
+			report(2, 25);
+			assert_eq!(
+				ParachainStaking::unapplied_slashes(3).into_inner(),
+				vec![
+					UnappliedSlash { candidate: 1, amount: 20 },
+					UnappliedSlash { candidate: 2, amount: 10 },
+				]
+			);
+			// MaxDeferredSlashesPerRound is 2
+			report(3, 25);
+			assert_eq!(ParachainStaking::unapplied_slashes(3).len(), 2);
+			assert_eq!(Balances::reserved_balance(3), 30);
+			assert_eq!(Balances::reserved_balance(1), 40);
+
+			roll_to_round_begin(3);
+			assert_eq!(Balances::reserved_balance(1), 20);
+			assert_eq!(Balances::reserved_balance(2), 30);
+		});
+}
+
+#[test]
+fn force_unbond_with_penalty_skips_revoke_delay_for_a_penalty() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParachainStaking::force_unbond_with_penalty(RuntimeOrigin::signed(2), 3),
+				Error::<Test>::DelegationDNE
+			);
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_ok!(ParachainStaking::force_unbond_with_penalty(RuntimeOrigin::signed(2), 1));
+			assert_eq!(
+				events().into_iter().rev().take(2).collect::<Vec<_>>(),
+				vec![
+					Event::DelegatorLeft { delegator: 2, unstaked_amount: 10 },
+					Event::DelegationForceUnbonded {
+						delegator: 2,
+						candidate: 1,
+						unstaked_amount: 10,
+						penalty: 1,
+					},
+				]
+			);
+			assert_eq!(Balances::free_balance(2), 99);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(ParachainStaking::total(), 20);
+			assert!(ParachainStaking::delegator_state(2).is_none());
+			assert!(!ParachainStaking::delegation_request_exists(&1, &2));
+		});
+}
+
+#[test]
+fn circuit_breaker_trips_once_when_total_drops_too_fast_in_a_block() {
+	MaxTotalDropPerBlock::set(Percent::from_percent(20));
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			roll_one_block();
+			// a drop within the limit is fine
+			ParachainStaking::candidate_slash(&1, 8);
+			roll_one_block();
+			assert_eq!(ParachainStaking::circuit_breaker_anomaly(), None);
+
+			ParachainStaking::candidate_slash(&2, 8);
+			ParachainStaking::candidate_slash(&1, 1);
+			roll_one_block();
+			let anomaly = crate::StakingAnomaly::TotalDropped { previous: 32, current: 23 };
+			assert_eq!(ParachainStaking::circuit_breaker_anomaly(), Some(anomaly.clone()));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::CircuitBreakerTripped { anomaly })
+			);
+			assert_eq!(CircuitBreakerTrips::get(), 1);
+
+			// further anomalies wait for governance
+			ParachainStaking::candidate_slash(&2, 12);
+			roll_one_block();
+			assert_eq!(CircuitBreakerTrips::get(), 1);
+
+			assert_noop!(
+				ParachainStaking::reset_circuit_breaker(RuntimeOrigin::signed(1)),
+				sp_runtime::DispatchError::BadOrigin
+			);
+			assert_ok!(ParachainStaking::reset_circuit_breaker(RuntimeOrigin::root()));
+			assert_eq!(last_event(), RuntimeEvent::ParachainStaking(Event::CircuitBreakerReset));
+			assert_noop!(
+				ParachainStaking::reset_circuit_breaker(RuntimeOrigin::root()),
+				Error::<Test>::CircuitBreakerNotTripped
+			);
+		});
+}
+
+#[test]
+fn auto_compound_every_n_rounds_bonds_the_put_aside_share_together() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParachainStaking::set_auto_compound_every(RuntimeOrigin::signed(2), 1, 2, 0),
+				Error::<Test>::AutoCompoundDNE
+			);
+			assert_ok!(ParachainStaking::set_auto_compound(
+				RuntimeOrigin::signed(2),
+				1,
+				Percent::from_percent(50),
+				0,
+				1
+			));
+			assert_noop!(
+				ParachainStaking::set_auto_compound_every(RuntimeOrigin::signed(2), 1, 0, 1),
+				Error::<Test>::InvalidCompoundFrequency
+			);
+			assert_ok!(ParachainStaking::set_auto_compound_every(
+				RuntimeOrigin::signed(2),
+				1,
+				2,
+				1
+			));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::AutoCompoundFrequencySet {
+					candidate: 1,
+					delegator: 2,
+					every: 2,
+				})
+			);
+			let rewards = || -> Vec<Balance> {
+				events()
+					.into_iter()
+					.filter_map(|e| match e {
+						Event::Rewarded { account: 2, rewards } => Some(rewards),
+						_ => None,
+					})
+					.collect()
+			};
+			let compounded = || -> Vec<Balance> {
+				events()
+					.into_iter()
+					.filter_map(|e| match e {
+						Event::Compounded { delegator: 2, amount, .. } => Some(amount),
+						_ => None,
+					})
+					.collect()
+			};
+			set_author(1, 1, 20);
+			set_author(2, 1, 20);
+			// round 1 is paid, off schedule
+			roll_to_round_begin(3);
+			let share = |reward: Balance| Percent::from_percent(50).mul_ceil(reward);
+			let first = share(rewards()[0]);
+			assert!(first > 0);
+			assert_eq!(ParachainStaking::pending_compound(1, 2), first);
+			assert!(compounded().is_empty());
+			// round 2 is paid and compounds both shares
+			roll_to_round_begin(4);
+			let second = share(rewards()[1]);
+			assert_eq!(compounded(), vec![first + second]);
+			assert_eq!(ParachainStaking::pending_compound(1, 2), 0);
+			assert_eq!(ParachainStaking::delegator_state(2).unwrap().total(), 20 + first + second);
+		});
+}
+
+#[test]
+fn bond_funder_tops_up_candidate_bond_from_its_own_balance() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParachainStaking::set_bond_funder(RuntimeOrigin::signed(3), Some(2)),
+				Error::<Test>::CandidateDNE
+			);
+			assert_noop!(
+				ParachainStaking::candidate_bond_more_for(RuntimeOrigin::signed(2), 1, 10),
+				Error::<Test>::NotBondFunder
+			);
+			assert_ok!(ParachainStaking::set_bond_funder(RuntimeOrigin::signed(1), Some(2)));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::BondFunderSet {
+					candidate: 1,
+					funder: Some(2),
+				})
+			);
+			assert_noop!(
+				ParachainStaking::candidate_bond_more_for(RuntimeOrigin::signed(3), 1, 10),
+				Error::<Test>::NotBondFunder
+			);
+			assert_ok!(ParachainStaking::candidate_bond_more_for(RuntimeOrigin::signed(2), 1, 10));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::CandidateBondedMore {
+					candidate: 1,
+					amount: 10,
+					new_total_bond: 30,
+				})
+			);
+			assert_eq!(Balances::free_balance(2), 90);
+			// the candidate's own free balance is untouched
+			assert_eq!(Balances::free_balance(1), 80);
+			assert_eq!(ParachainStaking::candidate_info(1).unwrap().bond, 30);
+
+			assert_ok!(ParachainStaking::set_bond_funder(RuntimeOrigin::signed(1), None));
+			assert_eq!(ParachainStaking::bond_funder(1), None);
+		});
+}
+
+#[test]
+fn candidate_exit_cannot_be_cancelled_within_cutoff() {
+	LeaveCancellationCutoff::set(1);
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			// exit of 1 is due in round 3, cancellable in round 1 only
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 2));
+			assert_ok!(ParachainStaking::cancel_leave_candidates(RuntimeOrigin::signed(1), 2));
+
+			roll_to_round_begin(2);
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(2), 2));
+			roll_to_round_begin(3);
+			assert_noop!(
+				ParachainStaking::cancel_leave_candidates(RuntimeOrigin::signed(2), 2),
+				Error::<Test>::CandidateExitCancellationClosed
+			);
+		});
+}
+
+#[test]
+fn force_restore_staking_state_replays_snapshot() {
+	use crate::{AutoCompoundConfig, DelegationAction, SnapshotRequest, StakingSnapshot};
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.build()
+		.execute_with(|| {
+			roll_to_round_begin(3);
+			let snapshot = StakingSnapshot {
+				// 4 has no funds to bond
+				candidates: vec![(1, 20), (4, 20)],
+				delegations: vec![
+					(2, 1, 20, Percent::from_percent(50), 3),
+					(3, 1, 10, Percent::zero(), 1),
+				],
+				requests: vec![SnapshotRequest {
+					delegator: 2,
+					candidate: 1,
+					action: DelegationAction::Decrease(5),
+					rounds_left: 1,
+				}],
+			};
+			assert_noop!(
+				ParachainStaking::force_restore_staking_state(
+					RuntimeOrigin::signed(1),
+					snapshot.clone()
+				),
+				sp_runtime::DispatchError::BadOrigin
+			);
+			assert_ok!(ParachainStaking::force_restore_staking_state(
+				RuntimeOrigin::root(),
+				snapshot
+			));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::StakingStateRestored {
+					candidates: 1,
+					delegations: 2,
+					requests: 1,
+				})
+			);
+			assert_eq!(ParachainStaking::candidate_info(1).unwrap().delegation_count, 2);
+			assert_eq!(
+				ParachainStaking::auto_compounding_delegations(1),
+				vec![AutoCompoundConfig {
+					delegator: 2,
+					value: Percent::from_percent(50),
+					every: 3
+				}]
+			);
+			let requests = ParachainStaking::delegation_scheduled_requests(1);
+			assert_eq!(requests.len(), 1);
+			assert_eq!(requests[0].when_executable, 4);
+			assert_eq!(ParachainStaking::delegator_state(2).unwrap().less_total, 5);
+		});
+}
+
+#[test]
+fn scheduled_parameter_change_applies_at_round_start() {
+	use crate::ParameterChange;
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let change = ParameterChange::CollatorCommission(Perbill::from_percent(30));
+			assert_noop!(
+				ParachainStaking::schedule_parameter_change(
+					RuntimeOrigin::root(),
+					1,
+					change.clone()
+				),
+				Error::<Test>::ParameterChangeNotInFuture
+			);
+			assert_noop!(
+				ParachainStaking::schedule_parameter_change(
+					RuntimeOrigin::root(),
+					3,
+					ParameterChange::TotalSelected(0)
+				),
+				Error::<Test>::CannotSetBelowMin
+			);
+			assert_ok!(ParachainStaking::schedule_parameter_change(
+				RuntimeOrigin::root(),
+				3,
+				change.clone()
+			));
+			let old = ParachainStaking::collator_commission();
+
+			roll_to_round_begin(2);
+			assert_eq!(ParachainStaking::collator_commission(), old);
+			roll_to_round_begin(3);
+			assert_eq!(ParachainStaking::collator_commission(), Perbill::from_percent(30));
+			assert!(ParachainStaking::scheduled_parameter_changes(3).is_empty());
+			assert_event_emitted!(Event::ParameterChangeApplied { round: 3, change });
+		});
+}
+
+#[test]
+fn inflation_decay_step_sets_inflation_at_round_start() {
+	use crate::InflationDecay;
+	ExtBuilder::default().build().execute_with(|| {
+		let lower = Range::from(Perbill::from_percent(1));
+		assert_noop!(
+			ParachainStaking::set_inflation_decay(
+				RuntimeOrigin::root(),
+				Some(InflationDecay::Piecewise(vec![]))
+			),
+			Error::<Test>::InvalidInflationDecay
+		);
+		assert_ok!(ParachainStaking::set_inflation_decay(
+			RuntimeOrigin::root(),
+			Some(InflationDecay::Piecewise(vec![(3, lower)]))
+		));
+
+		roll_to_round_begin(2);
+		assert_ne!(ParachainStaking::inflation_config().annual, lower);
+		roll_to_round_begin(3);
+		assert_eq!(ParachainStaking::inflation_config().annual, lower);
+	});
+}
+
+#[test]
+fn round_issuance_tapers_under_max_total_issuance() {
+	MaxTotalIssuance::set(300);
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			set_author(1, 1, 20);
+			roll_to_round_begin(3);
+			let capped: Vec<(u32, Balance, Balance)> = events()
+				.into_iter()
+				.filter_map(|e| match e {
+					Event::IssuanceCapped { round, uncapped, issued } =>
+						Some((round, uncapped, issued)),
+					_ => None,
+				})
+				.collect();
+			// 5% of the 100 left under the cap rather than 5% of the 200 issued
+			assert_eq!(capped, vec![(1, 10, 5)]);
+			assert!(Balances::total_issuance() < 300);
+		});
+}
+
+#[test]
+fn delegate_spread_splits_over_lowest_backed_candidates() {
+	use crate::SpreadStrategy;
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100)])
+		.with_candidates(vec![(1, 20), (2, 30), (3, 40)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParachainStaking::delegate_spread(
+					RuntimeOrigin::signed(4),
+					31,
+					0,
+					SpreadStrategy::LowestBacked,
+					3
+				),
+				Error::<Test>::NoDelegationSpreadTargets
+			);
+			assert_noop!(
+				ParachainStaking::delegate_spread(
+					RuntimeOrigin::signed(4),
+					31,
+					2,
+					SpreadStrategy::LowestBacked,
+					2
+				),
+				Error::<Test>::TooLowCandidateCountWeightHintDelegateSpread
+			);
+			assert_ok!(ParachainStaking::delegate_spread(
+				RuntimeOrigin::signed(4),
+				31,
+				2,
+				SpreadStrategy::LowestBacked,
+				3
+			));
+			let state = ParachainStaking::delegator_state(4).unwrap();
+			// the remainder of the split goes to the least backed candidate
+			assert_eq!(state.get_bond_amount(&1), Some(16));
+			assert_eq!(state.get_bond_amount(&2), Some(15));
+			assert_eq!(state.get_bond_amount(&3), None);
+			assert_eq!(state.total(), 31);
+		});
+}
+
+#[test]
+fn sqrt_per_bond_selection_favours_many_small_bonds() {
+	use crate::SelectionMode;
+	ExtBuilder::default()
+		.with_balances(vec![
+			(1, 100),
+			(2, 100),
+			(3, 100),
+			(4, 100),
+			(5, 100),
+			(6, 100),
+			(7, 100),
+			(8, 100),
+		])
+		.with_candidates(vec![(1, 40), (2, 41), (3, 42), (4, 43), (5, 44), (6, 12)])
+		.with_delegations(vec![(7, 6, 9), (8, 6, 9)])
+		.build()
+		.execute_with(|| {
+			roll_to_round_begin(2);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1, 2, 3, 4, 5]);
+
+			assert_noop!(
+				ParachainStaking::set_selection_mode(RuntimeOrigin::root(), SelectionMode::Stake),
+				Error::<Test>::NoWritingSameValue
+			);
+			assert_ok!(ParachainStaking::set_selection_mode(
+				RuntimeOrigin::root(),
+				SelectionMode::SqrtPerBond
+			));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::SelectionModeSet {
+					old: SelectionMode::Stake,
+					new: SelectionMode::SqrtPerBond,
+				})
+			);
+			// 3 + 3 + 3 for candidate 6 against 6 for every single-bond candidate
+			roll_to_round_begin(3);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![2, 3, 4, 5, 6]);
+		});
+}
+
+#[test]
+fn candidate_rankings_order_candidates_by_backing() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20), (2, 25)])
+		.with_delegations(vec![(3, 1, 10)])
+		.build()
+		.execute_with(|| {
+			set_author(1, 1, 15);
+			set_author(1, 2, 5);
+			roll_to_round_begin(2);
+			assert_eq!(ParachainStaking::candidate_rankings(), vec![(1, 30, 15, 3), (2, 25, 5, 4)]);
+			assert_eq!(ParachainStaking::estimated_apr(&3), Perbill::zero());
+		});
+}
+
+#[test]
+fn delegations_moving_between_top_and_bottom_emit_events() {
+	use crate::DelegationPosition;
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100), (6, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10), (3, 1, 11), (4, 1, 12), (5, 1, 13)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(6), 1, 14, 4, 0));
+			assert!(events().contains(&Event::DelegationMovedToBottom {
+				delegator: 2,
+				candidate: 1,
+				amount: 10,
+			}));
+			assert_eq!(
+				ParachainStaking::delegation_position(&1, &2),
+				Some(DelegationPosition::Bottom)
+			);
+			assert_eq!(
+				ParachainStaking::delegation_position(&1, &6),
+				Some(DelegationPosition::Top)
+			);
+
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 10));
+			assert!(events().contains(&Event::DelegationMovedToBottom {
+				delegator: 3,
+				candidate: 1,
+				amount: 11,
+			}));
+			assert!(events().contains(&Event::DelegationMovedToTop {
+				delegator: 2,
+				candidate: 1,
+				amount: 20,
+			}));
+			assert_eq!(
+				ParachainStaking::delegation_position(&1, &2),
+				Some(DelegationPosition::Top)
+			);
+			assert_eq!(
+				ParachainStaking::delegation_position(&1, &3),
+				Some(DelegationPosition::Bottom)
+			);
+			assert_eq!(ParachainStaking::delegation_position(&2, &3), None);
+		});
+}
+
+#[test]
+fn kicked_bottom_delegation_is_released_and_reported() {
+	ExtBuilder::default()
+		.with_balances((1..=10).map(|account| (account, 100)).collect())
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![
+			(2, 1, 20),
+			(3, 1, 20),
+			(4, 1, 20),
+			(5, 1, 20),
+			(6, 1, 10),
+			(7, 1, 11),
+			(8, 1, 12),
+			(9, 1, 13),
+		])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Balances::reserved_balance(6), 10);
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(10), 1, 14, 8, 0));
+			assert_eq!(Kicked::get(), vec![(6, 1, 10)]);
+			assert!(ParachainStaking::delegator_state(6).is_none());
+			// released straight away, without waiting for the revoke delay
+			assert_eq!(Balances::reserved_balance(6), 0);
+			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&6), 100);
+			assert_eq!(
+				ParachainStaking::delegation_position(&1, &10),
+				Some(crate::DelegationPosition::Bottom)
+			);
+		});
+}
+
+#[test]
+fn large_exposures_are_paged_and_paid_one_page_per_call() {
+	MaxExposurePageSize::set(2);
+	ExtBuilder::default()
+		.with_balances((1..=5).map(|account| (account, 100)).collect())
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10), (3, 1, 10), (4, 1, 10), (5, 1, 10)])
+		.build()
+		.execute_with(|| {
+			roll_to_round_begin(2);
+			assert_eq!(<crate::AtStake<Test>>::get(2, 1).delegations.len(), 2);
+			assert_eq!(ParachainStaking::at_stake_page(2, (1, 1)).map(|page| page.len()), Some(2));
+			assert_eq!(ParachainStaking::at_stake_page(2, (1, 2)), None);
+
+			set_author(2, 1, 20);
+			let payout = || crate::DelayedPayout {
+				round_issuance: 60,
+				total_staking_reward: 60,
+				collator_commission: Perbill::zero(),
+			};
+			let before: Vec<Balance> = (2..=5).map(Balances::free_balance).collect();
+			let rewarded = || {
+				(2..=5)
+					.zip(before.iter())
+					.filter(|(x, b)| Balances::free_balance(x) > **b)
+					.count()
+			};
+
+			assert_eq!(ParachainStaking::pay_one_collator_reward(2, payout()).0, Some((1, 60)));
+			assert_eq!(rewarded(), 2);
+			assert_eq!(ParachainStaking::paged_payout(2).map(|paged| paged.next_page), Some(1));
+
+			assert_eq!(ParachainStaking::pay_one_collator_reward(2, payout()).0, Some((1, 0)));
+			assert_eq!(rewarded(), 4);
+			assert_eq!(ParachainStaking::paged_payout(2), None);
+			assert_eq!(ParachainStaking::at_stake_page(2, (1, 1)), None);
+			assert_eq!(ParachainStaking::pay_one_collator_reward(2, payout()).0, None);
+		});
+}
+
+#[test]
+fn delayed_payouts_pay_several_collators_per_block_when_weight_allows() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20), (2, 20), (3, 20)])
+		.build()
+		.execute_with(|| {
+			for collator in 1..=3 {
+				set_author(1, collator, 10);
+			}
+			roll_to_round_begin(3);
+			assert_eq!(<crate::AwardedPts<Test>>::iter_prefix(1).count(), 2);
+			// the rest of round 1 is paid and cleaned up in the idle time of the same block
+			ParachainStaking::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+			assert_eq!(<crate::AwardedPts<Test>>::iter_prefix(1).count(), 0);
+			assert!(ParachainStaking::delayed_payouts(1).is_none());
+			assert_eq!(ParachainStaking::points(1), 0);
+		});
+}
+
+#[test]
+fn delayed_payouts_pay_one_collator_per_block_and_more_when_idle() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			set_author(1, 1, 10);
+			set_author(1, 2, 10);
+			start_next_round();
+			assert_eq!(start_next_round(), 3);
+			// the round change only prepares the payouts of round 1
+			assert!(ParachainStaking::delayed_payouts(1).is_some());
+			assert_eq!(<crate::AwardedPts<Test>>::iter_prefix(1).count(), 2);
+
+			ParachainStaking::on_initialize(System::block_number());
+			assert_eq!(<crate::AwardedPts<Test>>::iter_prefix(1).count(), 1);
+			ParachainStaking::on_idle(System::block_number(), Weight::zero());
+			assert_eq!(<crate::AwardedPts<Test>>::iter_prefix(1).count(), 1);
+
+			ParachainStaking::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+			assert_eq!(<crate::AwardedPts<Test>>::iter_prefix(1).count(), 0);
+			assert!(ParachainStaking::delayed_payouts(1).is_none());
+		});
+}
+
+#[test]
+fn candidate_pool_tracks_backing_and_count_per_candidate() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20), (2, 10)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(ParachainStaking::candidate_pool_count(), 2);
+			assert_eq!(ParachainStaking::candidate_pool(1), Some(20));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(3), 2, 15, 0, 0));
+			assert_eq!(ParachainStaking::candidate_pool(2), Some(25));
+			assert_ok!(ParachainStaking::go_offline(RuntimeOrigin::signed(1)));
+			assert_eq!(ParachainStaking::candidate_pool(1), None);
+			assert_eq!(ParachainStaking::candidate_pool_count(), 1);
+			assert_ok!(ParachainStaking::go_online(RuntimeOrigin::signed(1)));
+			assert_eq!(ParachainStaking::candidate_pool(1), Some(20));
+			assert_eq!(ParachainStaking::candidate_pool_count(), 2);
+		});
+}
+
+#[test]
+fn genesis_validation_rejects_bad_candidates_and_delegations() {
+	let config = |candidates: Vec<(AccountId, Balance)>,
+	              delegations: Vec<(AccountId, AccountId, Balance)>| {
+		pallet_parachain_staking::GenesisConfig::<Test> {
+			candidates,
+			delegations: delegations
+				.into_iter()
+				.map(|(delegator, target, amount)| (delegator, target, amount, Percent::zero()))
+				.collect(),
+			..Default::default()
+		}
+	};
+	assert_eq!(config(vec![(1, 20)], vec![(2, 1, 10)]).validate(), Ok(()));
+	assert!(config(vec![(1, 20), (1, 20)], vec![]).validate().is_err());
+	assert!(config(vec![(1, 5)], vec![]).validate().is_err());
+	assert!(config(vec![(1, 20)], vec![(2, 3, 10)]).validate().is_err());
+	assert!(config(vec![(1, 20)], vec![(2, 1, 10), (2, 1, 10)]).validate().is_err());
+	assert!(config(vec![(1, 20), (2, 20)], vec![(2, 1, 10)]).validate().is_err());
+}
+
+#[test]
+fn remote_reward_destination_batches_rewards_over_rounds() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParachainStaking::set_reward_destination(RuntimeOrigin::signed(3), Some(2000)),
+				Error::<Test>::DelegatorDNE
+			);
+			assert_ok!(ParachainStaking::set_reward_destination(
+				RuntimeOrigin::signed(2),
+				Some(2000)
+			));
+			let rewarded = || -> Balance {
+				events()
+					.into_iter()
+					.filter_map(|e| match e {
+						Event::Rewarded { account: 2, rewards } => Some(rewards),
+						_ => None,
+					})
+					.sum()
+			};
+			for round in 1..=3 {
+				set_author(round, 1, 20);
+			}
+			// rewards of rounds 1 and 2 are batched, reserved in the local account
+			roll_to_round_end(4);
+			assert!(rewarded() > 0);
+			assert!(Remitted::get().is_empty());
+			assert_eq!(ParachainStaking::remote_reward_destination(2).unwrap().pending, rewarded());
+			assert_eq!(Balances::reserved_balance(2), 20 + rewarded());
+			// and queued with those of round 3, two rounds after registering, to be remitted
+			// when blocks have room
+			roll_to_round_end(5);
+			assert!(Remitted::get().is_empty());
+			ParachainStaking::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+			assert_eq!(Remitted::get(), vec![(2, 2000, rewarded())]);
+			assert_eq!(
+				ParachainStaking::remote_reward_destination(2),
+				Some(crate::RemoteRewardDestination {
+					destination: 2000,
+					pending: 0,
+					last_remitted: 3
+				})
+			);
+			assert_eq!(Balances::reserved_balance(2), 20);
+			assert_ok!(ParachainStaking::set_reward_destination(RuntimeOrigin::signed(2), None));
+			assert_eq!(ParachainStaking::remote_reward_destination(2), None);
+		});
+}
+
+#[test]
+fn candidates_without_session_keys_are_not_selected() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(2), 30, 1));
+			roll_to_round_begin(2);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1]);
+
+			assert_noop!(
+				ParachainStaking::rotate_and_set_keys(
+					RuntimeOrigin::signed(3),
+					UintAuthorityId(3).into(),
+					vec![]
+				),
+				Error::<Test>::CandidateDNE
+			);
+			assert_ok!(ParachainStaking::rotate_and_set_keys(
+				RuntimeOrigin::signed(2),
+				UintAuthorityId(2).into(),
+				vec![]
+			));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::CandidateKeysRotated { candidate: 2 })
+			);
+			roll_to_round_begin(3);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1, 2]);
+		});
+}
+
+#[test]
+fn collator_without_points_is_kicked_after_max_offline_rounds() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			MaxOfflineRounds::set(2);
+			for round in 1..=2 {
+				set_author(round, 1, 20);
+			}
+			roll_to_round_begin(2);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1, 2]);
+			assert!(events().contains(&Event::CollatorMissedRound {
+				collator: 2,
+				round: 1,
+				consecutive_missed: 1,
+			}));
+
+			roll_to_round_begin(3);
+			assert!(
+				events().contains(&Event::CandidateKickedForInactivity { candidate: 2, round: 2 })
+			);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1]);
+			assert!(!ParachainStaking::candidate_info(2).unwrap().is_active());
+		});
+}
+
+#[test]
+fn invulnerables_are_not_kicked_for_missed_rounds() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			MaxOfflineRounds::set(2);
+			crate::InvulnerableCandidates::<Test>::put(frame_support::BoundedVec::truncate_from(
+				vec![2],
+			));
+			for round in 1..=3 {
+				set_author(round, 1, 20);
+			}
+			roll_to_round_begin(4);
+			assert!(!events().iter().any(|e| matches!(
+				e,
+				Event::CollatorMissedRound { collator: 2, .. } |
+					Event::CandidateKickedForInactivity { candidate: 2, .. }
+			)));
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1, 2]);
+			assert!(ParachainStaking::candidate_info(2).unwrap().is_active());
+		});
+}
+
+#[test]
+fn compact_payout_events_summarize_rewards_per_collator() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10), (3, 1, 10)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParachainStaking::set_compact_payout_events(RuntimeOrigin::root(), false),
+				Error::<Test>::NoWritingSameValue
+			);
+			assert_ok!(ParachainStaking::set_compact_payout_events(RuntimeOrigin::root(), true));
+			set_author(1, 1, 20);
+			let events_before = events().len();
+			ParachainStaking::pay_one_collator_reward(
+				1,
+				crate::DelayedPayout {
+					round_issuance: 100,
+					total_staking_reward: 100,
+					collator_commission: Perbill::zero(),
+				},
+			);
+
+			let payout_events = events().split_off(events_before);
+			assert!(!payout_events.iter().any(|e| matches!(e, Event::Rewarded { .. })));
+			match payout_events.last() {
+				Some(Event::CollatorPayoutSummary { collator: 1, round: 1, rewards }) => {
+					let mut rewards = rewards.clone().into_inner();
+					rewards.sort();
+					assert_eq!(rewards, vec![(1, 50), (2, 25), (3, 25)]);
+				},
+				e => panic!("expected a payout summary, got {:?}", e),
+			}
+			assert_eq!(Balances::free_balance(2), 100 - 10 + 25);
+		});
+}
+
+#[test]
+fn distributed_fees_split_between_treasury_and_next_round_paid_out() {
+	// total rewards paid for round 1, after `fees` were distributed in it
+	let paid_for_round_one = |fees: Balance| {
+		ExtBuilder::default()
+			.with_balances(vec![(1, 100), (2, 100), (8, fees + 1), (9, 10)])
+			.with_candidates(vec![(1, 20)])
+			.with_delegations(vec![(2, 1, 20)])
+			.build()
+			.execute_with(|| {
+				assert_ok!(ParachainStaking::set_staker_fee_share(
+					RuntimeOrigin::root(),
+					Percent::from_percent(25)
+				));
+				let issuance = Balances::total_issuance();
+				// the existential deposit stays in the pot
+				ParachainStaking::distribute_fees(1, &8, &9);
+				if fees > 0 {
+					assert!(events().contains(&Event::FeesDistributed {
+						round: 1,
+						to_treasury: fees * 3 / 4,
+						to_stakers: fees / 4,
+					}));
+				}
+				assert_eq!(Balances::free_balance(8), 1);
+				assert_eq!(Balances::free_balance(9), 10 + fees * 3 / 4);
+				assert_eq!(Balances::total_issuance(), issuance - fees / 4);
+				assert_eq!(ParachainStaking::issuance_boost(), fees / 4);
+
+				set_author(1, 1, 20);
+				roll_to_round_begin(3);
+				assert_eq!(ParachainStaking::issuance_boost(), 0);
+				events()
+					.into_iter()
+					.filter_map(|e| match e {
+						Event::Rewarded { rewards, .. } => Some(rewards),
+						_ => None,
+					})
+					.sum::<Balance>()
+			})
+	};
+
+	let boosted = paid_for_round_one(40);
+	let unboosted = paid_for_round_one(0);
+	// the burned fees shrink the inflation a little and each reward is rounded down
+	assert!((8..=10).contains(&(boosted - unboosted)), "{} vs {}", boosted, unboosted);
+}
+
+#[test]
+fn on_new_round_called_once_per_round() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			NewRounds::set(vec![]);
+			roll_to_round_begin(3);
+			assert_eq!(NewRounds::get(), vec![2, 3]);
+		});
+}
+
+#[test]
+fn collator_backing_is_the_round_snapshot() {
+	use crate::CollatorBacking;
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20), (3, 20)])
+		.with_delegations(vec![(2, 1, 30)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(ParachainStaking::backing(1, &1), Some(50));
+			assert_eq!(ParachainStaking::backing(1, &3), Some(20));
+			// delegations made during a round back the collator from the next one
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 10));
+			assert_eq!(ParachainStaking::backing(1, &1), Some(50));
+			roll_to_round_begin(2);
+			assert_eq!(ParachainStaking::backing(2, &1), Some(60));
+			assert_eq!(ParachainStaking::backing(2, &2), None);
+		});
+}
+
+#[test]
+fn pending_payouts_counts_collators_left_to_pay() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			set_author(1, 1, 20);
+			set_author(1, 2, 10);
+			assert_eq!(ParachainStaking::pending_payouts(), 0);
+
+			ParachainStaking::prepare_staking_payouts(1 + RewardPaymentDelay::get());
+			assert_eq!(ParachainStaking::pending_payouts(), 2);
+			// paid out in the first blocks of round 3, one collator per block
+			roll_to_round_begin(3);
+			assert_eq!(ParachainStaking::pending_payouts(), 1);
+			roll_one_block();
+			assert_eq!(ParachainStaking::pending_payouts(), 0);
+		});
+}
+
+#[test]
+fn dropped_collators_are_told_the_stake_they_lack() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100), (6, 100), (7, 100)])
+		.with_candidates(vec![(1, 30), (2, 30), (3, 30), (4, 30), (5, 20), (6, 10)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1, 2, 3, 4, 5]);
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(7), 6, 15, 0, 0));
+			// stake added during a round counts from the selection after the next one
+			roll_to_round_begin(3);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1, 2, 3, 4, 6]);
+			let warnings = events()
+				.into_iter()
+				.filter(|e| matches!(e, Event::CandidateBelowSelectionThreshold { .. }))
+				.collect::<Vec<_>>();
+			assert_eq!(
+				warnings,
+				vec![Event::CandidateBelowSelectionThreshold {
+					round: 3,
+					candidate: 5,
+					total_counted: 20,
+					stake_gap: 5,
+				}]
+			);
+		});
+}
+
+#[test]
+fn authority_bonus_paid_by_score_out_of_round_inflation() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1_000), (2, 1_000), (3, 1_000)])
+		.with_candidates(vec![(1, 20), (3, 20)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			let issuance = Balances::total_issuance();
+			// nothing is minted until inflation was set aside for it
+			ParachainStaking::pay_authority_bonus(1, vec![(1, 3), (3, 1)]);
+			assert_eq!(Balances::total_issuance(), issuance);
+
+			assert_ok!(ParachainStaking::set_authority_bonus_share(
+				RuntimeOrigin::root(),
+				Percent::from_percent(40)
+			));
+			set_author(1, 1, 20);
+			ParachainStaking::prepare_staking_payouts(1 + RewardPaymentDelay::get());
+			// 40% of the 5% round inflation of the 3_000 issued, out of the staking rewards
+			assert_eq!(ParachainStaking::authority_bonus_reserve(), 60);
+			assert_eq!(ParachainStaking::delayed_payouts(1).unwrap().total_staking_reward, 90);
+			assert!(events().contains(&Event::ReservedForAuthorityBonus { value: 60 }));
+			assert_eq!(Balances::total_issuance(), issuance);
+
+			// waits for scores
+			ParachainStaking::pay_authority_bonus(1, vec![]);
+			assert_eq!(ParachainStaking::authority_bonus_reserve(), 60);
+
+			ParachainStaking::pay_authority_bonus(1, vec![(1, 3), (3, 1)]);
+			assert_eq!(Balances::total_issuance(), issuance + 60);
+			assert_eq!(ParachainStaking::authority_bonus_reserve(), 0);
+			assert_eq!(Balances::free_balance(1), 1_000 - 20 + 45);
+			assert_eq!(Balances::free_balance(3), 1_000 - 20 + 15);
+			assert!(events().contains(&Event::AuthorityBonusPaid { round: 1, total: 60 }));
+			assert!(events().contains(&Event::Rewarded { account: 3, rewards: 15 }));
+		});
+}
+
+#[test]
+fn usage_rewards_reserve_paid_from_inflation() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			let reserve =
+				crate::ParachainBondConfig { account: 7, percent: Percent::from_percent(10) };
+			assert_ok!(ParachainStaking::set_usage_rewards_reserve(
+				RuntimeOrigin::root(),
+				Some(reserve.clone())
+			));
+			assert!(events()
+				.contains(&Event::UsageRewardsReserveSet { reserve: Some(reserve.clone()) }));
+			assert_noop!(
+				ParachainStaking::set_usage_rewards_reserve(RuntimeOrigin::root(), Some(reserve)),
+				Error::<Test>::NoWritingSameValue
+			);
+
+			set_author(1, 1, 20);
+			roll_to_round_begin(3);
+			let reserved = events()
+				.into_iter()
+				.find_map(|e| match e {
+					Event::ReservedForUsageRewards { account: 7, value } => Some(value),
+					_ => None,
+				})
+				.expect("usage rewards reserved for the round");
+			assert!(reserved > 0);
+			assert_eq!(Balances::free_balance(7), reserved);
+
+			assert_ok!(ParachainStaking::set_usage_rewards_reserve(RuntimeOrigin::root(), None));
+			assert_eq!(ParachainStaking::usage_rewards_reserve(), None);
+		});
+}
+
+#[test]
+fn inflation_shares_set_aside_cannot_exceed_the_whole() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the parachain bond takes 30%
+		assert_ok!(ParachainStaking::set_authority_bonus_share(
+			RuntimeOrigin::root(),
+			Percent::from_percent(40)
+		));
+		let reserve = |percent| {
+			Some(crate::ParachainBondConfig { account: 7, percent: Percent::from_percent(percent) })
+		};
+		assert_noop!(
+			ParachainStaking::set_usage_rewards_reserve(RuntimeOrigin::root(), reserve(31)),
+			Error::<Test>::InflationSharesExceedWhole
+		);
+		assert_ok!(ParachainStaking::set_usage_rewards_reserve(RuntimeOrigin::root(), reserve(30)));
+		assert_noop!(
+			ParachainStaking::set_parachain_bond_reserve_percent(
+				RuntimeOrigin::root(),
+				Percent::from_percent(31)
+			),
+			Error::<Test>::InflationSharesExceedWhole
+		);
+		assert_noop!(
+			ParachainStaking::set_authority_bonus_share(
+				RuntimeOrigin::root(),
+				Percent::from_percent(41)
+			),
+			Error::<Test>::InflationSharesExceedWhole
+		);
+		// lowering one share makes room for another
+		assert_ok!(ParachainStaking::set_parachain_bond_reserve_percent(
+			RuntimeOrigin::root(),
+			Percent::from_percent(20)
+		));
+		assert_ok!(ParachainStaking::set_authority_bonus_share(
+			RuntimeOrigin::root(),
+			Percent::from_percent(50)
+		));
+	});
+}
+
+#[test]
+fn delegate_via_xcm_emits_remote_delegation() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::delegate_via_xcm(RuntimeOrigin::signed(2), 1, 10, 0, 0));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::RemoteDelegation {
+					delegator: 2,
+					candidate: 1,
+					amount: 10,
+				})
+			);
+			assert_eq!(ParachainStaking::delegator_state(2).map(|state| state.total), Some(10));
+		});
+}
+
+#[test]
+fn rotate_invulnerables_installs_successors() {
+	ExtBuilder::default().build().execute_with(|| {
+		crate::InvulnerableCandidates::<Test>::put(frame_support::BoundedVec::truncate_from(vec![
+			1,
+		]));
+		assert_noop!(
+			ParachainStaking::set_successor_invulnerables(RuntimeOrigin::root(), vec![2]),
+			Error::<Test>::ValidatorNotRegistered
+		);
+		assert_ok!(ParachainStaking::set_successor_invulnerables(RuntimeOrigin::root(), vec![]));
+		assert_eq!(
+			ParachainStaking::successor_invulnerables().map(|s| s.into_inner()),
+			Some(vec![])
+		);
+		assert_eq!(ParachainStaking::invulnerable_candidates(), vec![1]);
+
+		ParachainStaking::rotate_invulnerables();
+		assert_eq!(ParachainStaking::invulnerable_candidates(), Vec::<AccountId>::new());
+		assert_eq!(ParachainStaking::successor_invulnerables(), None);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::ParachainStaking(Event::NewInvulnerables { invulnerables: vec![] })
+		);
+
+		// without successors the invulnerables are kept
+		crate::InvulnerableCandidates::<Test>::put(frame_support::BoundedVec::truncate_from(vec![
+			1,
+		]));
+		ParachainStaking::rotate_invulnerables();
+		assert_eq!(ParachainStaking::invulnerable_candidates(), vec![1]);
+	});
+}
+
+#[test]
+fn parachain_bond_reserves_are_tracked_and_swept_to_treasury() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (11, 1)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParachainStaking::sweep_parachain_bond(RuntimeOrigin::root()),
+				Error::<Test>::NothingToSweep
+			);
+			assert_ok!(ParachainStaking::set_parachain_bond_account(RuntimeOrigin::root(), 11));
+			set_author(1, 1, 20);
+			set_author(2, 1, 20);
+			roll_to_round_begin(4);
+			let reserved = events()
+				.into_iter()
+				.filter_map(|e| match e {
+					Event::ReservedForParachainBond { account: 11, value } => Some(value),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(reserved.len(), 2);
+			let total = reserved.iter().sum::<u128>();
+			assert_eq!(ParachainStaking::total_reserved_for_parachain_bond(), total);
+
+			assert_ok!(ParachainStaking::sweep_parachain_bond(RuntimeOrigin::root()));
+			assert_eq!(Balances::free_balance(11), 1);
+			assert_eq!(Balances::free_balance(99), total);
+			assert!(events().contains(&Event::ParachainBondSwept {
+				account: 11,
+				treasury: 99,
+				value: total,
+			}));
+			// the cumulative amount survives the sweep
+			assert_eq!(ParachainStaking::total_reserved_for_parachain_bond(), total);
+		});
+}
+
+#[test]
+fn reward_curve_guarantees_a_minimum_collator_share() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 80)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::set_collator_commission(
+				RuntimeOrigin::root(),
+				Perbill::zero()
+			));
+			let curve = crate::DelegatorRewardCurve::MinCollatorShare(Perbill::from_percent(50));
+			assert_ok!(ParachainStaking::set_delegator_reward_curve(RuntimeOrigin::root(), curve));
+			assert_eq!(ParachainStaking::delegator_reward_curve(), curve);
+			assert_noop!(
+				ParachainStaking::set_delegator_reward_curve(RuntimeOrigin::root(), curve),
+				Error::<Test>::NoWritingSameValue
+			);
+
+			set_author(1, 1, 20);
+			roll_to_round_begin(3);
+			roll_one_block();
+			let reward = |who| {
+				events()
+					.into_iter()
+					.find_map(|e| match e {
+						Event::Rewarded { account, rewards } if account == who => Some(rewards),
+						_ => None,
+					})
+					.expect("rewarded for round 1")
+			};
+			// a 20% self bond still earns half of the reward
+			let (collator, delegator) = (reward(1), reward(2));
+			assert!(collator > 0);
+			assert!(collator.abs_diff(delegator) <= 1);
+		});
+}
+
+#[test]
+fn unexecuted_delegation_requests_expire() {
+	RequestExpiryRounds::set(2);
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			assert_eq!(ParachainStaking::delegator_state(2).unwrap().less_total, 10);
+			// executable from round 3, kept until the selection of round 5
+			roll_to_round_begin(4);
+			assert!(ParachainStaking::delegation_request_exists(&1, &2));
+			roll_to_round_begin(5);
+			assert!(!ParachainStaking::delegation_request_exists(&1, &2));
+			assert_eq!(ParachainStaking::delegator_state(2).unwrap().less_total, 0);
+			assert!(events().contains(&Event::DelegationRequestExpired {
+				delegator: 2,
+				cancelled_request: crate::CancelledScheduledRequest {
+					when_executable: 3,
+					action: crate::DelegationAction::Revoke(10),
+				},
+				collator: 1,
+			}));
+			// the delegation is rewarded again
+			let (_, rewardable) = ParachainStaking::at_stake(5, 1)
+				.delegations
+				.into_iter()
+				.map(|d| (d.owner, d.amount))
+				.find(|(owner, _)| *owner == 2)
+				.expect("delegation snapshotted");
+			assert_eq!(rewardable, 10);
+		});
+}
+
+#[test]
+fn pending_revocations_are_rewarded_as_configured() {
+	let rewardable = |policy| {
+		PendingRequestRewardPolicy::set(policy);
+		ExtBuilder::default()
+			.with_balances(vec![(1, 100), (2, 100)])
+			.with_candidates(vec![(1, 20)])
+			.with_delegations(vec![(2, 1, 40)])
+			.build()
+			.execute_with(|| {
+				assert_ok!(ParachainStaking::schedule_revoke_delegation(
+					RuntimeOrigin::signed(2),
+					1
+				));
+				// executable in round 3, one of the two rounds of notice left at round 2
+				roll_to_round_begin(2);
+				let snapshot = ParachainStaking::at_stake(2, 1);
+				(snapshot.delegations[0].amount, snapshot.total)
+			})
+	};
+	assert_eq!(rewardable(crate::PendingRequestRewards::Forfeit), (0, 20));
+	assert_eq!(rewardable(crate::PendingRequestRewards::UntilExecution), (40, 60));
+	assert_eq!(rewardable(crate::PendingRequestRewards::ProRata), (20, 40));
+}
+
+#[test]
+fn leave_all_revokes_every_delegation_in_one_call() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.with_delegations(vec![(3, 1, 3), (3, 2, 3)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::schedule_leave_all(RuntimeOrigin::signed(3)));
+			assert!(events().contains(&Event::DelegatorExitScheduled {
+				round: 1,
+				delegator: 3,
+				scheduled_exit: 3,
+			}));
+			assert_noop!(
+				ParachainStaking::schedule_leave_all(RuntimeOrigin::signed(3)),
+				Error::<Test>::PendingDelegationRequestAlreadyExists
+			);
+			assert_noop!(
+				ParachainStaking::execute_leave_all(RuntimeOrigin::signed(1), 3),
+				Error::<Test>::DelegatorCannotLeaveYet
+			);
+
+			roll_to_round_begin(3);
+			// revoking one by one would leave less than the minimum delegator stake
+			assert_noop!(
+				ParachainStaking::execute_delegation_request(RuntimeOrigin::signed(3), 3, 1),
+				Error::<Test>::DelegatorBondBelowMin
+			);
+			assert_ok!(ParachainStaking::execute_leave_all(RuntimeOrigin::signed(1), 3));
+			assert!(ParachainStaking::delegator_state(3).is_none());
+			assert!(ParachainStaking::delegation_scheduled_requests(1).is_empty());
+			assert!(ParachainStaking::delegation_scheduled_requests(2).is_empty());
+			assert!(events().contains(&Event::DelegatorLeft { delegator: 3, unstaked_amount: 6 }));
+		});
+}
+
+#[test]
+fn delegate_applies_the_default_auto_compound() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 10, 0, 0));
+			assert!(ParachainStaking::auto_compounding_delegations(1).is_empty());
+
+			let default = Percent::from_percent(50);
+			assert_ok!(ParachainStaking::set_default_auto_compound(RuntimeOrigin::root(), default));
+			assert!(events()
+				.contains(&Event::DefaultAutoCompoundSet { old: Percent::zero(), new: default }));
+			assert_noop!(
+				ParachainStaking::set_default_auto_compound(RuntimeOrigin::root(), default),
+				Error::<Test>::NoWritingSameValue
+			);
+
+			// no auto-compounding hint is needed, even once the candidate has some
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(3), 1, 10, 1, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(4), 1, 10, 2, 0));
+			let values = ParachainStaking::auto_compounding_delegations(1)
+				.into_iter()
+				.map(|config| (config.delegator, config.value))
+				.collect::<Vec<_>>();
+			assert_eq!(values, vec![(3, default), (4, default)]);
+		});
+}
+
+#[test]
+fn rounds_follow_the_relay_chain_once_aligned() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParachainStaking::set_relay_round_length(RuntimeOrigin::root(), Some(0)),
+				Error::<Test>::CannotSetBelowMin
+			);
+			RelayBlockNumber::set(3);
+			assert_ok!(ParachainStaking::set_relay_round_length(RuntimeOrigin::root(), Some(10)));
+			assert_eq!(ParachainStaking::relay_round_length(), Some(10));
+
+			// parachain blocks alone no longer end the round
+			roll_to(12);
+			assert_eq!(ParachainStaking::round().current, 1);
+
+			// the relay chain moved past a multiple of 10 while no block was produced
+			RelayBlockNumber::set(27);
+			roll_one_block();
+			assert_eq!(ParachainStaking::round().current, 2);
+			assert_eq!(ParachainStaking::round_relay_start(), 27);
+			RelayBlockNumber::set(29);
+			roll_one_block();
+			assert_eq!(ParachainStaking::round().current, 2);
+			RelayBlockNumber::set(30);
+			roll_one_block();
+			assert_eq!(ParachainStaking::round().current, 3);
+
+			// back to the session schedule
+			assert_ok!(ParachainStaking::set_relay_round_length(RuntimeOrigin::root(), None));
+			assert!(events().contains(&Event::RelayRoundLengthSet { old: Some(10), new: None }));
+			roll_to(20);
+			assert_eq!(ParachainStaking::round().current, 4);
+		});
+}
+
+#[test]
+fn unpaid_rounds_are_swept_to_the_treasury() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			roll_to_round_begin(5);
+			// round 1 was left with collator 2 unpaid after collator 1 was paid 30 of 80
+			<crate::DelayedPayouts<Test>>::insert(
+				1,
+				crate::DelayedPayout {
+					round_issuance: 100,
+					total_staking_reward: 80,
+					collator_commission: Perbill::zero(),
+				},
+			);
+			<crate::Points<Test>>::insert(1, 40);
+			<crate::AwardedPts<Test>>::insert(1, 2, 20);
+			<crate::RoundPaidOut<Test>>::insert(1, 30);
+
+			assert_noop!(
+				ParachainStaking::sweep_unpaid_rounds(RuntimeOrigin::signed(1), 3),
+				Error::<Test>::RoundStillPaying
+			);
+			assert_noop!(
+				ParachainStaking::sweep_unpaid_rounds(RuntimeOrigin::signed(1), 0),
+				Error::<Test>::NothingToSweep
+			);
+			assert_ok!(ParachainStaking::sweep_unpaid_rounds(RuntimeOrigin::signed(1), 2));
+			assert_eq!(Balances::free_balance(99), 50);
+			assert!(events().contains(&Event::UnpaidRoundSwept {
+				round: 1,
+				unpaid_collators: 1,
+				treasury: 99,
+				value: 50,
+			}));
+			assert_eq!(ParachainStaking::delayed_payouts(1), None);
+			assert_eq!(ParachainStaking::awarded_pts(1, 2), 0);
+			assert_eq!(ParachainStaking::points(1), 0);
+			assert!(ParachainStaking::stale_at_stake(1).is_some());
+			assert_noop!(
+				ParachainStaking::sweep_unpaid_rounds(RuntimeOrigin::signed(1), 2),
+				Error::<Test>::NothingToSweep
+			);
+		});
+}
+
+#[test]
+fn round_payouts_can_be_estimated_before_they_are_paid() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.with_delegations(vec![(3, 1, 10)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::set_parachain_bond_reserve_percent(
+				RuntimeOrigin::root(),
+				Percent::zero()
+			));
+			set_author(1, 1, 20);
+			set_author(1, 2, 10);
+			roll_to_round_begin(2);
+			let mut estimate = ParachainStaking::estimate_round_payouts(1);
+			estimate.sort_by_key(|payout| payout.collator);
+			assert_eq!(estimate.len(), 2);
+			assert!(estimate[1].delegator_rewards.is_empty());
+			assert_eq!(estimate[0].delegator_rewards.len(), 1);
+
+			roll_to_round_begin(3);
+			roll_one_block();
+			let rewarded = |who| {
+				events().into_iter().find_map(|e| match e {
+					Event::Rewarded { account, rewards } if account == who => Some(rewards),
+					_ => None,
+				})
+			};
+			assert_eq!(rewarded(1), Some(estimate[0].collator_reward));
+			assert_eq!(rewarded(3), Some(estimate[0].delegator_rewards[0].1));
+			assert_eq!(rewarded(2), Some(estimate[1].collator_reward));
+			assert!(ParachainStaking::estimate_round_payouts(1).is_empty());
+		});
+}
+
+#[test]
+fn invulnerables_are_bounded_by_the_storage_version_5_migration() {
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		StorageValue,
+	};
+	use parity_scale_codec::Encode;
+
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::new(4).put::<ParachainStaking>();
+		// stored unbounded before version 5, one past `MaxInvulnerables` here
+		let unbounded = (1..=11).collect::<Vec<AccountId>>();
+		unhashed::put_raw(
+			&crate::InvulnerableCandidates::<Test>::hashed_key(),
+			&unbounded.encode(),
+		);
+		unhashed::put_raw(&crate::SuccessorInvulnerables::<Test>::hashed_key(), &vec![2].encode());
+
+		crate::migrations::BoundInvulnerables::<Test>::on_runtime_upgrade();
+		assert_eq!(ParachainStaking::invulnerable_candidates(), unbounded[..10].to_vec());
+		assert_eq!(
+			ParachainStaking::successor_invulnerables().map(|s| s.into_inner()),
+			Some(vec![2])
+		);
+		assert_eq!(ParachainStaking::on_chain_storage_version(), 5);
+	});
+}
+
+#[test]
+fn paged_election_elects_as_the_whole_pool_with_changes_made_while_it_runs() {
+	ElectionLookahead::set(3);
+	ExtBuilder::default()
+		.with_balances((1..=7).map(|account| (account, 100)).collect())
+		.with_candidates((1..=7).map(|account| (account, 19 + account as Balance)).collect())
+		.build()
+		.execute_with(|| {
+			roll_to_round_begin(2);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![3, 4, 5, 6, 7]);
+			assert!(ParachainStaking::ongoing_election().is_none());
+
+			// round 2 is expected to end at block 10, so its election begins 3 blocks ahead
+			roll_to(7);
+			let election = ParachainStaking::ongoing_election().expect("election began");
+			assert_eq!((election.round, election.complete), (2, false));
+			assert_eq!(election.leaders.len(), 2);
+
+			roll_to(8);
+			assert_ok!(ParachainStaking::candidate_bond_more(RuntimeOrigin::signed(1), 30));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(7), 7));
+			assert_eq!(<crate::ElectionRescore<Test>>::iter_keys().count(), 2);
+
+			roll_to(9);
+			let expected = vec![1, 3, 4, 5, 6];
+			assert_eq!(ParachainStaking::compute_top_candidates(), expected);
+			assert_eq!(ParachainStaking::paged_top_candidates(), Some(expected.clone()));
+			assert!(ParachainStaking::ongoing_election().is_none());
+			assert_eq!(<crate::ElectionRescore<Test>>::iter_keys().count(), 0);
+
+			roll_to_round_begin(3);
+			assert_eq!(ParachainStaking::selected_candidates(), expected);
+		});
+}
+
+#[test]
+fn funds_still_vesting_can_be_delegated() {
+	use frame_support::traits::VestingSchedule;
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 0)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			// as `pallet_ecdsa_claims` vests the funds of claimants
+			assert_ok!(<Vesting as VestingSchedule<_>>::add_vesting_schedule(&2, 80, 1, 100));
+			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 100);
+
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(2), 1, 90, 0, 0));
+			assert_eq!(ParachainStaking::vested_stake(2), 80);
+			assert_eq!(Balances::reserved_balance(2), 10);
+			assert_eq!(Balances::free_balance(2), 90);
+			assert_eq!(ParachainStaking::get_delegator_stakable_free_balance(&2), 10);
+			assert_eq!(ParachainStaking::do_try_state(), Ok(()));
+			assert_noop!(
+				ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(2), 1, 11),
+				Error::<Test>::InsufficientBalance
+			);
+			assert_noop!(
+				Balances::transfer(RuntimeOrigin::signed(2), 3, 11),
+				pallet_balances::Error::<Test>::LiquidityRestrictions
+			);
+
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			roll_to_round_begin(3);
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
+			assert_eq!(ParachainStaking::vested_stake(2), 0);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert!(Balances::locks(2).iter().all(|lock| lock.id != crate::VESTING_STAKE_LOCK_ID));
+			// only the vesting lock is left
+			assert_ok!(Balances::transfer(RuntimeOrigin::signed(2), 3, 20));
+			assert_noop!(
+				Balances::transfer(RuntimeOrigin::signed(2), 3, 1),
+				pallet_balances::Error::<Test>::LiquidityRestrictions
+			);
+		});
+}
+
+#[test]
+fn slashes_reach_self_bond_still_vesting_once_the_held_bond_is_gone() {
+	use frame_support::traits::VestingSchedule;
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(2, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Vesting as VestingSchedule<_>>::add_vesting_schedule(&1, 60, 1, 100));
+			assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(1), 70, 1));
+			assert_eq!(ParachainStaking::vested_stake(1), 60);
+			assert_eq!(Balances::reserved_balance(1), 10);
+
+			ParachainStaking::candidate_slash(&1, 15);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::CandidateSlashed {
+					candidate: 1,
+					amount: 15,
+					new_bond: 55,
+				})
+			);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(ParachainStaking::vested_stake(1), 55);
+			assert_eq!(Balances::total_balance(&1), 85);
+			assert_eq!(ParachainStaking::do_try_state(), Ok(()));
+		});
+}
+
+#[test]
+fn delegation_memos_are_kept_until_the_delegation_ends() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20), (2, 20), (3, 20), (4, 20)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.build()
+		.execute_with(|| {
+			let memo: crate::DelegationMemo = b"campaign".to_vec().try_into().unwrap();
+			assert_ok!(ParachainStaking::delegate_with_memo(
+				RuntimeOrigin::signed(3),
+				1,
+				10,
+				memo.clone(),
+				0,
+				0
+			));
+			assert_last_event!(RuntimeEvent::ParachainStaking(Event::DelegationMemoSet {
+				delegator: 3,
+				candidate: 1,
+				memo: memo.clone(),
+			}));
+			assert_eq!(ParachainStaking::delegation_memo(3, 1), Some(memo.clone()));
+			assert_ok!(ParachainStaking::delegate_with_memo(
+				RuntimeOrigin::signed(4),
+				2,
+				10,
+				memo.clone(),
+				0,
+				0
+			));
+			// the memo delegation is not auto-compounded
+			assert_eq!(
+				AutoCompoundDelegations::<Test>::get_storage(&1).get_for_delegator(&3),
+				None
+			);
+
+			// revoking the delegation drops its memo
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(3), 1));
+			roll_to(10);
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(3),
+				3,
+				1
+			));
+			assert_eq!(ParachainStaking::delegation_memo(3, 1), None);
+
+			// and so does the candidate leaving
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(2), 2));
+			roll_to(20);
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(2), 2, 1));
+			assert_eq!(ParachainStaking::delegation_memo(4, 2), None);
+		});
+}
+
+#[test]
+fn authors_of_fuller_blocks_are_awarded_bonus_points() {
+	use frame_support::{
+		pallet_prelude::DispatchClass,
+		traits::{Get, OnFinalize},
+	};
+	ExtBuilder::default().build().execute_with(|| {
+		MaxBonusPointsPerBlock::set(10);
+		let author = <Test as crate::Config>::BlockAuthor::get();
+		let max_block = <Test as frame_system::Config>::BlockWeights::get().max_block;
+
+		// an empty block earns the base points only
+		ParachainStaking::on_finalize(1);
+		assert_eq!(ParachainStaking::awarded_pts(1, author), 20);
+
+		// a half full block earns half the bonus
+		System::register_extra_weight_unchecked(
+			Weight::from_ref_time(max_block.ref_time() / 2),
+			DispatchClass::Mandatory,
+		);
+		ParachainStaking::on_finalize(1);
+		assert_eq!(ParachainStaking::awarded_pts(1, author), 20 + (20 + 5));
+
+		// an overfull block earns no more than the whole bonus
+		System::register_extra_weight_unchecked(max_block, DispatchClass::Mandatory);
+		ParachainStaking::on_finalize(1);
+		assert_eq!(ParachainStaking::awarded_pts(1, author), 20 + (20 + 5) + (20 + 10));
+		assert_eq!(ParachainStaking::points(1), 75);
+	});
+}
+
+#[test]
+fn large_delegations_and_bond_increases_are_deferred_from_selection() {
+	ExtBuilder::default()
+		.with_balances((1..=9).map(|account| (account, 100)).collect())
+		.with_candidates(vec![(1, 40), (2, 41), (3, 42), (4, 43), (5, 44), (6, 30)])
+		.with_delegations(vec![(7, 6, 5)])
+		.build()
+		.execute_with(|| {
+			DeferredDelegationThreshold::set(20);
+
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(8), 6, 25, 1, 0));
+			assert_event_emitted!(Event::DelegationDeferred {
+				delegator: 8,
+				candidate: 6,
+				amount: 25,
+				round: 1,
+			});
+			// bonding more to a small delegation does not get around the deferral
+			assert_ok!(ParachainStaking::delegator_bond_more(RuntimeOrigin::signed(7), 6, 30));
+			assert_event_emitted!(Event::DelegationDeferred {
+				delegator: 7,
+				candidate: 6,
+				amount: 30,
+				round: 1,
+			});
+			assert_eq!(ParachainStaking::deferred_stake(6), Some((1, 55)));
+			// while delegations below the threshold are counted right away
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(9), 2, 5, 0, 0));
+			assert_eq!(ParachainStaking::deferred_stake(2), None);
+
+			// 6 is backed by 90 but only 35 counts for the selection ending round 1
+			roll_to_round_begin(2);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![1, 2, 3, 4, 5]);
+
+			// and all of it once the round the delegations were made in has ended
+			roll_to_round_begin(3);
+			assert_eq!(ParachainStaking::selected_candidates(), vec![2, 3, 4, 5, 6]);
+		});
+}
+
+#[test]
+fn pending_rewards_are_what_the_payout_pays() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100)])
+		.with_candidates(vec![(1, 20), (2, 40)])
+		.with_delegations(vec![(3, 1, 10), (3, 2, 10), (4, 2, 30)])
+		.build()
+		.execute_with(|| {
+			// everything shaping the payout but the treasury cut, which the mock does not take
+			UseSqrtExposure::set(true);
+			assert_ok!(ParachainStaking::set_delegator_reward_curve(
+				RuntimeOrigin::root(),
+				crate::DelegatorRewardCurve::MinCollatorShare(Perbill::from_percent(60)),
+			));
+			assert_ok!(ParachainStaking::set_payout_split(
+				RuntimeOrigin::signed(2),
+				vec![(5, Perbill::from_percent(25))]
+			));
+			set_author(1, 1, 20);
+			set_author(1, 2, 10);
+			roll_to_round_begin(3);
+
+			let paid = |who| {
+				ParachainStaking::reward_history(who)
+					.iter()
+					.filter(|(round, _)| *round == 1)
+					.map(|(_, reward)| *reward)
+					.sum::<Balance>()
+			};
+			let due: Vec<Balance> =
+				(1..=5).map(|who| ParachainStaking::pending_rewards(&who) + paid(who)).collect();
+			assert!(due.iter().all(|due| *due > 0));
+			roll_to_round_begin(4);
+			for who in 1..=5 {
+				assert_eq!(ParachainStaking::pending_rewards(&who), 0);
+				assert_eq!(paid(who), due[who as usize - 1]);
+			}
+		});
+}
+
+#[test]
+fn reward_history_keeps_the_latest_rounds() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 10)])
+		.build()
+		.execute_with(|| {
+			for round in 1..=6 {
+				set_author(round, 1, 20);
+			}
+			roll_to_round_begin(9);
+			for who in [1, 2] {
+				let history = ParachainStaking::reward_history(who);
+				assert_eq!(
+					history.iter().map(|(round, _)| *round).collect::<Vec<_>>(),
+					vec![3, 4, 5, 6]
+				);
+				assert!(history.iter().all(|(_, reward)| *reward > 0));
+			}
+		});
+}
+
+#[test]
+fn staking_accounts_hold_one_consumer_reference_while_they_stake() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100)])
+		.with_candidates(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let (candidate, delegator) = (System::consumers(&2), System::consumers(&3));
+			assert_ok!(ParachainStaking::join_candidates(RuntimeOrigin::signed(2), 20, 1));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(3), 1, 10, 0, 0));
+			assert_ok!(ParachainStaking::delegate(RuntimeOrigin::signed(3), 2, 10, 0, 1));
+			assert_eq!(System::consumers(&2), candidate + 1);
+			// however many delegations it has
+			assert_eq!(System::consumers(&3), delegator + 1);
+
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(3), 1));
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(2), 2));
+			roll_to(10);
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(3),
+				3,
+				1
+			));
+			assert_eq!(System::consumers(&3), delegator + 1);
+			// the candidate leaving revokes the last delegation of 3
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(2), 2, 1));
+			assert_eq!(System::consumers(&2), candidate);
+			assert_eq!(System::consumers(&3), delegator);
+		});
+}
+
+#[test]
+fn the_storage_version_1_migration_adds_a_consumer_reference_per_staking_account() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100)])
+		.with_candidates(vec![(1, 20), (2, 20)])
+		.with_delegations(vec![(3, 1, 10), (3, 2, 10)])
+		.build()
+		.execute_with(|| {
+			let held: Vec<_> = (1..=4).map(|who| System::consumers(&who)).collect();
+			// as before version 1, when staking accounts held no reference of their own
+			StorageVersion::new(0).put::<ParachainStaking>();
+			for who in 1..=3 {
+				System::dec_consumers(&who);
+			}
+
+			crate::migrations::AddStakerConsumerRefs::<Test>::on_runtime_upgrade();
+			assert_eq!((1..=4).map(|who| System::consumers(&who)).collect::<Vec<_>>(), held);
+			assert_eq!(ParachainStaking::on_chain_storage_version(), 1);
+
+			// and only once
+			crate::migrations::AddStakerConsumerRefs::<Test>::on_runtime_upgrade();
+			assert_eq!((1..=4).map(|who| System::consumers(&who)).collect::<Vec<_>>(), held);
+		});
+}
+
+#[test]
+fn releasing_stake_leaves_other_reserves_alone() {
+	use frame_support::traits::ReservableCurrency;
+
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 30)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			// e.g. identity or proxy deposits, reserved from the same balance as the stake
+			assert_ok!(Balances::reserve(&1, 15));
+			assert_ok!(Balances::reserve(&2, 15));
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			roll_to(10);
+			assert_ok!(ParachainStaking::execute_candidate_bond_less(RuntimeOrigin::signed(1), 1));
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
+			assert_eq!(Balances::reserved_balance(&1), 20 + 15);
+			assert_eq!(Balances::reserved_balance(&2), 15);
+			assert_eq!(ParachainStaking::held_stake(1), 20);
+			assert_eq!(ParachainStaking::held_stake(2), 0);
+
+			// more than is staked is never released, even with enough reserved
+			assert_noop!(ParachainStaking::release_stake(&1, 21), Error::<Test>::StakeNotHeld);
+			assert_noop!(ParachainStaking::release_stake(&2, 1), Error::<Test>::StakeNotHeld);
+		});
+}
+
+#[test]
+fn stake_left_on_a_staking_lock_stays_on_it() {
+	use frame_support::traits::{LockableCurrency, ReservableCurrency, WithdrawReasons};
+
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100)])
+		.with_candidates(vec![(1, 30)])
+		.build()
+		.execute_with(|| {
+			// as left by the storage version 2 migration for stake it could not hold
+			Balances::unreserve(&1, 30);
+			crate::HeldStake::<Test>::remove(1);
+			Balances::set_lock(crate::COLLATOR_LOCK_ID, &1, 30, WithdrawReasons::all());
+			crate::LockedStake::<Test>::insert(1, (crate::COLLATOR_LOCK_ID, 30));
+			let locked = || Balances::locks(1).iter().map(|lock| lock.amount).sum::<Balance>();
+
+			assert_ok!(ParachainStaking::candidate_bond_more(RuntimeOrigin::signed(1), 10));
+			assert_eq!(locked(), 40);
+			assert_eq!(ParachainStaking::get_collator_stakable_free_balance(&1), 60);
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				15
+			));
+			roll_to(10);
+			assert_ok!(ParachainStaking::execute_candidate_bond_less(RuntimeOrigin::signed(1), 1));
+			assert_eq!(locked(), 25);
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(ParachainStaking::locked_stake(1), Some((crate::COLLATOR_LOCK_ID, 25)));
+
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(1), 1));
+			roll_to(20);
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 0));
+			assert_eq!(locked(), 0);
+			assert_eq!(ParachainStaking::locked_stake(1), None);
+		});
+}
+
+#[test]
+fn the_storage_version_6_migration_records_the_held_stake() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 30)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			StorageVersion::new(5).put::<ParachainStaking>();
+			crate::HeldStake::<Test>::remove(1);
+			crate::HeldStake::<Test>::remove(2);
+
+			crate::migrations::TrackHeldStake::<Test>::on_runtime_upgrade();
+			assert_eq!(ParachainStaking::held_stake(1), 30);
+			assert_eq!(ParachainStaking::held_stake(2), 20);
+			assert_eq!(ParachainStaking::on_chain_storage_version(), 6);
+		});
+}
+
+#[test]
+fn unbonding_eta_is_the_first_block_of_the_round_the_unbonding_is_executable() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100), (3, 100), (4, 100)])
+		.with_candidates(vec![(1, 30), (2, 30)])
+		.with_delegations(vec![(3, 1, 10), (3, 2, 10), (4, 1, 10), (4, 2, 10)])
+		.build()
+		.execute_with(|| {
+			// round 2, from block 5
+			roll_to(7);
+			assert_eq!(ParachainStaking::unbonding_eta(&3, &1), None);
+			assert_eq!(ParachainStaking::unbonding_eta(&1, &1), None);
+
+			// delegation requests, executable in round 4
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(3), 1));
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(4), 2));
+			assert_eq!(ParachainStaking::unbonding_eta(&3, &1), Some(15));
+			assert_eq!(ParachainStaking::unbonding_eta(&4, &2), Some(15));
+			assert_eq!(ParachainStaking::unbonding_eta(&4, &1), None);
+			// a candidate bond decrease, executable in round 4
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				10
+			));
+			assert_eq!(ParachainStaking::unbonding_eta(&1, &1), Some(15));
+
+			// round 3, from block 10
+			roll_to(12);
+			// a candidate exit, executable in round 5, which unbonds its delegations as well
+			assert_ok!(ParachainStaking::schedule_leave_candidates(RuntimeOrigin::signed(2), 2));
+			assert_eq!(ParachainStaking::unbonding_eta(&2, &2), Some(20));
+			assert_eq!(ParachainStaking::unbonding_eta(&3, &2), Some(20));
+			// unless a request is executable earlier
+			assert_eq!(ParachainStaking::unbonding_eta(&4, &2), Some(15));
+
+			// never before the current block
+			roll_to(16);
+			assert_eq!(ParachainStaking::unbonding_eta(&3, &1), Some(16));
+		});
+}
+
+#[test]
+fn slashes_clamp_bond_decreases_and_force_out_candidates_below_the_minimum() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 30)])
+		.with_delegations(vec![(2, 1, 10)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::schedule_candidate_bond_less(
+				RuntimeOrigin::signed(1),
+				15
+			));
+
+			// the decrease is clamped to keep the minimum bond of 10
+			ParachainStaking::candidate_slash(&1, 10);
+			let state = ParachainStaking::candidate_info(1).unwrap();
+			assert_eq!(state.bond, 20);
+			assert_eq!(state.request.map(|request| request.amount), Some(10));
+			assert!(state.is_active());
+
+			ParachainStaking::candidate_slash(&1, 15);
+			assert_eq!(
+				events().into_iter().rev().take(3).collect::<Vec<_>>(),
+				vec![
+					Event::CandidateScheduledExit {
+						exit_allowed_round: 1,
+						candidate: 1,
+						scheduled_exit: 3,
+					},
+					Event::CancelledCandidateBondLess {
+						candidate: 1,
+						amount: 10,
+						execute_round: 3
+					},
+					Event::CandidateSlashed { candidate: 1, amount: 15, new_bond: 5 },
+				]
+			);
+			let state = ParachainStaking::candidate_info(1).unwrap();
+			assert!(state.request.is_none());
+			assert!(state.is_leaving());
+			assert_eq!(ParachainStaking::candidate_pool(1), None);
+			assert_noop!(
+				ParachainStaking::cancel_leave_candidates(RuntimeOrigin::signed(1), 1),
+				Error::<Test>::CandidateBondBelowMin
+			);
+
+			// the exit returns the stake left to the candidate and its delegator
+			roll_to(10);
+			assert_ok!(ParachainStaking::execute_leave_candidates(RuntimeOrigin::signed(1), 1, 1));
+			assert!(ParachainStaking::candidate_info(1).is_none());
+			assert_eq!(Balances::total_balance(&1), 75);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(ParachainStaking::do_try_state(), Ok(()));
+		});
+}
+
+#[test]
+fn failed_reward_remittances_release_the_batch_instead_of_retrying() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::set_reward_destination(
+				RuntimeOrigin::signed(2),
+				Some(2000)
+			));
+			for round in 1..=3 {
+				set_author(round, 1, 20);
+			}
+			roll_to_round_end(5);
+			let pending = ParachainStaking::remote_reward_destination(2).unwrap().pending;
+			assert!(pending > 0);
+			assert_eq!(Balances::reserved_balance(2), 20 + pending);
+
+			RemitFails::set(true);
+			ParachainStaking::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::ParachainStaking(Event::RewardRemittanceFailed {
+					delegator: 2,
+					amount: pending
+				})
+			);
+			assert!(Remitted::get().is_empty());
+			assert_eq!(ParachainStaking::remote_reward_destination(2).unwrap().pending, 0);
+			assert_eq!(Balances::reserved_balance(2), 20);
+			assert_eq!(Balances::free_balance(2), 80 + pending);
+
+			// nothing is left to retry
+			RemitFails::set(false);
+			ParachainStaking::on_idle(System::block_number(), Weight::from_ref_time(u64::MAX));
+			assert!(Remitted::get().is_empty());
+		});
+}
+
+#[test]
+fn leaving_delegators_get_their_batched_rewards_back() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 100), (2, 100)])
+		.with_candidates(vec![(1, 20)])
+		.with_delegations(vec![(2, 1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParachainStaking::set_reward_destination(
+				RuntimeOrigin::signed(2),
+				Some(2000)
+			));
+			set_author(1, 1, 20);
+			roll_to_round_end(3);
+			let pending = ParachainStaking::remote_reward_destination(2).unwrap().pending;
+			assert!(pending > 0);
+
+			assert_ok!(ParachainStaking::schedule_revoke_delegation(RuntimeOrigin::signed(2), 1));
+			roll_to_round_begin(6);
+			assert_ok!(ParachainStaking::execute_delegation_request(
+				RuntimeOrigin::signed(2),
+				2,
+				1
+			));
+			assert_eq!(ParachainStaking::remote_reward_destination(2), None);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert!(Balances::free_balance(2) >= 100 + pending);
+		});
+}
+
+/// Random sequences of delegator operations, checking after each one that the top and bottom
+/// delegations stay ordered and bounded, that candidate metadata and `total_counted` follow them,
+/// and that exactly the staked amounts stay held
+mod delegation_properties {
+	use super::*;
+	use crate::{BottomDelegations, CandidateInfo, DelegatorState, TopDelegations, Total};
+	use proptest::prelude::*;
+
+	const CANDIDATES: [AccountId; 2] = [1, 2];
+	const DELEGATORS: std::ops::RangeInclusive<AccountId> = 3..=12;
+
+	#[derive(Clone, Debug)]
+	enum DelegationOp {
+		Delegate {
+			delegator: AccountId,
+			candidate: AccountId,
+			amount: Balance,
+		},
+		BondMore {
+			delegator: AccountId,
+			candidate: AccountId,
+			more: Balance,
+		},
+		ScheduleBondLess {
+			delegator: AccountId,
+			candidate: AccountId,
+			less: Balance,
+		},
+		ScheduleRevoke {
+			delegator: AccountId,
+			candidate: AccountId,
+		},
+		/// Rotate past the request delays and execute every scheduled request
+		ExecuteDue,
+	}
+
+	fn delegation_op() -> impl Strategy<Value = DelegationOp> {
+		let delegator = DELEGATORS;
+		let candidate = prop::sample::select(CANDIDATES.to_vec());
+		// a narrow range of amounts, so that delegations often tie
+		let amount = 3u128..=12;
+		prop_oneof![
+			4 => (delegator.clone(), candidate.clone(), amount.clone()).prop_map(
+				|(delegator, candidate, amount)| DelegationOp::Delegate { delegator, candidate, amount }
+			),
+			2 => (delegator.clone(), candidate.clone(), amount.clone()).prop_map(
+				|(delegator, candidate, more)| DelegationOp::BondMore { delegator, candidate, more }
+			),
+			2 => (delegator.clone(), candidate.clone(), amount).prop_map(
+				|(delegator, candidate, less)| DelegationOp::ScheduleBondLess {
+					delegator,
+					candidate,
+					less
+				}
+			),
+			1 => (delegator, candidate).prop_map(|(delegator, candidate)| {
+				DelegationOp::ScheduleRevoke { delegator, candidate }
+			}),
+			1 => Just(DelegationOp::ExecuteDue),
+		]
+	}
+
+	/// Applies `op`, which may well be rejected: only the state it leaves behind is checked
+	fn apply(op: DelegationOp) {
+		let candidate_count =
+			|c: AccountId| ParachainStaking::candidate_info(c).map_or(0, |i| i.delegation_count);
+		let delegation_count = |d: AccountId| {
+			ParachainStaking::delegator_state(d).map_or(0, |s| s.delegations.0.len() as u32)
+		};
+		let _ = match op {
+			DelegationOp::Delegate { delegator, candidate, amount } => ParachainStaking::delegate(
+				RuntimeOrigin::signed(delegator),
+				candidate,
+				amount,
+				candidate_count(candidate),
+				delegation_count(delegator),
+			),
+			DelegationOp::BondMore { delegator, candidate, more } =>
+				ParachainStaking::delegator_bond_more(
+					RuntimeOrigin::signed(delegator),
+					candidate,
+					more,
+				),
+			DelegationOp::ScheduleBondLess { delegator, candidate, less } =>
+				ParachainStaking::schedule_delegator_bond_less(
+					RuntimeOrigin::signed(delegator),
+					candidate,
+					less,
+				),
+			DelegationOp::ScheduleRevoke { delegator, candidate } =>
+				ParachainStaking::schedule_revoke_delegation(
+					RuntimeOrigin::signed(delegator),
+					candidate,
+				),
+			DelegationOp::ExecuteDue => {
+				for _ in 0..RevokeDelegationDelay::get().max(DelegationBondLessDelay::get()) {
+					start_next_round();
+				}
+				let due = <crate::DelegationScheduledRequests<Test>>::iter()
+					.flat_map(|(candidate, requests)| {
+						requests.into_iter().map(move |r| (r.delegator, candidate))
+					})
+					.collect::<Vec<_>>();
+				for (delegator, candidate) in due {
+					let _ = ParachainStaking::execute_delegation_request(
+						RuntimeOrigin::signed(delegator),
+						delegator,
+						candidate,
+					);
+				}
+				Ok(().into())
+			},
+		};
+	}
+
+	fn check_delegation_invariants() {
+		assert_eq!(ParachainStaking::do_try_state(), Ok(()));
+		let mut staked = 0;
+		for (candidate, info) in <CandidateInfo<Test>>::iter() {
+			let top = <TopDelegations<Test>>::get(candidate).expect("candidate has top");
+			let bottom = <BottomDelegations<Test>>::get(candidate).expect("candidate has bottom");
+			for delegations in [&top, &bottom] {
+				assert!(delegations.delegations.windows(2).all(|w| w[0].amount >= w[1].amount));
+			}
+			assert!(top.delegations.len() as u32 <= MaxTopDelegationsPerCandidate::get());
+			assert!(bottom.delegations.len() as u32 <= MaxBottomDelegationsPerCandidate::get());
+			// the bottom only fills up behind a full top, and never outranks it
+			if !bottom.delegations.is_empty() {
+				assert_eq!(top.delegations.len() as u32, MaxTopDelegationsPerCandidate::get());
+				assert!(top.lowest_delegation_amount() >= bottom.highest_delegation_amount());
+			}
+			assert_eq!(info.lowest_top_delegation_amount, top.lowest_delegation_amount());
+			assert_eq!(info.highest_bottom_delegation_amount, bottom.highest_delegation_amount());
+			assert_eq!(info.lowest_bottom_delegation_amount, bottom.lowest_delegation_amount());
+			assert_eq!(info.top_capacity, top.top_capacity::<Test>());
+			assert_eq!(info.bottom_capacity, bottom.bottom_capacity::<Test>());
+			if let Some(backing) = ParachainStaking::candidate_pool(candidate) {
+				assert_eq!(backing, info.total_counted);
+			}
+			assert_eq!(Balances::reserved_balance(candidate), info.bond);
+			staked += info.bond;
+		}
+		for (delegator, state) in <DelegatorState<Test>>::iter() {
+			for bond in state.delegations.0.iter() {
+				let listed = <TopDelegations<Test>>::get(bond.owner)
+					.into_iter()
+					.chain(<BottomDelegations<Test>>::get(bond.owner))
+					.flat_map(|d| d.delegations)
+					.filter(|d| d.owner == delegator)
+					.collect::<Vec<_>>();
+				assert_eq!(listed.len(), 1, "delegation listed exactly once by its candidate");
+				assert_eq!(listed[0].amount, bond.amount);
+			}
+			assert_eq!(Balances::reserved_balance(delegator), state.total);
+			staked += state.total;
+		}
+		// no delegation is listed for a delegator that does not hold it
+		for candidate in CANDIDATES {
+			for d in <TopDelegations<Test>>::get(candidate)
+				.into_iter()
+				.chain(<BottomDelegations<Test>>::get(candidate))
+				.flat_map(|d| d.delegations)
+			{
+				let state = ParachainStaking::delegator_state(d.owner).expect("delegator");
+				assert!(state.delegations.0.iter().any(|b| b.owner == candidate));
+			}
+		}
+		assert_eq!(<Total<Test>>::get(), staked);
+		// released stake is free again
+		for who in DELEGATORS {
+			if ParachainStaking::delegator_state(who).is_none() {
+				assert_eq!(Balances::reserved_balance(who), 0);
+			}
+		}
+	}
+
+	proptest! {
+		#![proptest_config(ProptestConfig::with_cases(64))]
+
+		#[test]
+		fn delegation_ordering_holds_for_any_operations(
+			ops in prop::collection::vec(delegation_op(), 1..60)
+		) {
+			ExtBuilder::default()
+				.with_balances((1..=12).map(|account| (account, 1_000)).collect())
+				.with_candidates(vec![(1, 20), (2, 20)])
+				.with_delegations(vec![(3, 1, 10), (4, 1, 10), (5, 2, 10)])
+				.build()
+				.execute_with(|| {
+					check_delegation_invariants();
+					for op in ops {
+						apply(op);
+						check_delegation_invariants();
+					}
+				});
+		}
+	}
+}
@@ -16,14 +16,29 @@
 
 //! traits for parachain-staking
 
-pub trait OnCollatorPayout<AccountId, Balance> {
+pub trait OnCollatorPayout<AccountId, Balance: sp_runtime::traits::Zero> {
+	/// The cut `take_cut` would take out of a reward of `amount` due to `collator_id` for
+	/// `for_round`, without paying it. Used to estimate rewards before they are paid.
+	fn cut(_for_round: crate::RoundIndex, _collator_id: &AccountId, _amount: Balance) -> Balance {
+		Balance::zero()
+	}
+	/// The part of the reward of `amount` due to `collator_id` for `for_round` which the hook
+	/// pays elsewhere instead of the collator, with the weight of paying it. The rest is minted
+	/// to the collator. Nothing is taken by default.
+	fn take_cut(
+		_for_round: crate::RoundIndex,
+		_collator_id: &AccountId,
+		_amount: Balance,
+	) -> (Balance, frame_support::pallet_prelude::Weight) {
+		(Balance::zero(), frame_support::pallet_prelude::Weight::zero())
+	}
 	fn on_collator_payout(
 		for_round: crate::RoundIndex,
 		collator_id: AccountId,
 		amount: Balance,
 	) -> frame_support::pallet_prelude::Weight;
 }
-impl<AccountId, Balance> OnCollatorPayout<AccountId, Balance> for () {
+impl<AccountId, Balance: sp_runtime::traits::Zero> OnCollatorPayout<AccountId, Balance> for () {
 	fn on_collator_payout(
 		_for_round: crate::RoundIndex,
 		_collator_id: AccountId,
@@ -33,6 +48,23 @@ impl<AccountId, Balance> OnCollatorPayout<AccountId, Balance> for () {
 	}
 }
 
+/// Source of liveness for selected collators, used to mark unresponsive collators offline
+pub trait CollatorLiveness<AccountId> {
+	fn is_live(round: crate::RoundIndex, collator: &AccountId) -> bool;
+}
+impl<AccountId> CollatorLiveness<AccountId> for () {
+	fn is_live(_round: crate::RoundIndex, _collator: &AccountId) -> bool {
+		true
+	}
+}
+
+/// Stake backing the collators selected for a round, for pallets that prefer well backed
+/// collators in their own authority selection
+pub trait CollatorBacking<AccountId, Balance> {
+	/// Self bond plus counted delegations of `collator`, if it was selected for `round`
+	fn backing(round: crate::RoundIndex, collator: &AccountId) -> Option<Balance>;
+}
+
 pub trait OnNewRound {
 	fn on_new_round(round_index: crate::RoundIndex) -> frame_support::pallet_prelude::Weight;
 }
@@ -41,3 +73,165 @@ impl OnNewRound for () {
 		frame_support::pallet_prelude::Weight::zero()
 	}
 }
+impl<A: OnNewRound, B: OnNewRound> OnNewRound for (A, B) {
+	fn on_new_round(round_index: crate::RoundIndex) -> frame_support::pallet_prelude::Weight {
+		A::on_new_round(round_index).saturating_add(B::on_new_round(round_index))
+	}
+}
+
+/// Notified when a delegation is evicted from a full bottom set by a larger one. The delegated
+/// funds are already released and the delegation's requests and auto-compound config removed.
+/// Its cost must be covered by the weight of the delegating call, e.g. a single storage write.
+pub trait OnDelegationKicked<AccountId, Balance> {
+	fn on_delegation_kicked(delegator: &AccountId, candidate: &AccountId, amount: Balance);
+}
+impl<AccountId, Balance> OnDelegationKicked<AccountId, Balance> for () {
+	fn on_delegation_kicked(_delegator: &AccountId, _candidate: &AccountId, _amount: Balance) {}
+}
+
+/// Shapes how the staking reward of a round is split between the collators awarded points in it
+pub trait RewardCurve<Balance> {
+	/// Claim of a collator on the round's staking reward, relative to the other collators', given
+	/// its points and the total stake backing it in the round
+	fn reward_weight(points: u32, exposure: Balance) -> u128;
+}
+/// Splits the reward by points alone
+impl<Balance> RewardCurve<Balance> for () {
+	fn reward_weight(points: u32, _exposure: Balance) -> u128 {
+		points.into()
+	}
+}
+
+/// Weighs points by the square root of the collator's exposure, so a collator backed by four
+/// times the stake earns twice the reward for the same points rather than four times
+pub struct SqrtExposure;
+impl<Balance: sp_runtime::traits::UniqueSaturatedInto<u128>> RewardCurve<Balance> for SqrtExposure {
+	fn reward_weight(points: u32, exposure: Balance) -> u128 {
+		use sp_runtime::traits::IntegerSquareRoot;
+		u128::from(points).saturating_mul(exposure.unique_saturated_into().integer_sqrt())
+	}
+}
+
+/// Funds of an account locked until they vest. Staking locks the vesting funds it stakes instead
+/// of holding them, so they can be staked while they vest.
+pub trait VestingBalance<AccountId, Balance> {
+	/// Funds of `who` that have yet to vest
+	fn vesting_balance(who: &AccountId) -> Balance;
+}
+/// Nothing vests, all stake is held
+impl<AccountId, Balance: sp_runtime::traits::Zero> VestingBalance<AccountId, Balance> for () {
+	fn vesting_balance(_who: &AccountId) -> Balance {
+		Balance::zero()
+	}
+}
+
+/// Funds yet to vest under the schedules of a vesting pallet, e.g. `pallet_vesting`
+pub struct VestingScheduleBalance<V>(sp_std::marker::PhantomData<V>);
+impl<AccountId, V>
+	VestingBalance<AccountId, <V::Currency as frame_support::traits::Currency<AccountId>>::Balance>
+	for VestingScheduleBalance<V>
+where
+	V: frame_support::traits::VestingSchedule<AccountId>,
+{
+	fn vesting_balance(
+		who: &AccountId,
+	) -> <V::Currency as frame_support::traits::Currency<AccountId>>::Balance {
+		V::vesting_balance(who).unwrap_or_default()
+	}
+}
+
+/// Limits the damage of an anomaly detected by staking, e.g. by pausing its calls, until
+/// governance steps in
+pub trait CircuitBreaker {
+	fn trip() -> frame_support::pallet_prelude::Weight;
+}
+impl CircuitBreaker for () {
+	fn trip() -> frame_support::pallet_prelude::Weight {
+		frame_support::pallet_prelude::Weight::zero()
+	}
+}
+
+/// Sends the batched staking rewards of a delegator to its account on another chain
+pub trait RemitReward<AccountId, Balance> {
+	/// Where rewards are sent, e.g. an XCM location on another chain
+	type Destination: frame_support::pallet_prelude::Member
+		+ frame_support::pallet_prelude::Parameter;
+
+	/// Move `amount` out of the local account of `who` to `destination`
+	fn remit(
+		who: &AccountId,
+		destination: &Self::Destination,
+		amount: Balance,
+	) -> Result<frame_support::pallet_prelude::Weight, sp_runtime::DispatchError>;
+
+	/// Most weight a remittance may take, reserved before it is made
+	fn weight() -> frame_support::pallet_prelude::Weight;
+}
+/// Remote reward destinations cannot be used
+impl<AccountId, Balance> RemitReward<AccountId, Balance> for () {
+	type Destination = ();
+
+	fn remit(
+		_who: &AccountId,
+		_destination: &(),
+		_amount: Balance,
+	) -> Result<frame_support::pallet_prelude::Weight, sp_runtime::DispatchError> {
+		Err(sp_runtime::DispatchError::Other("remote reward destinations are not supported"))
+	}
+
+	fn weight() -> frame_support::pallet_prelude::Weight {
+		frame_support::pallet_prelude::Weight::zero()
+	}
+}
+
+/// Sets the session keys of an account, letting staking rotate them together with its own checks
+pub trait SetSessionKeys<AccountId> {
+	type Keys: frame_support::pallet_prelude::Member + frame_support::pallet_prelude::Parameter;
+
+	fn set_keys(
+		who: &AccountId,
+		keys: Self::Keys,
+		proof: sp_std::vec::Vec<u8>,
+	) -> sp_runtime::DispatchResult;
+}
+
+/// Staking operations and queries for other pallets (pools, liquid staking, governance) that
+/// integrate with staking without depending on its storage layout
+pub trait StakingInterface {
+	type AccountId;
+	type Balance;
+	type BlockNumber;
+
+	/// The current round
+	fn current_round() -> crate::RoundInfo<Self::BlockNumber>;
+	/// The least amount a single delegation may bond
+	fn min_delegation() -> Self::Balance;
+	/// The total amount staked by all candidates and delegators
+	fn total_stake() -> Self::Balance;
+	fn is_candidate(who: &Self::AccountId) -> bool;
+	fn is_delegator(who: &Self::AccountId) -> bool;
+	/// Stake counted towards `candidate` in collator selection, its self bond plus its top
+	/// delegations
+	fn candidate_stake(candidate: &Self::AccountId) -> Option<Self::Balance>;
+	/// Amount `delegator` has delegated to `candidate`
+	fn delegation_stake(
+		delegator: &Self::AccountId,
+		candidate: &Self::AccountId,
+	) -> Option<Self::Balance>;
+	/// Delegate `amount` to `candidate`, adding to the existing delegation if there is one
+	fn delegate(
+		delegator: Self::AccountId,
+		candidate: Self::AccountId,
+		amount: Self::Balance,
+	) -> sp_runtime::DispatchResult;
+	/// Schedule the revocation of the delegation of `delegator` to `candidate`
+	fn undelegate(
+		delegator: Self::AccountId,
+		candidate: Self::AccountId,
+	) -> sp_runtime::DispatchResult;
+	/// Execute a scheduled revocation or decrease once it is due, releasing the unbonded funds
+	fn withdraw_unbonded(
+		delegator: Self::AccountId,
+		candidate: Self::AccountId,
+	) -> sp_runtime::DispatchResult;
+}
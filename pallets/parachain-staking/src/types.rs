@@ -17,14 +17,10 @@
 //! Types for parachain-staking
 
 use crate::{
-	auto_compound::AutoCompoundDelegations, set::OrderedSet, BalanceOf, BottomDelegations,
-	CandidateInfo, Config, DelegatorState, Error, Event, Pallet, Round, RoundIndex, TopDelegations,
-	Total, COLLATOR_LOCK_ID, DELEGATOR_LOCK_ID,
-};
-use frame_support::{
-	pallet_prelude::*,
-	traits::{tokens::WithdrawReasons, LockableCurrency},
+	set::OrderedSet, BalanceOf, BottomDelegations, CandidateInfo, Config, DelegationAction,
+	DelegatorState, Error, Event, Pallet, Range, Round, RoundIndex, TopDelegations, Total,
 };
+use frame_support::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Saturating, Zero},
@@ -32,6 +28,12 @@ use sp_runtime::{
 };
 use sp_std::{cmp::Ordering, collections::btree_map::BTreeMap, prelude::*};
 
+/// Maximum length in bytes of a delegation memo
+pub const MAX_DELEGATION_MEMO_LEN: u32 = 32;
+
+/// Bounded memo a delegator may attach to a delegation at creation time
+pub type DelegationMemo = BoundedVec<u8, ConstU32<MAX_DELEGATION_MEMO_LEN>>;
+
 pub struct CountedDelegations<T: Config> {
 	pub uncounted_stake: BalanceOf<T>,
 	pub rewardable_delegations: Vec<Bond<T::AccountId, BalanceOf<T>>>,
@@ -166,6 +168,47 @@ pub struct DelayedPayout<Balance> {
 	pub collator_commission: Perbill,
 }
 
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Payout of a collator's delegators spread over several `AtStakePages`, continued one page per
+/// payout after the collator and its first page are paid
+pub struct PagedPayout<AccountId, Balance> {
+	/// Collator whose delegators are being paid
+	pub collator: AccountId,
+	/// Next page of the collator's snapshot to pay
+	pub next_page: u32,
+	/// Reward shared by the delegators in proportion to their stake
+	pub amt_due: Balance,
+	/// Stake the delegators' shares of `amt_due` are relative to
+	pub total: Balance,
+}
+
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+/// Rewards of a collator, its payout split and its delegators for a round, as its payout pays them
+pub struct CollatorRoundPayout<AccountId, Balance> {
+	/// The collator
+	pub collator: AccountId,
+	/// Reward kept by the collator, commission included, after the cut and its payout split
+	pub collator_reward: Balance,
+	/// Cut of the collator's reward taken by `OnCollatorPayout`
+	pub cut: Balance,
+	/// Shares of the collator's reward paid to its `CollatorPayoutSplit`
+	pub split_rewards: Vec<(AccountId, Balance)>,
+	/// Reward of each rewardable delegator of the collator
+	pub delegator_rewards: Vec<(AccountId, Balance)>,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// A delegator's account on another chain and the rewards batched for it
+pub struct RemoteRewardDestination<Destination, Balance> {
+	/// Where the batched rewards are remitted
+	pub destination: Destination,
+	/// Rewards paid since the last remittance, reserved in the delegator's local account until
+	/// then
+	pub pending: Balance,
+	/// Round of the last remittance attempt, or of registering the destination
+	pub last_remitted: RoundIndex,
+}
+
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
 /// DEPRECATED
 /// Collator state with commission fee, bonded stake, and delegations
@@ -388,6 +431,18 @@ impl<
 		self.status = CollatorStatus::Leaving(when);
 		Ok((now, when))
 	}
+	pub fn can_cancel_leave<T: Config>(&self) -> DispatchResult {
+		if let CollatorStatus::Leaving(when) = self.status {
+			let cutoff = T::LeaveCancellationCutoff::get();
+			ensure!(
+				cutoff.is_zero() || <Round<T>>::get().current.saturating_add(cutoff) < when,
+				Error::<T>::CandidateExitCancellationClosed
+			);
+			Ok(())
+		} else {
+			Err(Error::<T>::CandidateNotLeaving.into())
+		}
+	}
 	pub fn can_leave<T: Config>(&self) -> DispatchResult {
 		if let CollatorStatus::Leaving(when) = self.status {
 			ensure!(<Round<T>>::get().current >= when, Error::<T>::CandidateCannotLeaveYet);
@@ -412,8 +467,8 @@ impl<
 		);
 		let new_total = <Total<T>>::get().saturating_add(more.into());
 		<Total<T>>::put(new_total);
+		<Pallet<T>>::hold_stake(&who, more.into())?;
 		self.bond = self.bond.saturating_add(more);
-		T::Currency::set_lock(COLLATOR_LOCK_ID, &who, self.bond.into(), WithdrawReasons::all());
 		self.total_counted = self.total_counted.saturating_add(more);
 		<Pallet<T>>::deposit_event(Event::CandidateBondedMore {
 			candidate: who,
@@ -459,7 +514,7 @@ impl<
 		// Arithmetic assumptions are self.bond > less && self.bond - less > CollatorMinBond
 		// (assumptions enforced by `schedule_bond_less`; if storage corrupts, must re-verify)
 		self.bond = self.bond.saturating_sub(request.amount);
-		T::Currency::set_lock(COLLATOR_LOCK_ID, &who, self.bond.into(), WithdrawReasons::all());
+		<Pallet<T>>::release_stake(&who, request.amount.into())?;
 		self.total_counted = self.total_counted.saturating_sub(request.amount);
 		let event = Event::CandidateBondedLess {
 			candidate: who.clone(),
@@ -588,6 +643,11 @@ impl<
 			if matches!(self.bottom_capacity, CapacityStatus::Full) {
 				less_total_staked = Some(self.lowest_bottom_delegation_amount);
 			}
+			Pallet::<T>::deposit_event(Event::DelegationMovedToBottom {
+				delegator: new_bottom_delegation.owner.clone(),
+				candidate: candidate.clone(),
+				amount: new_bottom_delegation.amount,
+			});
 			self.add_bottom_delegation::<T>(true, candidate, new_bottom_delegation);
 		}
 		// insert into top
@@ -629,37 +689,9 @@ impl<
 			// the lowest bottom to enforce first come first served
 			bottom_delegations.total =
 				bottom_delegations.total.saturating_sub(lowest_bottom_to_be_kicked.amount);
-			// update delegator state
 			// total staked is updated via propagation of lowest bottom delegation amount prior
 			// to call
-			let mut delegator_state = <DelegatorState<T>>::get(&lowest_bottom_to_be_kicked.owner)
-				.expect("Delegation existence => DelegatorState existence");
-			let leaving = delegator_state.delegations.0.len() == 1usize;
-			delegator_state.rm_delegation::<T>(candidate);
-			<Pallet<T>>::delegation_remove_request_with_state(
-				candidate,
-				&lowest_bottom_to_be_kicked.owner,
-				&mut delegator_state,
-			);
-			<AutoCompoundDelegations<T>>::remove_auto_compound(
-				candidate,
-				&lowest_bottom_to_be_kicked.owner,
-			);
-
-			Pallet::<T>::deposit_event(Event::DelegationKicked {
-				delegator: lowest_bottom_to_be_kicked.owner.clone(),
-				candidate: candidate.clone(),
-				unstaked_amount: lowest_bottom_to_be_kicked.amount,
-			});
-			if leaving {
-				<DelegatorState<T>>::remove(&lowest_bottom_to_be_kicked.owner);
-				Pallet::<T>::deposit_event(Event::DelegatorLeft {
-					delegator: lowest_bottom_to_be_kicked.owner,
-					unstaked_amount: lowest_bottom_to_be_kicked.amount,
-				});
-			} else {
-				<DelegatorState<T>>::insert(&lowest_bottom_to_be_kicked.owner, delegator_state);
-			}
+			<Pallet<T>>::delegation_kick(candidate, lowest_bottom_to_be_kicked);
 			false
 		} else {
 			!bumped_from_top
@@ -743,6 +775,11 @@ impl<
 				bottom_delegations.total.saturating_sub(highest_bottom_delegation.amount);
 			self.reset_bottom_data::<T>(&bottom_delegations);
 			<BottomDelegations<T>>::insert(candidate, bottom_delegations);
+			Pallet::<T>::deposit_event(Event::DelegationMovedToTop {
+				delegator: highest_bottom_delegation.owner.clone(),
+				candidate: candidate.clone(),
+				amount: highest_bottom_delegation.amount,
+			});
 			// insert highest bottom into top delegations
 			top_delegations.insert_sorted_greatest_to_least(highest_bottom_delegation);
 		}
@@ -900,8 +937,18 @@ impl<
 					.expect("Top capacity full => Exists at least 1 top delegation");
 				top_delegations.total =
 					top_delegations.total.saturating_sub(new_bottom_delegation.amount);
+				Pallet::<T>::deposit_event(Event::DelegationMovedToBottom {
+					delegator: new_bottom_delegation.owner.clone(),
+					candidate: candidate.clone(),
+					amount: new_bottom_delegation.amount,
+				});
 				bottom_delegations.insert_sorted_greatest_to_least(new_bottom_delegation);
 			}
+			Pallet::<T>::deposit_event(Event::DelegationMovedToTop {
+				delegator: delegation.owner.clone(),
+				candidate: candidate.clone(),
+				amount: delegation.amount,
+			});
 			// insert into top
 			top_delegations.insert_sorted_greatest_to_least(delegation);
 			self.reset_top_data::<T>(candidate.clone(), &top_delegations);
@@ -1011,6 +1058,16 @@ impl<
 			let highest_bottom_delegation = bottom_delegations.delegations.remove(0);
 			bottom_delegations.total =
 				bottom_delegations.total.saturating_sub(highest_bottom_delegation.amount);
+			Pallet::<T>::deposit_event(Event::DelegationMovedToBottom {
+				delegator: delegation.owner.clone(),
+				candidate: candidate.clone(),
+				amount: delegation.amount,
+			});
+			Pallet::<T>::deposit_event(Event::DelegationMovedToTop {
+				delegator: highest_bottom_delegation.owner.clone(),
+				candidate: candidate.clone(),
+				amount: highest_bottom_delegation.amount,
+			});
 			// insert highest bottom into top
 			top_delegations.insert_sorted_greatest_to_least(highest_bottom_delegation);
 			// insert previous top into bottom
@@ -1112,6 +1169,14 @@ impl<A: PartialEq, B: PartialEq> PartialEq for CollatorCandidate<A, B> {
 	}
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Whether a delegation is among the top delegations of its candidate, which count towards its
+/// stake and earn rewards, or among the bottom ones, which do neither
+pub enum DelegationPosition {
+	Top,
+	Bottom,
+}
+
 /// Convey relevant information describing if a delegator was added to the top or bottom
 /// Delegations added to the top yield a new total
 #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -1245,8 +1310,9 @@ impl<
 	{
 		let total = self.total.saturating_sub(amount);
 		check(total)?;
+		let decrease = self.total.saturating_sub(total);
 		self.total = total;
-		self.adjust_bond_lock::<T>(BondAdjust::Decrease)?;
+		self.adjust_bond_lock::<T>(BondAdjust::Decrease(decrease))?;
 		Ok(())
 	}
 
@@ -1267,8 +1333,10 @@ impl<
 		T::AccountId: From<AccountId>,
 		BalanceOf<T>: From<Balance>,
 	{
-		self.total = self.total.saturating_sub(amount);
-		self.adjust_bond_lock::<T>(BondAdjust::Decrease)?;
+		let total = self.total.saturating_sub(amount);
+		let decrease = self.total.saturating_sub(total);
+		self.total = total;
+		self.adjust_bond_lock::<T>(BondAdjust::Decrease(decrease))?;
 		Ok(())
 	}
 
@@ -1287,7 +1355,10 @@ impl<
 	}
 	// Return Some(remaining balance), must be more than MinDelegatorStk
 	// Return None if delegation not found
-	pub fn rm_delegation<T: Config>(&mut self, collator: &AccountId) -> Option<Balance>
+	pub fn rm_delegation<T: Config>(
+		&mut self,
+		collator: &AccountId,
+	) -> Result<Option<Balance>, DispatchError>
 	where
 		BalanceOf<T>: From<Balance>,
 		T::AccountId: From<AccountId>,
@@ -1308,10 +1379,10 @@ impl<
 			.collect();
 		if let Some(balance) = amt {
 			self.delegations = OrderedSet::from(delegations);
-			self.total_sub::<T>(balance).expect("Decreasing lock cannot fail, qed");
-			Some(self.total)
+			self.total_sub::<T>(balance)?;
+			Ok(Some(self.total))
 		} else {
-			None
+			Ok(None)
 		}
 	}
 
@@ -1363,14 +1434,11 @@ impl<
 		Err(Error::<T>::DelegationDNE.into())
 	}
 
-	/// Updates the bond locks for this delegator.
-	///
-	/// This will take the current self.total and ensure that a lock of the same amount is applied
-	/// and when increasing the bond lock will also ensure that the account has enough free balance.
+	/// Updates the funds held for this delegator.
 	///
-	/// `additional_required_balance` should reflect the change to the amount that should be locked
-	/// if positive, 0 otherwise (e.g. `min(0, change_in_total_bond)`). This is necessary because it
-	/// is not possible to query the amount that is locked for a given lock id.
+	/// `additional_required_balance` is the change to self.total that was just applied. An increase
+	/// is held from the account's free balance, after ensuring the account has enough of it, and
+	/// a decrease is released back into it.
 	pub fn adjust_bond_lock<T: Config>(
 		&mut self,
 		additional_required_balance: BondAdjust<Balance>,
@@ -1392,20 +1460,12 @@ impl<
 					log::warn!("LOGIC ERROR: request to reserve more than bond total");
 					return Err(DispatchError::Other("Invalid additional_required_balance"))
 				}
+				<Pallet<T>>::hold_stake(&self.id.clone().into(), amount.into())?;
+			},
+			BondAdjust::Decrease(amount) => {
+				<Pallet<T>>::release_stake(&self.id.clone().into(), amount.into())?;
 			},
-			BondAdjust::Decrease => (), // do nothing on decrease
 		};
-
-		if self.total.is_zero() {
-			T::Currency::remove_lock(DELEGATOR_LOCK_ID, &self.id.clone().into());
-		} else {
-			T::Currency::set_lock(
-				DELEGATOR_LOCK_ID,
-				&self.id.clone().into(),
-				self.total.into(),
-				WithdrawReasons::all(),
-			);
-		}
 		Ok(())
 	}
 
@@ -1590,6 +1650,24 @@ impl<
 	}
 }
 
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Condition that trips the staking circuit breaker
+pub enum StakingAnomaly<Balance> {
+	/// `Total` fell from `previous` to `current` over one block, by more than
+	/// `MaxTotalDropPerBlock`
+	TotalDropped { previous: Balance, current: Balance },
+	/// Rewards paid for `round` exceeded the staking reward computed for it
+	PayoutExceededIssuance { round: RoundIndex, paid: Balance, issuance: Balance },
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Slash of a candidate's self bond waiting for the round it is applied in
+pub struct UnappliedSlash<AccountId, Balance> {
+	pub candidate: AccountId,
+	/// Self bond slashed, at most the bond left once applied
+	pub amount: Balance,
+}
+
 #[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 /// Reserve information { account, percent_of_inflation }
 pub struct ParachainBondConfig<AccountId> {
@@ -1608,7 +1686,125 @@ impl<A: Decode> Default for ParachainBondConfig<A> {
 	}
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// How `compute_top_candidates` ranks candidates
+pub enum SelectionMode {
+	/// By counted stake
+	Stake,
+	/// By the sum of the square roots of the self bond and of each top delegation, so stake
+	/// spread over many delegators outranks the same stake from a few
+	SqrtPerBond,
+}
+impl Default for SelectionMode {
+	fn default() -> SelectionMode {
+		SelectionMode::Stake
+	}
+}
+
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Election of the collators of the next round, scoring the candidate pool a page per block
+/// ahead of the end of `round`
+pub struct PagedElection<AccountId, Balance> {
+	/// Round closed by the election
+	pub round: RoundIndex,
+	/// `TotalSelected` when the election began
+	pub total_selected: u32,
+	/// Selection mode when the election began
+	pub mode: SelectionMode,
+	/// Raw key of the last candidate scored, none before the first page
+	pub last_key: Option<Vec<u8>>,
+	/// Whether the whole candidate pool was scored
+	pub complete: bool,
+	/// Best candidates scored, best first, at most `total_selected` of them
+	pub leaders: Vec<(u128, Bond<AccountId, Balance>)>,
+	/// Best candidate scored but left out of `leaders`
+	pub best_left_out: Option<(u128, Bond<AccountId, Balance>)>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// How the stake of a scheduled revoke or decrease request is rewarded until it is executed.
+/// The stake stays locked and backs the collator until then, so forfeiting its rewards penalises
+/// delegators for giving notice.
+pub enum PendingRequestRewards {
+	/// Not rewarded from the round after the request is scheduled
+	Forfeit,
+	/// Rewarded in full until the request is executed
+	UntilExecution,
+	/// Rewarded in proportion to the rounds left until the request is executable
+	ProRata,
+}
+impl Default for PendingRequestRewards {
+	fn default() -> PendingRequestRewards {
+		PendingRequestRewards::Forfeit
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// How the reward of a collator left after commission is split between it and its delegators
+pub enum DelegatorRewardCurve {
+	/// Pro rata to the self bond and each delegation
+	ProRata,
+	/// Pro rata, but the collator keeps at least this share whatever its self bond, the
+	/// delegators splitting the rest by stake
+	MinCollatorShare(Perbill),
+}
+impl Default for DelegatorRewardCurve {
+	fn default() -> DelegatorRewardCurve {
+		DelegatorRewardCurve::ProRata
+	}
+}
+impl DelegatorRewardCurve {
+	/// Share of the reward kept by a collator whose self bond is `bond_share` of its stake
+	pub fn collator_share(&self, bond_share: Perbill) -> Perbill {
+		match self {
+			DelegatorRewardCurve::ProRata => bond_share,
+			DelegatorRewardCurve::MinCollatorShare(min) => bond_share.max(*min),
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// How `delegate_spread` picks the candidates a delegation is split over
+pub enum SpreadStrategy {
+	/// Candidates with the least counted stake first
+	LowestBacked,
+	/// Candidates that earned the most points per counted stake in the previous round first
+	HighestApr,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Staking parameter update applied at the start of a scheduled round
+pub enum ParameterChange {
+	/// Annual inflation range, as set by `set_inflation`
+	Inflation(Range<Perbill>),
+	/// Commission of all collators, as set by `set_collator_commission`
+	CollatorCommission(Perbill),
+	/// Collators selected per round, as set by `set_total_selected`
+	TotalSelected(u32),
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Delegation request pending when a staking snapshot was taken
+pub struct SnapshotRequest<AccountId, Balance> {
+	pub delegator: AccountId,
+	pub candidate: AccountId,
+	pub action: DelegationAction<Balance>,
+	/// Rounds that were left before the request became executable
+	pub rounds_left: RoundIndex,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// Staking state exported from another chain, to be restored by `force_restore_staking_state`
+pub struct StakingSnapshot<AccountId, Balance> {
+	/// `(candidate, self bond)`
+	pub candidates: Vec<(AccountId, Balance)>,
+	/// `(delegator, candidate, amount, auto-compound percent, rounds compounded every)`
+	pub delegations: Vec<(AccountId, AccountId, Balance, Percent, RoundIndex)>,
+	/// Pending delegation requests, rescheduled relative to the current round
+	pub requests: Vec<SnapshotRequest<AccountId, Balance>>,
+}
+
 pub enum BondAdjust<Balance> {
 	Increase(Balance),
-	Decrease,
+	Decrease(Balance),
 }
@@ -0,0 +1,146 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Root History
+//!
+//! Records the recent merkle roots of each tree, and the roots of its neighbors on other chains,
+//! with the block they became current in. Withdrawal provers read them through a runtime API to
+//! pick a recent root the vanchor still accepts, without walking the tree storage. The current
+//! roots are read from [`CurrentRoots`] at the start of every block, and a root is recorded only
+//! when it changed. Only the last `MaxRoots` roots of each tree and neighbor are kept.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::{One, Saturating};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub use module::*;
+
+/// The recorded roots of a tree or neighbor and the block they became current in, oldest first
+pub type RootsOf<T> = Vec<(<T as frame_system::Config>::BlockNumber, <T as Config>::Element)>;
+
+/// Reads the current roots of the trees to record
+pub trait CurrentRoots<TreeId, ChainId, Element> {
+	/// The root of each tree, with the roots of its neighbors by chain id
+	fn current_roots() -> Vec<(TreeId, Element, Vec<(ChainId, Element)>)>;
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The tree identifier.
+		type TreeId: Parameter + Copy;
+
+		/// The chain identifier of neighbors.
+		type ChainId: Parameter + Copy;
+
+		/// The merkle roots recorded.
+		type Element: Parameter;
+
+		/// Reads the current roots.
+		type Roots: CurrentRoots<Self::TreeId, Self::ChainId, Self::Element>;
+
+		/// The most roots kept for each tree and neighbor, older ones are dropped.
+		#[pallet::constant]
+		type MaxRoots: Get<u32>;
+	}
+
+	/// The recent roots of each tree
+	///
+	/// map TreeId => Vec<(BlockNumber, Root)>
+	#[pallet::storage]
+	#[pallet::getter(fn roots)]
+	pub type Roots<T: Config> = StorageMap<_, Twox64Concat, T::TreeId, RootsOf<T>, ValueQuery>;
+
+	/// The recent roots of the neighbors of each tree
+	///
+	/// double_map (TreeId, ChainId) => Vec<(BlockNumber, Root)>
+	#[pallet::storage]
+	#[pallet::getter(fn neighbor_roots)]
+	pub type NeighborRoots<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::TreeId,
+		Twox64Concat,
+		T::ChainId,
+		RootsOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Records the roots as of the end of the previous block
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let block = now.saturating_sub(One::one());
+			let mut reads = 0u64;
+			let mut writes = 0u64;
+			for (tree_id, root, neighbors) in T::Roots::current_roots() {
+				reads += 2;
+				writes += Roots::<T>::try_mutate(tree_id, |roots| Self::record(roots, block, root))
+					.is_ok() as u64;
+				for (chain_id, root) in neighbors {
+					reads += 2;
+					writes += NeighborRoots::<T>::try_mutate(tree_id, chain_id, |roots| {
+						Self::record(roots, block, root)
+					})
+					.is_ok() as u64;
+				}
+			}
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Up to `limit` of the latest roots of `tree_id`, and of each of its neighbors, newest first
+	pub fn recent_roots(
+		tree_id: T::TreeId,
+		limit: u32,
+	) -> (RootsOf<T>, Vec<(T::ChainId, RootsOf<T>)>) {
+		let latest = |roots: RootsOf<T>| roots.into_iter().rev().take(limit as usize).collect();
+		let neighbors = NeighborRoots::<T>::iter_prefix(tree_id)
+			.map(|(chain_id, roots)| (chain_id, latest(roots)))
+			.collect();
+		(latest(Roots::<T>::get(tree_id)), neighbors)
+	}
+
+	/// Appends `root` to `roots` unless it is the latest one already, dropping the oldest roots
+	/// beyond `MaxRoots`. Fails, leaving the storage untouched, if `root` is already the latest.
+	fn record(roots: &mut RootsOf<T>, block: T::BlockNumber, root: T::Element) -> Result<(), ()> {
+		if roots.last().map_or(false, |(_, latest)| *latest == root) {
+			return Err(())
+		}
+		roots.push((block, root));
+		let excess = roots.len().saturating_sub(T::MaxRoots::get() as usize);
+		roots.drain(..excess);
+		Ok(())
+	}
+}
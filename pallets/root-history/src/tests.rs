@@ -0,0 +1,64 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg(test)]
+use super::*;
+use mock::*;
+
+/// Ends block `block` with `roots` as the current roots
+fn end_block(block: u64, roots: Vec<(u32, u64, Vec<(u64, u64)>)>) {
+	TreeRoots::set(roots);
+	System::set_block_number(block + 1);
+	RootHistory::on_initialize(block + 1);
+}
+
+#[test]
+fn records_roots_when_they_change() {
+	ExtBuilder::default().build().execute_with(|| {
+		end_block(1, vec![(0, 10, vec![])]);
+		end_block(2, vec![(0, 10, vec![])]);
+		end_block(3, vec![(0, 11, vec![])]);
+
+		assert_eq!(RootHistory::roots(0), vec![(1, 10), (3, 11)]);
+		assert_eq!(RootHistory::recent_roots(0, 1), (vec![(3, 11)], vec![]));
+		assert_eq!(RootHistory::recent_roots(0, 10), (vec![(3, 11), (1, 10)], vec![]));
+		assert_eq!(RootHistory::recent_roots(1, 10), (vec![], vec![]));
+	});
+}
+
+#[test]
+fn records_neighbor_roots_by_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		end_block(1, vec![(0, 10, vec![(5, 50), (6, 60)])]);
+		end_block(2, vec![(0, 10, vec![(5, 51), (6, 60)])]);
+
+		assert_eq!(RootHistory::neighbor_roots(0, 5), vec![(1, 50), (2, 51)]);
+		assert_eq!(RootHistory::neighbor_roots(0, 6), vec![(1, 60)]);
+		let (roots, mut neighbors) = RootHistory::recent_roots(0, 1);
+		neighbors.sort();
+		assert_eq!(roots, vec![(1, 10)]);
+		assert_eq!(neighbors, vec![(5, vec![(2, 51)]), (6, vec![(1, 60)])]);
+	});
+}
+
+#[test]
+fn drops_the_oldest_roots_once_full() {
+	ExtBuilder::default().build().execute_with(|| {
+		for block in 1..=5 {
+			end_block(block, vec![(0, block, vec![])]);
+		}
+		assert_eq!(RootHistory::roots(0), vec![(3, 3), (4, 4), (5, 5)]);
+	});
+}
@@ -0,0 +1,114 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Signed Proposal Log
+//!
+//! Keeps the most recently signed DKG proposals with the block they were signed in, so relayers
+//! can page through the proposals signed since the last block they synced instead of polling
+//! the proposal handler storage. The runtime reports proposals through [`Pallet::note_signed`],
+//! typically from the proposal handler's signed proposal hook. Only the last
+//! `MaxLoggedProposals` proposals are kept.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub use module::*;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The signed proposals logged.
+		type Proposal: Parameter;
+
+		/// The most proposals kept, older ones are dropped.
+		#[pallet::constant]
+		type MaxLoggedProposals: Get<u32>;
+	}
+
+	/// The logged proposals and the block they were signed in, by log index
+	///
+	/// map Index => (BlockNumber, Proposal)
+	#[pallet::storage]
+	#[pallet::getter(fn logged)]
+	pub type Log<T: Config> =
+		StorageMap<_, Twox64Concat, u64, (T::BlockNumber, T::Proposal), OptionQuery>;
+
+	/// The index of the oldest logged proposal and the index of the next one
+	///
+	/// (First, Next)
+	#[pallet::storage]
+	#[pallet::getter(fn bounds)]
+	pub type Bounds<T: Config> = StorageValue<_, (u64, u64), ValueQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Logs `proposal` as signed in the current block, dropping the oldest proposal if the log
+	/// is full
+	pub fn note_signed(proposal: T::Proposal) {
+		let (mut first, next) = Bounds::<T>::get();
+		Log::<T>::insert(next, (<frame_system::Pallet<T>>::block_number(), proposal));
+		let next = next.saturating_add(1);
+		while next.saturating_sub(first) > T::MaxLoggedProposals::get() as u64 {
+			Log::<T>::remove(first);
+			first += 1;
+		}
+		Bounds::<T>::put((first, next));
+	}
+
+	/// Up to `limit` of the logged proposals signed in block `since` or later, from log index
+	/// `cursor` on. Returns them with the block they were signed in, and the cursor of the next
+	/// page if there are more.
+	pub fn signed_since(
+		since: T::BlockNumber,
+		cursor: u64,
+		limit: u32,
+	) -> (Vec<(T::BlockNumber, T::Proposal)>, Option<u64>) {
+		let (first, next) = Bounds::<T>::get();
+		let mut proposals = Vec::new();
+		for index in cursor.max(first)..next {
+			let (signed_at, proposal) = match Log::<T>::get(index) {
+				Some(logged) => logged,
+				None => continue,
+			};
+			if signed_at < since {
+				continue
+			}
+			if proposals.len() as u32 >= limit {
+				return (proposals, Some(index))
+			}
+			proposals.push((signed_at, proposal));
+		}
+		(proposals, None)
+	}
+}
@@ -0,0 +1,56 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg(test)]
+use super::*;
+use mock::*;
+
+/// Logs `proposal` as signed in block `block`
+fn sign_at(block: u64, proposal: u8) {
+	System::set_block_number(block);
+	SignedProposalLog::note_signed(vec![proposal]);
+}
+
+#[test]
+fn pages_through_proposals_signed_since_a_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		sign_at(1, 1);
+		sign_at(2, 2);
+		sign_at(2, 3);
+
+		assert_eq!(SignedProposalLog::signed_since(2, 0, 1), (vec![(2, vec![2])], Some(2)));
+		assert_eq!(SignedProposalLog::signed_since(2, 2, 1), (vec![(2, vec![3])], None));
+		assert_eq!(
+			SignedProposalLog::signed_since(0, 0, 10),
+			(vec![(1, vec![1]), (2, vec![2]), (2, vec![3])], None)
+		);
+		assert_eq!(SignedProposalLog::signed_since(3, 0, 10), (vec![], None));
+	});
+}
+
+#[test]
+fn drops_the_oldest_proposals_once_full() {
+	ExtBuilder::default().build().execute_with(|| {
+		for proposal in 1..=5 {
+			sign_at(proposal as u64, proposal);
+		}
+		assert_eq!(SignedProposalLog::bounds(), (2, 5));
+		assert_eq!(SignedProposalLog::logged(1), None);
+		assert_eq!(
+			SignedProposalLog::signed_since(0, 0, 10),
+			(vec![(3, vec![3]), (4, vec![4]), (5, vec![5])], None)
+		);
+	});
+}
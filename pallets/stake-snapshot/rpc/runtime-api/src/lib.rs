@@ -0,0 +1,36 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the stake-snapshot pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use parity_scale_codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait StakeSnapshotApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Amount `account` had staked when `round` started, for stake-weighted votes, or
+		/// `None` if that round was not recorded or was pruned already
+		fn voting_power(account: AccountId, round: u32) -> Option<Balance>;
+		/// Amount staked in total when `round` started, or `None` if that round was not
+		/// recorded or was pruned already
+		fn total_voting_power(round: u32) -> Option<Balance>;
+	}
+}
@@ -0,0 +1,105 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Stake Snapshot
+//!
+//! Records the amount each account has staked when a staking round starts, so votes can be
+//! weighted by stake as of a fixed round: off-chain polls read it through a runtime API, and
+//! referenda can scale vote weights by it. The runtime records a round with
+//! [`Pallet::record`], typically from the staking pallet's new round hook. Only the last
+//! `HistoryDepth` rounds are kept.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use sp_runtime::traits::{AtLeast32BitUnsigned, Saturating, Zero};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub use module::*;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The staked amounts recorded.
+		type Balance: Parameter + AtLeast32BitUnsigned + Copy + Default;
+
+		/// The most rounds kept, older ones are dropped.
+		#[pallet::constant]
+		type HistoryDepth: Get<u32>;
+	}
+
+	/// The amount each account had staked when a round started
+	///
+	/// double_map (Round, AccountId) => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn staked)]
+	pub type Staked<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, T::AccountId, T::Balance, ValueQuery>;
+
+	/// The amount staked in total when a round started, for the recorded rounds only
+	///
+	/// map Round => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_staked)]
+	pub type TotalStaked<T: Config> = StorageMap<_, Twox64Concat, u32, T::Balance, OptionQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Records `stakes` as the amounts staked when `round` started and drops the round that
+	/// falls out of `HistoryDepth`. Amounts of the same account add up, zero amounts are
+	/// left out.
+	pub fn record(round: u32, stakes: Vec<(T::AccountId, T::Balance)>) -> Weight {
+		let mut writes = 1u64;
+		if let Some(expired) = round.checked_sub(T::HistoryDepth::get().max(1)) {
+			TotalStaked::<T>::remove(expired);
+			writes += 1 + Staked::<T>::clear_prefix(expired, u32::MAX, None).unique as u64;
+		}
+
+		let mut total = T::Balance::zero();
+		for (who, amount) in stakes {
+			if amount.is_zero() {
+				continue
+			}
+			Staked::<T>::mutate(round, &who, |staked| *staked = staked.saturating_add(amount));
+			total = total.saturating_add(amount);
+			writes += 1;
+		}
+		TotalStaked::<T>::insert(round, total);
+		T::DbWeight::get().reads_writes(writes - 1, writes)
+	}
+
+	/// Amount `who` had staked when `round` started, or `None` if the round was not recorded
+	/// or was dropped already
+	pub fn voting_power(who: &T::AccountId, round: u32) -> Option<T::Balance> {
+		TotalStaked::<T>::contains_key(round).then(|| Staked::<T>::get(round, who))
+	}
+}
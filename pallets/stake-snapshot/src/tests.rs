@@ -0,0 +1,45 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg(test)]
+use super::*;
+use mock::*;
+
+#[test]
+fn records_the_stake_of_each_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		StakeSnapshot::record(1, vec![(1, 100), (2, 50), (1, 20), (3, 0)]);
+
+		assert_eq!(StakeSnapshot::voting_power(&1, 1), Some(120));
+		assert_eq!(StakeSnapshot::voting_power(&2, 1), Some(50));
+		assert_eq!(StakeSnapshot::voting_power(&3, 1), Some(0));
+		assert_eq!(StakeSnapshot::total_staked(1), Some(170));
+		assert_eq!(StakeSnapshot::voting_power(&1, 2), None);
+	});
+}
+
+#[test]
+fn drops_the_oldest_round_once_full() {
+	ExtBuilder::default().build().execute_with(|| {
+		for round in 1..=4 {
+			StakeSnapshot::record(round, vec![(1, round as u64)]);
+		}
+		assert_eq!(StakeSnapshot::voting_power(&1, 1), None);
+		assert_eq!(StakeSnapshot::total_staked(1), None);
+		assert_eq!(StakeSnapshot::staked(1, 1), 0);
+		assert_eq!(StakeSnapshot::voting_power(&1, 2), Some(2));
+		assert_eq!(StakeSnapshot::voting_power(&1, 4), Some(4));
+	});
+}
@@ -0,0 +1,32 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the transaction-pause pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait TransactionPauseApi {
+		/// Calls currently paused, as (pallet name, call name) pairs, so wallets can disable
+		/// them up front instead of failing on dispatch
+		fn paused_calls() -> Vec<(Vec<u8>, Vec<u8>)>;
+		/// Whether the call `function_name` of the pallet `pallet_name` is paused
+		fn is_paused(pallet_name: Vec<u8>, function_name: Vec<u8>) -> bool;
+	}
+}
@@ -33,6 +33,30 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// A group of calls paused and resumed together with `pause_group` and `resume_group`
+pub trait CallGroup<Call>: Parameter + Member {
+	/// Whether `call` is one of the calls of the group
+	fn contains(&self, call: &Call) -> bool;
+}
+
+impl<Call> CallGroup<Call> for () {
+	fn contains(&self, _call: &Call) -> bool {
+		false
+	}
+}
+
+/// Calls dispatched by another call on behalf of its origin, e.g. the calls of a batch
+pub trait NestedCalls<Call> {
+	/// The calls directly nested in `call`
+	fn nested_calls(call: &Call) -> Vec<&Call>;
+}
+
+impl<Call> NestedCalls<Call> for () {
+	fn nested_calls(_call: &Call) -> Vec<&Call> {
+		Vec::new()
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -44,6 +68,19 @@ pub mod module {
 		/// The origin which may set filter.
 		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// The origin which may pause and resume all bridge pallets at once.
+		type BridgePauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The names of the pallets paused with the bridge, e.g. the signature bridge and the
+		/// handlers of the proposals it executes.
+		type BridgePallets: Get<Vec<&'static str>>;
+
+		/// Groups of calls paused together by `pause_group`, e.g. every call of a pallet
+		type Group: CallGroup<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// Calls nested in others, paused along with the calls they are nested in
+		type NestedCalls: NestedCalls<<Self as frame_system::Config>::RuntimeCall>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -54,6 +91,14 @@ pub mod module {
 		CannotPause,
 		/// invalid character encoding
 		InvalidCharacter,
+		/// the bridge is already paused
+		BridgeAlreadyPaused,
+		/// the bridge is not paused
+		BridgeNotPaused,
+		/// the group is already paused
+		GroupAlreadyPaused,
+		/// the group is not paused
+		GroupNotPaused,
 	}
 
 	#[pallet::event]
@@ -63,6 +108,14 @@ pub mod module {
 		TransactionPaused { pallet_name_bytes: Vec<u8>, function_name_bytes: Vec<u8> },
 		/// Unpaused transaction
 		TransactionUnpaused { pallet_name_bytes: Vec<u8>, function_name_bytes: Vec<u8> },
+		/// Paused every call of the bridge pallets
+		BridgePaused,
+		/// Resumed the calls of the bridge pallets
+		BridgeResumed,
+		/// Paused every call of a group
+		GroupPaused { group: T::Group },
+		/// Resumed the calls of a group
+		GroupResumed { group: T::Group },
 	}
 
 	/// The paused transaction map
@@ -73,6 +126,16 @@ pub mod module {
 	pub type PausedTransactions<T: Config> =
 		StorageMap<_, Twox64Concat, (Vec<u8>, Vec<u8>), (), OptionQuery>;
 
+	/// Whether every call of the `BridgePallets` is paused
+	#[pallet::storage]
+	#[pallet::getter(fn bridge_paused)]
+	pub type BridgePaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The groups whose calls are all paused
+	#[pallet::storage]
+	#[pallet::getter(fn paused_groups)]
+	pub type PausedGroups<T: Config> = StorageValue<_, Vec<T::Group>, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -99,18 +162,7 @@ pub mod module {
 				Error::<T>::CannotPause
 			);
 
-			PausedTransactions::<T>::mutate_exists(
-				(pallet_name.clone(), function_name.clone()),
-				|maybe_paused| {
-					if maybe_paused.is_none() {
-						*maybe_paused = Some(());
-						Self::deposit_event(Event::TransactionPaused {
-							pallet_name_bytes: pallet_name,
-							function_name_bytes: function_name,
-						});
-					}
-				},
-			);
+			Self::do_pause_transaction(pallet_name, function_name);
 			Ok(())
 		}
 
@@ -130,16 +182,116 @@ pub mod module {
 			};
 			Ok(())
 		}
+
+		/// Pause every call of the bridge pallets, including the proposals the signature bridge
+		/// would execute on the handlers, until `resume_bridge`
+		#[pallet::weight(T::WeightInfo::pause_bridge())]
+		pub fn pause_bridge(origin: OriginFor<T>) -> DispatchResult {
+			T::BridgePauseOrigin::ensure_origin(origin)?;
+			ensure!(!BridgePaused::<T>::get(), Error::<T>::BridgeAlreadyPaused);
+			BridgePaused::<T>::put(true);
+			Self::deposit_event(Event::BridgePaused);
+			Ok(())
+		}
+
+		/// Resume the calls of the bridge pallets. Calls paused one by one stay paused.
+		#[pallet::weight(T::WeightInfo::resume_bridge())]
+		pub fn resume_bridge(origin: OriginFor<T>) -> DispatchResult {
+			T::BridgePauseOrigin::ensure_origin(origin)?;
+			ensure!(BridgePaused::<T>::get(), Error::<T>::BridgeNotPaused);
+			BridgePaused::<T>::kill();
+			Self::deposit_event(Event::BridgeResumed);
+			Ok(())
+		}
+
+		/// Pause every call of `group`, including calls nested in others such as batches, until
+		/// `resume_group`
+		#[pallet::weight(T::WeightInfo::pause_group())]
+		pub fn pause_group(origin: OriginFor<T>, group: T::Group) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			PausedGroups::<T>::try_mutate(|groups| -> DispatchResult {
+				ensure!(!groups.contains(&group), Error::<T>::GroupAlreadyPaused);
+				groups.push(group.clone());
+				Ok(())
+			})?;
+			Self::deposit_event(Event::GroupPaused { group });
+			Ok(())
+		}
+
+		/// Resume the calls of `group`. Calls paused one by one or with another group stay paused.
+		#[pallet::weight(T::WeightInfo::resume_group())]
+		pub fn resume_group(origin: OriginFor<T>, group: T::Group) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			PausedGroups::<T>::try_mutate(|groups| -> DispatchResult {
+				let paused = groups.len();
+				groups.retain(|paused| paused != &group);
+				ensure!(groups.len() < paused, Error::<T>::GroupNotPaused);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::GroupResumed { group });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Calls currently paused, as (pallet name, call name) pairs
+	pub fn paused_calls() -> Vec<(Vec<u8>, Vec<u8>)> {
+		PausedTransactions::<T>::iter_keys().collect()
+	}
+
+	/// Pause a call without checking the origin, e.g. for a circuit breaker in the runtime
+	pub fn do_pause_transaction(pallet_name: Vec<u8>, function_name: Vec<u8>) {
+		PausedTransactions::<T>::mutate_exists(
+			(pallet_name.clone(), function_name.clone()),
+			|maybe_paused| {
+				if maybe_paused.is_none() {
+					*maybe_paused = Some(());
+					Self::deposit_event(Event::TransactionPaused {
+						pallet_name_bytes: pallet_name,
+						function_name_bytes: function_name,
+					});
+				}
+			},
+		);
+	}
+
+	/// Pause the calls of `group` without checking the origin, e.g. for a circuit breaker in the
+	/// runtime. Does nothing if the group is already paused.
+	pub fn do_pause_group(group: T::Group) {
+		PausedGroups::<T>::mutate(|groups| {
+			if !groups.contains(&group) {
+				groups.push(group.clone());
+				Self::deposit_event(Event::GroupPaused { group });
+			}
+		});
 	}
 }
 
+/// Filters the paused calls, along with the calls any of them is nested in
 pub struct PausedTransactionFilter<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> PausedTransactionFilter<T>
+where
+	<T as frame_system::Config>::RuntimeCall: GetCallMetadata,
+{
+	fn is_paused(call: &T::RuntimeCall, groups: &[T::Group]) -> bool {
+		let CallMetadata { function_name, pallet_name } = call.get_call_metadata();
+		PausedTransactions::<T>::contains_key((pallet_name.as_bytes(), function_name.as_bytes())) ||
+			(BridgePaused::<T>::get() && T::BridgePallets::get().contains(&pallet_name)) ||
+			// the calls of this pallet are never paused, so that they can resume the others
+			(pallet_name != <Pallet<T> as PalletInfoAccess>::name() &&
+				groups.iter().any(|group| group.contains(call))) ||
+			T::NestedCalls::nested_calls(call)
+				.into_iter()
+				.any(|nested| Self::is_paused(nested, groups))
+	}
+}
+
 impl<T: Config> Contains<T::RuntimeCall> for PausedTransactionFilter<T>
 where
 	<T as frame_system::Config>::RuntimeCall: GetCallMetadata,
 {
 	fn contains(call: &T::RuntimeCall) -> bool {
-		let CallMetadata { function_name, pallet_name } = call.get_call_metadata();
-		PausedTransactions::<T>::contains_key((pallet_name.as_bytes(), function_name.as_bytes()))
+		Self::is_paused(call, &PausedGroups::<T>::get())
 	}
 }
@@ -16,14 +16,16 @@
 #![cfg(test)]
 
 use super::*;
+use codec::{Decode, Encode};
 use frame_support::{
-	construct_runtime, ord_parameter_types,
+	construct_runtime, ord_parameter_types, parameter_types,
 	traits::{ConstU128, ConstU32, ConstU64, Everything, Nothing},
 };
 use frame_system::EnsureSignedBy;
 use orml_traits::parameter_type_with_key;
+use scale_info::TypeInfo;
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_runtime::{testing::Header, traits::IdentityLookup, RuntimeDebug};
 
 pub type AccountId = u128;
 pub const ALICE: AccountId = 1;
@@ -104,9 +106,60 @@ ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
 
+parameter_types! {
+	pub BridgePallets: Vec<&'static str> = vec!["Tokens"];
+}
+
+impl pallet_utility::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
+}
+
+/// Groups of calls of the mock runtime
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum MockGroup {
+	/// Every call of `Tokens`
+	Tokens,
+	/// The transfers of both `Balances` and `Tokens`
+	Transfers,
+}
+
+impl CallGroup<RuntimeCall> for MockGroup {
+	fn contains(&self, call: &RuntimeCall) -> bool {
+		match self {
+			MockGroup::Tokens => matches!(call, RuntimeCall::Tokens(_)),
+			MockGroup::Transfers => matches!(
+				call,
+				RuntimeCall::Balances(pallet_balances::Call::transfer { .. }) |
+					RuntimeCall::Tokens(orml_tokens::Call::transfer { .. })
+			),
+		}
+	}
+}
+
+/// The calls batched with `Utility`
+pub struct BatchedCalls;
+impl NestedCalls<RuntimeCall> for BatchedCalls {
+	fn nested_calls(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+		match call {
+			RuntimeCall::Utility(pallet_utility::Call::batch { calls }) |
+			RuntimeCall::Utility(pallet_utility::Call::batch_all { calls }) |
+			RuntimeCall::Utility(pallet_utility::Call::force_batch { calls }) => calls.iter().collect(),
+			RuntimeCall::Utility(pallet_utility::Call::as_derivative { call, .. }) => vec![&**call],
+			_ => Vec::new(),
+		}
+	}
+}
+
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type BridgePauseOrigin = EnsureSignedBy<One, AccountId>;
+	type BridgePallets = BridgePallets;
+	type Group = MockGroup;
+	type NestedCalls = BatchedCalls;
 	type WeightInfo = ();
 }
 
@@ -123,6 +176,7 @@ construct_runtime!(
 		TransactionPause: transaction_pause::{Pallet, Storage, Call, Event<T>},
 		Balances: pallet_balances::{Pallet, Storage, Call, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Call, Event<T>},
+		Utility: pallet_utility::{Pallet, Call, Event},
 	}
 );
 
@@ -113,6 +113,22 @@ fn unpause_transaction_work() {
 	});
 }
 
+#[test]
+fn paused_calls_lists_paused_transactions() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(TransactionPause::paused_calls().is_empty());
+		assert_ok!(TransactionPause::pause_transaction(
+			RuntimeOrigin::signed(1),
+			b"Balances".to_vec(),
+			b"transfer".to_vec()
+		));
+		assert_eq!(
+			TransactionPause::paused_calls(),
+			vec![(b"Balances".to_vec(), b"transfer".to_vec())]
+		);
+	});
+}
+
 #[test]
 fn paused_transaction_filter_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -144,3 +160,138 @@ fn paused_transaction_filter_work() {
 		assert!(!PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
 	});
 }
+
+#[test]
+fn pause_bridge_filters_bridge_pallets() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(TransactionPause::pause_bridge(RuntimeOrigin::signed(5)), BadOrigin);
+		assert_noop!(
+			TransactionPause::resume_bridge(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::BridgeNotPaused
+		);
+
+		assert_ok!(TransactionPause::pause_bridge(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionPause(crate::Event::BridgePaused));
+		assert!(TransactionPause::bridge_paused());
+		assert!(PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
+		assert!(!PausedTransactionFilter::<Runtime>::contains(BALANCE_TRANSFER));
+		assert_noop!(
+			TransactionPause::pause_bridge(RuntimeOrigin::signed(1)),
+			Error::<Runtime>::BridgeAlreadyPaused
+		);
+
+		assert_ok!(TransactionPause::resume_bridge(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionPause(crate::Event::BridgeResumed));
+		assert!(!PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
+	});
+}
+
+#[test]
+fn resume_bridge_keeps_calls_paused_one_by_one() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TransactionPause::pause_transaction(
+			RuntimeOrigin::signed(1),
+			b"Tokens".to_vec(),
+			b"transfer".to_vec()
+		));
+		assert_ok!(TransactionPause::pause_bridge(RuntimeOrigin::signed(1)));
+		assert_ok!(TransactionPause::resume_bridge(RuntimeOrigin::signed(1)));
+		assert!(PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
+	});
+}
+
+#[test]
+fn pause_group_filters_every_call_of_the_group() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			TransactionPause::pause_group(RuntimeOrigin::signed(5), MockGroup::Tokens),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPause::resume_group(RuntimeOrigin::signed(1), MockGroup::Tokens),
+			Error::<Runtime>::GroupNotPaused
+		);
+
+		assert_ok!(TransactionPause::pause_group(RuntimeOrigin::signed(1), MockGroup::Tokens));
+		System::assert_last_event(RuntimeEvent::TransactionPause(crate::Event::GroupPaused {
+			group: MockGroup::Tokens,
+		}));
+		assert_eq!(TransactionPause::paused_groups(), vec![MockGroup::Tokens]);
+		assert!(PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
+		assert!(!PausedTransactionFilter::<Runtime>::contains(BALANCE_TRANSFER));
+		assert_noop!(
+			TransactionPause::pause_group(RuntimeOrigin::signed(1), MockGroup::Tokens),
+			Error::<Runtime>::GroupAlreadyPaused
+		);
+
+		// a call paused by another group stays paused
+		assert_ok!(TransactionPause::pause_group(RuntimeOrigin::signed(1), MockGroup::Transfers));
+		assert!(PausedTransactionFilter::<Runtime>::contains(BALANCE_TRANSFER));
+		assert_ok!(TransactionPause::resume_group(RuntimeOrigin::signed(1), MockGroup::Tokens));
+		System::assert_last_event(RuntimeEvent::TransactionPause(crate::Event::GroupResumed {
+			group: MockGroup::Tokens,
+		}));
+		assert!(PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
+
+		assert_ok!(TransactionPause::resume_group(RuntimeOrigin::signed(1), MockGroup::Transfers));
+		assert!(!PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
+		assert!(!PausedTransactionFilter::<Runtime>::contains(BALANCE_TRANSFER));
+	});
+}
+
+#[test]
+fn do_pause_group_pauses_the_group_once() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		TransactionPause::do_pause_group(MockGroup::Tokens);
+		System::assert_last_event(RuntimeEvent::TransactionPause(crate::Event::GroupPaused {
+			group: MockGroup::Tokens,
+		}));
+		assert!(PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
+
+		let events = System::events().len();
+		TransactionPause::do_pause_group(MockGroup::Tokens);
+		assert_eq!(System::events().len(), events);
+		assert_eq!(TransactionPause::paused_groups(), vec![MockGroup::Tokens]);
+
+		assert_ok!(TransactionPause::resume_group(RuntimeOrigin::signed(1), MockGroup::Tokens));
+		assert!(!PausedTransactionFilter::<Runtime>::contains(TOKENS_TRANSFER));
+	});
+}
+
+#[test]
+fn paused_calls_are_filtered_when_nested() {
+	ExtBuilder::default().build().execute_with(|| {
+		let batch =
+			|calls: Vec<RuntimeCall>| RuntimeCall::Utility(pallet_utility::Call::batch { calls });
+		let derivative = |call: RuntimeCall| {
+			RuntimeCall::Utility(pallet_utility::Call::as_derivative {
+				index: 0,
+				call: Box::new(call),
+			})
+		};
+		let nested_transfer =
+			batch(vec![BALANCE_TRANSFER.clone(), derivative(batch(vec![TOKENS_TRANSFER.clone()]))]);
+		assert!(!PausedTransactionFilter::<Runtime>::contains(&nested_transfer));
+
+		assert_ok!(TransactionPause::pause_group(RuntimeOrigin::signed(1), MockGroup::Tokens));
+		assert!(PausedTransactionFilter::<Runtime>::contains(&nested_transfer));
+		assert!(!PausedTransactionFilter::<Runtime>::contains(&batch(vec![
+			BALANCE_TRANSFER.clone()
+		])));
+		assert_ok!(TransactionPause::resume_group(RuntimeOrigin::signed(1), MockGroup::Tokens));
+
+		// calls paused one by one are caught when nested too
+		assert_ok!(TransactionPause::pause_transaction(
+			RuntimeOrigin::signed(1),
+			b"Tokens".to_vec(),
+			b"transfer".to_vec()
+		));
+		assert!(PausedTransactionFilter::<Runtime>::contains(&nested_transfer));
+	});
+}
@@ -46,6 +46,10 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn pause_transaction() -> Weight;
 	fn unpause_transaction() -> Weight;
+	fn pause_bridge() -> Weight;
+	fn resume_bridge() -> Weight;
+	fn pause_group() -> Weight;
+	fn resume_group() -> Weight;
 }
 
 /// Weights for module_transaction_pause using the Acala node and recommended hardware.
@@ -61,6 +65,26 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	fn pause_bridge() -> Weight {
+		Weight::from_ref_time(24_518_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn resume_bridge() -> Weight {
+		Weight::from_ref_time(23_910_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn pause_group() -> Weight {
+		Weight::from_ref_time(25_102_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn resume_group() -> Weight {
+		Weight::from_ref_time(24_687_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -75,4 +99,24 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	fn pause_bridge() -> Weight {
+		Weight::from_ref_time(24_518_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn resume_bridge() -> Weight {
+		Weight::from_ref_time(23_910_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn pause_group() -> Weight {
+		Weight::from_ref_time(25_102_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn resume_group() -> Weight {
+		Weight::from_ref_time(24_687_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
\ No newline at end of file
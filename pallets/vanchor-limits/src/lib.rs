@@ -0,0 +1,188 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # VAnchor Limits
+//!
+//! Deposit and withdrawal limits of each vanchor tree, adjustable by governance at runtime on
+//! top of the limits `pallet_vanchor` is configured with at genesis. Vanchor transactions are made
+//! through [`Pallet::transact`], which checks them against the limits before handing them to the
+//! vanchor of [`Config::VAnchor`]; the runtime is expected to filter out direct calls to the
+//! vanchor. Trees without a limit set are not restricted by this pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::AtLeast32BitUnsigned, SaturatedConversion};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// Makes the transactions of a vanchor
+pub trait VAnchorTransact<AccountId, TreeId> {
+	/// The proof and external data of a transaction
+	type Transaction: Parameter;
+
+	/// The tree `transaction` is made in
+	fn tree_id(transaction: &Self::Transaction) -> TreeId;
+	/// The amount `transaction` deposits (positive) or withdraws (negative)
+	fn ext_amount(transaction: &Self::Transaction) -> i128;
+	/// The weight of making `transaction`
+	fn weight(transaction: &Self::Transaction) -> Weight;
+	/// Make `transaction` on behalf of `who`
+	fn transact(who: AccountId, transaction: Self::Transaction) -> DispatchResult;
+}
+
+pub type TransactionOf<T> = <<T as Config>::VAnchor as VAnchorTransact<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::TreeId,
+>>::Transaction;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The id of a vanchor tree.
+		type TreeId: Parameter + Copy;
+
+		/// The balance deposits and withdrawals are limited in.
+		type Balance: Parameter + AtLeast32BitUnsigned + Copy;
+
+		/// The origin which may set the limits.
+		type LimitsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The vanchor transactions are made in once within the limits.
+		type VAnchor: VAnchorTransact<Self::AccountId, Self::TreeId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The largest deposit into a tree was set, or lifted with `None`
+		MaxDepositAmountSet { tree_id: T::TreeId, amount: Option<T::Balance> },
+		/// The smallest withdrawal from a tree was set, or lifted with `None`
+		MinWithdrawAmountSet { tree_id: T::TreeId, amount: Option<T::Balance> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The transaction deposits more than the largest deposit allowed into the tree
+		DepositAboveMax,
+		/// The transaction withdraws less than the smallest withdrawal allowed from the tree
+		WithdrawalBelowMin,
+	}
+
+	/// The largest deposit into each tree
+	///
+	/// map TreeId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn max_deposit_amount)]
+	pub type MaxDepositAmount<T: Config> =
+		StorageMap<_, Twox64Concat, T::TreeId, T::Balance, OptionQuery>;
+
+	/// The smallest withdrawal from each tree
+	///
+	/// map TreeId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn min_withdraw_amount)]
+	pub type MinWithdrawAmount<T: Config> =
+		StorageMap<_, Twox64Concat, T::TreeId, T::Balance, OptionQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the largest deposit into `tree_id`, or lift the limit with `None`
+		#[pallet::weight(T::WeightInfo::set_max_deposit_amount())]
+		pub fn set_max_deposit_amount(
+			origin: OriginFor<T>,
+			tree_id: T::TreeId,
+			amount: Option<T::Balance>,
+		) -> DispatchResult {
+			T::LimitsOrigin::ensure_origin(origin)?;
+			MaxDepositAmount::<T>::set(tree_id, amount);
+			Self::deposit_event(Event::MaxDepositAmountSet { tree_id, amount });
+			Ok(())
+		}
+
+		/// Set the smallest withdrawal from `tree_id`, or lift the limit with `None`
+		#[pallet::weight(T::WeightInfo::set_min_withdraw_amount())]
+		pub fn set_min_withdraw_amount(
+			origin: OriginFor<T>,
+			tree_id: T::TreeId,
+			amount: Option<T::Balance>,
+		) -> DispatchResult {
+			T::LimitsOrigin::ensure_origin(origin)?;
+			MinWithdrawAmount::<T>::set(tree_id, amount);
+			Self::deposit_event(Event::MinWithdrawAmountSet { tree_id, amount });
+			Ok(())
+		}
+
+		/// Make a vanchor `transaction`, failing if it is outside the limits of its tree
+		#[pallet::weight(T::WeightInfo::transact().saturating_add(T::VAnchor::weight(transaction)))]
+		pub fn transact(origin: OriginFor<T>, transaction: TransactionOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_within_limits(
+				T::VAnchor::tree_id(&transaction),
+				T::VAnchor::ext_amount(&transaction),
+			)?;
+			T::VAnchor::transact(who, transaction)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether a transaction of `tree_id` moving `ext_amount` is within the limits of the tree.
+	/// A positive `ext_amount` is a deposit and a negative one a withdrawal.
+	pub fn within_limits(tree_id: T::TreeId, ext_amount: i128) -> bool {
+		let amount: T::Balance = ext_amount.unsigned_abs().saturated_into();
+		if ext_amount > 0 {
+			MaxDepositAmount::<T>::get(tree_id).map_or(true, |max| amount <= max)
+		} else if ext_amount < 0 {
+			MinWithdrawAmount::<T>::get(tree_id).map_or(true, |min| amount >= min)
+		} else {
+			true
+		}
+	}
+
+	/// Like [`Pallet::within_limits`], with the limit the transaction is outside of as the error
+	pub fn ensure_within_limits(tree_id: T::TreeId, ext_amount: i128) -> DispatchResult {
+		if Self::within_limits(tree_id, ext_amount) {
+			Ok(())
+		} else if ext_amount > 0 {
+			Err(Error::<T>::DepositAboveMax.into())
+		} else {
+			Err(Error::<T>::WithdrawalBelowMin.into())
+		}
+	}
+}
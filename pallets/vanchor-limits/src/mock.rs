@@ -0,0 +1,131 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, ord_parameter_types, parameter_types,
+	traits::{ConstU32, ConstU64, Everything},
+};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+
+pub type AccountId = u128;
+pub type Balance = u128;
+
+pub const ADMIN: AccountId = 1;
+pub const TREE: u32 = 0;
+
+mod vanchor_limits {
+	pub use super::super::*;
+}
+
+impl frame_system::Config for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+ord_parameter_types! {
+	pub const Admin: AccountId = ADMIN;
+}
+
+parameter_types! {
+	/// The transactions made in `MockVAnchor`, with the account they were made by
+	pub static Transactions: Vec<(AccountId, (u32, i128))> = vec![];
+}
+
+/// A vanchor whose transactions are a tree and an ext amount
+pub struct MockVAnchor;
+impl VAnchorTransact<AccountId, u32> for MockVAnchor {
+	type Transaction = (u32, i128);
+
+	fn tree_id(transaction: &Self::Transaction) -> u32 {
+		transaction.0
+	}
+	fn ext_amount(transaction: &Self::Transaction) -> i128 {
+		transaction.1
+	}
+	fn weight(_transaction: &Self::Transaction) -> Weight {
+		Weight::from_ref_time(0)
+	}
+	fn transact(who: AccountId, transaction: Self::Transaction) -> DispatchResult {
+		Transactions::mutate(|transactions| transactions.push((who, transaction)));
+		Ok(())
+	}
+}
+
+impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type TreeId = u32;
+	type Balance = Balance;
+	type LimitsOrigin = EnsureSignedBy<Admin, AccountId>;
+	type VAnchor = MockVAnchor;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		VAnchorLimits: vanchor_limits::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
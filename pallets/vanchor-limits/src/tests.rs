@@ -0,0 +1,105 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg(test)]
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{RuntimeEvent, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn unlimited_without_limits() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(VAnchorLimits::within_limits(TREE, i128::MAX));
+		assert!(VAnchorLimits::within_limits(TREE, -1));
+		assert!(VAnchorLimits::within_limits(TREE, 0));
+	});
+}
+
+#[test]
+fn deposits_above_the_max_are_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			VAnchorLimits::set_max_deposit_amount(RuntimeOrigin::signed(2), TREE, Some(100)),
+			BadOrigin
+		);
+		assert_ok!(VAnchorLimits::set_max_deposit_amount(
+			RuntimeOrigin::signed(ADMIN),
+			TREE,
+			Some(100)
+		));
+		System::assert_last_event(RuntimeEvent::VAnchorLimits(crate::Event::MaxDepositAmountSet {
+			tree_id: TREE,
+			amount: Some(100),
+		}));
+		assert!(VAnchorLimits::within_limits(TREE, 100));
+		assert!(!VAnchorLimits::within_limits(TREE, 101));
+		// withdrawals and other trees are not limited by it
+		assert!(VAnchorLimits::within_limits(TREE, -101));
+		assert!(VAnchorLimits::within_limits(TREE + 1, 101));
+
+		assert_ok!(VAnchorLimits::set_max_deposit_amount(RuntimeOrigin::signed(ADMIN), TREE, None));
+		assert!(VAnchorLimits::within_limits(TREE, 101));
+	});
+}
+
+#[test]
+fn withdrawals_below_the_min_are_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(VAnchorLimits::set_min_withdraw_amount(
+			RuntimeOrigin::signed(ADMIN),
+			TREE,
+			Some(10)
+		));
+		System::assert_last_event(RuntimeEvent::VAnchorLimits(
+			crate::Event::MinWithdrawAmountSet { tree_id: TREE, amount: Some(10) },
+		));
+		assert!(VAnchorLimits::within_limits(TREE, -10));
+		assert!(!VAnchorLimits::within_limits(TREE, -9));
+		assert!(VAnchorLimits::within_limits(TREE, 9));
+		// transactions moving no funds in or out stay within the limits
+		assert!(VAnchorLimits::within_limits(TREE, 0));
+	});
+}
+
+#[test]
+fn transactions_are_made_only_within_the_limits() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(VAnchorLimits::set_max_deposit_amount(
+			RuntimeOrigin::signed(ADMIN),
+			TREE,
+			Some(100)
+		));
+		assert_ok!(VAnchorLimits::set_min_withdraw_amount(
+			RuntimeOrigin::signed(ADMIN),
+			TREE,
+			Some(10)
+		));
+
+		assert_noop!(
+			VAnchorLimits::transact(RuntimeOrigin::signed(2), (TREE, 101)),
+			Error::<Runtime>::DepositAboveMax
+		);
+		assert_noop!(
+			VAnchorLimits::transact(RuntimeOrigin::signed(2), (TREE, -9)),
+			Error::<Runtime>::WithdrawalBelowMin
+		);
+		assert!(Transactions::get().is_empty());
+
+		assert_ok!(VAnchorLimits::transact(RuntimeOrigin::signed(2), (TREE, 100)));
+		assert_ok!(VAnchorLimits::transact(RuntimeOrigin::signed(3), (TREE, -10)));
+		assert_eq!(Transactions::get(), vec![(2, (TREE, 100)), (3, (TREE, -10))]);
+	});
+}
@@ -0,0 +1,68 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_vanchor_limits
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_vanchor_limits.
+pub trait WeightInfo {
+	fn set_max_deposit_amount() -> Weight;
+	fn set_min_withdraw_amount() -> Weight;
+	fn transact() -> Weight;
+}
+
+/// Weights for pallet_vanchor_limits using the Substrate node and recommended hardware.
+pub struct WebbWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for WebbWeight<T> {
+	// Storage: VAnchorLimits MaxDepositAmount (r:0 w:1)
+	fn set_max_deposit_amount() -> Weight {
+		Weight::from_ref_time(21_000_000)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: VAnchorLimits MinWithdrawAmount (r:0 w:1)
+	fn set_min_withdraw_amount() -> Weight {
+		Weight::from_ref_time(21_000_000)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: VAnchorLimits MaxDepositAmount (r:1 w:0)
+	fn transact() -> Weight {
+		Weight::from_ref_time(9_000_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn set_max_deposit_amount() -> Weight {
+		Weight::from_ref_time(21_000_000)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_min_withdraw_amount() -> Weight {
+		Weight::from_ref_time(21_000_000)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn transact() -> Weight {
+		Weight::from_ref_time(9_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+}
@@ -0,0 +1,253 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # VAnchor Rewards
+//!
+//! Rewards privacy pool users out of a claims pot. The runtime reports every vanchor deposit with
+//! the account whose funds were deposited and their value through [`Pallet::note_deposit`], every
+//! withdrawal with the account it pays through [`Pallet::note_withdrawal`], and ends an epoch
+//! with [`Pallet::end_epoch`], typically once per staking round. Funds that arrived in the pot
+//! since the last epoch, e.g. the share of round inflation the staking pallet reserves for
+//! protocol usage, are then split between the depositors of the epoch pro rata to the amount
+//! they held times the blocks they held it for, and can be claimed at any time.
+//!
+//! An account holds what it deposited less what was withdrawn to it, so depositing the same
+//! funds again after withdrawing them earns nothing extra. Withdrawals do not reveal which
+//! deposit they spend, so funds withdrawn to another account still count for the depositor.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement},
+	PalletId,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, Zero},
+	Perbill, RuntimeDebug, SaturatedConversion,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Deposits held in an epoch, as the amount held since block `since` and the sum of each amount
+/// times the blocks it was held for before
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Deposits {
+	pub amount: u128,
+	pub held: u128,
+	pub since: u128,
+}
+
+impl Deposits {
+	fn add(&mut self, amount: u128, now: u128) {
+		self.held = self.held_until(now);
+		self.amount = self.amount.saturating_add(amount);
+		self.since = now;
+	}
+
+	/// Stops holding up to `amount` from `now`, returning the amount no longer held
+	fn remove(&mut self, amount: u128, now: u128) -> u128 {
+		let removed = amount.min(self.amount);
+		self.held = self.held_until(now);
+		self.amount = self.amount.saturating_sub(removed);
+		self.since = now;
+		removed
+	}
+
+	/// The sum of each amount times the blocks it was held for until `now`
+	pub fn held_until(&self, now: u128) -> u128 {
+		self.held
+			.saturating_add(self.amount.saturating_mul(now.saturating_sub(self.since)))
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency rewards are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The id of the claims pot rewards are paid from.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The most distinct depositors rewarded in an epoch, later depositors are not counted.
+		#[pallet::constant]
+		type MaxDepositorsPerEpoch: Get<u32>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account has no rewards to claim
+		NothingToClaim,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The pot was split between the depositors of an epoch
+		EpochRewarded { epoch: u32, depositors: u32, amount: BalanceOf<T> },
+		/// Rewards were claimed
+		Claimed { who: T::AccountId, amount: BalanceOf<T> },
+	}
+
+	/// The deposits each account made in the current epoch
+	///
+	/// map AccountId => Deposits
+	#[pallet::storage]
+	#[pallet::getter(fn epoch_deposits)]
+	pub type EpochDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Deposits, ValueQuery>;
+
+	/// The number of distinct depositors and all deposits in the current epoch
+	///
+	/// (Depositors, Deposits)
+	#[pallet::storage]
+	#[pallet::getter(fn epoch_totals)]
+	pub type EpochTotals<T: Config> = StorageValue<_, (u32, Deposits), ValueQuery>;
+
+	/// The rewards each account can claim
+	///
+	/// map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn claimable)]
+	pub type Claimable<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The sum of all claimable rewards, held by the pot but no longer distributable
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed)]
+	pub type Unclaimed<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claim all rewards of the caller
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let amount = Claimable::<T>::get(&who);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+			T::Currency::transfer(
+				&Self::account_id(),
+				&who,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Claimable::<T>::remove(&who);
+			Unclaimed::<T>::mutate(|unclaimed| *unclaimed = unclaimed.saturating_sub(amount));
+			Self::deposit_event(Event::Claimed { who, amount });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account of the claims pot
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Counts a vanchor deposit of `amount` out of the funds of `who` towards the current epoch
+	pub fn note_deposit(who: &T::AccountId, amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			return
+		}
+		let (depositors, mut deposits) = EpochTotals::<T>::get();
+		let first = !EpochDeposits::<T>::contains_key(who);
+		if first && depositors >= T::MaxDepositorsPerEpoch::get() {
+			return
+		}
+		let amount = amount.saturated_into::<u128>();
+		let now = frame_system::Pallet::<T>::block_number().saturated_into::<u128>();
+		EpochDeposits::<T>::mutate(who, |account_deposits| account_deposits.add(amount, now));
+		deposits.add(amount, now);
+		EpochTotals::<T>::put((depositors.saturating_add(first as u32), deposits));
+	}
+
+	/// Counts a vanchor withdrawal of `amount` to `who` against what it holds in the current epoch
+	pub fn note_withdrawal(who: &T::AccountId, amount: BalanceOf<T>) {
+		if amount.is_zero() || !EpochDeposits::<T>::contains_key(who) {
+			return
+		}
+		let amount = amount.saturated_into::<u128>();
+		let now = frame_system::Pallet::<T>::block_number().saturated_into::<u128>();
+		let removed = EpochDeposits::<T>::mutate(who, |account_deposits| {
+			account_deposits.remove(amount, now)
+		});
+		EpochTotals::<T>::mutate(|(_, deposits)| {
+			deposits.remove(removed, now);
+		});
+	}
+
+	/// Splits the pot funds that are not owed yet between the depositors of the epoch and starts
+	/// a new one. Without depositors the funds roll over to the next epoch.
+	pub fn end_epoch(epoch: u32) -> Weight {
+		let (depositors, deposits) = EpochTotals::<T>::take();
+		let db = T::DbWeight::get();
+		if depositors == 0 {
+			return db.reads_writes(1, 1)
+		}
+
+		let now = frame_system::Pallet::<T>::block_number().saturated_into::<u128>();
+		// nothing is held only when every deposit was made in this block, which rewards no one
+		let held = deposits.held_until(now).max(1);
+
+		let pot = T::Currency::free_balance(&Self::account_id())
+			.saturating_sub(T::Currency::minimum_balance())
+			.saturating_sub(Unclaimed::<T>::get());
+		let mut amount = BalanceOf::<T>::zero();
+		for (who, account_deposits) in EpochDeposits::<T>::drain() {
+			let reward = Perbill::from_rational(account_deposits.held_until(now), held) * pot;
+			if !reward.is_zero() {
+				Claimable::<T>::mutate(&who, |claimable| {
+					*claimable = claimable.saturating_add(reward)
+				});
+				amount = amount.saturating_add(reward);
+			}
+		}
+		Unclaimed::<T>::mutate(|unclaimed| *unclaimed = unclaimed.saturating_add(amount));
+		Self::deposit_event(Event::EpochRewarded { epoch, depositors, amount });
+
+		let depositors = depositors as u64;
+		db.reads_writes(3 + depositors, 2 + depositors * 2)
+	}
+}
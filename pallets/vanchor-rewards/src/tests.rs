@@ -0,0 +1,145 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg(test)]
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{RuntimeEvent, *};
+
+#[test]
+fn pot_split_pro_rata_to_amount_and_time_held() {
+	ExtBuilder::default().build().execute_with(|| {
+		VAnchorRewards::note_deposit(&ALICE, 300);
+		VAnchorRewards::note_deposit(&BOB, 100);
+		System::set_block_number(3);
+		VAnchorRewards::note_deposit(&BOB, 200);
+		fund_pot(1_000);
+
+		// Alice held 300 for 4 blocks and Bob 100 for 4 blocks and 200 for 2
+		System::set_block_number(5);
+		VAnchorRewards::end_epoch(1);
+		assert_eq!(VAnchorRewards::claimable(ALICE), 600);
+		assert_eq!(VAnchorRewards::claimable(BOB), 400);
+		assert_eq!(VAnchorRewards::unclaimed(), 1_000);
+		assert_eq!(VAnchorRewards::epoch_totals(), (0, Deposits::default()));
+		assert_eq!(VAnchorRewards::epoch_deposits(ALICE), Deposits::default());
+		System::assert_last_event(RuntimeEvent::VAnchorRewards(crate::Event::EpochRewarded {
+			epoch: 1,
+			depositors: 2,
+			amount: 1_000,
+		}));
+	});
+}
+
+#[test]
+fn owed_rewards_are_not_distributed_again() {
+	ExtBuilder::default().build().execute_with(|| {
+		VAnchorRewards::note_deposit(&ALICE, 10);
+		fund_pot(1_000);
+		System::set_block_number(2);
+		VAnchorRewards::end_epoch(1);
+
+		VAnchorRewards::note_deposit(&BOB, 10);
+		fund_pot(500);
+		System::set_block_number(3);
+		VAnchorRewards::end_epoch(2);
+		assert_eq!(VAnchorRewards::claimable(ALICE), 1_000);
+		assert_eq!(VAnchorRewards::claimable(BOB), 500);
+	});
+}
+
+#[test]
+fn funds_roll_over_epochs_without_deposits() {
+	ExtBuilder::default().build().execute_with(|| {
+		fund_pot(1_000);
+		VAnchorRewards::end_epoch(1);
+		assert_eq!(VAnchorRewards::unclaimed(), 0);
+
+		// deposits of this block were not held yet
+		VAnchorRewards::note_deposit(&ALICE, 10);
+		VAnchorRewards::end_epoch(2);
+		assert_eq!(VAnchorRewards::unclaimed(), 0);
+
+		VAnchorRewards::note_deposit(&ALICE, 10);
+		System::set_block_number(2);
+		VAnchorRewards::end_epoch(3);
+		assert_eq!(VAnchorRewards::claimable(ALICE), 1_000);
+	});
+}
+
+#[test]
+fn depositors_capped_per_epoch() {
+	ExtBuilder::default().build().execute_with(|| {
+		VAnchorRewards::note_deposit(&ALICE, 10);
+		VAnchorRewards::note_deposit(&BOB, 10);
+		VAnchorRewards::note_deposit(&CHARLIE, 10);
+		// known depositors are still counted, deposits of nothing are not
+		VAnchorRewards::note_deposit(&BOB, 20);
+		VAnchorRewards::note_deposit(&ALICE, 0);
+		assert_eq!(VAnchorRewards::epoch_totals(), (2, Deposits { amount: 40, held: 0, since: 1 }));
+		assert_eq!(VAnchorRewards::epoch_deposits(CHARLIE), Deposits::default());
+	});
+}
+
+#[test]
+fn claim_pays_out_of_the_pot() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			VAnchorRewards::claim(RuntimeOrigin::signed(ALICE)),
+			Error::<Runtime>::NothingToClaim
+		);
+
+		VAnchorRewards::note_deposit(&ALICE, 10);
+		fund_pot(1_000);
+		System::set_block_number(2);
+		VAnchorRewards::end_epoch(1);
+
+		assert_ok!(VAnchorRewards::claim(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Balances::free_balance(ALICE), 1_100);
+		assert_eq!(Balances::free_balance(VAnchorRewards::account_id()), EXISTENTIAL_DEPOSIT);
+		assert_eq!(VAnchorRewards::claimable(ALICE), 0);
+		assert_eq!(VAnchorRewards::unclaimed(), 0);
+		System::assert_last_event(RuntimeEvent::VAnchorRewards(crate::Event::Claimed {
+			who: ALICE,
+			amount: 1_000,
+		}));
+	});
+}
+
+#[test]
+fn withdrawals_stop_deposits_being_held() {
+	ExtBuilder::default().build().execute_with(|| {
+		VAnchorRewards::note_deposit(&ALICE, 100);
+		VAnchorRewards::note_deposit(&BOB, 100);
+		// Alice deposits and withdraws the same funds every block
+		for block in 2..=4 {
+			System::set_block_number(block);
+			VAnchorRewards::note_withdrawal(&ALICE, 100);
+			VAnchorRewards::note_deposit(&ALICE, 100);
+		}
+		// more than was deposited, or to an account without deposits, is not counted
+		VAnchorRewards::note_withdrawal(&BOB, 150);
+		VAnchorRewards::note_withdrawal(&CHARLIE, 100);
+		VAnchorRewards::note_deposit(&BOB, 100);
+		fund_pot(1_000);
+
+		// both held 100 for 4 blocks
+		System::set_block_number(5);
+		VAnchorRewards::end_epoch(1);
+		assert_eq!(VAnchorRewards::claimable(ALICE), 500);
+		assert_eq!(VAnchorRewards::claimable(BOB), 500);
+		assert_eq!(VAnchorRewards::claimable(CHARLIE), 0);
+	});
+}
@@ -0,0 +1,203 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Verifier Rotation
+//!
+//! Lets governance replace the verifying keys of the mixer and vanchor verifiers at runtime.
+//! A new key is scheduled with [`Pallet::schedule_rotation`] and takes effect `ActivationDelay`
+//! blocks later, leaving time to cancel it and for relayers and provers to prepare. Each
+//! verifier counts the versions of its key, the genesis key being version 0. The keys are set
+//! through [`SetVerifyingKey`], implemented by the runtime over the verifier pallets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, DispatchResult, RuntimeDebug};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// A verifier whose key can be rotated
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Verifier {
+	/// The mixer verifier
+	Mixer,
+	/// The vanchor verifier of transactions with `max_edges` edges and `num_inputs` inputs
+	VAnchor { max_edges: u8, num_inputs: u8 },
+}
+
+/// Sets the verifying key of a verifier
+pub trait SetVerifyingKey {
+	fn set_verifying_key(verifier: Verifier, key: Vec<u8>) -> DispatchResult;
+}
+
+/// A key waiting for its activation block
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PendingKey<BlockNumber> {
+	pub version: u32,
+	pub activate_at: BlockNumber,
+	pub key: Vec<u8>,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin which may schedule and cancel rotations.
+		type RotationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Sets the keys of the verifiers once their rotation is due.
+		type Verifiers: SetVerifyingKey;
+
+		/// Blocks between scheduling a key and its activation.
+		#[pallet::constant]
+		type ActivationDelay: Get<Self::BlockNumber>;
+
+		/// The length of the longest verifying key.
+		#[pallet::constant]
+		type MaxKeyLength: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The key is longer than `MaxKeyLength`
+		KeyTooLong,
+		/// The verifier has no rotation scheduled
+		NoPendingRotation,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new key version was scheduled, replacing the one pending if any
+		RotationScheduled { verifier: Verifier, version: u32, activate_at: T::BlockNumber },
+		/// A pending rotation was cancelled
+		RotationCancelled { verifier: Verifier, version: u32 },
+		/// A new key version is in use
+		KeyRotated { verifier: Verifier, version: u32 },
+		/// The verifier rejected a new key version, the previous one stays in use
+		RotationFailed { verifier: Verifier, version: u32, error: DispatchError },
+	}
+
+	/// The version of the key each verifier uses
+	///
+	/// map Verifier => Version
+	#[pallet::storage]
+	#[pallet::getter(fn key_version)]
+	pub type KeyVersion<T: Config> = StorageMap<_, Twox64Concat, Verifier, u32, ValueQuery>;
+
+	/// The key each verifier switches to once its activation block is reached
+	///
+	/// map Verifier => PendingKey
+	#[pallet::storage]
+	#[pallet::getter(fn pending_rotation)]
+	pub type PendingRotation<T: Config> =
+		StorageMap<_, Twox64Concat, Verifier, PendingKey<T::BlockNumber>, OptionQuery>;
+
+	/// The verifiers with a rotation due at each block
+	///
+	/// map BlockNumber => Vec<Verifier>
+	#[pallet::storage]
+	pub type Agenda<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<Verifier>, ValueQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let due = Agenda::<T>::take(now);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			for verifier in due {
+				weight = weight.saturating_add(Self::activate(verifier, now));
+			}
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule `key` as the next key of `verifier`, active after `ActivationDelay` blocks.
+		/// Replaces the rotation already pending for `verifier`, if any.
+		#[pallet::weight(T::WeightInfo::schedule_rotation(key.len() as u32))]
+		pub fn schedule_rotation(
+			origin: OriginFor<T>,
+			verifier: Verifier,
+			key: Vec<u8>,
+		) -> DispatchResult {
+			T::RotationOrigin::ensure_origin(origin)?;
+			ensure!(key.len() as u32 <= T::MaxKeyLength::get(), Error::<T>::KeyTooLong);
+
+			let version = KeyVersion::<T>::get(verifier).saturating_add(1);
+			let activate_at =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::ActivationDelay::get());
+			PendingRotation::<T>::insert(verifier, PendingKey { version, activate_at, key });
+			Agenda::<T>::append(activate_at, verifier);
+			Self::deposit_event(Event::RotationScheduled { verifier, version, activate_at });
+			Ok(())
+		}
+
+		/// Cancel the rotation pending for `verifier`
+		#[pallet::weight(T::WeightInfo::cancel_rotation())]
+		pub fn cancel_rotation(origin: OriginFor<T>, verifier: Verifier) -> DispatchResult {
+			T::RotationOrigin::ensure_origin(origin)?;
+			let pending =
+				PendingRotation::<T>::take(verifier).ok_or(Error::<T>::NoPendingRotation)?;
+			// the agenda entry is skipped once due, the pending key being gone
+			Self::deposit_event(Event::RotationCancelled { verifier, version: pending.version });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Sets the pending key of `verifier` if it is due at `now`. Rotations cancelled or
+	/// rescheduled since this agenda entry was added are skipped.
+	fn activate(verifier: Verifier, now: T::BlockNumber) -> Weight {
+		let pending = match PendingRotation::<T>::get(verifier) {
+			Some(pending) if pending.activate_at == now => pending,
+			_ => return T::DbWeight::get().reads(1),
+		};
+		PendingRotation::<T>::remove(verifier);
+		let PendingKey { version, key, .. } = pending;
+		match T::Verifiers::set_verifying_key(verifier, key) {
+			Ok(()) => {
+				KeyVersion::<T>::insert(verifier, version);
+				Self::deposit_event(Event::KeyRotated { verifier, version });
+			},
+			Err(error) => Self::deposit_event(Event::RotationFailed { verifier, version, error }),
+		}
+		T::WeightInfo::activate_rotation(T::MaxKeyLength::get())
+	}
+}
@@ -0,0 +1,133 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg(test)]
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{RuntimeEvent, *};
+use sp_runtime::traits::BadOrigin;
+
+const VANCHOR: Verifier = Verifier::VAnchor { max_edges: 2, num_inputs: 16 };
+
+#[test]
+fn key_activates_after_the_delay() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(VerifierRotation::schedule_rotation(
+			RuntimeOrigin::root(),
+			VANCHOR,
+			vec![1, 2, 3]
+		));
+		System::assert_last_event(RuntimeEvent::VerifierRotation(
+			crate::Event::RotationScheduled {
+				verifier: VANCHOR,
+				version: 1,
+				activate_at: 1 + DELAY,
+			},
+		));
+
+		run_to_block(DELAY);
+		assert!(SetKeys::get().is_empty());
+		assert_eq!(VerifierRotation::key_version(VANCHOR), 0);
+
+		run_to_block(1 + DELAY);
+		assert_eq!(SetKeys::get(), vec![(VANCHOR, vec![1, 2, 3])]);
+		assert_eq!(VerifierRotation::key_version(VANCHOR), 1);
+		assert_eq!(VerifierRotation::key_version(Verifier::Mixer), 0);
+		assert_eq!(VerifierRotation::pending_rotation(VANCHOR), None);
+		System::assert_last_event(RuntimeEvent::VerifierRotation(crate::Event::KeyRotated {
+			verifier: VANCHOR,
+			version: 1,
+		}));
+	});
+}
+
+#[test]
+fn rescheduling_replaces_the_pending_key() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(VerifierRotation::schedule_rotation(
+			RuntimeOrigin::root(),
+			Verifier::Mixer,
+			vec![1]
+		));
+		run_to_block(5);
+		assert_ok!(VerifierRotation::schedule_rotation(
+			RuntimeOrigin::root(),
+			Verifier::Mixer,
+			vec![2]
+		));
+
+		// the first agenda entry no longer matches the pending key
+		run_to_block(1 + DELAY);
+		assert!(SetKeys::get().is_empty());
+
+		run_to_block(5 + DELAY);
+		assert_eq!(SetKeys::get(), vec![(Verifier::Mixer, vec![2])]);
+		assert_eq!(VerifierRotation::key_version(Verifier::Mixer), 1);
+	});
+}
+
+#[test]
+fn cancelled_rotation_is_not_applied() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			VerifierRotation::cancel_rotation(RuntimeOrigin::root(), Verifier::Mixer),
+			Error::<Runtime>::NoPendingRotation
+		);
+		assert_ok!(VerifierRotation::schedule_rotation(
+			RuntimeOrigin::root(),
+			Verifier::Mixer,
+			vec![1]
+		));
+		assert_ok!(VerifierRotation::cancel_rotation(RuntimeOrigin::root(), Verifier::Mixer));
+		System::assert_last_event(RuntimeEvent::VerifierRotation(
+			crate::Event::RotationCancelled { verifier: Verifier::Mixer, version: 1 },
+		));
+
+		run_to_block(1 + DELAY);
+		assert!(SetKeys::get().is_empty());
+		assert_eq!(VerifierRotation::key_version(Verifier::Mixer), 0);
+	});
+}
+
+#[test]
+fn rejected_key_keeps_the_current_version() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(VerifierRotation::schedule_rotation(RuntimeOrigin::root(), VANCHOR, vec![]));
+
+		run_to_block(1 + DELAY);
+		assert_eq!(VerifierRotation::key_version(VANCHOR), 0);
+		assert_eq!(VerifierRotation::pending_rotation(VANCHOR), None);
+		System::assert_last_event(RuntimeEvent::VerifierRotation(crate::Event::RotationFailed {
+			verifier: VANCHOR,
+			version: 1,
+			error: DispatchError::Other("empty key"),
+		}));
+	});
+}
+
+#[test]
+fn schedule_rotation_checks_origin_and_key_length() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			VerifierRotation::schedule_rotation(RuntimeOrigin::signed(ALICE), VANCHOR, vec![1]),
+			BadOrigin
+		);
+		assert_noop!(
+			VerifierRotation::schedule_rotation(RuntimeOrigin::root(), VANCHOR, vec![0; 9]),
+			Error::<Runtime>::KeyTooLong
+		);
+		assert_ok!(VerifierRotation::schedule_rotation(RuntimeOrigin::root(), VANCHOR, vec![0; 8]));
+	});
+}
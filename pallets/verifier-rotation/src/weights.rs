@@ -0,0 +1,86 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_verifier_rotation
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_verifier_rotation.
+pub trait WeightInfo {
+	fn schedule_rotation(k: u32, ) -> Weight;
+	fn cancel_rotation() -> Weight;
+	fn activate_rotation(k: u32, ) -> Weight;
+}
+
+/// Weights for pallet_verifier_rotation using the Substrate node and recommended hardware.
+pub struct WebbWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for WebbWeight<T> {
+	// Storage: VerifierRotation KeyVersion (r:1 w:0)
+	// Storage: VerifierRotation PendingRotation (r:0 w:1)
+	// Storage: VerifierRotation Agenda (r:1 w:1)
+	fn schedule_rotation(k: u32, ) -> Weight {
+		Weight::from_ref_time(24_000_000)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(1_000).saturating_mul(k as u64))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: VerifierRotation PendingRotation (r:1 w:1)
+	fn cancel_rotation() -> Weight {
+		Weight::from_ref_time(19_000_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: VerifierRotation PendingRotation (r:1 w:1)
+	// Storage: VerifierBn254 Parameters (r:0 w:1)
+	// Storage: VerifierRotation KeyVersion (r:0 w:1)
+	fn activate_rotation(k: u32, ) -> Weight {
+		Weight::from_ref_time(31_000_000)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000).saturating_mul(k as u64))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn schedule_rotation(k: u32, ) -> Weight {
+		Weight::from_ref_time(24_000_000)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(1_000).saturating_mul(k as u64))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn cancel_rotation() -> Weight {
+		Weight::from_ref_time(19_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn activate_rotation(k: u32, ) -> Weight {
+		Weight::from_ref_time(31_000_000)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000).saturating_mul(k as u64))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
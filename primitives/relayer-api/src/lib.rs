@@ -0,0 +1,109 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime APIs returning in one call the chain state the Webb relayer polls every block, the
+//! proposals signed by the DKG since a block, ranges of merkle tree leaves, and the recent roots
+//! of a tree.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use sp_arithmetic::Permill;
+use sp_std::vec::Vec;
+
+/// Snapshot of the bridge state the relayer needs to sync a block
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RelayerSyncState<Element> {
+	/// Latest merkle root of each anchor, by tree id
+	pub anchor_roots: Vec<(u32, Element)>,
+	/// Authority set id and DKG public key currently signing proposals
+	pub dkg_public_key: (u64, Vec<u8>),
+	/// Nonce of the next DKG key refresh proposal
+	pub refresh_nonce: u32,
+	/// Number of proposals waiting to be signed by the DKG
+	pub unsigned_proposal_count: u32,
+	/// Progress through the current DKG session, if it can be estimated
+	pub session_progress: Option<Permill>,
+}
+
+/// A page of the proposals signed by the DKG since a block
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SignedProposalsPage<Proposal, BlockNumber> {
+	/// Signed proposals, each with its signature, and the block it was signed in, oldest first
+	pub proposals: Vec<(BlockNumber, Proposal)>,
+	/// Cursor of the next page, if there are more proposals
+	pub next: Option<u64>,
+}
+
+/// The recent roots of a tree and of its neighbors, each with the block it became current in
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RootHistory<Element, ChainId, BlockNumber> {
+	/// Roots of the tree, newest first
+	pub roots: Vec<(BlockNumber, Element)>,
+	/// Roots of each neighbor of the tree on other chains, newest first
+	pub neighbor_roots: Vec<(ChainId, Vec<(BlockNumber, Element)>)>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait RelayerApi<Element, BlockNumber>
+	where
+		Element: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the anchor roots, DKG key and refresh nonce, unsigned proposal count and
+		/// session progress as of `block_number`, replacing the separate queries for each.
+		fn sync_state(block_number: BlockNumber) -> RelayerSyncState<Element>;
+	}
+
+	pub trait SignedProposalsApi<Proposal, BlockNumber>
+	where
+		Proposal: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns up to `limit` of the proposals signed in `since` or later, starting at
+		/// `cursor`, which is 0 for the first page and the `next` of the previous page after.
+		/// The runtime may return fewer proposals than `limit`.
+		fn signed_proposals(
+			since: BlockNumber,
+			cursor: u64,
+			limit: u32,
+		) -> SignedProposalsPage<Proposal, BlockNumber>;
+	}
+
+	pub trait MerkleTreeLeavesApi<Element>
+	where
+		Element: Codec,
+	{
+		/// Returns the leaves of `tree_id` from index `from` up to, but excluding, `to`, stopping
+		/// at the first index without a leaf. The runtime may return fewer leaves than requested.
+		fn get_leaves(tree_id: u32, from: u32, to: u32) -> Vec<Element>;
+	}
+
+	pub trait RootHistoryApi<Element, ChainId, BlockNumber>
+	where
+		Element: Codec,
+		ChainId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns up to `limit` of the latest roots of `tree_id` and of each of its neighbors,
+		/// so withdrawal provers can pick a recent root the tree still accepts.
+		fn root_history(tree_id: u32, limit: u32) -> RootHistory<Element, ChainId, BlockNumber>;
+	}
+}
@@ -109,9 +109,15 @@ pub mod fee {
 }
 
 /// The number of blocks in one session
+#[cfg(not(feature = "fast-runtime"))]
 #[allow(clippy::identity_op)]
 pub const SESSION_PERIOD_BLOCKS: BlockNumber = 1 * crate::time::HOURS;
 
+/// The number of blocks in one session, short enough for end-to-end tests to see sessions, rounds
+/// and DKG rotations within minutes while leaving the DKG time to generate the next key
+#[cfg(feature = "fast-runtime")]
+pub const SESSION_PERIOD_BLOCKS: BlockNumber = 20;
+
 /// We assume that ~10% of the block weight is consumed by `on_initialize` handlers.
 /// This is used to limit the maximal weight of a single extrinsic.
 pub const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(10);
@@ -0,0 +1,76 @@
+//! OpenGov: referenda voted on with conviction, each on a track dispatching with its own origin.
+use super::*;
+use frame_support::traits::EitherOf;
+
+mod origins;
+pub use origins::{pallet_custom_origins, AssetAdmin, StakingAdmin, Treasurer, WhitelistedCaller};
+mod tracks;
+pub use tracks::TracksInfo;
+mod staked_voting;
+pub use staked_voting::{StakedVotingCurrency, StakedVotingShare};
+
+/// Root, or a referendum passed on the `StakingAdmin` track
+pub type StakingAdminOrRoot = EitherOf<EnsureRoot<AccountId>, StakingAdmin>;
+/// Root, or a referendum passed on the `Treasurer` track
+pub type TreasurerOrRoot = EitherOf<EnsureRoot<AccountId>, Treasurer>;
+/// Root, a referendum passed on the `AssetAdmin` track, or a majority of the council, so
+/// wrapped assets can be registered without a root referendum
+pub type AssetAdminOrigin = EitherOf<
+	EitherOf<EnsureRoot<AccountId>, AssetAdmin>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
+>;
+
+parameter_types! {
+	pub const VoteLockingPeriod: BlockNumber = 7 * DAYS;
+}
+
+impl pallet_conviction_voting::Config for Runtime {
+	type WeightInfo = pallet_conviction_voting::weights::SubstrateWeight<Self>;
+	type RuntimeEvent = RuntimeEvent;
+	/// Votes may count the stake bonded in `ParachainStaking` without unbonding it
+	type Currency = StakedVotingCurrency;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type MaxVotes = ConstU32<512>;
+	type MaxTurnout = frame_support::traits::TotalIssuanceOf<Balances, Self::AccountId>;
+	type Polls = Referenda;
+}
+
+parameter_types! {
+	pub const AlarmInterval: BlockNumber = 1;
+	pub const SubmissionDeposit: Balance = 10 * DOLLAR;
+	pub const UndecidingTimeout: BlockNumber = 14 * DAYS;
+}
+
+impl pallet_custom_origins::Config for Runtime {}
+
+impl pallet_referenda::Config for Runtime {
+	type WeightInfo = pallet_referenda::weights::SubstrateWeight<Self>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type Currency = Balances;
+	type SubmitOrigin = frame_system::EnsureSigned<AccountId>;
+	type CancelOrigin = EnsureRoot<AccountId>;
+	type KillOrigin = EnsureRoot<AccountId>;
+	type Slash = Treasury;
+	type Votes = pallet_conviction_voting::VotesOf<Runtime>;
+	type Tally = pallet_conviction_voting::TallyOf<Runtime>;
+	type SubmissionDeposit = SubmissionDeposit;
+	type MaxQueued = ConstU32<100>;
+	type UndecidingTimeout = UndecidingTimeout;
+	type AlarmInterval = AlarmInterval;
+	type Tracks = TracksInfo;
+}
+
+impl pallet_whitelist::Config for Runtime {
+	type WeightInfo = pallet_whitelist::weights::SubstrateWeight<Self>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	/// Two thirds of the council can whitelist a call for the `WhitelistedCaller` track
+	type WhitelistOrigin = EitherOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+	>;
+	type DispatchWhitelistedOrigin = EitherOf<EnsureRoot<AccountId>, WhitelistedCaller>;
+	type PreimageProvider = Preimage;
+}
@@ -0,0 +1,50 @@
+//! Custom origins dispatched by referenda passed on the non-root tracks.
+pub use pallet_custom_origins::*;
+
+#[frame_support::pallet]
+pub mod pallet_custom_origins {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[derive(PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, TypeInfo, RuntimeDebug)]
+	#[pallet::origin]
+	pub enum Origin {
+		/// Origin able to approve and reject treasury spends.
+		Treasurer,
+		/// Origin able to update parachain staking parameters and monetary settings.
+		StakingAdmin,
+		/// Origin able to dispatch a call whitelisted by the council.
+		WhitelistedCaller,
+		/// Origin able to register assets and update their metadata and existential deposits.
+		AssetAdmin,
+	}
+
+	macro_rules! decl_unit_ensures {
+		( $name:ident ) => {
+			pub struct $name;
+			impl<O: Into<Result<Origin, O>> + From<Origin>> EnsureOrigin<O> for $name {
+				type Success = ();
+				fn try_origin(o: O) -> Result<Self::Success, O> {
+					o.into().and_then(|o| match o {
+						Origin::$name => Ok(()),
+						r => Err(O::from(r)),
+					})
+				}
+				#[cfg(feature = "runtime-benchmarks")]
+				fn successful_origin() -> O {
+					O::from(Origin::$name)
+				}
+			}
+		};
+		( $name:ident, $( $rest:ident ),* ) => {
+			decl_unit_ensures! { $name }
+			decl_unit_ensures! { $( $rest ),* }
+		};
+	}
+	decl_unit_ensures!(Treasurer, StakingAdmin, WhitelistedCaller, AssetAdmin);
+}
@@ -0,0 +1,245 @@
+//! Lets conviction votes count the stake bonded in `ParachainStaking`.
+//!
+//! Stake is held out of the free balance, which is all `pallet_conviction_voting` lets an account
+//! vote with. [`StakedVotingCurrency`] is the currency handed to it instead of `Balances`: it adds
+//! the `StakedVotingShare` of the account's stake to its free balance and forwards everything
+//! else. The vote lock is still set on the free balance, so stake counted towards a vote stays
+//! bonded, and once unbonded it is returned into the locked free balance until the vote lock
+//! expires.
+use super::*;
+use frame_support::traits::{
+	fungible,
+	tokens::{DepositConsequence, WithdrawConsequence},
+	BalanceStatus, ExistenceRequirement, LockIdentifier, LockableCurrency, ReservableCurrency,
+	SignedImbalance, WithdrawReasons,
+};
+use pallet_parachain_staking::{COLLATOR_LOCK_ID, DELEGATOR_LOCK_ID};
+use sp_runtime::{DispatchError, DispatchResult};
+
+parameter_types! {
+	/// Share of their stake accounts may vote with on top of their free balance, all of it until
+	/// governance changes it with `System::set_storage` at `StakedVotingShare::key()`
+	pub storage StakedVotingShare: Perbill = Perbill::one();
+}
+
+/// `Balances` with the `StakedVotingShare` of each account's held stake counted as free balance
+pub struct StakedVotingCurrency;
+
+impl StakedVotingCurrency {
+	/// Stake of `who` held out of its free balance. Stake of accounts still on the staking locks,
+	/// and stake locked while vesting, is in the free balance already.
+	fn held_stake(who: &AccountId) -> Balance {
+		let (staked, _) = ParachainStaking::staked_and_unbonding(who);
+		let locked_stake = Balances::locks(who)
+			.iter()
+			.filter(|lock| lock.id == COLLATOR_LOCK_ID || lock.id == DELEGATOR_LOCK_ID)
+			.fold(ParachainStaking::vested_stake(who), |total: Balance, lock| {
+				total.saturating_add(lock.amount)
+			});
+		staked.saturating_sub(locked_stake)
+	}
+}
+
+type PositiveImbalanceOf = <Balances as Currency<AccountId>>::PositiveImbalance;
+type NegativeImbalanceOf = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+impl Currency<AccountId> for StakedVotingCurrency {
+	type Balance = Balance;
+	type PositiveImbalance = PositiveImbalanceOf;
+	type NegativeImbalance = NegativeImbalanceOf;
+
+	fn total_balance(who: &AccountId) -> Balance {
+		Balances::total_balance(who)
+	}
+
+	fn can_slash(who: &AccountId, value: Balance) -> bool {
+		Balances::can_slash(who, value)
+	}
+
+	fn total_issuance() -> Balance {
+		<Balances as Currency<AccountId>>::total_issuance()
+	}
+
+	fn minimum_balance() -> Balance {
+		<Balances as Currency<AccountId>>::minimum_balance()
+	}
+
+	fn burn(amount: Balance) -> PositiveImbalanceOf {
+		Balances::burn(amount)
+	}
+
+	fn issue(amount: Balance) -> NegativeImbalanceOf {
+		Balances::issue(amount)
+	}
+
+	/// The free balance plus the `StakedVotingShare` of the held stake, which conviction votes
+	/// and delegations are checked against
+	fn free_balance(who: &AccountId) -> Balance {
+		Balances::free_balance(who).saturating_add(StakedVotingShare::get() * Self::held_stake(who))
+	}
+
+	fn ensure_can_withdraw(
+		who: &AccountId,
+		amount: Balance,
+		reasons: WithdrawReasons,
+		new_balance: Balance,
+	) -> DispatchResult {
+		Balances::ensure_can_withdraw(who, amount, reasons, new_balance)
+	}
+
+	fn transfer(
+		source: &AccountId,
+		dest: &AccountId,
+		value: Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		<Balances as Currency<AccountId>>::transfer(source, dest, value, existence_requirement)
+	}
+
+	fn slash(who: &AccountId, value: Balance) -> (NegativeImbalanceOf, Balance) {
+		<Balances as Currency<AccountId>>::slash(who, value)
+	}
+
+	fn deposit_into_existing(
+		who: &AccountId,
+		value: Balance,
+	) -> Result<PositiveImbalanceOf, DispatchError> {
+		Balances::deposit_into_existing(who, value)
+	}
+
+	fn deposit_creating(who: &AccountId, value: Balance) -> PositiveImbalanceOf {
+		Balances::deposit_creating(who, value)
+	}
+
+	fn withdraw(
+		who: &AccountId,
+		value: Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> Result<NegativeImbalanceOf, DispatchError> {
+		Balances::withdraw(who, value, reasons, liveness)
+	}
+
+	fn make_free_balance_be(
+		who: &AccountId,
+		balance: Balance,
+	) -> SignedImbalance<Balance, PositiveImbalanceOf> {
+		Balances::make_free_balance_be(who, balance)
+	}
+}
+
+impl ReservableCurrency<AccountId> for StakedVotingCurrency {
+	fn can_reserve(who: &AccountId, value: Balance) -> bool {
+		Balances::can_reserve(who, value)
+	}
+
+	fn slash_reserved(who: &AccountId, value: Balance) -> (NegativeImbalanceOf, Balance) {
+		Balances::slash_reserved(who, value)
+	}
+
+	fn reserved_balance(who: &AccountId) -> Balance {
+		Balances::reserved_balance(who)
+	}
+
+	fn reserve(who: &AccountId, value: Balance) -> DispatchResult {
+		Balances::reserve(who, value)
+	}
+
+	fn unreserve(who: &AccountId, value: Balance) -> Balance {
+		Balances::unreserve(who, value)
+	}
+
+	fn repatriate_reserved(
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Balance,
+		status: BalanceStatus,
+	) -> Result<Balance, DispatchError> {
+		Balances::repatriate_reserved(slashed, beneficiary, value, status)
+	}
+}
+
+impl LockableCurrency<AccountId> for StakedVotingCurrency {
+	type Moment = BlockNumber;
+	type MaxLocks = <Balances as LockableCurrency<AccountId>>::MaxLocks;
+
+	fn set_lock(id: LockIdentifier, who: &AccountId, amount: Balance, reasons: WithdrawReasons) {
+		Balances::set_lock(id, who, amount, reasons)
+	}
+
+	fn extend_lock(id: LockIdentifier, who: &AccountId, amount: Balance, reasons: WithdrawReasons) {
+		Balances::extend_lock(id, who, amount, reasons)
+	}
+
+	fn remove_lock(id: LockIdentifier, who: &AccountId) {
+		Balances::remove_lock(id, who)
+	}
+}
+
+impl fungible::Inspect<AccountId> for StakedVotingCurrency {
+	type Balance = Balance;
+
+	fn total_issuance() -> Balance {
+		<Balances as fungible::Inspect<AccountId>>::total_issuance()
+	}
+
+	fn minimum_balance() -> Balance {
+		<Balances as fungible::Inspect<AccountId>>::minimum_balance()
+	}
+
+	fn balance(who: &AccountId) -> Balance {
+		<Balances as fungible::Inspect<AccountId>>::balance(who)
+	}
+
+	fn reducible_balance(who: &AccountId, keep_alive: bool) -> Balance {
+		<Balances as fungible::Inspect<AccountId>>::reducible_balance(who, keep_alive)
+	}
+
+	fn can_deposit(who: &AccountId, amount: Balance, mint: bool) -> DepositConsequence {
+		<Balances as fungible::Inspect<AccountId>>::can_deposit(who, amount, mint)
+	}
+
+	fn can_withdraw(who: &AccountId, amount: Balance) -> WithdrawConsequence<Balance> {
+		<Balances as fungible::Inspect<AccountId>>::can_withdraw(who, amount)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{assert_ok, traits::VestingSchedule};
+	use sp_runtime::BuildStorage;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn stake_locked_while_vesting_is_not_counted_twice() {
+		new_test_ext().execute_with(|| {
+			let who = AccountId::from([1u8; 32]);
+			let bond = crate::staking::NORMAL_COLLATOR_MINIMUM_STAKE;
+			Balances::make_free_balance_be(&who, 2 * bond);
+			// half the bond is still vesting and is locked rather than held when staked
+			assert_ok!(<Vesting as VestingSchedule<AccountId>>::add_vesting_schedule(
+				&who,
+				bond / 2,
+				1,
+				10,
+			));
+			assert_ok!(ParachainStaking::join_candidates(
+				RuntimeOrigin::signed(who.clone()),
+				bond,
+				0,
+			));
+			assert_eq!(ParachainStaking::vested_stake(&who), bond / 2);
+			assert_eq!(Balances::free_balance(&who), 2 * bond - bond / 2);
+
+			assert_eq!(StakedVotingCurrency::held_stake(&who), bond / 2);
+			assert_eq!(StakedVotingCurrency::free_balance(&who), 2 * bond);
+		});
+	}
+}
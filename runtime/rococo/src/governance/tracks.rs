@@ -0,0 +1,147 @@
+//! Referendum tracks and the origin each one dispatches its proposals with.
+use super::*;
+use pallet_referenda::{Curve, TrackInfo};
+
+const fn percent(x: u32) -> Perbill {
+	Perbill::from_percent(x)
+}
+
+const TRACKS_DATA: [(u16, TrackInfo<Balance, BlockNumber>); 5] = [
+	(
+		0,
+		TrackInfo {
+			name: "root",
+			max_deciding: 1,
+			decision_deposit: 10_000 * DOLLAR,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: DAYS,
+			min_enactment_period: DAYS,
+			min_approval: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(50),
+				ceil: percent(100),
+			},
+			min_support: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(5),
+				ceil: percent(50),
+			},
+		},
+	),
+	(
+		1,
+		TrackInfo {
+			name: "whitelisted_caller",
+			max_deciding: 10,
+			decision_deposit: 1_000 * DOLLAR,
+			prepare_period: 30 * MINUTES,
+			decision_period: 14 * DAYS,
+			confirm_period: 10 * MINUTES,
+			min_enactment_period: 10 * MINUTES,
+			min_approval: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(50),
+				ceil: percent(100),
+			},
+			min_support: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(1),
+				ceil: percent(20),
+			},
+		},
+	),
+	(
+		2,
+		TrackInfo {
+			name: "treasurer",
+			max_deciding: 10,
+			decision_deposit: 1_000 * DOLLAR,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 3 * HOURS,
+			min_enactment_period: DAYS,
+			min_approval: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(50),
+				ceil: percent(100),
+			},
+			min_support: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(1),
+				ceil: percent(50),
+			},
+		},
+	),
+	(
+		3,
+		TrackInfo {
+			name: "staking_admin",
+			max_deciding: 10,
+			decision_deposit: 1_000 * DOLLAR,
+			prepare_period: 2 * HOURS,
+			decision_period: 14 * DAYS,
+			confirm_period: 3 * HOURS,
+			min_enactment_period: 10 * MINUTES,
+			min_approval: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(50),
+				ceil: percent(100),
+			},
+			min_support: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(1),
+				ceil: percent(50),
+			},
+		},
+	),
+	(
+		4,
+		TrackInfo {
+			name: "asset_admin",
+			max_deciding: 10,
+			decision_deposit: 1_000 * DOLLAR,
+			prepare_period: 2 * HOURS,
+			decision_period: 7 * DAYS,
+			confirm_period: 3 * HOURS,
+			min_enactment_period: 10 * MINUTES,
+			min_approval: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(50),
+				ceil: percent(100),
+			},
+			min_support: Curve::LinearDecreasing {
+				length: percent(100),
+				floor: percent(1),
+				ceil: percent(25),
+			},
+		},
+	),
+];
+
+pub struct TracksInfo;
+impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
+	type Id = u16;
+	type RuntimeOrigin = <RuntimeOrigin as frame_support::traits::OriginTrait>::PalletsOrigin;
+	fn tracks() -> &'static [(Self::Id, TrackInfo<Balance, BlockNumber>)] {
+		&TRACKS_DATA[..]
+	}
+	fn track_for(id: &Self::RuntimeOrigin) -> Result<Self::Id, ()> {
+		if let Ok(system_origin) = frame_system::RawOrigin::try_from(id.clone()) {
+			match system_origin {
+				frame_system::RawOrigin::Root => Ok(0),
+				_ => Err(()),
+			}
+		} else if let Ok(custom_origin) = origins::Origin::try_from(id.clone()) {
+			match custom_origin {
+				origins::Origin::WhitelistedCaller => Ok(1),
+				origins::Origin::Treasurer => Ok(2),
+				origins::Origin::StakingAdmin => Ok(3),
+				origins::Origin::AssetAdmin => Ok(4),
+			}
+		} else {
+			Err(())
+		}
+	}
+}
+pallet_referenda::impl_tracksinfo_get!(TracksInfo, Balance, BlockNumber);
@@ -14,8 +14,57 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use crate::NegativeImbalance;
-use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use crate::{
+	protocol_substrate_config::{GetNativeCurrencyId, WrappingFeePotId},
+	AccountId, AssetRates, Balance, BlockNumber, DKGId, DkgMisbehaviourSlash, GovernanceRebates,
+	IdentityCollator, ImOnline, LinkableTreeBn254, MixerVerifierBn254, NegativeImbalance, Offences,
+	ParachainStaking, PauseGroup, Runtime, RuntimeCall, RuntimeOrigin, Session, SignedProposalLog,
+	StakeSnapshot, TransactionPause, Treasury, VAnchorRewards, VAnchorVerifier, DKG,
+};
+use codec::{Decode, Encode};
+use dkg_runtime_primitives::{traits::OnSignedProposal, Proposal};
+use frame_support::{
+	dispatch::GetDispatchInfo,
+	pallet_prelude::ValueQuery,
+	storage_alias,
+	traits::{
+		fungibles::{Balanced, CreditOf},
+		Contains, Currency, EnsureOrigin, EstimateNextSessionRotation, FindAuthor, Get, Imbalance,
+		OnUnbalanced, OneSessionHandler, UnfilteredDispatchable,
+	},
+	weights::Weight,
+};
+use pallet_asset_tx_payment::{HandleCredit, OnChargeAssetTransaction};
+use pallet_parachain_staking::{
+	weights::WeightInfo as _, CollatorBacking, OnCollatorPayout, OnNewRound, RoundIndex,
+	StakingInterface,
+};
+use pallet_session::ShouldEndSession;
+use pallet_verifier_rotation::Verifier;
+use sp_core::{H160, U256};
+use sp_runtime::{
+	traits::{
+		AccountIdConversion, Convert, DispatchInfoOf, PostDispatchInfoOf, Saturating,
+		SignedExtension, Zero,
+	},
+	transaction_validity::{TransactionValidity, TransactionValidityError, ValidTransaction},
+	ConsensusEngineId, DispatchError, DispatchResult, Perbill, Permill, RuntimeDebug,
+};
+use sp_staking::{
+	offence::{Kind, Offence, OffenceError, ReportOffence},
+	SessionIndex,
+};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	prelude::*,
+};
+use webb_primitives::{
+	linkable_tree::LinkableTreeInspector,
+	runtime::Element,
+	types::vanchor::{ExtData, ProofData},
+	Amount, AssetId, ChainId,
+};
+use xcm::latest::MultiLocation;
 
 /// Logic for the author to get a portion of fees.
 pub struct ToAuthor<R>(sp_std::marker::PhantomData<R>);
@@ -53,3 +102,963 @@ where
 		}
 	}
 }
+
+/// Charges fees paid in a registered asset with `Inner`, noting the asset for `GovernanceRebates`
+/// so that a rebated fee is paid back in the asset it was paid in
+pub struct NoteRebateFeeAsset<Inner>(sp_std::marker::PhantomData<Inner>);
+impl<Inner> OnChargeAssetTransaction<Runtime> for NoteRebateFeeAsset<Inner>
+where
+	Inner: OnChargeAssetTransaction<Runtime, AssetId = AssetId>,
+{
+	type Balance = Inner::Balance;
+	type AssetId = Inner::AssetId;
+	type LiquidityInfo = Inner::LiquidityInfo;
+
+	fn withdraw_fee(
+		who: &AccountId,
+		call: &RuntimeCall,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		asset_id: Self::AssetId,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let paid = Inner::withdraw_fee(who, call, dispatch_info, asset_id, fee, tip)?;
+		GovernanceRebates::note_fee_asset(who, asset_id);
+		Ok(paid)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &AccountId,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		post_info: &PostDispatchInfoOf<RuntimeCall>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		Inner::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			corrected_fee,
+			tip,
+			already_withdrawn,
+		)
+	}
+}
+
+/// Deposits fees paid in a registered asset to the block author, or to the treasury when there
+/// is no author or the author cannot receive the asset.
+pub struct AssetCreditToBlockAuthor<R, F>(sp_std::marker::PhantomData<(R, F)>);
+impl<R, F> HandleCredit<R::AccountId, F> for AssetCreditToBlockAuthor<R, F>
+where
+	R: pallet_authorship::Config + pallet_treasury::Config,
+	F: Balanced<R::AccountId>,
+{
+	fn handle_credit(credit: CreditOf<R::AccountId, F>) {
+		let credit = match <pallet_authorship::Pallet<R>>::author() {
+			Some(author) => match F::resolve(&author, credit) {
+				Ok(()) => return,
+				Err(credit) => credit,
+			},
+			None => credit,
+		};
+		// only dropped, and so burned, if the treasury cannot receive the asset either
+		let _ = F::resolve(&<pallet_treasury::Pallet<R>>::account_id(), credit);
+	}
+}
+
+/// Mints the governance-set `CollatorPayoutTreasuryCut` of every collator payout into the
+/// treasury pot, the collator being minted the rest. A cut below the existential deposit of an
+/// empty treasury is left to the collator.
+pub struct CollatorPayoutToTreasury<R>(sp_std::marker::PhantomData<R>);
+impl<R> OnCollatorPayout<R::AccountId, Balance> for CollatorPayoutToTreasury<R>
+where
+	R: pallet_parachain_staking::Config
+		+ pallet_treasury::Config
+		+ pallet_balances::Config<Balance = Balance>,
+{
+	fn cut(_for_round: RoundIndex, _collator_id: &R::AccountId, amount: Balance) -> Balance {
+		<pallet_parachain_staking::Pallet<R>>::collator_payout_treasury_cut() * amount
+	}
+
+	fn take_cut(
+		for_round: RoundIndex,
+		collator_id: &R::AccountId,
+		amount: Balance,
+	) -> (Balance, Weight) {
+		let db_weight = <R as frame_system::Config>::DbWeight::get();
+		let cut = Self::cut(for_round, collator_id, amount);
+		if cut.is_zero() {
+			return (0, db_weight.reads(1))
+		}
+		let treasury = <pallet_treasury::Pallet<R>>::account_id();
+		let minted =
+			<pallet_balances::Pallet<R> as Currency<_>>::deposit_creating(&treasury, cut).peek();
+		(minted, db_weight.reads_writes(2, 1))
+	}
+
+	fn on_collator_payout(
+		_for_round: RoundIndex,
+		_collator_id: R::AccountId,
+		_amount: Balance,
+	) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Accepts `pallet_xcm` origins, i.e. `Transact` with `OriginKind::Xcm`, from locations passing
+/// `Filter` and maps them to a local account with `Conv`. Using the same conversion as the asset
+/// transactor makes this the account reserve transfers from that location are deposited into.
+pub struct EnsureXcmAccount<Filter, Conv>(sp_std::marker::PhantomData<(Filter, Conv)>);
+impl<Filter, Conv> EnsureOrigin<RuntimeOrigin> for EnsureXcmAccount<Filter, Conv>
+where
+	Filter: Contains<MultiLocation>,
+	Conv: xcm_executor::traits::Convert<MultiLocation, AccountId>,
+{
+	type Success = AccountId;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<AccountId, RuntimeOrigin> {
+		let location = pallet_xcm::EnsureXcm::<Filter>::try_origin(o.clone())?;
+		Conv::convert(location).map_err(|_| o)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> RuntimeOrigin {
+		pallet_xcm::Origin::Xcm(MultiLocation::parent()).into()
+	}
+}
+
+/// A collator jailed by the DKG for misbehaving during keygen or signing. Its time slot is the
+/// block the jail sentence started at, so reporting the same sentence again is a duplicate.
+pub struct DkgMisbehaviourOffence<Offender> {
+	pub session_index: SessionIndex,
+	pub validator_set_count: u32,
+	pub time_slot: BlockNumber,
+	pub offender: Offender,
+}
+
+impl<Offender: Clone> Offence<Offender> for DkgMisbehaviourOffence<Offender> {
+	const ID: Kind = *b"dkg:misbehaviour";
+	type TimeSlot = BlockNumber;
+
+	fn offenders(&self) -> Vec<Offender> {
+		vec![self.offender.clone()]
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		self.validator_set_count
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		self.time_slot
+	}
+
+	fn slash_fraction(&self, _offenders_count: u32) -> Perbill {
+		DkgMisbehaviourSlash::get()
+	}
+}
+
+/// Reports the current DKG authorities jailed by `pallet_dkg_metadata` for keygen or signing
+/// misbehaviour to `pallet_offences`, which slashes their staking bond through
+/// `pallet_parachain_staking`'s `OnOffenceHandler`.
+pub struct ReportJailedDkgAuthorities;
+impl OnNewRound for ReportJailedDkgAuthorities {
+	fn on_new_round(_round_index: RoundIndex) -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let accounts: BTreeMap<DKGId, AccountId> = DKG::authorities()
+			.into_iter()
+			.zip(DKG::current_authorities_accounts())
+			.collect();
+		let session_index = Session::current_index();
+		let validator_set_count = Session::validators().len() as u32;
+
+		let jailed = pallet_dkg_metadata::JailedKeygenAuthorities::<Runtime>::iter()
+			.chain(pallet_dkg_metadata::JailedSigningAuthorities::<Runtime>::iter());
+		let mut reads = 4u64;
+		let mut reported = 0u64;
+		for (authority, jailed_at) in jailed {
+			reads += 1;
+			let (account, full_identification) = match accounts.get(&authority).and_then(|a| {
+				<IdentityCollator as Convert<_, Option<_>>>::convert(a.clone()).map(|f| (a, f))
+			}) {
+				Some(identified) => identified,
+				None => continue,
+			};
+			let offence = DkgMisbehaviourOffence {
+				session_index,
+				validator_set_count,
+				time_slot: jailed_at,
+				offender: (account.clone(), full_identification),
+			};
+			match Offences::report_offence(vec![], offence) {
+				Ok(()) => reported += 1,
+				// already reported for this jail sentence
+				Err(OffenceError::DuplicateReport) => {},
+				Err(e) => log::warn!("failed to report DKG misbehaviour of {:?}: {:?}", account, e),
+			}
+		}
+		// each report is deduplicated against and stored by `pallet_offences` before the slash
+		db_weight.reads_writes(reads + 2 * reported, 3 * reported)
+	}
+}
+
+/// Pays the authority bonus staking set aside out of round inflation, by the governance-set
+/// `AuthorityBonusShare`, to the DKG authorities of the round that ended by their reputation in
+/// `pallet_dkg_metadata`. Jailed authorities are left out.
+pub struct RewardDkgReputation;
+impl OnNewRound for RewardDkgReputation {
+	fn on_new_round(round_index: RoundIndex) -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let authorities = DKG::authorities();
+		// the authorities, their accounts, and the jails and reputation of each
+		let reads = 2 + 3 * authorities.len() as u64;
+		let scores: Vec<(AccountId, u128)> = authorities
+			.into_iter()
+			.zip(DKG::current_authorities_accounts())
+			.filter(|(authority, _)| {
+				!pallet_dkg_metadata::JailedKeygenAuthorities::<Runtime>::contains_key(authority) &&
+					!pallet_dkg_metadata::JailedSigningAuthorities::<Runtime>::contains_key(
+						authority,
+					)
+			})
+			.map(|(authority, account)| {
+				(account, pallet_dkg_metadata::AuthorityReputations::<Runtime>::get(authority))
+			})
+			.collect();
+		db_weight.reads(reads).saturating_add(ParachainStaking::pay_authority_bonus(
+			round_index.saturating_sub(1),
+			scores,
+		))
+	}
+}
+
+/// Stake backing a DKG authority, which the DKG authority selection weighs reputation by
+pub trait AuthorityBacking<AccountId> {
+	/// The backing of `who`, zero if it is not backed
+	fn backing(who: &AccountId) -> Balance;
+}
+
+/// The counted backing of the collators selected for the current round
+pub struct StakedAuthorityBacking;
+impl AuthorityBacking<AccountId> for StakedAuthorityBacking {
+	fn backing(who: &AccountId) -> Balance {
+		<ParachainStaking as CollatorBacking<_, _>>::backing(ParachainStaking::round().current, who)
+			.unwrap_or_default()
+	}
+}
+
+/// The accounts of the DKG authorities `BackedDkgAuthorities` holds a consumer reference for
+#[storage_alias]
+type DkgAuthorityConsumers = StorageValue<DkgAuthorityRefs, Vec<AccountId>, ValueQuery>;
+
+/// Hands the session validators to `DKG`, then picks its best keygen and signing authorities by
+/// their reputation times their backing instead of by reputation alone, so a better backed
+/// authority is preferred over a slightly better reputed one. Reputations are left to the DKG.
+///
+/// The accounts of the current and next DKG authorities hold a consumer reference, so they are
+/// not reaped while they hold their role.
+pub struct BackedDkgAuthorities<B>(sp_std::marker::PhantomData<B>);
+
+impl<B> BackedDkgAuthorities<B>
+where
+	B: AuthorityBacking<AccountId>,
+{
+	/// The `count` best of the DKG `authorities`, held by `accounts`, by their reputation times
+	/// their backing, each with its position in `authorities` counted from one as the DKG indexes
+	/// them. Jailed keygen authorities are left out, equally scored ones are taken in order.
+	fn best_authorities(
+		authorities: Vec<DKGId>,
+		accounts: Vec<AccountId>,
+		count: u16,
+	) -> Vec<(u16, DKGId)> {
+		let mut scored: Vec<_> = authorities
+			.into_iter()
+			.zip(accounts)
+			.enumerate()
+			.filter(|(_, (authority, _))| {
+				!pallet_dkg_metadata::JailedKeygenAuthorities::<Runtime>::contains_key(authority)
+			})
+			.map(|(index, (authority, account))| {
+				let reputation =
+					pallet_dkg_metadata::AuthorityReputations::<Runtime>::get(&authority);
+				// unbacked authorities, e.g. invulnerables, are scored by reputation alone
+				let score = reputation.saturating_mul(B::backing(&account).max(1));
+				(score, index as u16 + 1, authority)
+			})
+			.collect();
+		scored.sort_by_key(|(score, index, _)| (sp_std::cmp::Reverse(*score), *index));
+		scored
+			.into_iter()
+			.take(count as usize)
+			.map(|(_, index, authority)| (index, authority))
+			.collect()
+	}
+
+	/// Replaces the best authorities the DKG picked by reputation alone
+	fn weigh_best_authorities_by_backing() {
+		pallet_dkg_metadata::BestAuthorities::<Runtime>::put(Self::best_authorities(
+			DKG::authorities(),
+			DKG::current_authorities_accounts(),
+			DKG::keygen_threshold(),
+		));
+		pallet_dkg_metadata::NextBestAuthorities::<Runtime>::put(Self::best_authorities(
+			DKG::next_authorities(),
+			DKG::next_authorities_accounts(),
+			DKG::next_keygen_threshold(),
+		));
+	}
+
+	/// The accounts of the current and next DKG authorities
+	fn authority_accounts() -> BTreeSet<AccountId> {
+		DKG::current_authorities_accounts()
+			.into_iter()
+			.chain(DKG::next_authorities_accounts())
+			.collect()
+	}
+
+	/// Holds a consumer reference for each of `accounts`, releasing those held for any other
+	fn hold_consumer_refs(accounts: BTreeSet<AccountId>) {
+		let held: BTreeSet<AccountId> = DkgAuthorityConsumers::take().into_iter().collect();
+		for account in held.difference(&accounts) {
+			frame_system::Pallet::<Runtime>::dec_consumers(account);
+		}
+		let held = accounts
+			.into_iter()
+			.filter(|account| {
+				held.contains(account) ||
+					match frame_system::Pallet::<Runtime>::inc_consumers_without_limit(account) {
+						Ok(()) => true,
+						Err(e) => {
+							log::error!(
+								"failed to add DKG authority consumer reference for {:?}: {:?}",
+								account,
+								e
+							);
+							false
+						},
+					}
+			})
+			.collect();
+		DkgAuthorityConsumers::put(held);
+	}
+}
+
+impl<B> sp_runtime::BoundToRuntimeAppPublic for BackedDkgAuthorities<B> {
+	type Public = <DKG as sp_runtime::BoundToRuntimeAppPublic>::Public;
+}
+
+impl<B> OneSessionHandler<AccountId> for BackedDkgAuthorities<B>
+where
+	B: AuthorityBacking<AccountId>,
+{
+	type Key = <DKG as OneSessionHandler<AccountId>>::Key;
+
+	fn on_genesis_session<'a, I: 'a>(validators: I)
+	where
+		I: Iterator<Item = (&'a AccountId, Self::Key)>,
+	{
+		DKG::on_genesis_session(validators);
+		Self::weigh_best_authorities_by_backing();
+		Self::hold_consumer_refs(Self::authority_accounts())
+	}
+
+	fn on_new_session<'a, I: 'a>(changed: bool, validators: I, queued_validators: I)
+	where
+		I: Iterator<Item = (&'a AccountId, Self::Key)>,
+	{
+		DKG::on_new_session(changed, validators, queued_validators);
+		Self::weigh_best_authorities_by_backing();
+		Self::hold_consumer_refs(Self::authority_accounts())
+	}
+
+	fn on_before_session_ending() {
+		DKG::on_before_session_ending()
+	}
+
+	fn on_disabled(validator_index: u32) {
+		// the DKG indexes the validators in the order it was handed them
+		let index = Session::validators().get(validator_index as usize).and_then(|validator| {
+			DKG::current_authorities_accounts()
+				.iter()
+				.position(|account| account == validator)
+		});
+		if let Some(index) = index {
+			DKG::on_disabled(index as u32)
+		}
+	}
+}
+
+/// Hands the session validators to `ImOnline` only while `Enabled` holds. Otherwise `ImOnline`
+/// is left without authorities, so its offchain worker has no local key to build heartbeats with,
+/// heartbeats fail validation and no validator is reported unresponsive.
+pub struct SwitchableImOnline<Enabled>(sp_std::marker::PhantomData<Enabled>);
+
+impl<Enabled: Get<bool>> SwitchableImOnline<Enabled> {
+	fn if_enabled<'a, K>(
+		validators: impl Iterator<Item = (&'a AccountId, K)>,
+	) -> impl Iterator<Item = (&'a AccountId, K)> {
+		validators.take(if Enabled::get() { usize::MAX } else { 0 })
+	}
+}
+
+impl<Enabled> sp_runtime::BoundToRuntimeAppPublic for SwitchableImOnline<Enabled> {
+	type Public = <ImOnline as sp_runtime::BoundToRuntimeAppPublic>::Public;
+}
+
+impl<Enabled: Get<bool>> OneSessionHandler<AccountId> for SwitchableImOnline<Enabled> {
+	type Key = <ImOnline as OneSessionHandler<AccountId>>::Key;
+
+	fn on_genesis_session<'a, I: 'a>(validators: I)
+	where
+		I: Iterator<Item = (&'a AccountId, Self::Key)>,
+	{
+		ImOnline::on_genesis_session(Self::if_enabled(validators))
+	}
+
+	fn on_new_session<'a, I: 'a>(changed: bool, validators: I, queued_validators: I)
+	where
+		I: Iterator<Item = (&'a AccountId, Self::Key)>,
+	{
+		ImOnline::on_new_session(
+			changed,
+			Self::if_enabled(validators),
+			Self::if_enabled(queued_validators),
+		)
+	}
+
+	fn on_before_session_ending() {
+		ImOnline::on_before_session_ending()
+	}
+
+	fn on_disabled(validator_index: u32) {
+		ImOnline::on_disabled(validator_index)
+	}
+}
+
+/// Records the amount each account has staked as the round starts, which stake-weighted votes
+/// on the round read their voting power from
+pub struct SnapshotVotingPower;
+impl OnNewRound for SnapshotVotingPower {
+	fn on_new_round(round_index: RoundIndex) -> Weight {
+		let stakes = ParachainStaking::staked_amounts();
+		let reads = stakes.len() as u64;
+		<Runtime as frame_system::Config>::DbWeight::get()
+			.reads(reads)
+			.saturating_add(StakeSnapshot::record(round_index, stakes))
+	}
+}
+
+/// Splits the native wrapping fees collected by `TokenWrapper` between the treasury and the
+/// staking rewards, by the governance-set `StakerFeeShare`. Fees in other assets stay in the pot.
+pub struct DistributeWrappingFees;
+impl OnNewRound for DistributeWrappingFees {
+	fn on_new_round(round_index: RoundIndex) -> Weight {
+		let pot = WrappingFeePotId::get().into_account_truncating();
+		ParachainStaking::distribute_fees(round_index, &pot, &Treasury::account_id())
+	}
+}
+
+/// Splits the usage rewards pot between the vanchor depositors of the round that just ended
+pub struct EndVAnchorRewardsEpoch;
+impl OnNewRound for EndVAnchorRewardsEpoch {
+	fn on_new_round(round_index: RoundIndex) -> Weight {
+		VAnchorRewards::end_epoch(round_index.saturating_sub(1))
+	}
+}
+
+/// Logs every proposal signed by the DKG with its signing block for the relayer API
+pub struct LogSignedProposal;
+impl OnSignedProposal<DispatchError> for LogSignedProposal {
+	fn on_signed_proposal(proposal: Proposal) -> Result<(), DispatchError> {
+		SignedProposalLog::note_signed(proposal);
+		Ok(())
+	}
+}
+
+/// Pauses the staking calls bonding funds through `TransactionPause` once staking trips its
+/// circuit breaker. Stakers can still unbond and leave, and governance resumes the
+/// `PauseGroup::StakingBonds` group once it has reset the breaker.
+pub struct PauseStakingCalls;
+impl pallet_parachain_staking::CircuitBreaker for PauseStakingCalls {
+	fn trip() -> Weight {
+		TransactionPause::do_pause_group(PauseGroup::StakingBonds);
+		<Runtime as frame_system::Config>::DbWeight::get().reads_writes(1, 1)
+	}
+}
+
+/// Raises the priority of calls executing or cancelling scheduled staking requests in the last
+/// `STAKING_EXIT_PRIORITY_BLOCKS` blocks before the round ends, so exits due by the round boundary
+/// are not starved by congestion. Calls nested in others, e.g. batches, keep their priority.
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub struct PrioritizeStakingExits;
+
+impl PrioritizeStakingExits {
+	fn is_staking_exit(call: &RuntimeCall) -> bool {
+		use pallet_parachain_staking::Call;
+		matches!(
+			call,
+			RuntimeCall::ParachainStaking(
+				Call::execute_leave_candidates { .. } |
+					Call::cancel_leave_candidates { .. } |
+					Call::execute_candidate_bond_less { .. } |
+					Call::cancel_candidate_bond_less { .. } |
+					Call::execute_delegation_request { .. } |
+					Call::cancel_delegation_request { .. } |
+					Call::execute_leave_all { .. }
+			)
+		)
+	}
+
+	/// Whether the round, ending with the session, is due to end within the priority window
+	fn near_round_end() -> bool {
+		let now = frame_system::Pallet::<Runtime>::block_number();
+		let (next, _) =
+			<Runtime as pallet_session::Config>::NextSessionRotation::estimate_next_session_rotation(
+				now,
+			);
+		next.map_or(false, |next| {
+			next.saturating_sub(now) < crate::staking::STAKING_EXIT_PRIORITY_BLOCKS
+		})
+	}
+}
+
+impl SignedExtension for PrioritizeStakingExits {
+	const IDENTIFIER: &'static str = "PrioritizeStakingExits";
+	type AccountId = AccountId;
+	type Call = RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if !Self::is_staking_exit(call) || !Self::near_round_end() {
+			return Ok(ValidTransaction::default())
+		}
+		Ok(ValidTransaction {
+			priority: crate::staking::STAKING_EXIT_PRIORITY_BOOST,
+			..Default::default()
+		})
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+}
+
+/// Names the author of a block to the EVM by the first 20 bytes of the author's account
+pub struct FindAuthorTruncated<F>(sp_std::marker::PhantomData<F>);
+impl<F: FindAuthor<AccountId>> FindAuthor<H160> for FindAuthorTruncated<F> {
+	fn find_author<'a, I>(digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		F::find_author(digests)
+			.map(|author| H160::from_slice(&AsRef::<[u8]>::as_ref(&author)[..20]))
+	}
+}
+
+/// EVM gas price, fixed until fees follow block fullness
+pub struct FixedGasPrice;
+impl pallet_evm::FeeCalculator for FixedGasPrice {
+	fn min_gas_price() -> (U256, Weight) {
+		(crate::MinGasPrice::get(), Weight::zero())
+	}
+}
+
+/// Sets the keys rotated by `VerifierRotation` on the BN254 verifier pallets
+pub struct SetBn254VerifyingKeys;
+impl pallet_verifier_rotation::SetVerifyingKey for SetBn254VerifyingKeys {
+	fn set_verifying_key(verifier: Verifier, key: Vec<u8>) -> DispatchResult {
+		match verifier {
+			Verifier::Mixer => MixerVerifierBn254::force_set_parameters(RuntimeOrigin::root(), key),
+			Verifier::VAnchor { max_edges, num_inputs } => VAnchorVerifier::force_set_parameters(
+				RuntimeOrigin::root(),
+				(max_edges, num_inputs),
+				key,
+			),
+		}
+		.map(|_| ())
+		.map_err(|e| e.error)
+	}
+}
+
+/// A transaction of the BN254 vanchor, made through `VAnchorLimits::transact`
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub struct Bn254VAnchorTransaction {
+	pub id: u32,
+	pub proof_data: ProofData<Element>,
+	pub ext_data: ExtData<AccountId, Amount, Balance, AssetId>,
+}
+
+impl From<Bn254VAnchorTransaction> for RuntimeCall {
+	fn from(transaction: Bn254VAnchorTransaction) -> Self {
+		let Bn254VAnchorTransaction { id, proof_data, ext_data } = transaction;
+		pallet_vanchor::Call::<Runtime, pallet_vanchor::Instance1>::transact {
+			id,
+			proof_data,
+			ext_data,
+		}
+		.into()
+	}
+}
+
+/// The native value of `amount` of `asset`, at the rate `AssetRates` converts fees with. Assets
+/// without a rate are worth nothing.
+fn native_value(amount: Balance, asset: AssetId) -> Balance {
+	if asset == GetNativeCurrencyId::get() {
+		return amount
+	}
+	AssetRates::to_native_balance(amount, asset).unwrap_or_default()
+}
+
+/// Makes the transactions `VAnchorLimits` let through in `VAnchorBn254`, whose `transact` is
+/// filtered out of direct calls. Paused vanchor transactions stay paused.
+///
+/// Deposits count towards the usage rewards of the caller, whose funds they are made out of, with
+/// their native value, and withdrawals count against the rewards of their recipient. Relayers
+/// only submit withdrawals, which are not rewarded.
+pub struct Bn254VAnchor;
+impl pallet_vanchor_limits::VAnchorTransact<AccountId, u32> for Bn254VAnchor {
+	type Transaction = Bn254VAnchorTransaction;
+
+	fn tree_id(transaction: &Self::Transaction) -> u32 {
+		transaction.id
+	}
+
+	fn ext_amount(transaction: &Self::Transaction) -> i128 {
+		transaction.ext_data.ext_amount
+	}
+
+	fn weight(transaction: &Self::Transaction) -> Weight {
+		// and noting a deposit or withdrawal in `VAnchorRewards`
+		RuntimeCall::from(transaction.clone())
+			.get_dispatch_info()
+			.weight
+			.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads_writes(3, 2))
+	}
+
+	fn transact(who: AccountId, transaction: Self::Transaction) -> DispatchResult {
+		let ext_amount = transaction.ext_data.ext_amount;
+		let value = native_value(ext_amount.unsigned_abs(), transaction.ext_data.token);
+		let recipient = transaction.ext_data.recipient.clone();
+		let call = RuntimeCall::from(transaction);
+		if pallet_transaction_pause::PausedTransactionFilter::<Runtime>::contains(&call) {
+			return Err(frame_system::Error::<Runtime>::CallFiltered.into())
+		}
+		call.dispatch_bypass_filter(RuntimeOrigin::signed(who.clone()))
+			.map_err(|e| e.error)?;
+		if ext_amount > 0 {
+			VAnchorRewards::note_deposit(&who, value);
+		} else {
+			VAnchorRewards::note_withdrawal(&recipient, value);
+		}
+		Ok(())
+	}
+}
+
+/// The roots of the BN254 trees and of the neighbors of the linkable ones, for `RootHistory`
+pub struct Bn254TreeRoots;
+impl pallet_root_history::CurrentRoots<u32, ChainId, Element> for Bn254TreeRoots {
+	fn current_roots() -> Vec<(u32, Element, Vec<(ChainId, Element)>)> {
+		pallet_mt::Trees::<Runtime, pallet_mt::Instance1>::iter()
+			.map(|(tree_id, tree)| {
+				let neighbors = LinkableTreeBn254::get_neighbor_edges(tree_id)
+					.unwrap_or_default()
+					.into_iter()
+					.map(|edge| (edge.src_chain_id, edge.root))
+					.collect();
+				(tree_id, tree.root, neighbors)
+			})
+			.collect()
+	}
+}
+
+/// Which schedule ends sessions, and with them staking rounds and DKG sessions
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub enum SessionRotation {
+	/// The DKG session period alone
+	Dkg,
+	/// The staking round length alone
+	Staking,
+	/// Whichever of the DKG period and the staking round length is due first
+	Earliest,
+	/// The first DKG period boundary once the staking round length has passed
+	Latest,
+}
+
+/// Ends sessions on the DKG schedule `Dkg`, the staking round schedule, or a combination of both
+/// as chosen by `Mode`. Staking being the session manager, a session and its round start
+/// together.
+pub struct RotateSessions<Dkg, Mode>(sp_std::marker::PhantomData<(Dkg, Mode)>);
+
+impl<Dkg, Mode> RotateSessions<Dkg, Mode> {
+	/// First block of the session and the block its staking round is due to end at
+	fn round_bounds() -> (BlockNumber, BlockNumber) {
+		let round = ParachainStaking::round();
+		(round.first, round.first.saturating_add(round.length))
+	}
+}
+
+impl<Dkg, Mode> ShouldEndSession<BlockNumber> for RotateSessions<Dkg, Mode>
+where
+	Dkg: ShouldEndSession<BlockNumber>,
+	Mode: Get<SessionRotation>,
+{
+	fn should_end_session(now: BlockNumber) -> bool {
+		let staking_due = || now >= Self::round_bounds().1;
+		match Mode::get() {
+			SessionRotation::Dkg => Dkg::should_end_session(now),
+			SessionRotation::Staking => staking_due(),
+			SessionRotation::Earliest => Dkg::should_end_session(now) || staking_due(),
+			SessionRotation::Latest => Dkg::should_end_session(now) && staking_due(),
+		}
+	}
+}
+
+impl<Dkg, Mode> EstimateNextSessionRotation<BlockNumber> for RotateSessions<Dkg, Mode>
+where
+	Dkg: EstimateNextSessionRotation<BlockNumber>,
+	Mode: Get<SessionRotation>,
+{
+	fn average_session_length() -> BlockNumber {
+		let round_length = ParachainStaking::round().length;
+		match Mode::get() {
+			SessionRotation::Dkg => Dkg::average_session_length(),
+			SessionRotation::Staking => round_length,
+			SessionRotation::Earliest => Dkg::average_session_length().min(round_length),
+			SessionRotation::Latest => Dkg::average_session_length().max(round_length),
+		}
+	}
+
+	fn estimate_current_session_progress(now: BlockNumber) -> (Option<Permill>, Weight) {
+		if Mode::get() == SessionRotation::Dkg {
+			return Dkg::estimate_current_session_progress(now)
+		}
+		let (first, _) = Self::round_bounds();
+		let (next, weight) = Self::estimate_next_session_rotation(now);
+		let progress = next.map(|next| {
+			Permill::from_rational(now.saturating_sub(first), next.saturating_sub(first).max(1))
+		});
+		(
+			progress,
+			weight.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(1)),
+		)
+	}
+
+	fn estimate_next_session_rotation(now: BlockNumber) -> (Option<BlockNumber>, Weight) {
+		let (dkg_next, weight) = Dkg::estimate_next_session_rotation(now);
+		let weight =
+			weight.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(1));
+		let staking_next = Self::round_bounds().1.max(now);
+		let next = match Mode::get() {
+			SessionRotation::Dkg => dkg_next,
+			SessionRotation::Staking => Some(staking_next),
+			SessionRotation::Earliest =>
+				Some(dkg_next.map_or(staking_next, |dkg_next| dkg_next.min(staking_next))),
+			// the first DKG period boundary at or after the end of the round
+			SessionRotation::Latest => dkg_next.map(|dkg_next| {
+				let period = Dkg::average_session_length().max(1);
+				let behind = staking_next.saturating_sub(dkg_next);
+				dkg_next.saturating_add((behind + period - 1) / period * period)
+			}),
+		};
+		(next, weight)
+	}
+}
+
+/// Delegates the funds claimed with `claim_and_delegate` through parachain staking, which stakes
+/// them while they vest
+pub struct DelegateClaimThroughStaking;
+impl pallet_ecdsa_claims::DelegateClaim<AccountId, Balance> for DelegateClaimThroughStaking {
+	fn delegate(who: &AccountId, candidate: AccountId, amount: Balance) -> DispatchResult {
+		<ParachainStaking as StakingInterface>::delegate(who.clone(), candidate, amount)
+	}
+
+	fn weight() -> Weight {
+		use pallet_parachain_staking::Config;
+		type StakingWeight = <Runtime as Config>::WeightInfo;
+		let candidate_delegations = <Runtime as Config>::MaxTopDelegationsPerCandidate::get()
+			.saturating_add(<Runtime as Config>::MaxBottomDelegationsPerCandidate::get());
+		// a claimant already delegating to the candidate bonds more instead
+		StakingWeight::delegate_with_auto_compound(
+			candidate_delegations,
+			candidate_delegations,
+			<Runtime as Config>::MaxDelegationsPerDelegator::get(),
+		)
+		.max(StakingWeight::delegator_bond_more())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Balances;
+	use frame_support::assert_ok;
+	use sp_runtime::{BuildStorage, Percent};
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn collator_payout_cut_is_minted_to_the_treasury() {
+		new_test_ext().execute_with(|| {
+			let collator = AccountId::from([1u8; 32]);
+			let amount = 1_000 * crate::DOLLAR;
+			assert_eq!(CollatorPayoutToTreasury::<Runtime>::take_cut(1, &collator, amount).0, 0);
+
+			assert_ok!(ParachainStaking::set_collator_payout_treasury_cut(
+				RuntimeOrigin::root(),
+				Percent::from_percent(10),
+			));
+			let issuance = Balances::total_issuance();
+			let (cut, _) = CollatorPayoutToTreasury::<Runtime>::take_cut(1, &collator, amount);
+			assert_eq!(cut, amount / 10);
+			assert_eq!(Balances::free_balance(Treasury::account_id()), cut);
+			// the collator is not paid by the hook, and so cannot keep the cut
+			assert_eq!(Balances::free_balance(&collator), 0);
+			assert_eq!(Balances::total_issuance(), issuance + cut);
+		});
+	}
+
+	#[test]
+	fn dkg_authorities_hold_a_consumer_reference_while_they_are_authorities() {
+		new_test_ext().execute_with(|| {
+			let [a, b, c] = [1u8, 2, 3].map(|i| AccountId::from([i; 32]));
+			for account in [&a, &b, &c] {
+				Balances::make_free_balance_be(account, crate::DOLLAR);
+			}
+			let hold = |accounts: &[&AccountId]| {
+				BackedDkgAuthorities::<StakedAuthorityBacking>::hold_consumer_refs(
+					accounts.iter().map(|account| (*account).clone()).collect(),
+				)
+			};
+			let consumers =
+				|| [&a, &b, &c].map(|account| frame_system::Pallet::<Runtime>::consumers(account));
+
+			hold(&[&a, &b]);
+			assert_eq!(consumers(), [1, 1, 0]);
+			hold(&[&b, &c]);
+			assert_eq!(consumers(), [0, 1, 1]);
+			hold(&[]);
+			assert_eq!(consumers(), [0, 0, 0]);
+		});
+	}
+
+	#[test]
+	fn better_backed_dkg_authorities_are_picked_over_slightly_better_reputed_ones() {
+		struct TestBacking;
+		impl AuthorityBacking<AccountId> for TestBacking {
+			fn backing(who: &AccountId) -> Balance {
+				if who == &AccountId::from([2u8; 32]) {
+					2
+				} else {
+					1
+				}
+			}
+		}
+
+		new_test_ext().execute_with(|| {
+			let accounts = [1u8, 2, 3].map(|i| AccountId::from([i; 32])).to_vec();
+			let authorities: Vec<DKGId> =
+				[1u8, 2, 3].map(|i| sp_core::ecdsa::Public::from_raw([i; 33]).into()).to_vec();
+			for (authority, reputation) in authorities.iter().zip([100u128, 80, 90]) {
+				pallet_dkg_metadata::AuthorityReputations::<Runtime>::insert(authority, reputation);
+			}
+			let best = |count| {
+				BackedDkgAuthorities::<TestBacking>::best_authorities(
+					authorities.clone(),
+					accounts.clone(),
+					count,
+				)
+			};
+
+			// by reputation alone the first and third would be picked
+			assert_eq!(best(2), vec![(2, authorities[1].clone()), (1, authorities[0].clone())]);
+			pallet_dkg_metadata::JailedKeygenAuthorities::<Runtime>::insert(&authorities[1], 1);
+			assert_eq!(best(2), vec![(1, authorities[0].clone()), (3, authorities[2].clone())]);
+		});
+	}
+
+	#[test]
+	fn claims_delegating_the_claimed_funds_are_paused_with_staking_bonds() {
+		new_test_ext().execute_with(|| {
+			let account = AccountId::from([1u8; 32]);
+			let call = RuntimeCall::Claims(pallet_ecdsa_claims::Call::claim_and_delegate {
+				dest: account.clone(),
+				ethereum_signature: pallet_ecdsa_claims::EcdsaSignature([0u8; 65]),
+				statement: Vec::new(),
+				candidate: account.clone(),
+			});
+			let claim = RuntimeCall::Claims(pallet_ecdsa_claims::Call::claim {
+				dest: account,
+				ethereum_signature: pallet_ecdsa_claims::EcdsaSignature([0u8; 65]),
+			});
+			let paused = |call: &RuntimeCall| {
+				pallet_transaction_pause::PausedTransactionFilter::<Runtime>::contains(call)
+			};
+
+			TransactionPause::do_pause_group(PauseGroup::StakingJoins);
+			assert!(paused(&call));
+			assert!(!paused(&claim));
+
+			assert_ok!(TransactionPause::resume_group(
+				RuntimeOrigin::root(),
+				PauseGroup::StakingJoins
+			));
+			assert!(!paused(&call));
+			// as when the staking circuit breaker trips
+			TransactionPause::do_pause_group(PauseGroup::StakingBonds);
+			assert!(paused(&call));
+		});
+	}
+
+	#[test]
+	fn im_online_only_has_authorities_while_enabled() {
+		new_test_ext().execute_with(|| {
+			let validator = AccountId::from([1u8; 32]);
+			let key = crate::ImOnlineId::from(sp_core::sr25519::Public::from_raw([1u8; 32]));
+			let new_session = || {
+				let validators = vec![(&validator, key.clone())];
+				SwitchableImOnline::<crate::ImOnlineEnabled>::on_new_session(
+					true,
+					validators.clone().into_iter(),
+					validators.into_iter(),
+				)
+			};
+
+			new_session();
+			assert!(ImOnline::keys().is_empty());
+
+			crate::ImOnlineEnabled::set(&true);
+			new_session();
+			assert_eq!(ImOnline::keys().into_inner(), vec![key.clone()]);
+
+			crate::ImOnlineEnabled::set(&false);
+			new_session();
+			assert!(ImOnline::keys().is_empty());
+		});
+	}
+}
@@ -20,21 +20,34 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+pub mod governance;
 pub mod impls;
+pub mod migrations;
+pub mod precompiles;
 pub mod protocol_substrate_config;
 pub mod weights;
 pub mod xcm_config;
 
-use codec::Encode;
+use codec::{Decode, Encode};
 use dkg_runtime_primitives::{TypedChainId, UnsignedProposal};
+use fp_rpc::TransactionStatus;
 use frame_support::pallet_prelude::TransactionPriority;
+use impls::{FindAuthorTruncated, FixedGasPrice};
 use pallet_dkg_proposals::DKGEcdsaToEthereum;
+use pallet_ethereum::{Call::transact, Transaction as EthereumTransaction};
+use pallet_evm::{
+	Account as EVMAccount, EnsureAddressTruncated, FeeCalculator, HashedAddressMapping, Runner,
+};
+use precompiles::TanglePrecompiles;
 use sp_api::impl_runtime_apis;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160, H256, U256};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{self, BlakeTwo256, Block as BlockT, StaticLookup},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	traits::{
+		self, BlakeTwo256, Block as BlockT, DispatchInfoOf, Dispatchable, PostDispatchInfoOf,
+		StaticLookup, UniqueSaturatedInto,
+	},
+	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
 	ApplyExtrinsicResult, SaturatedConversion,
 };
 
@@ -64,7 +77,7 @@ pub use frame_support::{
 	dispatch::DispatchClass,
 	match_types, parameter_types,
 	traits::{
-		ConstU128, ConstU32, Currency, EitherOfDiverse, EqualPrivilegeOnly, Everything, IsInVec,
+		ConstU128, ConstU32, Contains, Currency, EitherOfDiverse, EqualPrivilegeOnly, IsInVec,
 		Randomness,
 	},
 	weights::{constants::WEIGHT_PER_SECOND, IdentityFee, Weight},
@@ -76,6 +89,7 @@ use frame_system::{
 	limits::{BlockLength, BlockWeights},
 	EnsureRoot,
 };
+use governance::{pallet_custom_origins, StakingAdminOrRoot, TreasurerOrRoot};
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -92,6 +106,7 @@ use weights::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight};
 
 pub mod nimbus_session_adapter;
 pub mod staking;
+pub mod staking_extension;
 use nimbus_session_adapter::NimbusId;
 // XCM Imports
 
@@ -112,13 +127,17 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
+	pallet_governance_rebates::RebateGovernanceFees<Runtime>,
+	impls::PrioritizeStakingExits,
 );
-/// Unchecked extrinsic type as expected by this runtime.
+/// Unchecked extrinsic type as expected by this runtime. Ethereum transactions are
+/// self-contained: they carry their own signature instead of a substrate one.
 pub type UncheckedExtrinsic =
-	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
+	fp_self_contained::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
-pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra>;
+pub type CheckedExtrinsic =
+	fp_self_contained::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra, H160>;
 /// Signed payload
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
@@ -134,7 +153,16 @@ pub type Executive = frame_executive::Executive<
 pub struct OnRuntimeUpgrade;
 impl frame_support::traits::OnRuntimeUpgrade for OnRuntimeUpgrade {
 	fn on_runtime_upgrade() -> Weight {
-		Weight::from_ref_time(0u64)
+		<(
+			pallet_parachain_staking::migrations::AddStakerConsumerRefs<Runtime>,
+			pallet_ecdsa_claims::migrations::AddPreclaimRefs<Runtime>,
+			pallet_parachain_staking::migrations::HoldStakedFunds<Runtime>,
+			pallet_parachain_staking::migrations::AddAutoCompoundFrequency<Runtime>,
+			pallet_parachain_staking::migrations::SplitCandidatePool<Runtime>,
+			pallet_parachain_staking::migrations::BoundInvulnerables<Runtime>,
+			pallet_parachain_staking::migrations::TrackHeldStake<Runtime>,
+			migrations::RetireGenesisAccounts,
+		) as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade()
 	}
 }
 
@@ -161,10 +189,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("tangle-parachain"),
 	impl_name: create_runtime_str!("tangle-parachain"),
 	authoring_version: 1,
-	spec_version: 3,
+	spec_version: 6,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 0,
 };
 
@@ -204,17 +232,17 @@ use nimbus_session_adapter::{AuthorInherentWithNoOpSession, VrfWithNoOpSession};
 impl_opaque_keys! {
 	pub struct SessionKeys {
 		pub aura: Aura,
-		pub dkg: DKG,
+		pub dkg: crate::impls::BackedDkgAuthorities<crate::impls::StakedAuthorityBacking>,
 		pub nimbus: AuthorInherentWithNoOpSession<Runtime>,
 		pub vrf: VrfWithNoOpSession,
-		pub im_online: ImOnline,
+		pub im_online: crate::impls::SwitchableImOnline<ImOnlineEnabled>,
 	}
 }
 
 impl frame_system::Config for Runtime {
 	type AccountData = pallet_balances::AccountData<Balance>;
 	type AccountId = AccountId;
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = BaseFilter;
 	type BlockHashCount = BlockHashCount;
 	type BlockLength = RuntimeBlockLength;
 	type BlockNumber = BlockNumber;
@@ -298,8 +326,8 @@ parameter_types! {
 
 impl pallet_treasury::Config for Runtime {
 	type Currency = Balances;
-	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
-	type RejectOrigin = frame_system::EnsureRoot<AccountId>;
+	type ApproveOrigin = TreasurerOrRoot;
+	type RejectOrigin = TreasurerOrRoot;
 	type RuntimeEvent = RuntimeEvent;
 	type OnSlash = ();
 	type ProposalBond = ProposalBond;
@@ -333,6 +361,26 @@ impl pallet_transaction_payment::Config for Runtime {
 		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 }
 
+impl pallet_asset_tx_payment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Fungibles = Tokens;
+	/// Fees are converted at the governance-set rates of `AssetRates`
+	type OnChargeAssetTransaction = crate::impls::NoteRebateFeeAsset<
+		pallet_asset_tx_payment::FungiblesAdapter<
+			AssetRates,
+			crate::impls::AssetCreditToBlockAuthor<Runtime, Tokens>,
+		>,
+	>;
+}
+
+impl pallet_asset_rates::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = webb_primitives::AssetId;
+	type Balance = Balance;
+	type RateOrigin = crate::governance::AssetAdminOrigin;
+	type WeightInfo = pallet_asset_rates::weights::WebbWeight<Runtime>;
+}
+
 impl pallet_randomness_collective_flip::Config for Runtime {}
 
 impl pallet_sudo::Config for Runtime {
@@ -364,16 +412,27 @@ impl pallet_authorship::Config for Runtime {
 parameter_types! {
 	pub const Period: u32 = SESSION_PERIOD_BLOCKS;
 	pub const Offset: u32 = 0;
+	/// Schedule ending sessions, the DKG period until governance switches it with
+	/// `System::set_storage` at `SessionRotationMode::key()`
+	pub storage SessionRotationMode: crate::impls::SessionRotation =
+		crate::impls::SessionRotation::Dkg;
 }
 
+/// Sessions end as `SessionRotationMode` combines the DKG period and the staking round length
+pub type SessionSchedule = crate::impls::RotateSessions<
+	pallet_dkg_metadata::DKGPeriodicSessions<Period, Offset, Runtime>,
+	SessionRotationMode,
+>;
+
 impl pallet_session::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Keys = SessionKeys;
-	type NextSessionRotation = pallet_dkg_metadata::DKGPeriodicSessions<Period, Offset, Runtime>;
+	type NextSessionRotation = SessionSchedule;
 	// Essentially just Aura, but lets be pedantic.
 	type SessionHandler = <SessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
 	type SessionManager = ParachainStaking;
-	type ShouldEndSession = pallet_dkg_metadata::DKGPeriodicSessions<Period, Offset, Runtime>;
+	/// Rounds follow the relay chain once governance sets `RelayRoundLength`
+	type ShouldEndSession = pallet_parachain_staking::RelayAlignedRounds<Runtime, SessionSchedule>;
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	// we don't have stash and controller, thus we don't need the convert as well.
 	type ValidatorIdOf = IdentityCollator;
@@ -406,7 +465,7 @@ impl pallet_dkg_metadata::Config for Runtime {
 	type OnAuthoritySetChangeHandler = DKGProposals;
 	type OnDKGPublicKeyChangeHandler = ();
 	type OffChainAuthId = dkg_runtime_primitives::offchain::crypto::OffchainAuthId;
-	type NextSessionRotation = pallet_dkg_metadata::DKGPeriodicSessions<Period, Offset, Runtime>;
+	type NextSessionRotation = SessionSchedule;
 	type RefreshDelay = RefreshDelay;
 	type KeygenJailSentence = Period;
 	type SigningJailSentence = Period;
@@ -434,17 +493,22 @@ impl pallet_dkg_proposal_handler::Config for Runtime {
 	type OffChainAuthId = dkg_runtime_primitives::offchain::crypto::OffchainAuthId;
 	type MaxSubmissionsPerBatch = frame_support::traits::ConstU16<100>;
 	type UnsignedProposalExpiry = UnsignedProposalExpiry;
-	type SignedProposalHandler = ();
+	type SignedProposalHandler = crate::impls::LogSignedProposal;
 	type WeightInfo = pallet_dkg_proposal_handler::weights::WebbWeight<Runtime>;
 }
 
+impl pallet_signed_proposal_log::Config for Runtime {
+	type Proposal = dkg_runtime_primitives::Proposal;
+	type MaxLoggedProposals = ConstU32<1_000>;
+}
+
 impl pallet_dkg_proposals::Config for Runtime {
 	type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type DKGAuthorityToMerkleLeaf = DKGEcdsaToEthereum;
 	type DKGId = DKGId;
 	type ChainIdentifier = ChainIdentifier;
 	type RuntimeEvent = RuntimeEvent;
-	type NextSessionRotation = pallet_dkg_metadata::DKGPeriodicSessions<Period, Offset, Runtime>;
+	type NextSessionRotation = SessionSchedule;
 	type Proposal = Vec<u8>;
 	type ProposalLifetime = ProposalLifetime;
 	type ProposalHandler = DKGProposalHandler;
@@ -476,6 +540,26 @@ impl pallet_identity::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const ConfigDepositBase: Balance = deposit(1, 88);
+	pub const FriendDepositFactor: Balance = deposit(0, 32);
+	pub const MaxFriends: u16 = 9;
+	pub const RecoveryDeposit: Balance = deposit(1, 89);
+}
+
+/// Social recovery of accounts, e.g. of collators and delegators who lost their keys. Their
+/// staking calls stay reachable through `as_recovered`, under the same pauses as direct calls.
+impl pallet_recovery::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_recovery::weights::SubstrateWeight<Runtime>;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ConfigDepositBase = ConfigDepositBase;
+	type FriendDepositFactor = FriendDepositFactor;
+	type MaxFriends = MaxFriends;
+	type RecoveryDeposit = RecoveryDeposit;
+}
+
 impl pallet_utility::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -483,6 +567,114 @@ impl pallet_utility::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	// one storage item; key size 32, value size 8
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	// additional storage item size of 33 bytes
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const MaxProxies: u16 = 32;
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
+	pub const MaxPending: u16 = 32;
+}
+
+/// The calls a proxy may make on behalf of the proxied account
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	sp_runtime::RuntimeDebug,
+	codec::MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Every call
+	Any,
+	/// Every call but those transferring funds
+	NonTransfer,
+	/// Governance calls
+	Governance,
+	/// Staking and session key calls
+	Staking,
+	/// Only rejecting the announcements of other proxies
+	CancelProxy,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl frame_support::traits::InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, c: &RuntimeCall) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(
+				c,
+				RuntimeCall::Balances(..) |
+					RuntimeCall::Currencies(..) |
+					RuntimeCall::Tokens(..) |
+					RuntimeCall::Vesting(pallet_vesting::Call::vested_transfer { .. }) |
+					RuntimeCall::Indices(pallet_indices::Call::transfer { .. })
+			),
+			ProxyType::Governance => matches!(
+				c,
+				RuntimeCall::Democracy(..) |
+					RuntimeCall::Council(..) |
+					RuntimeCall::ConvictionVoting(..) |
+					RuntimeCall::Referenda(..) |
+					RuntimeCall::Whitelist(..) |
+					RuntimeCall::Treasury(..) |
+					RuntimeCall::Utility(..)
+			),
+			ProxyType::Staking => matches!(
+				c,
+				RuntimeCall::ParachainStaking(..) |
+					RuntimeCall::Session(..) |
+					RuntimeCall::Utility(..)
+			),
+			ProxyType::CancelProxy => matches!(
+				c,
+				RuntimeCall::Proxy(pallet_proxy::Call::reject_announcement { .. }) |
+					RuntimeCall::Utility(..)
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, _) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Proxy accounts. Calls made through `proxy` and `proxy_announced` are under the same pauses as
+/// direct calls.
+impl pallet_proxy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
 parameter_types! {
 	pub Prefix: &'static [u8] = b"Pay TNTs to the Tangle account:";
 }
@@ -493,6 +685,8 @@ impl pallet_ecdsa_claims::Config for Runtime {
 	type Prefix = Prefix;
 	type ForceOrigin = EnsureRoot<Self::AccountId>;
 	type MoveClaimOrigin = EnsureRoot<Self::AccountId>;
+	/// Claimants may stake their claim while it vests
+	type DelegateClaim = crate::impls::DelegateClaimThroughStaking;
 	type WeightInfo = pallet_ecdsa_claims::TestWeightInfo;
 }
 
@@ -537,7 +731,9 @@ where
 			frame_system::CheckEra::<Runtime>::from(era),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
+			pallet_governance_rebates::RebateGovernanceFees::<Runtime>::new(),
+			impls::PrioritizeStakingExits,
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -647,6 +843,10 @@ impl pallet_aura_style_filter::Config for Runtime {
 
 parameter_types! {
 	pub LeaveDelayRounds: BlockNumber = SESSION_PERIOD_BLOCKS;
+	/// Share of a delegation forfeited to unbond it without waiting for `LeaveDelayRounds`
+	pub const EmergencyUnbondPenalty: Perbill = Perbill::from_percent(10);
+	pub const PendingRequestRewards: pallet_parachain_staking::PendingRequestRewards =
+		pallet_parachain_staking::PendingRequestRewards::UntilExecution;
 }
 
 /// A convertor from collators id. Since this pallet does not have stash/controller, this is
@@ -667,11 +867,15 @@ impl pallet_parachain_staking::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type BlockAuthor = AuthorInherent;
-	type MonetaryGovernanceOrigin = EnsureRoot<AccountId>;
+	type MonetaryGovernanceOrigin = StakingAdminOrRoot;
+	/// Total issuance the staking rewards taper off towards
+	type MaxTotalIssuance = ConstU128<{ crate::staking::MAX_TOTAL_ISSUANCE }>;
 	/// Minimum round length is 2 minutes (10 * 12 second block times)
 	type MinBlocksPerRound = ConstU32<10>;
 	/// Rounds before the collator leaving the candidates request can be executed
 	type LeaveCandidatesDelay = LeaveDelayRounds;
+	/// Last round before a scheduled collator exit in which it can no longer be cancelled
+	type LeaveCancellationCutoff = ConstU32<1>;
 	/// Rounds before the candidate bond increase/decrease can be executed
 	type CandidateBondLessDelay = LeaveDelayRounds;
 	/// Rounds before the delegator exit can be executed
@@ -703,10 +907,64 @@ impl pallet_parachain_staking::Config for Runtime {
 	type AccountIdOf = IdentityCollator;
 	type MaxInvulnerables = ConstU32<10>;
 	type ValidatorRegistration = Session;
-	type UpdateOrigin = EnsureRoot<AccountId>;
-	type OnCollatorPayout = ();
-	type OnNewRound = ();
-	type WeightInfo = ();
+	type UpdateOrigin = StakingAdminOrRoot;
+	type OnCollatorPayout = crate::impls::CollatorPayoutToTreasury<Runtime>;
+	/// Report collators jailed by the DKG to the offences pallet, distribute the wrapping fees
+	/// of the last round and record the stakes voting power is read from at each round change
+	type OnNewRound = (
+		(crate::impls::ReportJailedDkgAuthorities, crate::impls::RewardDkgReputation),
+		(
+			(crate::impls::DistributeWrappingFees, crate::impls::EndVAnchorRewardsEpoch),
+			crate::impls::SnapshotVotingPower,
+		),
+	);
+	type OnDelegationKicked = ();
+	/// Bond slashed for DKG misbehaviour and emergency unbond penalties go to the treasury
+	type Slash = Treasury;
+	type EmergencyUnbondPenalty = EmergencyUnbondPenalty;
+	/// Rounds governance has to cancel a slash reported by mistake
+	type SlashDeferDuration = ConstU32<{ crate::staking::SLASH_DEFER_DURATION_ROUNDS }>;
+	type SlashCancelOrigin = EnsureRoot<AccountId>;
+	type MaxDeferredSlashesPerRound = ConstU32<{ crate::staking::MAX_DEFERRED_SLASHES_PER_ROUND }>;
+	/// Pause staking calls when `Total` falls by more than a quarter in one block, or a round
+	/// pays out more than its staking reward
+	type MaxTotalDropPerBlock = MaxTotalStakeDropPerBlock;
+	type CircuitBreaker = crate::impls::PauseStakingCalls;
+	/// Rounds a selected collator may author no blocks before being marked offline
+	type MaxOfflineRounds = ConstU32<3>;
+	/// Delegations keep earning until their revoke or decrease is executed, as they still back
+	/// their collator
+	type PendingRequestRewards = PendingRequestRewards;
+	/// Rounds an executable delegation request may wait before it is cancelled
+	type RequestExpiryRounds = ConstU32<{ crate::staking::REQUEST_EXPIRY_ROUNDS }>;
+	type CollatorLiveness = pallet_parachain_staking::AuthoredBlocks<Runtime>;
+	type DeferredDelegationThreshold = ConstU128<{ crate::staking::DEFERRED_DELEGATION_THRESHOLD }>;
+	type MaxRewardHistoryRounds = ConstU32<{ crate::staking::MAX_REWARD_HISTORY_ROUNDS }>;
+	type MaxExposurePageSize = ConstU32<{ crate::staking::MAX_EXPOSURE_PAGE_SIZE }>;
+	/// Points for authoring any block, plus up to as many again for a full block
+	type BasePointsPerBlock = ConstU32<20>;
+	type MaxBonusPointsPerBlock = ConstU32<20>;
+	/// Relay chain and sibling parachain accounts delegate from their hashed local account
+	type RemoteDelegatorOrigin = crate::impls::EnsureXcmAccount<
+		xcm_config::RemoteDelegators,
+		xcm_config::LocationToAccountId,
+	>;
+	/// Accounts a collator may share its commission and self bond rewards with
+	type MaxPayoutSplitShares = ConstU32<10>;
+	/// Damp the rewards of collators backed by more stake, so delegating to smaller collators pays
+	type RewardCurve = pallet_parachain_staking::SqrtExposure;
+	/// Delegators may have their rewards reserve transferred to an account on another chain
+	type RemitReward = xcm_config::RemitRewardViaXcm;
+	type RemitRewardsEvery = ConstU32<{ crate::staking::REMIT_REWARDS_EVERY_ROUNDS }>;
+	/// Spread the election of the next round's collators over the blocks before the round ends
+	type ElectionLookahead = ConstU32<{ crate::staking::ELECTION_LOOKAHEAD_BLOCKS }>;
+	type ElectionPageSize = ConstU32<{ crate::staking::ELECTION_PAGE_SIZE }>;
+	/// Let funds still vesting, e.g. those of claimants of `Claims`, be staked
+	type VestingBalance = pallet_parachain_staking::VestingScheduleBalance<Vesting>;
+	type SessionKeys = pallet_parachain_staking::PalletSessionKeys<Runtime>;
+	type RelayBlockNumber = cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Self>;
+	type TreasuryAccount = TreasuryAccount;
+	type WeightInfo = pallet_parachain_staking::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_author_inherent::Config for Runtime {
@@ -768,13 +1026,151 @@ where
 	type Extrinsic = UncheckedExtrinsic;
 }
 
+/// Filters out the calls paused through `TransactionPause`, and direct vanchor transactions, which
+/// are made through `VAnchorLimits::transact` to be checked against the limits of their tree
+pub struct BaseFilter;
+impl Contains<RuntimeCall> for BaseFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::VAnchorBn254(pallet_vanchor::Call::transact { .. })) &&
+			!pallet_transaction_pause::PausedTransactionFilter::<Runtime>::contains(call)
+	}
+}
+
+/// Pallets paused together by `TransactionPause::pause_bridge`: the signature bridge and the
+/// handlers of the proposals it executes
+pub struct BridgePallets;
+impl frame_support::traits::Get<Vec<&'static str>> for BridgePallets {
+	fn get() -> Vec<&'static str> {
+		use frame_support::traits::PalletInfoAccess;
+		vec![
+			<SignatureBridge as PalletInfoAccess>::name(),
+			<VAnchorHandlerBn254 as PalletInfoAccess>::name(),
+			<TokenWrapperHandler as PalletInfoAccess>::name(),
+		]
+	}
+}
+
+/// Groups of calls paused together by `TransactionPause::pause_group`
+#[derive(
+	Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug, scale_info::TypeInfo,
+)]
+pub enum PauseGroup {
+	/// Every call of `VAnchorBn254`
+	VAnchorBn254,
+	/// Every call of `MixerBn254`
+	MixerBn254,
+	/// The staking calls joining the candidates or delegating to them, and claims delegating the
+	/// claimed funds
+	StakingJoins,
+	/// The staking calls bonding funds, i.e. `StakingJoins` and the calls bonding more. Calls
+	/// unbonding, exiting or cancelling requests are not part of it.
+	StakingBonds,
+}
+
+impl pallet_transaction_pause::CallGroup<RuntimeCall> for PauseGroup {
+	fn contains(&self, call: &RuntimeCall) -> bool {
+		use pallet_parachain_staking::Call as StakingCall;
+		match self {
+			PauseGroup::VAnchorBn254 => matches!(call, RuntimeCall::VAnchorBn254(_)),
+			PauseGroup::MixerBn254 => matches!(call, RuntimeCall::MixerBn254(_)),
+			PauseGroup::StakingJoins => matches!(
+				call,
+				RuntimeCall::ParachainStaking(
+					StakingCall::join_candidates { .. } |
+						StakingCall::delegate { .. } |
+						StakingCall::delegate_with_auto_compound { .. } |
+						StakingCall::delegate_with_memo { .. } |
+						StakingCall::delegate_via_xcm { .. } |
+						StakingCall::delegate_spread { .. }
+				) | RuntimeCall::Claims(pallet_ecdsa_claims::Call::claim_and_delegate { .. })
+			),
+			PauseGroup::StakingBonds =>
+				PauseGroup::StakingJoins.contains(call) ||
+					matches!(
+						call,
+						RuntimeCall::ParachainStaking(
+							StakingCall::candidate_bond_more { .. } |
+								StakingCall::candidate_bond_more_for { .. } |
+								StakingCall::delegator_bond_more { .. }
+						)
+					),
+		}
+	}
+}
+
+/// The calls `Utility` dispatches on behalf of the origin of a batch or a derivative call, those
+/// `Recovery` dispatches on behalf of a recovered account and those `Proxy` dispatches on behalf
+/// of a proxied account
+pub struct DispatchedNestedCalls;
+impl pallet_transaction_pause::NestedCalls<RuntimeCall> for DispatchedNestedCalls {
+	fn nested_calls(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+		match call {
+			RuntimeCall::Utility(pallet_utility::Call::batch { calls }) |
+			RuntimeCall::Utility(pallet_utility::Call::batch_all { calls }) |
+			RuntimeCall::Utility(pallet_utility::Call::force_batch { calls }) => calls.iter().collect(),
+			RuntimeCall::Utility(pallet_utility::Call::as_derivative { call, .. }) |
+			RuntimeCall::Utility(pallet_utility::Call::dispatch_as { call, .. }) |
+			RuntimeCall::Recovery(pallet_recovery::Call::as_recovered { call, .. }) |
+			RuntimeCall::Proxy(pallet_proxy::Call::proxy { call, .. }) |
+			RuntimeCall::Proxy(pallet_proxy::Call::proxy_announced { call, .. }) => vec![&**call],
+			_ => Vec::new(),
+		}
+	}
+}
+
 impl pallet_transaction_pause::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type UpdateOrigin = EnsureRoot<AccountId>;
+	// half of the council can stop the bridge without waiting for a referendum
+	type BridgePauseOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>,
+	>;
+	type BridgePallets = BridgePallets;
+	type Group = PauseGroup;
+	type NestedCalls = DispatchedNestedCalls;
 	type WeightInfo = ();
 }
 
 parameter_types! {
+	pub TreasuryAccount: AccountId = Treasury::account_id();
+}
+
+/// Referendum votes and council motion votes
+pub struct GovernanceVotes;
+impl Contains<RuntimeCall> for GovernanceVotes {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::Democracy(pallet_democracy::Call::vote { .. }) |
+				RuntimeCall::Council(pallet_collective::Call::vote { .. })
+		)
+	}
+}
+
+impl pallet_stake_snapshot::Config for Runtime {
+	type Balance = Balance;
+	type HistoryDepth = ConstU32<{ crate::staking::VOTING_POWER_HISTORY_ROUNDS }>;
+}
+
+impl pallet_governance_rebates::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type AssetId = webb_primitives::AssetId;
+	type Assets = Tokens;
+	type AssetConversion = AssetRates;
+	type RebateSource = TreasuryAccount;
+	type RebatableCalls = GovernanceVotes;
+	type BudgetPeriod = ConstU32<{ 7 * DAYS }>;
+	type BudgetPerPeriod = ConstU128<{ 1_000 * DOLLAR }>;
+	type MaxRebatesPerAccount = ConstU32<20>;
+	type WeightInfo = pallet_governance_rebates::weights::WebbWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Whether `ImOnline` is handed the session validators, off until governance switches it on
+	/// with `System::set_storage` at `ImOnlineEnabled::key()`. Takes effect from the next session.
+	pub storage ImOnlineEnabled: bool = false;
 	pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
 	pub const MaxKeys: u32 = 10_000;
 	pub const MaxPeerInHeartbeats: u32 = 10_000;
@@ -784,7 +1180,7 @@ parameter_types! {
 impl pallet_im_online::Config for Runtime {
 	type AuthorityId = ImOnlineId;
 	type RuntimeEvent = RuntimeEvent;
-	type NextSessionRotation = pallet_dkg_metadata::DKGPeriodicSessions<Period, Offset, Runtime>;
+	type NextSessionRotation = SessionSchedule;
 	type ValidatorSet = Historical;
 	type ReportUnresponsiveness = ();
 	type UnsignedPriority = ImOnlineUnsignedPriority;
@@ -794,6 +1190,139 @@ impl pallet_im_online::Config for Runtime {
 	type MaxPeerDataEncodingSize = MaxPeerDataEncodingSize;
 }
 
+parameter_types! {
+	/// Share of a collator's self bond slashed when the DKG jails it for misbehaviour
+	pub const DkgMisbehaviourSlash: Perbill = Perbill::from_percent(1);
+	pub const MaxTotalStakeDropPerBlock: Percent = Percent::from_percent(25);
+}
+
+impl pallet_offences::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type IdentificationTuple = pallet_session_historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ParachainStaking;
+}
+
+/// Gas that fits in one second of block execution, as on Ethereum mainnet
+pub const GAS_PER_SECOND: u64 = 40_000_000;
+/// Approximate weight per gas, so EVM execution is metered like the rest of the block
+pub const WEIGHT_PER_GAS: u64 = WEIGHT_PER_SECOND.ref_time() / GAS_PER_SECOND;
+
+parameter_types! {
+	/// EIP-155 chain id of the Tangle EVM
+	pub const EVMChainId: u64 = 4006;
+	pub BlockGasLimit: U256 =
+		U256::from(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT.ref_time() / WEIGHT_PER_GAS);
+	// 1 gwei
+	pub MinGasPrice: U256 = U256::from(1_000_000_000u64);
+	pub PrecompilesValue: TanglePrecompiles<Runtime> = TanglePrecompiles::<_>::new();
+	pub WeightPerGas: Weight = Weight::from_ref_time(WEIGHT_PER_GAS);
+}
+
+impl pallet_evm::Config for Runtime {
+	type FeeCalculator = FixedGasPrice;
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
+	// EVM calls from a substrate account are made from the first 20 bytes of the account
+	type CallOrigin = EnsureAddressTruncated;
+	type WithdrawOrigin = EnsureAddressTruncated;
+	// an EVM address owns the substrate account at the hash of the address
+	type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type PrecompilesType = TanglePrecompiles<Self>;
+	type PrecompilesValue = PrecompilesValue;
+	type ChainId = EVMChainId;
+	type BlockGasLimit = BlockGasLimit;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type OnChargeTransaction = ();
+	type FindAuthor = FindAuthorTruncated<pallet_session::FindAccountFromAuthorIndex<Self, Aura>>;
+}
+
+impl pallet_ethereum::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self>;
+}
+
+impl fp_self_contained::SelfContainedCall for RuntimeCall {
+	type SignedInfo = H160;
+
+	fn is_self_contained(&self) -> bool {
+		match self {
+			RuntimeCall::Ethereum(call) => call.is_self_contained(),
+			_ => false,
+		}
+	}
+
+	fn check_self_contained(&self) -> Option<Result<Self::SignedInfo, TransactionValidityError>> {
+		match self {
+			RuntimeCall::Ethereum(call) => call.check_self_contained(),
+			_ => None,
+		}
+	}
+
+	fn validate_self_contained(
+		&self,
+		info: &Self::SignedInfo,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		len: usize,
+	) -> Option<TransactionValidity> {
+		match self {
+			RuntimeCall::Ethereum(call) => call.validate_self_contained(info, dispatch_info, len),
+			_ => None,
+		}
+	}
+
+	fn pre_dispatch_self_contained(
+		&self,
+		info: &Self::SignedInfo,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		len: usize,
+	) -> Option<Result<(), TransactionValidityError>> {
+		match self {
+			RuntimeCall::Ethereum(call) =>
+				call.pre_dispatch_self_contained(info, dispatch_info, len),
+			_ => None,
+		}
+	}
+
+	fn apply_self_contained(
+		self,
+		info: Self::SignedInfo,
+	) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
+		match self {
+			call @ RuntimeCall::Ethereum(pallet_ethereum::Call::transact { .. }) =>
+				Some(call.dispatch(RuntimeOrigin::from(
+					pallet_ethereum::RawOrigin::EthereumTransaction(info),
+				))),
+			_ => None,
+		}
+	}
+}
+
+/// Wraps Ethereum transactions submitted through the Ethereum RPC into unsigned extrinsics
+#[derive(Clone)]
+pub struct TransactionConverter;
+
+impl fp_rpc::ConvertTransaction<UncheckedExtrinsic> for TransactionConverter {
+	fn convert_transaction(&self, transaction: EthereumTransaction) -> UncheckedExtrinsic {
+		UncheckedExtrinsic::new_unsigned(
+			pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+		)
+	}
+}
+
+impl fp_rpc::ConvertTransaction<opaque::UncheckedExtrinsic> for TransactionConverter {
+	fn convert_transaction(&self, transaction: EthereumTransaction) -> opaque::UncheckedExtrinsic {
+		let extrinsic = UncheckedExtrinsic::new_unsigned(
+			pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+		);
+		let encoded = extrinsic.encode();
+		opaque::UncheckedExtrinsic::decode(&mut &encoded[..])
+			.expect("Encoded extrinsic is always valid")
+	}
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -811,6 +1340,7 @@ construct_runtime!(
 		DKG: pallet_dkg_metadata::{Pallet, Storage, Call, Event<T>, Config<T>, ValidateUnsigned} = 10,
 		DKGProposals: pallet_dkg_proposals = 11,
 		DKGProposalHandler: pallet_dkg_proposal_handler = 12,
+		SignedProposalLog: pallet_signed_proposal_log::{Pallet, Storage} = 13,
 
 		// Monetary stuff
 		Sudo: pallet_sudo::{Pallet, Call, Storage, Config<T>, Event<T>} = 20,
@@ -832,22 +1362,30 @@ construct_runtime!(
 		Aura: pallet_aura::{Pallet, Storage, Config<T>} = 33,
 		//AuraExt: cumulus_pallet_aura_ext::{Pallet, Storage, Config} = 34,
 		Historical: pallet_session_historical::{Pallet} = 35,
+		Offences: pallet_offences::{Pallet, Storage, Event} = 36,
 
 		// XCM helpers.
 		XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 40,
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin} = 41,
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 42,
 		DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 43,
+		HrmpManager: pallet_hrmp_manager::{Pallet, Call, Event<T>} = 44,
 
 		// Asset helpers
 		AssetRegistry: pallet_asset_registry::{Pallet, Call, Storage, Event<T>, Config<T>} = 50,
 		Currencies: orml_currencies::{Pallet, Call} = 51,
 		Tokens: orml_tokens::{Pallet, Storage, Call, Event<T>} = 52,
 		TokenWrapper: pallet_token_wrapper::{Pallet, Storage, Call, Event<T>} = 53,
+		AssetTxPayment: pallet_asset_tx_payment::{Pallet, Event<T>} = 54,
+		AssetRates: pallet_asset_rates::{Pallet, Call, Storage, Event<T>} = 55,
 
 		// Privacy pallets
+		RootHistory: pallet_root_history::{Pallet, Storage} = 57,
+		VerifierRotation: pallet_verifier_rotation::{Pallet, Call, Storage, Event<T>} = 58,
+		VAnchorLimits: pallet_vanchor_limits::{Pallet, Call, Storage, Event<T>} = 59,
 		HasherBn254: pallet_hasher::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 60,
 		MixerVerifierBn254: pallet_verifier::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 61,
+		VAnchorRewards: pallet_vanchor_rewards::{Pallet, Call, Storage, Event<T>} = 62,
 		MerkleTreeBn254: pallet_mt::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 63,
 		LinkableTreeBn254: pallet_linkable_tree::<Instance1>::{Pallet, Call, Storage, Event<T>} = 64,
 		MixerBn254: pallet_mixer::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 65,
@@ -860,6 +1398,14 @@ construct_runtime!(
 		SignatureBridge: pallet_signature_bridge::<Instance1>::{Pallet, Call, Storage, Event<T>} = 70,
 		TokenWrapperHandler: pallet_token_wrapper_handler::{Pallet, Storage, Call, Event<T>} = 71,
 
+		// Privacy pallets over BLS12-381
+		HasherBls381: pallet_hasher::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 72,
+		MixerVerifierBls381: pallet_verifier::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 73,
+		MerkleTreeBls381: pallet_mt::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 74,
+		LinkableTreeBls381: pallet_linkable_tree::<Instance2>::{Pallet, Call, Storage, Event<T>} = 75,
+		VAnchorVerifierBls381: pallet_vanchor_verifier::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 76,
+		VAnchorBls381: pallet_vanchor::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 77,
+
 		// Substrate utility pallets
 		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>} = 80,
 		Utility: pallet_utility::{Pallet, Call, Event} = 81,
@@ -869,7 +1415,27 @@ construct_runtime!(
 		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>} = 85,
 		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>} = 86,
 		TransactionPause: pallet_transaction_pause::{Pallet, Call, Storage, Event<T>} = 87,
+		// Liveness is tracked by `ParachainStaking` through authored blocks. Heartbeats are only
+		// built and accepted while `ImOnlineEnabled` is set.
 		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 88,
+		GovernanceRebates: pallet_governance_rebates::{Pallet, Storage, Event<T>} = 89,
+
+		// OpenGov
+		ConvictionVoting: pallet_conviction_voting::{Pallet, Call, Storage, Event<T>} = 90,
+		Referenda: pallet_referenda::{Pallet, Call, Storage, Event<T>} = 91,
+		Origins: pallet_custom_origins::{Origin} = 92,
+		Whitelist: pallet_whitelist::{Pallet, Call, Storage, Event<T>} = 93,
+		StakeSnapshot: pallet_stake_snapshot::{Pallet, Storage} = 94,
+
+		// Account recovery
+		Recovery: pallet_recovery::{Pallet, Call, Storage, Event<T>} = 95,
+
+		// Proxy accounts
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 96,
+
+		// EVM
+		EVM: pallet_evm::{Pallet, Config, Call, Storage, Event<T>} = 100,
+		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, Origin} = 101,
 	}
 );
 
@@ -980,7 +1546,7 @@ impl_runtime_apis! {
 
 		fn get_current_session_progress(block_number: BlockNumber) -> Option<Permill> {
 			use frame_support::traits::EstimateNextSessionRotation;
-			<pallet_dkg_metadata::DKGPeriodicSessions<Period, Offset, Runtime> as EstimateNextSessionRotation<BlockNumber>>::estimate_current_session_progress(block_number).0
+			<SessionSchedule as EstimateNextSessionRotation<BlockNumber>>::estimate_current_session_progress(block_number).0
 		}
 
 		fn get_unsigned_proposals() -> Vec<UnsignedProposal> {
@@ -1081,6 +1647,158 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			<Runtime as pallet_evm::Config>::ChainId::get()
+		}
+
+		fn account_basic(address: H160) -> EVMAccount {
+			let (account, _) = EVM::account_basic(&address);
+			account
+		}
+
+		fn gas_price() -> U256 {
+			let (gas_price, _) = <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+			gas_price
+		}
+
+		fn account_code_at(address: H160) -> Vec<u8> {
+			EVM::account_codes(address)
+		}
+
+		fn author() -> H160 {
+			<pallet_evm::Pallet<Runtime>>::find_author()
+		}
+
+		fn storage_at(address: H160, index: U256) -> H256 {
+			let mut tmp = [0u8; 32];
+			index.to_big_endian(&mut tmp);
+			EVM::account_storages(address, H256::from_slice(&tmp[..]))
+		}
+
+		fn call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
+			let config = if estimate {
+				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				config.estimate = true;
+				Some(config)
+			} else {
+				None
+			};
+
+			let is_transactional = false;
+			let validate = true;
+			<Runtime as pallet_evm::Config>::Runner::call(
+				from,
+				to,
+				data,
+				value,
+				gas_limit.unique_saturated_into(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				is_transactional,
+				validate,
+				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
+			).map_err(|err| err.error.into())
+		}
+
+		fn create(
+			from: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
+			let config = if estimate {
+				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				config.estimate = true;
+				Some(config)
+			} else {
+				None
+			};
+
+			let is_transactional = false;
+			let validate = true;
+			<Runtime as pallet_evm::Config>::Runner::create(
+				from,
+				data,
+				value,
+				gas_limit.unique_saturated_into(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				is_transactional,
+				validate,
+				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
+			).map_err(|err| err.error.into())
+		}
+
+		fn current_transaction_statuses() -> Option<Vec<TransactionStatus>> {
+			Ethereum::current_transaction_statuses()
+		}
+
+		fn current_block() -> Option<pallet_ethereum::Block> {
+			Ethereum::current_block()
+		}
+
+		fn current_receipts() -> Option<Vec<pallet_ethereum::Receipt>> {
+			Ethereum::current_receipts()
+		}
+
+		fn current_all() -> (
+			Option<pallet_ethereum::Block>,
+			Option<Vec<pallet_ethereum::Receipt>>,
+			Option<Vec<TransactionStatus>>,
+		) {
+			(
+				Ethereum::current_block(),
+				Ethereum::current_receipts(),
+				Ethereum::current_transaction_statuses(),
+			)
+		}
+
+		fn extrinsic_filter(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+		) -> Vec<EthereumTransaction> {
+			xts.into_iter().filter_map(|xt| match xt.0.function {
+				RuntimeCall::Ethereum(transact { transaction }) => Some(transaction),
+				_ => None,
+			}).collect::<Vec<EthereumTransaction>>()
+		}
+
+		fn elasticity() -> Option<Permill> {
+			// the gas price is fixed
+			None
+		}
+
+		fn gas_limit_multiplier_support() {}
+	}
+
+	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
+		fn convert_transaction(transaction: EthereumTransaction) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+			)
+		}
+	}
+
 	impl pallet_linkable_tree_rpc_runtime_api::LinkableTreeApi<Block, ChainId, Element, LeafIndex> for Runtime {
 		fn get_neighbor_roots(tree_id: u32) -> Vec<Element> {
 			LinkableTreeBn254::get_neighbor_roots(tree_id).ok().unwrap_or_default()
@@ -1102,6 +1820,53 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl tangle_relayer_runtime_api::RelayerApi<Block, Element, BlockNumber> for Runtime {
+		fn sync_state(block_number: BlockNumber) -> tangle_relayer_runtime_api::RelayerSyncState<Element> {
+			use frame_support::traits::EstimateNextSessionRotation;
+			let mut anchor_roots: Vec<(u32, Element)> =
+				pallet_mt::Trees::<Runtime, pallet_mt::Instance1>::iter()
+					.map(|(tree_id, tree)| (tree_id, tree.root))
+					.collect();
+			anchor_roots.sort_by_key(|(tree_id, _)| *tree_id);
+			tangle_relayer_runtime_api::RelayerSyncState {
+				anchor_roots,
+				dkg_public_key: DKG::dkg_public_key(),
+				refresh_nonce: DKG::refresh_nonce(),
+				unsigned_proposal_count: DKGProposalHandler::get_unsigned_proposals().len() as u32,
+				session_progress: <SessionSchedule as EstimateNextSessionRotation<BlockNumber>>::estimate_current_session_progress(block_number).0,
+			}
+		}
+	}
+
+	impl tangle_relayer_runtime_api::RootHistoryApi<Block, Element, ChainId, BlockNumber> for Runtime {
+		fn root_history(tree_id: u32, limit: u32) -> tangle_relayer_runtime_api::RootHistory<Element, ChainId, BlockNumber> {
+			let (roots, neighbor_roots) = RootHistory::recent_roots(tree_id, limit);
+			tangle_relayer_runtime_api::RootHistory { roots, neighbor_roots }
+		}
+	}
+
+	impl tangle_relayer_runtime_api::MerkleTreeLeavesApi<Block, Element> for Runtime {
+		fn get_leaves(tree_id: u32, from: u32, to: u32) -> Vec<Element> {
+			const MAX_LEAVES: u32 = 1_024;
+			(from..to.min(from.saturating_add(MAX_LEAVES)))
+				.map(|index| MerkleTreeBn254::leaves(tree_id, index))
+				.take_while(|leaf| *leaf != Element::default())
+				.collect()
+		}
+	}
+
+	impl tangle_relayer_runtime_api::SignedProposalsApi<Block, dkg_runtime_primitives::Proposal, BlockNumber> for Runtime {
+		fn signed_proposals(
+			since: BlockNumber,
+			cursor: u64,
+			limit: u32,
+		) -> tangle_relayer_runtime_api::SignedProposalsPage<dkg_runtime_primitives::Proposal, BlockNumber> {
+			const MAX_PAGE_SIZE: u32 = 100;
+			let (proposals, next) = SignedProposalLog::signed_since(since, cursor, limit.min(MAX_PAGE_SIZE));
+			tangle_relayer_runtime_api::SignedProposalsPage { proposals, next }
+		}
+	}
+
 	impl nimbus_primitives::NimbusApi<Block> for Runtime {
 		fn can_author(author: NimbusId, relay_parent: u32, parent_header: &<Block as BlockT>::Header) -> bool {
 			use pallet_session::ShouldEndSession;
@@ -1111,7 +1876,7 @@ impl_runtime_apis! {
 			// of the first block in the new round, the only way to accurately predict the
 			// authors is to compute the selection during prediction.
 			// NOTE: This logic must manually be kept in sync with the nimbus filter pipeline
-			if pallet_dkg_metadata::DKGPeriodicSessions::<Period, Offset, Runtime>::should_end_session(next_block_number)
+			if <Runtime as pallet_session::Config>::ShouldEndSession::should_end_session(next_block_number)
 			{
 				// lookup account from nimbusId
 				// mirrors logic in `pallet_author_inherent`
@@ -1124,10 +1889,8 @@ impl_runtime_apis! {
 					}
 				};
 				// manually check aura eligibility (in the new round)
-				// mirrors logic in `aura_style_filter`
-				let truncated_half_slot = (slot >> 1) as usize;
 				let active: Vec<AccountId> = pallet_parachain_staking::Pallet::<Self>::compute_top_candidates();
-				account == active[truncated_half_slot % active.len()]
+				crate::staking::aura_style_author(slot.into(), &active) == Some(account)
 			} else {
 				// We're not changing rounds, `PotentialAuthors` is not changing, just use can_author
 				<AuthorInherent as nimbus_primitives::CanAuthor<_>>::can_author(&author, &relay_parent)
@@ -1135,6 +1898,192 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_transaction_pause_rpc_runtime_api::TransactionPauseApi<Block> for Runtime {
+		fn paused_calls() -> Vec<(Vec<u8>, Vec<u8>)> {
+			TransactionPause::paused_calls()
+		}
+
+		fn is_paused(pallet_name: Vec<u8>, function_name: Vec<u8>) -> bool {
+			TransactionPause::paused_transactions((pallet_name, function_name)).is_some()
+		}
+	}
+
+	impl pallet_stake_snapshot_rpc_runtime_api::StakeSnapshotApi<Block, AccountId, Balance> for Runtime {
+		fn voting_power(account: AccountId, round: u32) -> Option<Balance> {
+			StakeSnapshot::voting_power(&account, round)
+		}
+
+		fn total_voting_power(round: u32) -> Option<Balance> {
+			StakeSnapshot::total_staked(round)
+		}
+	}
+
+	impl pallet_parachain_staking_rpc_runtime_api::ParachainStakingApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn authorship_schedule(from_slot: u64, count: u32) -> Vec<(u64, AccountId)> {
+			let active: Vec<AccountId> = ParachainStaking::selected_candidates();
+			let count = count.min(crate::staking::MAX_AUTHORSHIP_SCHEDULE_SLOTS);
+			(from_slot..from_slot.saturating_add(count.into()))
+				.filter_map(|slot| {
+					crate::staking::aura_style_author(slot, &active).map(|author| (slot, author))
+				})
+				.collect()
+		}
+
+		fn pending_and_historic_rewards(account: AccountId) -> (Balance, Vec<(u32, Balance)>) {
+			(
+				ParachainStaking::pending_rewards(&account),
+				ParachainStaking::reward_history(&account).into_inner(),
+			)
+		}
+
+		fn unbonding_eta(account: AccountId, candidate: AccountId) -> Option<(BlockNumber, u64)> {
+			let eta = ParachainStaking::unbonding_eta(&account, &candidate)?;
+			let remaining_blocks = eta.saturating_sub(System::block_number());
+			let timestamp = Timestamp::now()
+				.saturating_add(Moment::from(remaining_blocks).saturating_mul(MILLISECS_PER_BLOCK));
+			Some((eta, timestamp))
+		}
+
+		fn account_balances(
+			account: AccountId,
+		) -> pallet_parachain_staking_rpc_runtime_api::AccountBalances<Balance> {
+			use frame_support::traits::VestingSchedule;
+			use pallet_parachain_staking::{COLLATOR_LOCK_ID, DELEGATOR_LOCK_ID};
+
+			let data = System::account(&account).data;
+			let (staked, pending_unbond) = ParachainStaking::staked_and_unbonding(&account);
+			// stake of accounts that could not be moved from the staking locks to holds, and stake
+			// locked while vesting, is still in the free balance
+			let locked_stake = Balances::locks(&account)
+				.iter()
+				.filter(|lock| lock.id == COLLATOR_LOCK_ID || lock.id == DELEGATOR_LOCK_ID)
+				.fold(ParachainStaking::vested_stake(&account), |total: Balance, lock| {
+					total.saturating_add(lock.amount)
+				});
+			let held_stake = staked.saturating_sub(locked_stake);
+			pallet_parachain_staking_rpc_runtime_api::AccountBalances {
+				free: data.free,
+				transferable: data.free.saturating_sub(data.misc_frozen.max(data.fee_frozen)),
+				vesting: Vesting::vesting_balance(&account).unwrap_or_default(),
+				staked,
+				pending_unbond,
+				reserved: data.reserved.saturating_sub(held_stake),
+			}
+		}
+
+		fn round_progress() -> pallet_parachain_staking_rpc_runtime_api::RoundProgress<BlockNumber> {
+			use frame_support::traits::EstimateNextSessionRotation;
+			// rounds start with sessions, mirrors the round change check in `can_author`
+			let now = System::block_number();
+			let round = ParachainStaking::round();
+			let next_election = SessionSchedule::estimate_next_session_rotation(now)
+				.0
+				.unwrap_or_else(|| round.first.saturating_add(round.length));
+			pallet_parachain_staking_rpc_runtime_api::RoundProgress {
+				round: round.current,
+				elapsed: now.saturating_sub(round.first),
+				remaining: next_election.saturating_sub(now),
+				next_election,
+			}
+		}
+
+		fn candidate_rankings(
+		) -> Vec<pallet_parachain_staking_rpc_runtime_api::CandidateRanking<AccountId, Balance>> {
+			let commission = ParachainStaking::collator_commission();
+			ParachainStaking::candidate_rankings()
+				.into_iter()
+				.map(|(candidate, total_backing, last_round_points, delegation_slots_remaining)| {
+					pallet_parachain_staking_rpc_runtime_api::CandidateRanking {
+						estimated_apr: ParachainStaking::estimated_apr(&candidate),
+						candidate,
+						total_backing,
+						last_round_points,
+						commission,
+						delegation_slots_remaining,
+					}
+				})
+				.collect()
+		}
+
+		fn delegation_position(
+			candidate: AccountId,
+			delegator: AccountId,
+		) -> Option<pallet_parachain_staking_rpc_runtime_api::DelegationPosition> {
+			use pallet_parachain_staking::DelegationPosition;
+			use pallet_parachain_staking_rpc_runtime_api::DelegationPosition as ApiPosition;
+			ParachainStaking::delegation_position(&candidate, &delegator).map(|position| {
+				match position {
+					DelegationPosition::Top => ApiPosition::Top,
+					DelegationPosition::Bottom => ApiPosition::Bottom,
+				}
+			})
+		}
+
+		fn collator_metrics(
+			collator: Option<AccountId>,
+		) -> pallet_parachain_staking_rpc_runtime_api::CollatorMetrics {
+			let (rank, delegation_count) = match collator {
+				Some(collator) => (
+					ParachainStaking::candidate_rankings()
+						.iter()
+						.position(|(candidate, ..)| *candidate == collator)
+						.map(|index| index as u32 + 1),
+					ParachainStaking::candidate_info(&collator).map(|info| info.delegation_count),
+				),
+				None => (None, None),
+			};
+			pallet_parachain_staking_rpc_runtime_api::CollatorMetrics {
+				round: ParachainStaking::round().current,
+				selected_candidates: ParachainStaking::selected_candidates().len() as u32,
+				rank,
+				delegation_count,
+				pending_payouts: ParachainStaking::pending_payouts(),
+			}
+		}
+
+		fn parachain_bond_funds(
+		) -> pallet_parachain_staking_rpc_runtime_api::ParachainBondFunds<AccountId, Balance> {
+			let bond = ParachainStaking::parachain_bond_info();
+			pallet_parachain_staking_rpc_runtime_api::ParachainBondFunds {
+				balance: Balances::free_balance(&bond.account),
+				account: bond.account,
+				percent: bond.percent,
+				total_reserved: ParachainStaking::total_reserved_for_parachain_bond(),
+			}
+		}
+
+		fn estimate_round_payouts(
+			round: u32,
+		) -> Vec<pallet_parachain_staking_rpc_runtime_api::RoundPayoutEstimate<AccountId, Balance>> {
+			ParachainStaking::estimate_round_payouts(round)
+				.into_iter()
+				.map(|payout| pallet_parachain_staking_rpc_runtime_api::RoundPayoutEstimate {
+					collator: payout.collator,
+					collator_reward: payout.collator_reward,
+					cut: payout.cut,
+					split_rewards: payout.split_rewards,
+					delegator_rewards: payout.delegator_rewards,
+				})
+				.collect()
+		}
+
+		fn pending_call(
+			call_hash: [u8; 32],
+		) -> Option<pallet_parachain_staking_rpc_runtime_api::PendingCall> {
+			let call_hash = Hash::from(call_hash);
+			if let Some(proposal) = Council::proposal_of(call_hash) {
+				return Some(pallet_parachain_staking_rpc_runtime_api::PendingCall::Proposal(
+					proposal.encode(),
+				))
+			}
+			pallet_proxy::Announcements::<Runtime>::iter_values()
+				.any(|(announcements, _)| {
+					announcements.iter().any(|announcement| announcement.call_hash == call_hash)
+				})
+				.then_some(pallet_parachain_staking_rpc_runtime_api::PendingCall::Announcement)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -1146,6 +2095,8 @@ impl_runtime_apis! {
 			use frame_support::traits::StorageInfoTrait;
 
 			use frame_system_benchmarking::Pallet as SystemBench;
+			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
 
 			let mut list = Vec::<BenchmarkList>::new();
 
@@ -1158,8 +2109,11 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_mt, MerkleTreeBn254);
 			list_benchmark!(list, extra, pallet_asset_registry, AssetRegistry);
 			list_benchmark!(list, extra, pallet_mixer, MixerBn254);
+			list_benchmark!(list, extra, pallet_parachain_staking, ParachainStaking);
 			list_orml_benchmark!(list, extra, orml_tokens, benchmarking::orml_tokens);
 			list_orml_benchmark!(list, extra, orml_currencies, benchmarking::orml_currencies);
+			list_benchmark!(list, extra, pallet_xcm_benchmarks::fungible, XcmBalances);
+			list_benchmark!(list, extra, pallet_xcm_benchmarks::generic, XcmGeneric);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -1174,6 +2128,62 @@ impl_runtime_apis! {
 			use frame_system_benchmarking::Pallet as SystemBench;
 			impl frame_system_benchmarking::Config for Runtime {}
 
+			use frame_benchmarking::BenchmarkError;
+			use xcm_config::{RelayLocation, SelfReserve};
+
+			impl pallet_xcm_benchmarks::Config for Runtime {
+				type XcmConfig = xcm_config::XcmConfig;
+				type AccountIdConverter = xcm_config::LocationToAccountId;
+				fn valid_destination() -> Result<MultiLocation, BenchmarkError> {
+					Ok(RelayLocation::get())
+				}
+				fn worst_case_holding() -> MultiAssets {
+					// the relay chain and native tokens, the ones held without the asset registry
+					vec![
+						MultiAsset { id: Concrete(RelayLocation::get()), fun: Fungible(u128::MAX) },
+						MultiAsset { id: Concrete(SelfReserve::get()), fun: Fungible(u128::MAX) },
+					]
+					.into()
+				}
+			}
+
+			parameter_types! {
+				pub const CheckedAccount: Option<AccountId> = None;
+				pub const TrustedTeleporter: Option<(MultiLocation, MultiAsset)> = None;
+			}
+
+			impl pallet_xcm_benchmarks::fungible::Config for Runtime {
+				type TransactAsset = Balances;
+				type CheckedAccount = CheckedAccount;
+				type TrustedTeleporter = TrustedTeleporter;
+				// the native token, which `Balances` transacts through `SelfReserveAssetTransactor`
+				fn get_multi_asset() -> MultiAsset {
+					MultiAsset { id: Concrete(SelfReserve::get()), fun: Fungible(DOLLAR) }
+				}
+			}
+
+			impl pallet_xcm_benchmarks::generic::Config for Runtime {
+				type RuntimeCall = RuntimeCall;
+				fn worst_case_response() -> (u64, Response) {
+					(0u64, Response::Version(Default::default()))
+				}
+				fn transact_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(RelayLocation::get())
+				}
+				fn subscribe_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(RelayLocation::get())
+				}
+				fn claimable_asset() -> Result<(MultiLocation, MultiLocation, MultiAssets), BenchmarkError> {
+					let origin = RelayLocation::get();
+					let assets: MultiAssets = (Concrete(RelayLocation::get()), DOLLAR).into();
+					let ticket = MultiLocation { parents: 0, interior: Here };
+					Ok((origin, ticket, assets))
+				}
+			}
+
+			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
 				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
@@ -1199,8 +2209,11 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_mt, MerkleTreeBn254);
 			add_benchmark!(params, batches, pallet_asset_registry, AssetRegistry);
 			add_benchmark!(params, batches, pallet_mixer, MixerBn254);
+			add_benchmark!(params, batches, pallet_parachain_staking, ParachainStaking);
 			add_orml_benchmark!(params, batches, orml_tokens, benchmarking::orml_tokens);
 			add_orml_benchmark!(params, batches, orml_currencies, benchmarking::orml_currencies);
+			add_benchmark!(params, batches, pallet_xcm_benchmarks::fungible, XcmBalances);
+			add_benchmark!(params, batches, pallet_xcm_benchmarks::generic, XcmGeneric);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -0,0 +1,104 @@
+// This file is part of Webb.
+
+// Copyright (C) 2022 Webb Technologies Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime specific migrations
+
+use crate::{AccountId, ParachainStaking, Runtime, Sudo};
+use frame_support::{
+	pallet_prelude::OptionQuery,
+	storage_alias,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+
+/// The sudo key, which `pallet_sudo` keeps private to the pallet
+#[storage_alias]
+type Key = StorageValue<Sudo, AccountId, OptionQuery>;
+
+/// Clears the sudo key and replaces the genesis invulnerables with the successors chosen by
+/// governance through `ParachainStaking::set_successor_invulnerables`. Does nothing until
+/// successors are chosen, so the chain is never left without invulnerables by an upgrade, and
+/// runs once, recorded by bumping the storage version of `pallet_sudo` to 1.
+pub struct RetireGenesisAccounts;
+impl OnRuntimeUpgrade for RetireGenesisAccounts {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		if Sudo::on_chain_storage_version() >= 1 {
+			return db_weight.reads(1)
+		}
+		if ParachainStaking::successor_invulnerables().is_none() {
+			log::info!("genesis accounts kept until successor invulnerables are chosen");
+			return db_weight.reads(2)
+		}
+		let key = Key::take();
+		StorageVersion::new(1).put::<Sudo>();
+		log::info!("retired sudo key {:?} and genesis invulnerables", key);
+		ParachainStaking::rotate_invulnerables().saturating_add(db_weight.reads_writes(3, 2))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::RuntimeOrigin;
+	use frame_support::{assert_ok, pallet_prelude::ValueQuery};
+	use sp_runtime::BuildStorage;
+
+	#[storage_alias]
+	type InvulnerableCandidates = StorageValue<ParachainStaking, Vec<AccountId>, ValueQuery>;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn genesis_accounts_are_retired_once_successors_are_chosen() {
+		new_test_ext().execute_with(|| {
+			let genesis = AccountId::from([1u8; 32]);
+			Key::put(&genesis);
+			InvulnerableCandidates::put(vec![genesis.clone()]);
+
+			// no successors, nothing is retired
+			RetireGenesisAccounts::on_runtime_upgrade();
+			assert_eq!(Key::get(), Some(genesis.clone()));
+			assert_eq!(InvulnerableCandidates::get(), vec![genesis.clone()]);
+			assert_eq!(Sudo::on_chain_storage_version(), 0);
+
+			assert_ok!(ParachainStaking::set_successor_invulnerables(
+				RuntimeOrigin::root(),
+				vec![]
+			));
+			RetireGenesisAccounts::on_runtime_upgrade();
+			assert_eq!(Key::get(), None);
+			assert_eq!(InvulnerableCandidates::get(), Vec::<AccountId>::new());
+			assert_eq!(Sudo::on_chain_storage_version(), 1);
+
+			// once retired, later upgrades change nothing
+			Key::put(&genesis);
+			assert_ok!(ParachainStaking::set_successor_invulnerables(
+				RuntimeOrigin::root(),
+				vec![]
+			));
+			RetireGenesisAccounts::on_runtime_upgrade();
+			assert_eq!(Key::get(), Some(genesis));
+			assert!(ParachainStaking::successor_invulnerables().is_some());
+		});
+	}
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.0;
+
+/// @dev The parachain staking precompile
+address constant STAKING_ADDRESS = 0x0000000000000000000000000000000000000800;
+
+/// @dev Calls are made from the substrate account the caller's address maps to, which must
+/// hold the staked funds. Substrate accounts are passed as their 32 byte public key.
+ParachainStaking constant STAKING_CONTRACT = ParachainStaking(STAKING_ADDRESS);
+
+interface ParachainStaking {
+    /// @dev Delegate `amount` to `candidate`
+    /// @param candidateDelegationCount Upper bound of the delegations `candidate` already has
+    /// @param delegatorDelegationCount Upper bound of the delegations the caller already made
+    function delegate(
+        bytes32 candidate,
+        uint256 amount,
+        uint32 candidateDelegationCount,
+        uint32 delegatorDelegationCount
+    ) external;
+
+    /// @dev Request to revoke the delegation to `candidate`, executable after the revoke delay
+    function scheduleRevokeDelegation(bytes32 candidate) external;
+
+    /// @dev Candidacy of `candidate`
+    /// @return isCandidate Whether `candidate` is a collator candidate
+    /// @return bond Self bond of the candidate
+    /// @return totalCounted Self bond plus the top delegations
+    /// @return delegationCount Delegations to the candidate
+    /// @return status 0 when active, 1 when idle, 2 when leaving
+    function candidateInfo(bytes32 candidate)
+        external
+        view
+        returns (
+            bool isCandidate,
+            uint256 bond,
+            uint256 totalCounted,
+            uint32 delegationCount,
+            uint8 status
+        );
+
+    /// @dev Delegations of `delegator`
+    /// @return isDelegator Whether `delegator` delegates to any candidate
+    /// @return total Total amount delegated
+    /// @return lessTotal Amount pending to be revoked or decreased
+    /// @return delegationCount Candidates delegated to
+    function delegatorState(bytes32 delegator)
+        external
+        view
+        returns (
+            bool isDelegator,
+            uint256 total,
+            uint256 lessTotal,
+            uint32 delegationCount
+        );
+}
@@ -0,0 +1,75 @@
+// Copyright 2022 Webb Technologies Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Precompiles exposed to EVM contracts: the Ethereum standard ones, then Tangle specific
+//! ones (staking, DKG proposals) at their own addresses from `0x800`.
+
+pub mod staking;
+
+use pallet_evm::{Precompile, PrecompileHandle, PrecompileResult, PrecompileSet};
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_simple::{ECRecover, Identity, Ripemd160, Sha256};
+use sp_core::H160;
+use sp_std::marker::PhantomData;
+use staking::StakingPrecompile;
+
+/// Address of the parachain staking precompile, see `StakingInterface.sol`
+pub const STAKING_PRECOMPILE_ADDRESS: u64 = 2048;
+
+/// The precompiles of the Tangle EVM
+#[derive(Default)]
+pub struct TanglePrecompiles<R>(PhantomData<R>);
+
+impl<R> TanglePrecompiles<R>
+where
+	R: pallet_evm::Config,
+{
+	pub fn new() -> Self {
+		Self(Default::default())
+	}
+
+	/// Addresses the precompiles are found at
+	pub fn used_addresses() -> [H160; 6] {
+		[hash(1), hash(2), hash(3), hash(4), hash(5), hash(STAKING_PRECOMPILE_ADDRESS)]
+	}
+}
+
+impl<R> PrecompileSet for TanglePrecompiles<R>
+where
+	R: pallet_evm::Config,
+	StakingPrecompile<R>: Precompile,
+{
+	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+		match handle.code_address() {
+			// Ethereum precompiles
+			a if a == hash(1) => Some(ECRecover::execute(handle)),
+			a if a == hash(2) => Some(Sha256::execute(handle)),
+			a if a == hash(3) => Some(Ripemd160::execute(handle)),
+			a if a == hash(4) => Some(Identity::execute(handle)),
+			a if a == hash(5) => Some(Modexp::execute(handle)),
+			// Tangle precompiles
+			a if a == hash(STAKING_PRECOMPILE_ADDRESS) =>
+				Some(StakingPrecompile::<R>::execute(handle)),
+			_ => None,
+		}
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		Self::used_addresses().contains(&address)
+	}
+}
+
+fn hash(a: u64) -> H160 {
+	H160::from_low_u64_be(a)
+}
@@ -0,0 +1,455 @@
+// Copyright 2022 Webb Technologies Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parachain staking for EVM users, following `StakingInterface.sol`. Substrate accounts are
+//! passed as `bytes32`; the caller stakes from the substrate account its address maps to.
+
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::Get,
+};
+use pallet_evm::{
+	AddressMapping, ExitError, ExitRevert, ExitSucceed, GasWeightMapping, Precompile,
+	PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+};
+use pallet_parachain_staking::{BalanceOf, CollatorStatus};
+use sp_core::U256;
+use sp_runtime::traits::Dispatchable;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// `delegate(bytes32,uint256,uint32,uint32)`
+const SELECTOR_DELEGATE: [u8; 4] = [0x77, 0xda, 0x73, 0xb5];
+/// `scheduleRevokeDelegation(bytes32)`
+const SELECTOR_SCHEDULE_REVOKE_DELEGATION: [u8; 4] = [0x98, 0x80, 0x3c, 0x17];
+/// `candidateInfo(bytes32)`
+const SELECTOR_CANDIDATE_INFO: [u8; 4] = [0x6b, 0x30, 0xdf, 0xe2];
+/// `delegatorState(bytes32)`
+const SELECTOR_DELEGATOR_STATE: [u8; 4] = [0xb5, 0xc2, 0x42, 0x1d];
+/// `Error(string)`, the revert reason understood by Solidity tooling
+const SELECTOR_ERROR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Staking precompile of the parachain staking pallet
+pub struct StakingPrecompile<R>(PhantomData<R>);
+
+impl<R> Precompile for StakingPrecompile<R>
+where
+	R: pallet_evm::Config + pallet_parachain_staking::Config,
+	R::AccountId: From<[u8; 32]>,
+	R::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_parachain_staking::Call<R>>,
+	<R::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<R::AccountId>>,
+	BalanceOf<R>: TryFrom<U256> + Into<U256>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let input = Input::new(handle.input().to_vec())?;
+		match input.selector {
+			SELECTOR_DELEGATE => Self::delegate(handle, input),
+			SELECTOR_SCHEDULE_REVOKE_DELEGATION => Self::schedule_revoke_delegation(handle, input),
+			SELECTOR_CANDIDATE_INFO => Self::candidate_info(handle, input),
+			SELECTOR_DELEGATOR_STATE => Self::delegator_state(handle, input),
+			_ => Err(revert("unknown selector")),
+		}
+	}
+}
+
+impl<R> StakingPrecompile<R>
+where
+	R: pallet_evm::Config + pallet_parachain_staking::Config,
+	R::AccountId: From<[u8; 32]>,
+	R::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_parachain_staking::Call<R>>,
+	<R::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<R::AccountId>>,
+	BalanceOf<R>: TryFrom<U256> + Into<U256>,
+{
+	fn delegate(handle: &mut impl PrecompileHandle, input: Input) -> PrecompileResult {
+		let candidate = input.account::<R>(0)?;
+		let amount = BalanceOf::<R>::try_from(input.uint(1)?)
+			.map_err(|_| revert("amount does not fit a balance"))?;
+		let candidate_delegation_count = input.u32(2)?;
+		let delegation_count = input.u32(3)?;
+
+		Self::dispatch(
+			handle,
+			pallet_parachain_staking::Call::<R>::delegate {
+				candidate,
+				amount,
+				candidate_delegation_count,
+				delegation_count,
+			},
+		)
+	}
+
+	fn schedule_revoke_delegation(
+		handle: &mut impl PrecompileHandle,
+		input: Input,
+	) -> PrecompileResult {
+		let collator = input.account::<R>(0)?;
+
+		Self::dispatch(
+			handle,
+			pallet_parachain_staking::Call::<R>::schedule_revoke_delegation { collator },
+		)
+	}
+
+	/// Returns `(bool isCandidate, uint256 bond, uint256 totalCounted, uint32 delegationCount,
+	/// uint8 status)`, where status is 0 when active, 1 when idle and 2 when leaving
+	fn candidate_info(handle: &mut impl PrecompileHandle, input: Input) -> PrecompileResult {
+		let candidate = input.account::<R>(0)?;
+		Self::record_reads(handle, 1)?;
+
+		let output = match <pallet_parachain_staking::Pallet<R>>::candidate_info(&candidate) {
+			Some(info) => {
+				let status = match info.status {
+					CollatorStatus::Active => 0u8,
+					CollatorStatus::Idle => 1,
+					CollatorStatus::Leaving(_) => 2,
+				};
+				encode(&[
+					U256::one(),
+					info.bond.into(),
+					info.total_counted.into(),
+					info.delegation_count.into(),
+					status.into(),
+				])
+			},
+			None => encode(&[U256::zero(); 5]),
+		};
+		Ok(returned(output))
+	}
+
+	/// Returns `(bool isDelegator, uint256 total, uint256 lessTotal, uint32 delegationCount)`
+	fn delegator_state(handle: &mut impl PrecompileHandle, input: Input) -> PrecompileResult {
+		let delegator = input.account::<R>(0)?;
+		Self::record_reads(handle, 1)?;
+
+		let output = match <pallet_parachain_staking::Pallet<R>>::delegator_state(&delegator) {
+			Some(state) => encode(&[
+				U256::one(),
+				state.total.into(),
+				state.less_total.into(),
+				(state.delegations.0.len() as u32).into(),
+			]),
+			None => encode(&[U256::zero(); 4]),
+		};
+		Ok(returned(output))
+	}
+
+	/// Dispatch `call` from the substrate account of the EVM caller, charging its weight as gas
+	fn dispatch(
+		handle: &mut impl PrecompileHandle,
+		call: pallet_parachain_staking::Call<R>,
+	) -> PrecompileResult {
+		if handle.is_static() {
+			return Err(revert("cannot modify staking in a static call"))
+		}
+		// through DELEGATECALL or CALLCODE the caller is whoever called the calling contract,
+		// who never agreed to stake
+		if handle.code_address() != handle.context().address {
+			return Err(revert("cannot modify staking through a delegate call"))
+		}
+		if !handle.context().apparent_value.is_zero() {
+			return Err(revert("staking calls are not payable"))
+		}
+
+		let call: R::RuntimeCall = call.into();
+		let weight = call.get_dispatch_info().weight;
+		handle.record_cost(R::GasWeightMapping::weight_to_gas(weight))?;
+
+		let origin = R::AddressMapping::into_account_id(handle.context().caller);
+		call.dispatch(Some(origin).into()).map_err(|e| {
+			let reason: &'static str = e.error.into();
+			revert(reason)
+		})?;
+
+		Ok(returned(Vec::new()))
+	}
+
+	fn record_reads(handle: &mut impl PrecompileHandle, reads: u64) -> Result<(), ExitError> {
+		let weight = <R as frame_system::Config>::DbWeight::get().reads(reads);
+		handle.record_cost(R::GasWeightMapping::weight_to_gas(weight))
+	}
+}
+
+/// ABI encoded call data: a selector followed by 32 byte words
+struct Input {
+	selector: [u8; 4],
+	words: Vec<u8>,
+}
+
+impl Input {
+	fn new(mut data: Vec<u8>) -> Result<Self, PrecompileFailure> {
+		if data.len() < 4 {
+			return Err(revert("input too short"))
+		}
+		let words = data.split_off(4);
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&data);
+		Ok(Self { selector, words })
+	}
+
+	fn word(&self, index: usize) -> Result<[u8; 32], PrecompileFailure> {
+		let word = self
+			.words
+			.get(index * 32..(index + 1) * 32)
+			.ok_or_else(|| revert("missing argument"))?;
+		let mut out = [0u8; 32];
+		out.copy_from_slice(word);
+		Ok(out)
+	}
+
+	fn account<R: frame_system::Config>(
+		&self,
+		index: usize,
+	) -> Result<R::AccountId, PrecompileFailure>
+	where
+		R::AccountId: From<[u8; 32]>,
+	{
+		self.word(index).map(Into::into)
+	}
+
+	fn uint(&self, index: usize) -> Result<U256, PrecompileFailure> {
+		self.word(index).map(|word| U256::from_big_endian(&word))
+	}
+
+	fn u32(&self, index: usize) -> Result<u32, PrecompileFailure> {
+		let value = self.uint(index)?;
+		if value > U256::from(u32::MAX) {
+			return Err(revert("argument does not fit a uint32"))
+		}
+		Ok(value.low_u32())
+	}
+}
+
+/// ABI encode static `words`
+fn encode(words: &[U256]) -> Vec<u8> {
+	let mut out = Vec::with_capacity(words.len() * 32);
+	for word in words {
+		let mut buf = [0u8; 32];
+		word.to_big_endian(&mut buf);
+		out.extend_from_slice(&buf);
+	}
+	out
+}
+
+fn returned(output: Vec<u8>) -> PrecompileOutput {
+	PrecompileOutput { exit_status: ExitSucceed::Returned, output }
+}
+
+/// Revert with `reason` as an `Error(string)`
+fn revert(reason: &str) -> PrecompileFailure {
+	let reason = reason.as_bytes();
+	let mut output = SELECTOR_ERROR.to_vec();
+	output.extend(encode(&[U256::from(32), U256::from(reason.len())]));
+	output.extend_from_slice(reason);
+	output.resize(output.len() + (32 - reason.len() % 32) % 32, 0);
+	PrecompileFailure::Revert { exit_status: ExitRevert::Reverted, output }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		precompiles::STAKING_PRECOMPILE_ADDRESS, AccountId, Balances, ParachainStaking, Runtime,
+		RuntimeOrigin, DOLLAR,
+	};
+	use fp_evm::{Context, ExitReason, Transfer};
+	use frame_support::assert_ok;
+	use pallet_evm::HashedAddressMapping;
+	use sp_core::{H160, H256};
+	use sp_runtime::{traits::BlakeTwo256, BuildStorage};
+
+	struct MockHandle {
+		input: Vec<u8>,
+		context: Context,
+		code_address: H160,
+	}
+
+	impl MockHandle {
+		/// A call of the staking precompile by `caller`, or through a DELEGATECALL from the
+		/// contract at `delegated_from`
+		fn new(caller: H160, delegated_from: Option<H160>, input: Vec<u8>) -> Self {
+			let code_address = H160::from_low_u64_be(STAKING_PRECOMPILE_ADDRESS);
+			let context = Context {
+				address: delegated_from.unwrap_or(code_address),
+				caller,
+				apparent_value: U256::zero(),
+			};
+			Self { input, context, code_address }
+		}
+	}
+
+	impl PrecompileHandle for MockHandle {
+		fn call(
+			&mut self,
+			_: H160,
+			_: Option<Transfer>,
+			_: Vec<u8>,
+			_: Option<u64>,
+			_: bool,
+			_: &Context,
+		) -> (ExitReason, Vec<u8>) {
+			unimplemented!("the staking precompile makes no calls")
+		}
+
+		fn record_cost(&mut self, _: u64) -> Result<(), ExitError> {
+			Ok(())
+		}
+
+		fn remaining_gas(&self) -> u64 {
+			u64::MAX
+		}
+
+		fn log(&mut self, _: H160, _: Vec<H256>, _: Vec<u8>) -> Result<(), ExitError> {
+			Ok(())
+		}
+
+		fn code_address(&self) -> H160 {
+			self.code_address
+		}
+
+		fn input(&self) -> &[u8] {
+			&self.input
+		}
+
+		fn context(&self) -> &Context {
+			&self.context
+		}
+
+		fn is_static(&self) -> bool {
+			false
+		}
+
+		fn gas_limit(&self) -> Option<u64> {
+			None
+		}
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	fn call_data(selector: [u8; 4], words: &[U256]) -> Vec<u8> {
+		let mut data = selector.to_vec();
+		data.extend(encode(words));
+		data
+	}
+
+	/// A candidate to delegate to and the EVM address of a funded delegator
+	fn setup() -> (AccountId, H160) {
+		let candidate = AccountId::from([1u8; 32]);
+		let bond = crate::staking::NORMAL_COLLATOR_MINIMUM_STAKE;
+		Balances::make_free_balance_be(&candidate, 2 * bond);
+		assert_ok!(ParachainStaking::join_candidates(
+			RuntimeOrigin::signed(candidate.clone()),
+			bond,
+			0,
+		));
+		let caller = H160::repeat_byte(0xaa);
+		Balances::make_free_balance_be(&delegator(caller), 100 * DOLLAR);
+		(candidate, caller)
+	}
+
+	fn delegator(caller: H160) -> AccountId {
+		HashedAddressMapping::<BlakeTwo256>::into_account_id(caller)
+	}
+
+	fn delegate_input(candidate: &AccountId) -> Vec<u8> {
+		let candidate: &[u8; 32] = candidate.as_ref();
+		call_data(
+			SELECTOR_DELEGATE,
+			&[U256::from_big_endian(candidate), (10 * DOLLAR).into(), U256::zero(), U256::zero()],
+		)
+	}
+
+	fn selector(signature: &str) -> [u8; 4] {
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&sp_io::hashing::keccak_256(signature.as_bytes())[..4]);
+		selector
+	}
+
+	#[test]
+	fn selectors_match_the_solidity_interface() {
+		assert_eq!(SELECTOR_DELEGATE, selector("delegate(bytes32,uint256,uint32,uint32)"));
+		assert_eq!(
+			SELECTOR_SCHEDULE_REVOKE_DELEGATION,
+			selector("scheduleRevokeDelegation(bytes32)")
+		);
+		assert_eq!(SELECTOR_CANDIDATE_INFO, selector("candidateInfo(bytes32)"));
+		assert_eq!(SELECTOR_DELEGATOR_STATE, selector("delegatorState(bytes32)"));
+		assert_eq!(SELECTOR_ERROR, selector("Error(string)"));
+	}
+
+	#[test]
+	fn revert_reason_is_abi_encoded() {
+		let PrecompileFailure::Revert { output, .. } = revert("no") else {
+			panic!("revert must revert")
+		};
+		assert_eq!(output.len(), 4 + 3 * 32);
+		assert_eq!(U256::from_big_endian(&output[36..68]), U256::from(2));
+		assert_eq!(&output[68..70], b"no");
+	}
+
+	#[test]
+	fn delegate_and_schedule_revoke_delegation_stake_from_the_caller() {
+		new_test_ext().execute_with(|| {
+			let (candidate, caller) = setup();
+
+			let mut handle = MockHandle::new(caller, None, delegate_input(&candidate));
+			assert!(StakingPrecompile::<Runtime>::execute(&mut handle).is_ok());
+			assert_eq!(
+				ParachainStaking::delegator_state(delegator(caller))
+					.and_then(|state| state.get_bond_amount(&candidate)),
+				Some(10 * DOLLAR)
+			);
+
+			let candidate_word: &[u8; 32] = candidate.as_ref();
+			let mut handle = MockHandle::new(
+				caller,
+				None,
+				call_data(
+					SELECTOR_SCHEDULE_REVOKE_DELEGATION,
+					&[U256::from_big_endian(candidate_word)],
+				),
+			);
+			assert!(StakingPrecompile::<Runtime>::execute(&mut handle).is_ok());
+			assert_eq!(ParachainStaking::delegation_scheduled_requests(&candidate).len(), 1);
+		});
+	}
+
+	#[test]
+	fn staking_is_not_modified_through_a_delegate_call() {
+		new_test_ext().execute_with(|| {
+			let (candidate, caller) = setup();
+			let contract = H160::repeat_byte(0xcc);
+
+			let mut handle = MockHandle::new(caller, Some(contract), delegate_input(&candidate));
+			let Err(PrecompileFailure::Revert { output, .. }) =
+				StakingPrecompile::<Runtime>::execute(&mut handle)
+			else {
+				panic!("a delegate call must revert")
+			};
+			assert!(output.ends_with(&{
+				let mut reason = b"cannot modify staking through a delegate call".to_vec();
+				reason.resize(64, 0);
+				reason
+			}));
+			assert!(ParachainStaking::delegator_state(delegator(caller)).is_none());
+		});
+	}
+}
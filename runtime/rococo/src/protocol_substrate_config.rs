@@ -5,10 +5,13 @@ use frame_support::{
 };
 use orml_currencies::{BasicCurrencyAdapter, NativeCurrencyOf};
 use webb_primitives::{
-	field_ops::ArkworksIntoFieldBn254,
-	hashing::{ethereum::Keccak256HasherBn254, ArkworksPoseidonHasherBn254},
+	field_ops::{ArkworksIntoFieldBls381, ArkworksIntoFieldBn254},
+	hashing::{
+		ethereum::{Keccak256HasherBls381, Keccak256HasherBn254},
+		ArkworksPoseidonHasherBls381, ArkworksPoseidonHasherBn254,
+	},
 	runtime::Element,
-	verifying::ArkworksVerifierBn254,
+	verifying::{ArkworksVerifierBls381, ArkworksVerifierBn254},
 	Amount, ChainId,
 };
 
@@ -69,6 +72,8 @@ impl pallet_verifier::Config<pallet_verifier::Instance1> for Runtime {
 
 parameter_types! {
 	pub const TokenWrapperPalletId: PalletId = PalletId(*b"dw/tkwrp");
+	/// Collects wrapping fees until they are split between the treasury and stakers each round
+	pub const WrappingFeePotId: PalletId = PalletId(*b"dw/wrfee");
 	pub const WrappingFeeDivider: Balance = 100;
 }
 
@@ -77,7 +82,7 @@ impl pallet_token_wrapper::Config for Runtime {
 	type Currency = Currencies;
 	type RuntimeEvent = RuntimeEvent;
 	type PalletId = TokenWrapperPalletId;
-	type TreasuryId = DKGAccountId;
+	type TreasuryId = WrappingFeePotId;
 	type ProposalNonce = u32;
 	type WeightInfo = pallet_token_wrapper::weights::WebbWeight<Runtime>;
 	type WrappingFeeDivider = WrappingFeeDivider;
@@ -85,11 +90,12 @@ impl pallet_token_wrapper::Config for Runtime {
 
 impl pallet_asset_registry::Config for Runtime {
 	type AssetId = webb_primitives::AssetId;
-	type AssetNativeLocation = ();
+	type AssetNativeLocation = crate::xcm_config::AssetLocation;
 	type Balance = Balance;
 	type RuntimeEvent = RuntimeEvent;
 	type NativeAssetId = GetNativeCurrencyId;
-	type RegistryOrigin = frame_system::EnsureRoot<AccountId>;
+	// also guards updating the metadata and existential deposit of registered assets
+	type RegistryOrigin = crate::governance::AssetAdminOrigin;
 	type StringLimit = RegistryStringLimit;
 	type WeightInfo = ();
 }
@@ -250,6 +256,54 @@ impl pallet_vanchor::Config<pallet_vanchor::Instance1> for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	/// Leaves relayers and provers a day to pick up a new verifying key
+	pub const VerifierActivationDelay: BlockNumber = DAYS;
+	pub const MaxVerifyingKeyLength: u32 = 4_096;
+}
+
+impl pallet_verifier_rotation::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RotationOrigin = frame_support::traits::EitherOf<
+		frame_system::EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+	>;
+	type Verifiers = crate::impls::SetBn254VerifyingKeys;
+	type ActivationDelay = VerifierActivationDelay;
+	type MaxKeyLength = MaxVerifyingKeyLength;
+	type WeightInfo = pallet_verifier_rotation::weights::WebbWeight<Runtime>;
+}
+
+impl pallet_root_history::Config for Runtime {
+	type TreeId = u32;
+	type ChainId = ChainId;
+	type Element = Element;
+	type Roots = crate::impls::Bn254TreeRoots;
+	// as many as the neighbor roots the linkable trees accept
+	type MaxRoots = HistoryLength;
+}
+
+impl pallet_vanchor_limits::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type TreeId = u32;
+	type Balance = Balance;
+	type LimitsOrigin = crate::governance::AssetAdminOrigin;
+	type VAnchor = crate::impls::Bn254VAnchor;
+	type WeightInfo = pallet_vanchor_limits::weights::WebbWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const VAnchorRewardsPalletId: PalletId = PalletId(*b"py/vanrw");
+}
+
+impl pallet_vanchor_rewards::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type PalletId = VAnchorRewardsPalletId;
+	type MaxDepositorsPerEpoch = ConstU32<1_000>;
+	type WeightInfo = pallet_vanchor_rewards::weights::WebbWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const ProposalLifetime: BlockNumber = 50;
 }
@@ -277,3 +331,80 @@ impl pallet_vanchor_verifier::Config for Runtime {
 	type Verifier = ArkworksVerifierBn254;
 	type WeightInfo = pallet_vanchor_verifier::weights::WebbWeight<Runtime>;
 }
+
+// BLS12-381 instances, for circuits over BLS friendly curves. They share the chain
+// identity and key storage of the BN254 instances but keep their own trees and pots.
+
+impl pallet_hasher::Config<pallet_hasher::Instance2> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type Hasher = ArkworksPoseidonHasherBls381;
+	type WeightInfo = pallet_hasher::weights::WebbWeight<Runtime>;
+}
+
+impl pallet_verifier::Config<pallet_verifier::Instance2> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type Verifier = ArkworksVerifierBls381;
+	type WeightInfo = pallet_verifier::weights::WebbWeight<Runtime>;
+}
+
+impl pallet_mt::Config<pallet_mt::Instance2> for Runtime {
+	type Currency = Balances;
+	type DataDepositBase = LeafDepositBase;
+	type DataDepositPerByte = LeafDepositPerByte;
+	type DefaultZeroElement = NewDefaultZeroElement;
+	type Element = Element;
+	type RuntimeEvent = RuntimeEvent;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type Hasher = HasherBls381;
+	type LeafIndex = u32;
+	type MaxTreeDepth = MaxTreeDepth;
+	type RootHistorySize = RootHistorySize;
+	type RootIndex = u32;
+	type StringLimit = StringLimit;
+	type TreeDeposit = TreeDeposit;
+	type TreeId = u32;
+	type Two = Two;
+	type WeightInfo = pallet_mt::weights::WebbWeight<Runtime>;
+}
+
+impl pallet_linkable_tree::Config<pallet_linkable_tree::Instance2> for Runtime {
+	type ChainId = ChainId;
+	type ChainType = ChainType;
+	type ChainIdentifier = ChainIdentifier;
+	type RuntimeEvent = RuntimeEvent;
+	type HistoryLength = HistoryLength;
+	type Tree = MerkleTreeBls381;
+	type WeightInfo = ();
+}
+
+impl pallet_vanchor_verifier::Config<pallet_vanchor_verifier::Instance2> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type Verifier = ArkworksVerifierBls381;
+	type WeightInfo = pallet_vanchor_verifier::weights::WebbWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const VAnchorBls381PalletId: PalletId = PalletId(*b"py/vanbl");
+}
+
+impl pallet_vanchor::Config<pallet_vanchor::Instance2> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = VAnchorBls381PalletId;
+	type ProposalNonce = u32;
+	type LinkableTree = LinkableTreeBls381;
+	type KeyStorage = KeyStorage;
+	type VAnchorVerifier = VAnchorVerifierBls381;
+	type EthereumHasher = Keccak256HasherBls381;
+	type IntoField = ArkworksIntoFieldBls381;
+	type Currency = Currencies;
+	type MaxFee = MaxFee;
+	type MaxExtAmount = MaxExtAmount;
+	type PostDepositHook = ();
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type MaxCurrencyId = MaxCurrencyId;
+	type TokenWrapper = TokenWrapper;
+	type WeightInfo = ();
+}
@@ -1,9 +1,52 @@
+use frame_support::pallet_prelude::TransactionPriority;
 use pallet_parachain_staking::{BalanceOf, InflationInfo};
 use tangle_primitives::{currency::DOLLAR, Balance};
 
 pub const NORMAL_COLLATOR_MINIMUM_STAKE: Balance = 400 * DOLLAR;
 pub const EARLY_COLLATOR_MINIMUM_STAKE: Balance = 400 * DOLLAR;
 pub const MIN_BOND_TO_BE_CONSIDERED_COLLATOR: Balance = EARLY_COLLATOR_MINIMUM_STAKE;
+/// Delegations of at least this amount only count towards selection after the next round
+pub const DEFERRED_DELEGATION_THRESHOLD: Balance = 100_000 * DOLLAR;
+/// Maximum number of slots returned by a single authorship schedule query
+pub const MAX_AUTHORSHIP_SCHEDULE_SLOTS: u32 = 1_000;
+/// Rewarded rounds kept per account, roughly a week of hourly rounds
+pub const MAX_REWARD_HISTORY_ROUNDS: u32 = 168;
+/// Rewardable delegations per page of a collator's round snapshot, each paid as one payout
+pub const MAX_EXPOSURE_PAGE_SIZE: u32 = 64;
+/// Rounds the rewards remitted to other chains are batched over, roughly a day of hourly rounds
+pub const REMIT_REWARDS_EVERY_ROUNDS: u32 = 24;
+/// Rounds a slash is deferred for, roughly a day of hourly rounds
+pub const SLASH_DEFER_DURATION_ROUNDS: u32 = 24;
+/// Slashes deferred to the same round, beyond which offences are slashed on report
+pub const MAX_DEFERRED_SLASHES_PER_ROUND: u32 = 100;
+/// Rounds an executable delegation request is kept before it is cancelled, roughly a week of
+/// hourly rounds
+pub const REQUEST_EXPIRY_ROUNDS: u32 = 168;
+/// Rounds of staked amounts kept for stake-weighted votes, roughly a month of hourly rounds
+pub const VOTING_POWER_HISTORY_ROUNDS: u32 = 720;
+/// Blocks before the end of a round at which the election of the next round's collators begins
+pub const ELECTION_LOOKAHEAD_BLOCKS: u32 = 10;
+/// Candidates scored per block while electing the next round's collators
+pub const ELECTION_PAGE_SIZE: u32 = 32;
+/// Blocks before the end of a round in which executing or cancelling scheduled staking requests
+/// is prioritized
+pub const STAKING_EXIT_PRIORITY_BLOCKS: u32 = 10;
+/// Priority added to staking exits in the last `STAKING_EXIT_PRIORITY_BLOCKS` blocks of a round,
+/// above any fee paid for ordinary transactions but keeping exits ordered by their fees
+pub const STAKING_EXIT_PRIORITY_BOOST: TransactionPriority = TransactionPriority::MAX / 2;
+/// Total issuance the staking rewards taper off towards
+pub const MAX_TOTAL_ISSUANCE: Balance = 1_000_000_000 * DOLLAR;
+
+/// The collator of `active` expected to author `slot`, as picked by `pallet_aura_style_filter`:
+/// each collator authors two consecutive slots in turn
+pub fn aura_style_author<AccountId: Clone>(slot: u64, active: &[AccountId]) -> Option<AccountId> {
+	if active.is_empty() {
+		return None
+	}
+	let truncated_half_slot = slot >> 1;
+	// reduced as a `u64`, as `usize` truncates slots on 32 bit targets
+	active.get((truncated_half_slot % active.len() as u64) as usize).cloned()
+}
 
 pub fn inflation_config<T: frame_system::Config + pallet_parachain_staking::Config>(
 ) -> InflationInfo<BalanceOf<T>> {
@@ -40,3 +83,22 @@ pub fn inflation_config<T: frame_system::Config + pallet_parachain_staking::Conf
 		round: to_round_inflation(annual),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn aura_style_authors_take_two_slots_in_turn() {
+		let active = vec![1u32, 2, 3];
+		let authors: Vec<_> = (0..8).map(|slot| aura_style_author(slot, &active)).collect();
+		assert_eq!(
+			authors,
+			vec![Some(1), Some(1), Some(2), Some(2), Some(3), Some(3), Some(1), Some(1)]
+		);
+		// slots beyond `u32` are not truncated
+		let slot = (u32::MAX as u64 + 1) * 2;
+		assert_eq!(aura_style_author(slot, &active), Some(active[((slot >> 1) % 3) as usize]));
+		assert_eq!(aura_style_author(slot, &Vec::<u32>::new()), None);
+	}
+}
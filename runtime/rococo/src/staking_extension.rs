@@ -0,0 +1,224 @@
+// Copyright 2022 Webb Technologies Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parachain staking for ink! contracts. Functions take and return SCALE encoded values and go
+//! through [`StakingInterface`], never staking storage, so vault contracts keep working across
+//! storage migrations.
+//!
+//! The runtime does not include pallet-contracts yet. Once it does, its `ChainExtension` reads
+//! the function id and input from the environment, charges [`StakingExtension::weight`] and
+//! writes back what [`StakingExtension::call`] returns, with the contract as the caller.
+
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchError, pallet_prelude::Weight, traits::Get};
+use pallet_parachain_staking::{weights::WeightInfo, BalanceOf, RoundIndex, StakingInterface};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// `candidate_stake(AccountId) -> Option<Balance>`
+pub const FUNC_CANDIDATE_STAKE: u16 = 0x0001;
+/// `delegation_stake(delegator: AccountId, candidate: AccountId) -> Option<Balance>`
+pub const FUNC_DELEGATION_STAKE: u16 = 0x0002;
+/// `round() -> RoundOutput<BlockNumber>`
+pub const FUNC_ROUND: u16 = 0x0003;
+/// `delegate(candidate: AccountId, amount: Balance)`, adding to an existing delegation
+pub const FUNC_DELEGATE: u16 = 0x0101;
+/// `undelegate(candidate: AccountId)`, scheduling the revocation of the delegation
+pub const FUNC_UNDELEGATE: u16 = 0x0102;
+/// `withdraw_unbonded(candidate: AccountId)`, once a revocation or decrease is due
+pub const FUNC_WITHDRAW_UNBONDED: u16 = 0x0103;
+
+/// Current round index, its first block and its length, as returned by [`FUNC_ROUND`]
+pub type RoundOutput<BlockNumber> = (RoundIndex, BlockNumber, u32);
+
+type StakingWeight<R> = <R as pallet_parachain_staking::Config>::WeightInfo;
+
+/// Staking chain extension of the parachain staking pallet
+pub struct StakingExtension<R>(PhantomData<R>);
+
+impl<R> StakingExtension<R>
+where
+	R: pallet_parachain_staking::Config,
+{
+	/// Weight charged up front for `func_id`, the worst case of the staking call it makes
+	pub fn weight(func_id: u16) -> Result<Weight, DispatchError> {
+		let db = <R as frame_system::Config>::DbWeight::get();
+		Ok(match func_id {
+			FUNC_CANDIDATE_STAKE | FUNC_DELEGATION_STAKE | FUNC_ROUND => db.reads(1),
+			FUNC_DELEGATE => StakingWeight::<R>::delegate(
+				R::MaxTopDelegationsPerCandidate::get()
+					.saturating_add(R::MaxBottomDelegationsPerCandidate::get()),
+				R::MaxDelegationsPerDelegator::get(),
+			)
+			.max(StakingWeight::<R>::delegator_bond_more()),
+			FUNC_UNDELEGATE => StakingWeight::<R>::schedule_revoke_delegation(),
+			FUNC_WITHDRAW_UNBONDED => StakingWeight::<R>::execute_revoke_delegation()
+				.max(StakingWeight::<R>::execute_delegator_bond_less()),
+			_ => return Err(DispatchError::Other("unknown staking function")),
+		})
+	}
+
+	/// Run `func_id` for `caller`, returning the encoded output
+	pub fn call(
+		func_id: u16,
+		caller: R::AccountId,
+		mut input: &[u8],
+	) -> Result<Vec<u8>, DispatchError> {
+		let input = &mut input;
+		Ok(match func_id {
+			FUNC_CANDIDATE_STAKE => {
+				let candidate = decode::<R::AccountId>(input)?;
+				<pallet_parachain_staking::Pallet<R> as StakingInterface>::candidate_stake(
+					&candidate,
+				)
+				.encode()
+			},
+			FUNC_DELEGATION_STAKE => {
+				let (delegator, candidate) = decode::<(R::AccountId, R::AccountId)>(input)?;
+				<pallet_parachain_staking::Pallet<R> as StakingInterface>::delegation_stake(
+					&delegator, &candidate,
+				)
+				.encode()
+			},
+			FUNC_ROUND => {
+				let round =
+					<pallet_parachain_staking::Pallet<R> as StakingInterface>::current_round();
+				let output: RoundOutput<R::BlockNumber> =
+					(round.current, round.first, round.length);
+				output.encode()
+			},
+			FUNC_DELEGATE => {
+				let (candidate, amount) = decode::<(R::AccountId, BalanceOf<R>)>(input)?;
+				<pallet_parachain_staking::Pallet<R> as StakingInterface>::delegate(
+					caller, candidate, amount,
+				)?;
+				Vec::new()
+			},
+			FUNC_UNDELEGATE => {
+				let candidate = decode::<R::AccountId>(input)?;
+				<pallet_parachain_staking::Pallet<R> as StakingInterface>::undelegate(
+					caller, candidate,
+				)?;
+				Vec::new()
+			},
+			FUNC_WITHDRAW_UNBONDED => {
+				let candidate = decode::<R::AccountId>(input)?;
+				<pallet_parachain_staking::Pallet<R> as StakingInterface>::withdraw_unbonded(
+					caller, candidate,
+				)?;
+				Vec::new()
+			},
+			_ => return Err(DispatchError::Other("unknown staking function")),
+		})
+	}
+}
+
+/// Decode all of `input` as a `V`
+fn decode<V: Decode>(input: &mut &[u8]) -> Result<V, DispatchError> {
+	let value = V::decode(input).map_err(|_| DispatchError::Other("invalid staking input"))?;
+	if !input.is_empty() {
+		return Err(DispatchError::Other("invalid staking input"))
+	}
+	Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		AccountId, Balances, BlockNumber, ParachainStaking, Runtime, RuntimeOrigin, DOLLAR,
+	};
+	use frame_support::{assert_noop, assert_ok};
+	use sp_runtime::BuildStorage;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn contracts_delegate_and_query_through_the_extension() {
+		new_test_ext().execute_with(|| {
+			let [candidate, contract] = [1u8, 2].map(|i| AccountId::from([i; 32]));
+			let bond = crate::staking::NORMAL_COLLATOR_MINIMUM_STAKE;
+			Balances::make_free_balance_be(&candidate, 2 * bond);
+			Balances::make_free_balance_be(&contract, 100 * DOLLAR);
+			assert_ok!(ParachainStaking::join_candidates(
+				RuntimeOrigin::signed(candidate.clone()),
+				bond,
+				0,
+			));
+
+			let delegate = (candidate.clone(), 10 * DOLLAR).encode();
+			assert_ok!(StakingExtension::<Runtime>::call(
+				FUNC_DELEGATE,
+				contract.clone(),
+				&delegate,
+			));
+			assert_eq!(
+				StakingExtension::<Runtime>::call(
+					FUNC_DELEGATION_STAKE,
+					contract.clone(),
+					&(contract.clone(), candidate.clone()).encode(),
+				),
+				Ok(Some(10 * DOLLAR).encode())
+			);
+			assert_eq!(
+				StakingExtension::<Runtime>::call(
+					FUNC_CANDIDATE_STAKE,
+					contract.clone(),
+					&candidate.encode(),
+				),
+				Ok(Some(bond + 10 * DOLLAR).encode())
+			);
+
+			assert_ok!(StakingExtension::<Runtime>::call(
+				FUNC_UNDELEGATE,
+				contract.clone(),
+				&candidate.encode(),
+			));
+			// the revocation is only due after the delay
+			assert!(StakingExtension::<Runtime>::call(
+				FUNC_WITHDRAW_UNBONDED,
+				contract.clone(),
+				&candidate.encode(),
+			)
+			.is_err());
+		});
+	}
+
+	#[test]
+	fn malformed_calls_are_rejected() {
+		new_test_ext().execute_with(|| {
+			let caller = AccountId::from([1u8; 32]);
+			assert_noop!(
+				StakingExtension::<Runtime>::call(0x0fff, caller.clone(), &[]),
+				DispatchError::Other("unknown staking function")
+			);
+			assert!(StakingExtension::<Runtime>::weight(0x0fff).is_err());
+			// trailing bytes are not ignored
+			let mut input = caller.encode();
+			input.push(0);
+			assert_noop!(
+				StakingExtension::<Runtime>::call(FUNC_CANDIDATE_STAKE, caller, &input),
+				DispatchError::Other("invalid staking input")
+			);
+			let round =
+				StakingExtension::<Runtime>::call(FUNC_ROUND, AccountId::from([2u8; 32]), &[])
+					.unwrap();
+			assert!(RoundOutput::<BlockNumber>::decode(&mut &round[..]).is_ok());
+		});
+	}
+}
@@ -22,6 +22,7 @@ pub mod extrinsic_weights;
 pub mod orml_currencies;
 pub mod orml_tokens;
 pub mod rocksdb_weights;
+pub mod xcm;
 
 pub use block_weights::constants::BlockExecutionWeight;
 pub use extrinsic_weights::constants::ExtrinsicBaseWeight;
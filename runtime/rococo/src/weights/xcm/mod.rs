@@ -0,0 +1,184 @@
+// This file is part of Webb.
+
+// Copyright (C) 2022 Webb Technologies Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights of XCM instructions, from PLACEHOLDER weights for the `pallet_xcm_benchmarks`
+//! benchmarks until they are run against this runtime.
+
+mod pallet_xcm_benchmarks_fungible;
+mod pallet_xcm_benchmarks_generic;
+
+use crate::Runtime;
+use frame_support::weights::Weight;
+use pallet_xcm_benchmarks_fungible::WeightInfo as XcmFungibleWeight;
+use pallet_xcm_benchmarks_generic::WeightInfo as XcmGeneric;
+use sp_std::prelude::*;
+use xcm::{
+	latest::{prelude::*, Weight as XCMWeight},
+	DoubleEncoded,
+};
+
+/// Most assets an instruction is weighed for when it is given a wildcard
+const MAX_ASSETS: u32 = 100;
+
+trait WeighMultiAssets {
+	fn weigh_multi_assets(&self, weight: Weight) -> XCMWeight;
+}
+
+impl WeighMultiAssets for MultiAssetFilter {
+	fn weigh_multi_assets(&self, weight: Weight) -> XCMWeight {
+		let weight = weight.ref_time();
+		match self {
+			Self::Definite(assets) => weight.saturating_mul(assets.inner().len() as u64),
+			Self::Wild(_) => weight.saturating_mul(MAX_ASSETS as u64),
+		}
+	}
+}
+
+impl WeighMultiAssets for MultiAssets {
+	fn weigh_multi_assets(&self, weight: Weight) -> XCMWeight {
+		weight.ref_time().saturating_mul(self.inner().len() as u64)
+	}
+}
+
+/// Weight of every XCM instruction, for `xcm_builder::WeightInfoBounds`
+pub struct TangleXcmWeight<RuntimeCall>(core::marker::PhantomData<RuntimeCall>);
+impl<RuntimeCall> XcmWeightInfo<RuntimeCall> for TangleXcmWeight<RuntimeCall> {
+	fn withdraw_asset(assets: &MultiAssets) -> XCMWeight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::withdraw_asset())
+	}
+	fn reserve_asset_deposited(assets: &MultiAssets) -> XCMWeight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::reserve_asset_deposited())
+	}
+	fn receive_teleported_asset(assets: &MultiAssets) -> XCMWeight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::receive_teleported_asset())
+	}
+	fn query_response(_query_id: &u64, _response: &Response, _max_weight: &u64) -> XCMWeight {
+		XcmGeneric::<Runtime>::query_response().ref_time()
+	}
+	fn transfer_asset(assets: &MultiAssets, _dest: &MultiLocation) -> XCMWeight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::transfer_asset())
+	}
+	fn transfer_reserve_asset(
+		assets: &MultiAssets,
+		_dest: &MultiLocation,
+		_xcm: &Xcm<()>,
+	) -> XCMWeight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::transfer_reserve_asset())
+	}
+	fn transact(
+		_origin_type: &OriginKind,
+		_require_weight_at_most: &u64,
+		_call: &DoubleEncoded<RuntimeCall>,
+	) -> XCMWeight {
+		XcmGeneric::<Runtime>::transact().ref_time()
+	}
+	fn hrmp_new_channel_open_request(
+		_sender: &u32,
+		_max_message_size: &u32,
+		_max_capacity: &u32,
+	) -> XCMWeight {
+		// only the relay chain sends HRMP notifications, which are not handled here
+		XCMWeight::MAX
+	}
+	fn hrmp_channel_accepted(_recipient: &u32) -> XCMWeight {
+		XCMWeight::MAX
+	}
+	fn hrmp_channel_closing(_initiator: &u32, _sender: &u32, _recipient: &u32) -> XCMWeight {
+		XCMWeight::MAX
+	}
+	fn clear_origin() -> XCMWeight {
+		XcmGeneric::<Runtime>::clear_origin().ref_time()
+	}
+	fn descend_origin(_who: &InteriorMultiLocation) -> XCMWeight {
+		XcmGeneric::<Runtime>::descend_origin().ref_time()
+	}
+	fn report_error(
+		_query_id: &QueryId,
+		_dest: &MultiLocation,
+		_max_response_weight: &u64,
+	) -> XCMWeight {
+		XcmGeneric::<Runtime>::report_error().ref_time()
+	}
+	fn deposit_asset(
+		assets: &MultiAssetFilter,
+		_max_assets: &u32,
+		_dest: &MultiLocation,
+	) -> XCMWeight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::deposit_asset())
+	}
+	fn deposit_reserve_asset(
+		assets: &MultiAssetFilter,
+		_max_assets: &u32,
+		_dest: &MultiLocation,
+		_xcm: &Xcm<()>,
+	) -> XCMWeight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::deposit_reserve_asset())
+	}
+	fn exchange_asset(_give: &MultiAssetFilter, _receive: &MultiAssets) -> XCMWeight {
+		// not supported by the executor
+		XCMWeight::MAX
+	}
+	fn initiate_reserve_withdraw(
+		assets: &MultiAssetFilter,
+		_reserve: &MultiLocation,
+		_xcm: &Xcm<()>,
+	) -> XCMWeight {
+		assets.weigh_multi_assets(XcmGeneric::<Runtime>::initiate_reserve_withdraw())
+	}
+	fn initiate_teleport(
+		assets: &MultiAssetFilter,
+		_dest: &MultiLocation,
+		_xcm: &Xcm<()>,
+	) -> XCMWeight {
+		assets.weigh_multi_assets(XcmFungibleWeight::<Runtime>::initiate_teleport())
+	}
+	fn query_holding(
+		_query_id: &u64,
+		_dest: &MultiLocation,
+		_assets: &MultiAssetFilter,
+		_max_response_weight: &u64,
+	) -> XCMWeight {
+		XcmGeneric::<Runtime>::query_holding().ref_time()
+	}
+	fn buy_execution(_fees: &MultiAsset, _weight_limit: &WeightLimit) -> XCMWeight {
+		XcmGeneric::<Runtime>::buy_execution().ref_time()
+	}
+	fn refund_surplus() -> XCMWeight {
+		XcmGeneric::<Runtime>::refund_surplus().ref_time()
+	}
+	fn set_error_handler(_xcm: &Xcm<RuntimeCall>) -> XCMWeight {
+		XcmGeneric::<Runtime>::set_error_handler().ref_time()
+	}
+	fn set_appendix(_xcm: &Xcm<RuntimeCall>) -> XCMWeight {
+		XcmGeneric::<Runtime>::set_appendix().ref_time()
+	}
+	fn clear_error() -> XCMWeight {
+		XcmGeneric::<Runtime>::clear_error().ref_time()
+	}
+	fn claim_asset(_assets: &MultiAssets, _ticket: &MultiLocation) -> XCMWeight {
+		XcmGeneric::<Runtime>::claim_asset().ref_time()
+	}
+	fn trap(_code: &u64) -> XCMWeight {
+		XcmGeneric::<Runtime>::trap().ref_time()
+	}
+	fn subscribe_version(_query_id: &QueryId, _max_response_weight: &u64) -> XCMWeight {
+		XcmGeneric::<Runtime>::subscribe_version().ref_time()
+	}
+	fn unsubscribe_version() -> XCMWeight {
+		XcmGeneric::<Runtime>::unsubscribe_version().ref_time()
+	}
+}
@@ -0,0 +1,103 @@
+// This file is part of Webb.
+
+// Copyright (C) 2022 Webb Technologies Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! PLACEHOLDER weights for `pallet_xcm_benchmarks::fungible`. These are NOT generated: the
+//! benchmarks have not been run against this runtime yet, so each weight is written by hand from
+//! the storage accessed by the instruction and the weights other parachains benchmarked for it.
+//! Replace this file with the generated one once the benchmarks are run.
+//!
+//! Generate on reference collator hardware with:
+//!
+//! ./target/release/tangle-parachain benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_xcm_benchmarks::fungible --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --output=./runtime/rococo/src/weights/xcm/pallet_xcm_benchmarks_fungible.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weights for `pallet_xcm_benchmarks::fungible`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo<T> {
+	// Storage: System Account (r:1 w:1)
+	pub(crate) fn withdraw_asset() -> Weight {
+		Weight::from_ref_time(20_385_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: System Account (r:2 w:2)
+	pub(crate) fn transfer_asset() -> Weight {
+		Weight::from_ref_time(32_756_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	pub(crate) fn transfer_reserve_asset() -> Weight {
+		Weight::from_ref_time(50_645_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	// Storage: System Account (r:1 w:1)
+	pub(crate) fn receive_teleported_asset() -> Weight {
+		Weight::from_ref_time(19_595_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Not benchmarked by this release of `pallet_xcm_benchmarks`. Like `receive_teleported_asset`,
+	// it only adds the assets to the holding register, but without reading a checking account.
+	pub(crate) fn reserve_asset_deposited() -> Weight {
+		Weight::from_ref_time(19_595_000)
+	}
+	// Storage: System Account (r:1 w:1)
+	pub(crate) fn deposit_asset() -> Weight {
+		Weight::from_ref_time(21_763_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	pub(crate) fn deposit_reserve_asset() -> Weight {
+		Weight::from_ref_time(40_930_000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	pub(crate) fn initiate_teleport() -> Weight {
+		Weight::from_ref_time(40_788_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
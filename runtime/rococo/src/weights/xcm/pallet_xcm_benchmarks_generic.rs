@@ -0,0 +1,123 @@
+// This file is part of Webb.
+
+// Copyright (C) 2022 Webb Technologies Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! PLACEHOLDER weights for `pallet_xcm_benchmarks::generic`. These are NOT generated: the
+//! benchmarks have not been run against this runtime yet, so each weight is written by hand from
+//! the storage accessed by the instruction and the weights other parachains benchmarked for it.
+//! Replace this file with the generated one once the benchmarks are run.
+//!
+//! Generate on reference collator hardware with:
+//!
+//! ./target/release/tangle-parachain benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_xcm_benchmarks::generic --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --output=./runtime/rococo/src/weights/xcm/pallet_xcm_benchmarks_generic.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weights for `pallet_xcm_benchmarks::generic`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo<T> {
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	pub(crate) fn query_holding() -> Weight {
+		Weight::from_ref_time(21_822_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	pub(crate) fn buy_execution() -> Weight {
+		Weight::from_ref_time(3_109_000)
+	}
+	// Storage: PolkadotXcm Queries (r:1 w:0)
+	pub(crate) fn query_response() -> Weight {
+		Weight::from_ref_time(12_087_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	pub(crate) fn transact() -> Weight {
+		Weight::from_ref_time(12_398_000)
+	}
+	pub(crate) fn refund_surplus() -> Weight {
+		Weight::from_ref_time(3_258_000)
+	}
+	pub(crate) fn set_error_handler() -> Weight {
+		Weight::from_ref_time(3_115_000)
+	}
+	pub(crate) fn set_appendix() -> Weight {
+		Weight::from_ref_time(3_106_000)
+	}
+	pub(crate) fn clear_error() -> Weight {
+		Weight::from_ref_time(3_116_000)
+	}
+	pub(crate) fn descend_origin() -> Weight {
+		Weight::from_ref_time(4_007_000)
+	}
+	pub(crate) fn clear_origin() -> Weight {
+		Weight::from_ref_time(3_091_000)
+	}
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	pub(crate) fn report_error() -> Weight {
+		Weight::from_ref_time(15_620_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: PolkadotXcm AssetTraps (r:1 w:1)
+	pub(crate) fn claim_asset() -> Weight {
+		Weight::from_ref_time(7_827_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	pub(crate) fn trap() -> Weight {
+		Weight::from_ref_time(3_103_000)
+	}
+	// Storage: PolkadotXcm VersionNotifyTargets (r:1 w:1)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	pub(crate) fn subscribe_version() -> Weight {
+		Weight::from_ref_time(16_036_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: PolkadotXcm VersionNotifyTargets (r:0 w:1)
+	pub(crate) fn unsubscribe_version() -> Weight {
+		Weight::from_ref_time(4_969_000)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	pub(crate) fn initiate_reserve_withdraw() -> Weight {
+		Weight::from_ref_time(20_895_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
@@ -13,34 +13,49 @@
 // limitations under the License.
 //
 use super::{
-	AccountId, Balances, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall,
-	RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
+	AccountId, AssetRegistry, Balance, Balances, Currencies, ParachainInfo, ParachainSystem,
+	PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, TreasuryAccount, WeightToFee,
+	XcmpQueue,
+};
+use crate::{
+	protocol_substrate_config::GetNativeCurrencyId, DmpQueue, Weight, MAXIMUM_BLOCK_WEIGHT,
 };
-use crate::{DmpQueue, Weight, MAXIMUM_BLOCK_WEIGHT};
+use codec::{Decode, Encode, MaxEncodedLen};
 use core::marker::PhantomData;
 use frame_support::{
 	log, match_types, parameter_types,
-	traits::{Everything, Nothing},
+	traits::{ConstU128, ConstU64, ConstU8, Everything, Nothing},
 };
 use frame_system::EnsureRoot;
+use orml_traits::location::AbsoluteReserveProvider;
+use orml_xcm_support::{
+	DepositToAlternative, IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset,
+};
 use pallet_xcm::XcmPassthrough;
-use polkadot_parachain::primitives::Sibling;
+use polkadot_parachain::primitives::{Id as ParaId, Sibling};
 use polkadot_runtime_common::impls::ToAuthor;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Convert, DispatchError, RuntimeDebug};
+use webb_primitives::AssetId;
 use xcm::latest::{prelude::*, Weight as XCMWeight};
 use xcm_builder::{
-	AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, CurrencyAdapter,
-	EnsureXcmOrigin, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset, ParentIsPreset,
+	Account32Hash, AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
+	CurrencyAdapter, EnsureXcmOrigin, IsConcrete, LocationInverter, ParentIsPreset,
 	RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
-	UsingComponents,
+	UsingComponents, WeightInfoBounds,
+};
+use xcm_executor::{
+	traits::{ShouldExecute, WeightBounds},
+	XcmExecutor,
 };
-use xcm_executor::{traits::ShouldExecute, XcmExecutor};
 
 parameter_types! {
 	pub const RelayLocation: MultiLocation = MultiLocation::parent();
 	pub const RelayNetwork: NetworkId = NetworkId::Any;
 	pub RelayChainOrigin: RuntimeOrigin = cumulus_pallet_xcm::Origin::Relay.into();
 	pub Ancestry: MultiLocation = Parachain(ParachainInfo::parachain_id().into()).into();
+	pub SelfReserve: MultiLocation = MultiLocation::here();
 }
 
 /// Type for specifying how a `MultiLocation` can be converted into an `AccountId`. This is used
@@ -53,6 +68,9 @@ pub type LocationToAccountId = (
 	SiblingParachainConvertsVia<Sibling, AccountId>,
 	// Straight up local `AccountId32` origins just alias directly to `AccountId`.
 	AccountId32Aliases<RelayNetwork, AccountId>,
+	// Any other location, e.g. an account on the relay chain or a sibling parachain, gets an
+	// account derived from the hash of the location.
+	Account32Hash<RelayNetwork, AccountId>,
 );
 
 /// Means for transacting assets on this chain.
@@ -69,6 +87,71 @@ pub type LocalAssetTransactor = CurrencyAdapter<
 	(),
 >;
 
+/// Location of an asset registered with `AssetRegistry`, e.g. `(1, X2(Parachain(id), ..))` for a
+/// token whose reserve is a sibling parachain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AssetLocation(pub MultiLocation);
+
+impl Default for AssetLocation {
+	fn default() -> Self {
+		AssetLocation(MultiLocation::here())
+	}
+}
+
+/// Converts between `AssetRegistry` asset ids and the locations registered for them. The native
+/// asset is left to `LocalAssetTransactor`, so it never converts.
+pub struct CurrencyIdConvert;
+
+impl Convert<AssetId, Option<MultiLocation>> for CurrencyIdConvert {
+	fn convert(id: AssetId) -> Option<MultiLocation> {
+		if id == GetNativeCurrencyId::get() {
+			return None
+		}
+		AssetRegistry::asset_to_location(id).map(|location| location.0)
+	}
+}
+
+impl Convert<MultiLocation, Option<AssetId>> for CurrencyIdConvert {
+	fn convert(location: MultiLocation) -> Option<AssetId> {
+		AssetRegistry::location_to_asset(AssetLocation(location))
+			.filter(|id| *id != GetNativeCurrencyId::get())
+	}
+}
+
+impl Convert<MultiAsset, Option<AssetId>> for CurrencyIdConvert {
+	fn convert(asset: MultiAsset) -> Option<AssetId> {
+		match asset.id {
+			Concrete(location) => Self::convert(location),
+			Abstract(_) => None,
+		}
+	}
+}
+
+/// Means for transacting the assets of other chains, which are held in `Tokens` under the id
+/// they were registered with in `AssetRegistry`. From there they can be wrapped by
+/// `TokenWrapper` like any local asset.
+pub type ForeignAssetTransactor = MultiCurrencyAdapter<
+	Currencies,
+	// Unregistered assets are rejected rather than tracked.
+	(),
+	IsNativeConcrete<AssetId, CurrencyIdConvert>,
+	AccountId,
+	LocationToAccountId,
+	AssetId,
+	CurrencyIdConvert,
+	// Deposits failing e.g. for being below the existential deposit go to the treasury.
+	DepositToAlternative<TreasuryAccount, Currencies, AssetId, AccountId, Balance>,
+>;
+
+/// Means for transacting the native currency when it is referred to by its own location, as in
+/// reserve transfers of it to other chains.
+pub type SelfReserveAssetTransactor =
+	CurrencyAdapter<Balances, IsConcrete<SelfReserve>, LocationToAccountId, AccountId, ()>;
+
+/// Means for transacting assets, native first.
+pub type AssetTransactors =
+	(LocalAssetTransactor, SelfReserveAssetTransactor, ForeignAssetTransactor);
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -91,11 +174,16 @@ pub type XcmOriginToTransactDispatchOrigin = (
 );
 
 parameter_types! {
-	// One XCM operation is 1_000_000_000 weight - almost certainly a conservative estimate.
-	pub UnitWeightCost: u64 = 1_000_000_000;
 	pub const MaxInstructions: u32 = 100;
 }
 
+/// Weighs messages by the benchmarked weight of each of their instructions
+pub type XcmWeigher = WeightInfoBounds<
+	crate::weights::xcm::TangleXcmWeight<RuntimeCall>,
+	RuntimeCall,
+	MaxInstructions,
+>;
+
 match_types! {
 	pub type ParentOrParentsExecutivePlurality: impl Contains<MultiLocation> = {
 		MultiLocation { parents: 1, interior: Here } |
@@ -103,6 +191,17 @@ match_types! {
 	};
 }
 
+match_types! {
+	/// Locations that may delegate through `ParachainStaking::delegate_via_xcm`: the relay chain,
+	/// sibling parachains and accounts on either of them.
+	pub type RemoteDelegators: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 1, interior: Here } |
+		MultiLocation { parents: 1, interior: X1(Parachain(_)) } |
+		MultiLocation { parents: 1, interior: X1(AccountId32 { .. }) } |
+		MultiLocation { parents: 1, interior: X2(Parachain(_), AccountId32 { .. }) }
+	};
+}
+
 //TODO: move DenyThenTry to polkadot's xcm module.
 /// Deny executing the xcm message if it matches any of the Deny filter regardless of anything else.
 /// If it passes the Deny, and matches one of the Allow cases then it is let through.
@@ -182,13 +281,15 @@ impl xcm_executor::Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
 	type XcmSender = XcmRouter;
 	// How to withdraw and deposit an asset.
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
-	type IsReserve = NativeAsset;
+	// Trust the relay chain and siblings as reserves of their own assets, including those under
+	// one of their pallets, e.g. `(1, X3(Parachain(id), PalletInstance(_), GeneralIndex(_)))`.
+	type IsReserve = MultiNativeAsset<AbsoluteReserveProvider>;
 	type IsTeleporter = (); // Teleporting is disabled.
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Barrier = Barrier;
-	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type Weigher = XcmWeigher;
 	type Trader =
 		UsingComponents<WeightToFee, RelayLocation, AccountId, Balances, ToAuthor<Runtime>>;
 	type ResponseHandler = PolkadotXcm;
@@ -220,7 +321,7 @@ impl pallet_xcm::Config for Runtime {
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type XcmTeleportFilter = Everything;
 	type XcmReserveTransferFilter = Nothing;
-	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type Weigher = XcmWeigher;
 	type LocationInverter = LocationInverter<Ancestry>;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
@@ -230,6 +331,89 @@ impl pallet_xcm::Config for Runtime {
 	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
 }
 
+parameter_types! {
+	pub SelfParaId: ParaId = ParachainInfo::parachain_id();
+}
+
+impl pallet_hrmp_manager::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type XcmSender = XcmRouter;
+	type SelfParaId = SelfParaId;
+	/// Index of `Hrmp` in the Rococo relay chain runtime
+	type RelayHrmpPalletIndex = ConstU8<60>;
+	/// 0.1 ROC, refunded to the sovereign account except for the execution fee
+	type RelayFee = ConstU128<100_000_000_000>;
+	type RelayCallWeight = ConstU64<1_000_000_000>;
+	type WeightInfo = pallet_hrmp_manager::weights::SubstrateWeight<Runtime>;
+}
+
+/// Remits the batched staking rewards of a delegator to its account on another chain, with a
+/// reserve transfer of the native currency out of its local account. The destination is the
+/// account itself, e.g. `(1, X2(Parachain(2000), AccountId32 { .. }))`.
+pub struct RemitRewardViaXcm;
+impl RemitRewardViaXcm {
+	fn message(
+		dest: MultiLocation,
+		beneficiary: MultiLocation,
+		asset: MultiAsset,
+		fees: MultiAsset,
+	) -> Xcm<RuntimeCall> {
+		Xcm(vec![TransferReserveAsset {
+			assets: asset.into(),
+			dest,
+			xcm: Xcm(vec![
+				BuyExecution { fees, weight_limit: Unlimited },
+				DepositAsset { assets: Wild(All), max_assets: 1, beneficiary },
+			]),
+		}])
+	}
+}
+impl pallet_parachain_staking::RemitReward<AccountId, Balance> for RemitRewardViaXcm {
+	type Destination = MultiLocation;
+
+	fn remit(
+		who: &AccountId,
+		destination: &MultiLocation,
+		amount: Balance,
+	) -> Result<Weight, DispatchError> {
+		let (dest, beneficiary) = match destination.clone().split_last_interior() {
+			(dest, Some(account)) => (dest, MultiLocation::new(0, X1(account))),
+			(_, None) => return Err(DispatchError::Other("reward destination is not an account")),
+		};
+		let asset: MultiAsset = (SelfReserve::get(), amount).into();
+		let fees = asset
+			.clone()
+			.reanchored(&dest, &Ancestry::get())
+			.map_err(|()| DispatchError::Other("cannot reanchor the native currency"))?;
+		let mut message = Self::message(dest, beneficiary, asset, fees);
+		let weight = <XcmWeigher as WeightBounds<RuntimeCall>>::weight(&mut message)
+			.map_err(|()| DispatchError::Other("cannot weigh the reward remittance"))?;
+		let origin = MultiLocation::new(
+			0,
+			X1(AccountId32 { network: RelayNetwork::get(), id: who.clone().into() }),
+		);
+		// reserved by staking through `weight`, which the weight credit lets past
+		// `TakeWeightCredit`
+		XcmExecutor::<XcmConfig>::execute_xcm_in_credit(origin, message, weight, weight)
+			.ensure_complete()
+			.map_err(|e| {
+				log::warn!(target: "xcm::remit_reward", "reward remittance failed: {:?}", e);
+				DispatchError::Other("reward remittance failed")
+			})?;
+		Ok(Weight::from_ref_time(weight))
+	}
+
+	// every remittance sends the same instructions, whatever the amount and destination
+	fn weight() -> Weight {
+		let asset: MultiAsset = (SelfReserve::get(), 1).into();
+		let mut message =
+			Self::message(RelayLocation::get(), MultiLocation::here(), asset.clone(), asset);
+		<XcmWeigher as WeightBounds<RuntimeCall>>::weight(&mut message)
+			.map_or(Weight::zero(), Weight::from_ref_time)
+	}
+}
+
 impl cumulus_pallet_xcm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
@@ -222,7 +222,7 @@ pub mod opaque {
 impl frame_system::Config for Runtime {
 	type AccountData = pallet_balances::AccountData<Balance>;
 	type AccountId = AccountId;
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = BaseFilter;
 	type BlockHashCount = BlockHashCount;
 	type BlockLength = RuntimeBlockLength;
 	type BlockNumber = BlockNumber;
@@ -881,6 +881,7 @@ impl pallet_ecdsa_claims::Config for Runtime {
 	type ForceOrigin = EnsureRoot<Self::AccountId>;
 	type Prefix = Prefix;
 	type MoveClaimOrigin = EnsureRoot<Self::AccountId>;
+	type DelegateClaim = ();
 	type WeightInfo = pallet_ecdsa_claims::TestWeightInfo;
 }
 
@@ -1038,9 +1039,40 @@ impl pallet_im_online::Config for Runtime {
 	type MaxPeerDataEncodingSize = MaxPeerDataEncodingSize;
 }
 
+/// Filters out the calls paused through `TransactionPause`
+pub struct BaseFilter;
+impl frame_support::traits::Contains<RuntimeCall> for BaseFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		use frame_support::traits::Contains;
+		!pallet_transaction_pause::PausedTransactionFilter::<Runtime>::contains(call)
+	}
+}
+
+/// Pallets paused together by `TransactionPause::pause_bridge`: the signature bridge and the
+/// handlers of the proposals it executes
+pub struct BridgePallets;
+impl frame_support::traits::Get<Vec<&'static str>> for BridgePallets {
+	fn get() -> Vec<&'static str> {
+		use frame_support::traits::PalletInfoAccess;
+		vec![
+			<SignatureBridge as PalletInfoAccess>::name(),
+			<VAnchorHandlerBn254 as PalletInfoAccess>::name(),
+			<TokenWrapperHandler as PalletInfoAccess>::name(),
+		]
+	}
+}
+
 impl pallet_transaction_pause::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type UpdateOrigin = EnsureRoot<AccountId>;
+	// half of the council can stop the bridge without waiting for a referendum
+	type BridgePauseOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>,
+	>;
+	type BridgePallets = BridgePallets;
+	type Group = ();
+	type NestedCalls = ();
 	type WeightInfo = ();
 }
 
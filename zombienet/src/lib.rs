@@ -0,0 +1,37 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Zombienet Tests
+//!
+//! End-to-end tests spawning `network/tangle.toml` with zombienet: a rococo-local relay chain
+//! running two tangle parachains joined by HRMP channels. They check what only a real network
+//! shows, namely that rounds turn over with the sessions, that the elected collators take over
+//! the session, that the DKG rotates its key along with the authorities, and that the parachains
+//! keep processing their channels.
+//!
+//! The tests are ignored by default. They need `zombienet` and `polkadot` on the `PATH` (or
+//! `ZOMBIENET_BIN` pointing at zombienet), and `tangle-parachain` built with the
+//! `fast-runtime` feature so that sessions last minutes rather than hours:
+//!
+//! ```sh
+//! cargo build --release -p tangle-parachain --features fast-runtime
+//! PATH=$PATH:$PWD/target/release cargo test -p tangle-zombienet-tests -- --ignored
+//! ```
+
+#![cfg(test)]
+
+mod network;
+mod tests;
@@ -0,0 +1,162 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+//! Spawning the network and reading the storage of its nodes over RPC
+
+use jsonrpsee::{
+	core::client::ClientT,
+	rpc_params,
+	ws_client::{WsClient, WsClientBuilder},
+};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::{
+	hashing::{twox_128, twox_64},
+	Bytes,
+};
+use std::{
+	path::PathBuf,
+	process::{Child, Command, Stdio},
+	time::Duration,
+};
+
+/// Websocket port of the relay chain node `alice`
+pub const RELAY_PORT: u16 = 9944;
+/// Websocket port of the tangle collator `tangle-alice`
+pub const TANGLE_PORT: u16 = 9988;
+/// Websocket port of the sibling parachain collator
+pub const SIBLING_PORT: u16 = 9999;
+/// Para id of the tangle parachain
+pub const TANGLE_PARA_ID: u32 = 2000;
+/// Para id of the sibling parachain
+pub const SIBLING_PARA_ID: u32 = 2001;
+
+/// How long nodes are given to come up, parachains included
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(600);
+/// Parachain block time
+const BLOCK_TIME: Duration = Duration::from_secs(12);
+
+/// A network spawned by zombienet, torn down when dropped
+pub struct Network {
+	process: Child,
+}
+
+impl Network {
+	/// Spawns `network/tangle.toml` and waits until the tangle parachain produces blocks
+	pub async fn spawn() -> Self {
+		let zombienet = std::env::var("ZOMBIENET_BIN").unwrap_or_else(|_| "zombienet".into());
+		let config = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("network/tangle.toml");
+		let process = Command::new(zombienet)
+			.arg("spawn")
+			.arg(config)
+			.stdout(Stdio::null())
+			.spawn()
+			.expect("zombienet can be run");
+		let network = Network { process };
+		let tangle = Node::connect(TANGLE_PORT).await;
+		tangle.wait_until(STARTUP_TIMEOUT, |number| async move { number > 0 }).await;
+		network
+	}
+}
+
+impl Drop for Network {
+	fn drop(&mut self) {
+		let _ = self.process.kill();
+		let _ = self.process.wait();
+	}
+}
+
+/// RPC connection to a node of the network
+pub struct Node {
+	client: WsClient,
+}
+
+impl Node {
+	/// Connects to the node on `port`, retrying while it starts up
+	pub async fn connect(port: u16) -> Self {
+		let url = format!("ws://127.0.0.1:{}", port);
+		let started = tokio::time::Instant::now();
+		loop {
+			match WsClientBuilder::default().build(&url).await {
+				Ok(client) => return Node { client },
+				Err(e) if started.elapsed() > STARTUP_TIMEOUT =>
+					panic!("node on {} did not come up: {:?}", url, e),
+				Err(_) => tokio::time::sleep(Duration::from_secs(2)).await,
+			}
+		}
+	}
+
+	/// Number of the best block
+	pub async fn best_number(&self) -> u32 {
+		let header: serde_json::Value = self
+			.client
+			.request("chain_getHeader", rpc_params![])
+			.await
+			.expect("node serves its best header");
+		let number = header["number"].as_str().expect("header has a number");
+		u32::from_str_radix(number.trim_start_matches("0x"), 16).expect("number is hex")
+	}
+
+	/// Raw value under `pallet`'s `item`, `key` being the hashed key of a map entry
+	pub async fn storage_raw(&self, pallet: &str, item: &str, key: &[u8]) -> Option<Vec<u8>> {
+		let mut full_key = twox_128(pallet.as_bytes()).to_vec();
+		full_key.extend(twox_128(item.as_bytes()));
+		full_key.extend(key);
+		let value: Option<Bytes> = self
+			.client
+			.request("state_getStorage", rpc_params![Bytes(full_key)])
+			.await
+			.expect("node serves its storage");
+		value.map(|v| v.0)
+	}
+
+	/// Decoded value under `pallet`'s `item`, `key` being the hashed key of a map entry
+	pub async fn storage<T: Decode>(&self, pallet: &str, item: &str, key: &[u8]) -> Option<T> {
+		self.storage_raw(pallet, item, key).await.map(|raw| {
+			T::decode(&mut &raw[..]).unwrap_or_else(|e| {
+				panic!("{}::{} does not decode: {:?}", pallet, item, e);
+			})
+		})
+	}
+
+	/// Polls each block until `done` holds for the best block number, panicking once
+	/// `timeout` has passed
+	pub async fn wait_until<F, Fut>(&self, timeout: Duration, mut done: F)
+	where
+		F: FnMut(u32) -> Fut,
+		Fut: std::future::Future<Output = bool>,
+	{
+		let started = tokio::time::Instant::now();
+		loop {
+			if done(self.best_number().await).await {
+				return
+			}
+			assert!(started.elapsed() < timeout, "condition not met within {:?}", timeout);
+			tokio::time::sleep(BLOCK_TIME / 2).await;
+		}
+	}
+}
+
+/// Hashed key of a `Twox64Concat` map entry
+pub fn twox_64_concat(key: impl Encode) -> Vec<u8> {
+	let encoded = key.encode();
+	let mut hashed = twox_64(&encoded).to_vec();
+	hashed.extend(encoded);
+	hashed
+}
+
+/// Time taken by `blocks` parachain blocks, with room for the relay chain to lag
+pub fn blocks(blocks: u32) -> Duration {
+	BLOCK_TIME * blocks * 2
+}
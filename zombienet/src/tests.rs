@@ -0,0 +1,145 @@
+// This file is part of Webb.
+// Copyright (C) 2021 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+use crate::network::*;
+use pallet_parachain_staking::RoundInfo;
+use sp_runtime::AccountId32;
+use tangle_primitives::SESSION_PERIOD_BLOCKS;
+
+async fn round(tangle: &Node) -> RoundInfo<u32> {
+	tangle
+		.storage("ParachainStaking", "Round", &[])
+		.await
+		.expect("round is set at genesis")
+}
+
+async fn sorted(tangle: &Node, pallet: &str, item: &str) -> Vec<AccountId32> {
+	let mut accounts: Vec<AccountId32> =
+		tangle.storage(pallet, item, &[]).await.unwrap_or_default();
+	accounts.sort();
+	accounts
+}
+
+async fn para_head(relay: &Node, para_id: u32) -> Option<Vec<u8>> {
+	relay.storage_raw("Paras", "Heads", &twox_64_concat(para_id)).await
+}
+
+/// Relay chain block up to which the parachain processed its inbound channels
+async fn hrmp_watermark(parachain: &Node) -> u32 {
+	parachain
+		.storage("ParachainSystem", "HrmpWatermark", &[])
+		.await
+		.unwrap_or_default()
+}
+
+/// Rounds turn over with the sessions, each starting where the last one ended
+async fn rounds_follow_sessions(tangle: &Node) {
+	let before = round(tangle).await;
+	let session: u32 = tangle.storage("Session", "CurrentIndex", &[]).await.unwrap_or_default();
+
+	tangle
+		.wait_until(blocks(2 * SESSION_PERIOD_BLOCKS), |_| async move {
+			round(tangle).await.current > before.current
+		})
+		.await;
+	let after = round(tangle).await;
+	assert_eq!(after.current, before.current + 1);
+	assert!(after.first > before.first);
+	let next_session: u32 =
+		tangle.storage("Session", "CurrentIndex", &[]).await.unwrap_or_default();
+	assert_eq!(next_session, session + 1);
+}
+
+/// The collators selected for a round validate the session after the one opening it
+async fn selected_collators_take_over_the_session(tangle: &Node) {
+	let start = round(tangle).await.current;
+	let selected = sorted(tangle, "ParachainStaking", "SelectedCandidates").await;
+	assert!(!selected.is_empty());
+
+	tangle
+		.wait_until(blocks(2 * SESSION_PERIOD_BLOCKS), |_| async move {
+			round(tangle).await.current > start
+		})
+		.await;
+	assert_eq!(sorted(tangle, "Session", "Validators").await, selected);
+}
+
+/// The DKG moves on to a new authority set each session, publishing the key generated
+/// for it
+async fn dkg_key_rotates_with_the_authorities(tangle: &Node) {
+	let set_id: u64 = tangle.storage("DKG", "AuthoritySetId", &[]).await.unwrap_or_default();
+	let key: Option<(u64, Vec<u8>)> = tangle.storage("DKG", "DKGPublicKey", &[]).await;
+
+	tangle
+		.wait_until(blocks(3 * SESSION_PERIOD_BLOCKS), |_| async move {
+			let rotated: Option<(u64, Vec<u8>)> = tangle.storage("DKG", "DKGPublicKey", &[]).await;
+			rotated.map_or(false, |(rotated_set_id, _)| rotated_set_id > set_id)
+		})
+		.await;
+	let (rotated_set_id, rotated) = tangle
+		.storage::<(u64, Vec<u8>)>("DKG", "DKGPublicKey", &[])
+		.await
+		.expect("key rotated");
+	let current_set_id: u64 =
+		tangle.storage("DKG", "AuthoritySetId", &[]).await.unwrap_or_default();
+	assert_eq!(rotated_set_id, current_set_id);
+	assert!(!rotated.is_empty());
+	assert_ne!(Some(rotated), key.map(|(_, key)| key));
+}
+
+/// Channels opened at genesis stay open both ways, while both parachains keep being included
+/// and processing their inbound messages
+async fn hrmp_channels_operate(relay: &Node, tangle: &Node, sibling: &Node) {
+	let channel = |sender: u32, recipient: u32| twox_64_concat((sender, recipient));
+	for (sender, recipient) in
+		[(TANGLE_PARA_ID, SIBLING_PARA_ID), (SIBLING_PARA_ID, TANGLE_PARA_ID)]
+	{
+		assert!(
+			relay
+				.storage_raw("Hrmp", "HrmpChannels", &channel(sender, recipient))
+				.await
+				.is_some(),
+			"channel {} -> {} is open",
+			sender,
+			recipient
+		);
+	}
+
+	let tangle_head = para_head(relay, TANGLE_PARA_ID).await;
+	let sibling_head = para_head(relay, SIBLING_PARA_ID).await;
+	let tangle_watermark = hrmp_watermark(tangle).await;
+	let sibling_watermark = hrmp_watermark(sibling).await;
+
+	let target = tangle.best_number().await + 3;
+	tangle.wait_until(blocks(5), |number| async move { number >= target }).await;
+	assert_ne!(para_head(relay, TANGLE_PARA_ID).await, tangle_head);
+	assert_ne!(para_head(relay, SIBLING_PARA_ID).await, sibling_head);
+	assert!(hrmp_watermark(tangle).await > tangle_watermark);
+	assert!(hrmp_watermark(sibling).await > sibling_watermark);
+}
+
+#[tokio::test]
+#[ignore = "spawns a zombienet network of built binaries, see the crate docs"]
+async fn network_end_to_end() {
+	let _network = Network::spawn().await;
+	let relay = Node::connect(RELAY_PORT).await;
+	let tangle = Node::connect(TANGLE_PORT).await;
+	let sibling = Node::connect(SIBLING_PORT).await;
+
+	rounds_follow_sessions(&tangle).await;
+	selected_collators_take_over_the_session(&tangle).await;
+	dkg_key_rotates_with_the_authorities(&tangle).await;
+	hrmp_channels_operate(&relay, &tangle, &sibling).await;
+}